@@ -7,14 +7,15 @@
 //! ## Action Categories
 //!
 //! - **Application**: Quit, open file, settings, shortcuts
-//! - **Canvas Navigation**: Zoom in/out/reset
+//! - **Canvas Navigation**: Zoom in/out/reset, zoom to fit/selection, bookmarks, minimap
 //! - **Selection**: Select all, deselect, delete, duplicate, copy/paste
 //! - **Item Nudging**: Arrow key movement of selected items
 //! - **History**: Undo/redo operations
 //! - **Preview Panel**: Tab management, split controls, navigation
 //! - **PDF Viewer**: Page navigation and zoom
 //! - **Command Palette**: Open/close, navigation, selection
-//! - **Tool Selection**: Select, text, arrow, shape tools
+//! - **Tool Selection**: Select, text, arrow, shape, pen, highlighter, eraser tools
+//! - **Presentation**: Start, step through and leave presentation mode
 //! - **Focus Management**: Canvas and preview focus control
 
 use crate::app::Humanboard;
@@ -33,9 +34,12 @@ actions!(
         GoHome,        // Navigate to landing page (Cmd+H)
         NewBoard,      // Create a new board (Cmd+N)
         // === Canvas Navigation ===
-        ZoomIn,    // Zoom in on canvas (Cmd+=)
-        ZoomOut,   // Zoom out on canvas (Cmd+-)
-        ZoomReset, // Reset zoom to 100% (Cmd+0)
+        ZoomIn,          // Zoom in on canvas (Cmd+=)
+        ZoomOut,         // Zoom out on canvas (Cmd+-)
+        ZoomReset,       // Reset zoom to 100% (Cmd+0)
+        ZoomToFit,       // Fit all items on screen (Shift+1)
+        ZoomToSelection, // Fit the selected items on screen (Shift+2)
+        ToggleMinimap,   // Show or hide the board minimap (Cmd+Shift+M)
         // === Selection ===
         SelectAll,         // Select all items (Cmd+A)
         DeselectAll,       // Deselect all items (Escape when items selected)
@@ -82,13 +86,28 @@ actions!(
         CmdPaletteDown,       // Navigate down in command palette
         CmdPaletteSelect,     // Select current item in command palette
         // === Tool Selection ===
-        ToolSelect, // Switch to select tool (V or Escape)
-        ToolText,   // Switch to text tool (T)
-        ToolArrow,  // Switch to arrow tool (A)
-        ToolShape,  // Switch to shape tool (S)
+        ToolSelect,      // Switch to select tool (V or Escape)
+        ToolText,        // Switch to text tool (T)
+        ToolArrow,       // Switch to arrow tool (A)
+        ToolShape,       // Switch to shape tool (S)
+        ToolPen,         // Switch to pen tool
+        ToolHighlighter, // Switch to highlighter tool
+        ToolEraser,      // Switch to stroke eraser
+        // === Presentation ===
+        StartPresentation, // Present the board's frames (F5)
+        PresentationNext,  // Go to the next frame (Right, Down, Space, Page Down)
+        PresentationPrev,  // Go to the previous frame (Left, Up, Page Up)
+        ExitPresentation,  // Leave presentation mode (Escape)
         // === TextBox Editing ===
-        CancelTextboxEdit, // Cancel textbox editing (Escape)
-        CommitTextboxEdit, // Commit textbox editing (Cmd+Enter or click outside)
+        CancelTextboxEdit,    // Cancel textbox editing (Escape)
+        CommitTextboxEdit,    // Commit textbox editing (Cmd+Enter or click outside)
+        TextboxBold,          // Toggle bold on the selection (Cmd+B)
+        TextboxItalic,        // Toggle italic on the selection (Cmd+I)
+        TextboxStrikethrough, // Toggle strikethrough on the selection (Cmd+Shift+X)
+        TextboxCode,          // Toggle inline code on the selection (Cmd+E)
+        // === Image Editing ===
+        CommitCrop,    // Apply the crop being drawn on an image (Enter)
+        ShowImageInfo, // Show the selected image's size and EXIF details (Cmd+I)
         // === Focus Management ===
         FocusCanvas,  // Return focus to canvas
         FocusPreview, // Focus preview panel
@@ -99,6 +118,14 @@ actions!(
     ]
 );
 
+/// Jump to a saved viewport bookmark (1-9)
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = humanboard, no_json)]
+pub struct GoToBookmark {
+    /// Zero-based index into the board's bookmarks
+    pub index: usize,
+}
+
 impl Humanboard {
     /// Get the center point of the canvas area (accounting for preview panel)
     fn canvas_center(&self, window: &Window) -> Point<Pixels> {
@@ -259,6 +286,19 @@ impl Humanboard {
 
     /// Deselect all selected items
     pub fn deselect_all(&mut self, cx: &mut Context<Self>) {
+        // Escape leaves crop mode, image info, the page picker or the annotator
+        // before it clears the selection
+        if self.crop_editing.is_some() {
+            self.cancel_crop(cx);
+            return;
+        }
+        if self.image_info.take().is_some()
+            || self.pdf_page_picker.take().is_some()
+            || self.pdf_annotator.take().is_some()
+        {
+            cx.notify();
+            return;
+        }
         if !self.selected_items.is_empty() {
             self.selected_items.clear();
             cx.notify();
@@ -275,6 +315,15 @@ impl Humanboard {
             return;
         }
 
+        // A filled shape or text box, like a palette swatch, copies its hex code
+        if let Some(hex) = self.selected_fill_color() {
+            cx.write_to_clipboard(ClipboardItem::new_string(hex.clone()));
+            self.toast_manager
+                .push(Toast::success(format!("Copied {}", hex)));
+            cx.notify();
+            return;
+        }
+
         if self.board.is_some() {
             // Store copied items in a Vec for later paste
             // For now, we'll just show a toast - full implementation would
@@ -338,6 +387,9 @@ impl Humanboard {
     }
 
     pub fn undo(&mut self, cx: &mut Context<Self>) {
+        // A running layout must be committed before it can be undone
+        self.finish_layout_animation();
+        self.crop_editing = None;
         if let Some(ref mut board) = self.board {
             if board.undo() {
                 self.selected_items.clear();
@@ -347,6 +399,8 @@ impl Humanboard {
     }
 
     pub fn redo(&mut self, cx: &mut Context<Self>) {
+        self.finish_layout_animation();
+        self.crop_editing = None;
         if let Some(ref mut board) = self.board {
             if board.redo() {
                 self.selected_items.clear();
@@ -356,10 +410,10 @@ impl Humanboard {
     }
 
     pub fn open_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        // Show file picker for multiple files
+        // Show file picker for multiple files; folders are imported as framed grids
         let paths_rx = cx.prompt_for_paths(PathPromptOptions {
             files: true,
-            directories: false,
+            directories: true,
             multiple: true,
             prompt: None,
         });
@@ -0,0 +1,125 @@
+//! Arrange methods - animating the selection or board into an automatic layout.
+
+use super::{Humanboard, LayoutAnimation};
+use crate::animations::ease_in_out_cubic;
+use crate::auto_layout::{self, LayoutKind};
+use crate::constants::LAYOUT_ANIMATION_DURATION;
+use crate::notifications::Toast;
+use gpui::*;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+impl Humanboard {
+    /// Arrange the selected items, or the whole board when nothing is selected
+    pub fn arrange(&mut self, kind: LayoutKind, cx: &mut Context<Self>) {
+        self.arrange_sorted(kind, &HashMap::new(), cx);
+    }
+
+    /// Arrange with photos first, oldest to newest by their EXIF capture date.
+    /// Dates are read in the background first when the cached ones are stale.
+    pub fn arrange_by_capture_date(&mut self, kind: LayoutKind, cx: &mut Context<Self>) {
+        if !self.capture_dates_current() {
+            self.pending_date_arrange = Some(kind);
+            self.refresh_capture_dates();
+            return;
+        }
+        self.arrange_with_capture_dates(kind, cx);
+    }
+
+    /// Arrange by the cached capture dates of the selected images, or of all
+    /// images when nothing is selected
+    pub fn arrange_with_capture_dates(&mut self, kind: LayoutKind, cx: &mut Context<Self>) {
+        let dates: HashMap<u64, String> = self
+            .capture_dates
+            .iter()
+            .flat_map(|cached| cached.dates.iter())
+            .filter(|(id, _)| self.selected_items.is_empty() || self.selected_items.contains(id))
+            .map(|(id, date)| (*id, date.clone()))
+            .collect();
+        if dates.is_empty() {
+            self.toast_manager
+                .push(Toast::info("None of these images have a capture date"));
+            cx.notify();
+            return;
+        }
+        self.arrange_sorted(kind, &dates, cx);
+    }
+
+    /// Animate into a layout, placing items with a sort key first
+    fn arrange_sorted(
+        &mut self,
+        kind: LayoutKind,
+        keys: &HashMap<u64, String>,
+        cx: &mut Context<Self>,
+    ) {
+        self.finish_layout_animation();
+        let Some(ref board) = self.board else {
+            return;
+        };
+
+        let to = auto_layout::arrange_sorted(kind, &board.items, &self.selected_items, keys);
+        if to.is_empty() {
+            self.toast_manager.push(Toast::info(
+                "Nothing to arrange - select at least two items",
+            ));
+            cx.notify();
+            return;
+        }
+        let from: Vec<_> = to
+            .iter()
+            .filter_map(|item| board.get_item(item.id).cloned())
+            .collect();
+
+        self.layout_animation = Some(LayoutAnimation {
+            from,
+            to,
+            start_time: Instant::now(),
+            duration: Duration::from_secs_f64(LAYOUT_ANIMATION_DURATION),
+        });
+        if crate::settings::app_settings().should_reduce_motion() {
+            self.finish_layout_animation();
+        }
+        cx.notify();
+    }
+
+    /// Move items along their layout animation, returns true while it runs
+    pub fn update_layout_animation(&mut self) -> bool {
+        let Some(ref anim) = self.layout_animation else {
+            return false;
+        };
+        let progress =
+            (anim.start_time.elapsed().as_secs_f32() / anim.duration.as_secs_f32()).min(1.0);
+        if progress >= 1.0 {
+            self.finish_layout_animation();
+            return false;
+        }
+
+        if let Some(ref mut board) = self.board {
+            let eased = ease_in_out_cubic(progress);
+            for (from, to) in anim.from.iter().zip(&anim.to) {
+                if let Some(item) = board.get_item_mut(to.id) {
+                    item.copy_geometry_from(&auto_layout::interpolate(from, to, eased));
+                }
+            }
+        }
+        true
+    }
+
+    /// Jump a running layout animation to its end and record it for undo.
+    ///
+    /// Only geometry is animated, so edits made while it runs are kept.
+    pub fn finish_layout_animation(&mut self) {
+        let Some(anim) = self.layout_animation.take() else {
+            return;
+        };
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+        for to in &anim.to {
+            if let Some(item) = board.get_item_mut(to.id) {
+                item.copy_geometry_from(to);
+            }
+        }
+        board.commit_layout(&anim.from);
+    }
+}
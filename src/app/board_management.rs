@@ -1,9 +1,10 @@
 //! Board management methods - create, open, edit, delete, trash operations
 
-use super::{AppView, Humanboard, StorageLocation};
+use super::{AppView, Humanboard, SavedTemplateResult, StorageLocation};
 use crate::board::Board;
 use crate::board_index::BoardIndex;
 use crate::focus::FocusContext;
+use crate::templates::{self, TemplateChoice};
 use gpui::*;
 use gpui_component::input::InputState;
 
@@ -24,6 +25,8 @@ impl Humanboard {
 
         self.create_board_input = Some(input);
         self.create_board_location = StorageLocation::default();
+        self.create_board_template = TemplateChoice::default();
+        self.saved_templates = templates::list_saved_templates(&templates::templates_dir());
         self.show_create_board_modal = true;
         self.modal_animations.open_create_board();
         cx.notify();
@@ -42,6 +45,8 @@ impl Humanboard {
         self.show_create_board_modal = false;
         self.create_board_input = None;
         self.create_board_location = StorageLocation::default();
+        self.create_board_template = TemplateChoice::default();
+        self.saved_templates.clear();
     }
 
     /// Set the storage location for the new board
@@ -50,7 +55,13 @@ impl Humanboard {
         cx.notify();
     }
 
-    /// Create a new board with custom name and storage location
+    /// Set the template the new board starts from
+    pub fn set_create_board_template(&mut self, template: TemplateChoice, cx: &mut Context<Self>) {
+        self.create_board_template = template;
+        cx.notify();
+    }
+
+    /// Create a new board with custom name, storage location and template
     pub fn confirm_create_board(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let name = self
             .create_board_input
@@ -67,8 +78,19 @@ impl Humanboard {
         let location = std::mem::take(&mut self.create_board_location);
         let location_name = location.display_name().to_owned();
 
-        // Create the board with custom location
+        // Create the board with custom location, then fill it from the template
         let metadata = self.board_index.create_board_at(name, location);
+        let template = std::mem::take(&mut self.create_board_template);
+        let templates_dir = templates::templates_dir();
+        let board_dir = metadata.board_dir();
+        if let Err(e) = templates::apply_template(&template, &templates_dir, &board_dir) {
+            tracing::error!("Failed to apply template '{}': {}", template.name(), e);
+            self.toast_manager
+                .push(crate::notifications::Toast::error(format!(
+                    "Couldn't apply template: {}",
+                    e
+                )));
+        }
 
         // Close modal immediately (no animation when confirming - we're navigating away)
         self.show_create_board_modal = false;
@@ -85,6 +107,60 @@ impl Humanboard {
         self.open_board(metadata.id, cx);
     }
 
+    /// Save the open board, including copies of its files, as a reusable template.
+    /// Without a name the template is named after the board.
+    pub fn save_board_as_template(&mut self, name: Option<String>, cx: &mut Context<Self>) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let name = name.unwrap_or_else(|| {
+            self.board_index
+                .get_board(&board.id)
+                .map(|meta| meta.name.clone())
+                .unwrap_or_else(|| "Untitled Template".to_string())
+        });
+
+        // Copying the board's files can take a while, so it happens off the UI thread
+        let state = board.to_state();
+        let template_name = name.clone();
+        let tx = self.template_tx.clone();
+        self.background.spawn(
+            &format!("template:{}", name),
+            move || {
+                templates::save_template(&templates::templates_dir(), &template_name, &state)
+                    .map_err(|e| e.to_string())
+            },
+            move |result| {
+                let _ = tx.send(SavedTemplateResult { name, result });
+            },
+        );
+        cx.notify();
+    }
+
+    /// Report templates that finished saving in the background
+    pub fn poll_saved_templates(&mut self, cx: &mut Context<Self>) {
+        while let Ok(done) = self.template_rx.try_recv() {
+            cx.notify();
+            match done.result {
+                Ok(template) => {
+                    self.toast_manager
+                        .push(crate::notifications::Toast::success(format!(
+                            "Saved template \"{}\"",
+                            template.name
+                        )));
+                }
+                Err(e) => {
+                    tracing::error!("Failed to save template '{}': {}", done.name, e);
+                    self.toast_manager
+                        .push(crate::notifications::Toast::error(format!(
+                            "Couldn't save template: {}",
+                            e
+                        )));
+                }
+            }
+        }
+    }
+
     /// Quick create (backwards compatible) - creates with default name and location
     pub fn create_new_board(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         // Show modal instead of directly creating
@@ -95,11 +171,30 @@ impl Humanboard {
         self.board_index.touch_board(&id);
         let board = Board::load(id.clone());
         self.board = Some(board);
+        self.pending_date_arrange = None;
+        self.refresh_capture_dates();
+
+        // Layer the board's own settings over the user's while it's open
+        let board_dir = self
+            .board_index
+            .get_board(&id)
+            .map(|meta| meta.board_dir())
+            .unwrap_or_else(|| BoardIndex::board_dir(&id));
+        if let Err(e) = crate::settings::load_board_settings(board_dir) {
+            self.toast_manager
+                .push(crate::notifications::Toast::warning(format!(
+                    "Couldn't load board settings: {}",
+                    e
+                )));
+        }
+
         self.view = AppView::Board(id);
         cx.notify();
     }
 
     pub fn go_home(&mut self, cx: &mut Context<Self>) {
+        self.finish_layout_animation();
+        self.crop_editing = None;
         // Force save current board before leaving
         if let Some(ref mut board) = self.board {
             if let Err(e) = board.flush_save() {
@@ -111,6 +206,7 @@ impl Humanboard {
             }
         }
         self.board = None;
+        crate::settings::unload_board_settings();
         // Clean up preview panel resources before dropping
         if let Some(ref mut preview) = self.preview {
             preview.cleanup(cx);
@@ -119,6 +215,11 @@ impl Humanboard {
         self.youtube_webviews.clear(); // Clear YouTube WebViews when leaving board
         self.audio_webviews.clear(); // Clear Audio WebViews when leaving board
         self.video_webviews.clear(); // Clear Video WebViews when leaving board
+        self.live_media.clear(); // Media starts as posters on the next board
+        self.pending_seeks.clear();
+        self.pdf_page_picker = None;
+        self.pdf_annotator = None;
+        self.comments_panel = None;
         self.view = AppView::Landing;
         self.selected_items.clear();
         // Reload index to get any changes
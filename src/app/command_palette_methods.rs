@@ -1,8 +1,13 @@
 //! Command palette methods - show/hide, search, execute commands
 
-use super::{AppView, CmdPaletteMode, Humanboard, PanAnimation};
+use super::{AppView, CaptureDates, CmdPaletteMode, Humanboard, PanAnimation};
+use crate::command_palette::{PaletteHistory, PaletteMatch, SearchResult};
+use crate::exif;
 use crate::focus::FocusContext;
+use crate::notifications::Toast;
 use crate::settings::Settings;
+use crate::timestamps::unix_now;
+use crate::types::TextAlignment;
 use gpui::*;
 use gpui_component::input::InputState;
 use std::time::{Duration, Instant};
@@ -66,6 +71,7 @@ impl Humanboard {
         self.command_palette = Some(input);
 
         // Show all items initially
+        self.cmd_palette_mode = CmdPaletteMode::Items;
         self.update_search_results("", cx);
     }
 
@@ -101,24 +107,9 @@ impl Humanboard {
         let text = text.trim();
 
         // Check if user typed "theme " to enter theme mode
-        if text.starts_with("theme ") {
+        if let Some(filter) = text.strip_prefix("theme ") {
             self.cmd_palette_mode = CmdPaletteMode::Themes;
-            let filter = text.strip_prefix("theme ").unwrap_or("").trim();
-            let themes = Settings::available_themes(cx);
-            if filter.is_empty() {
-                self.search_results = themes
-                    .into_iter()
-                    .enumerate()
-                    .map(|(idx, name)| (idx as u64, name))
-                    .collect();
-            } else {
-                self.search_results = themes
-                    .into_iter()
-                    .enumerate()
-                    .filter(|(_, name)| name.to_lowercase().contains(&filter.to_lowercase()))
-                    .map(|(idx, name)| (idx as u64, name))
-                    .collect();
-            }
+            self.search_results = self.rank_themes(filter.trim(), cx);
             self.selected_result = 0;
             cx.notify();
             return;
@@ -126,84 +117,221 @@ impl Humanboard {
 
         // Handle theme mode (when entered via click or command selection)
         if self.cmd_palette_mode == CmdPaletteMode::Themes {
-            let themes = Settings::available_themes(cx);
             // If text is just "theme" (entered via command), treat as empty filter
             let filter = if text.eq_ignore_ascii_case("theme") {
                 ""
             } else {
                 text
             };
-
-            if filter.is_empty() {
-                // Show all themes
-                self.search_results = themes
-                    .into_iter()
-                    .enumerate()
-                    .map(|(idx, name)| (idx as u64, name))
-                    .collect();
-            } else {
-                // Filter themes by search text
-                self.search_results = themes
-                    .into_iter()
-                    .enumerate()
-                    .filter(|(_, name)| name.to_lowercase().contains(&filter.to_lowercase()))
-                    .map(|(idx, name)| (idx as u64, name))
-                    .collect();
-            }
+            self.search_results = self.rank_themes(filter, cx);
             self.selected_result = 0;
             cx.notify();
             return;
         }
 
-        // Check if typing a command prefix - show matching commands
-        if !text.is_empty() && text.len() <= 7 {
-            let text_lower = text.to_lowercase();
-            // Available commands with special IDs (using high numbers to avoid collision with item IDs)
-            let commands = [
-                (u64::MAX - 1, "theme", "Change theme"),
-                (u64::MAX - 2, "md", "Create markdown note"),
-            ];
-
-            let matching_commands: Vec<(u64, String)> = commands
-                .iter()
-                .filter(|(_, cmd, _)| cmd.starts_with(&text_lower))
-                .map(|(id, cmd, desc)| (*id, format!("{} - {}", cmd, desc)))
-                .collect();
-
-            if !matching_commands.is_empty() {
-                self.search_results = matching_commands;
-                self.selected_result = 0;
-                cx.notify();
+        // Photos in the order they were taken (`taken 2024-05` narrows them down)
+        if let Some(query) = text.strip_prefix("taken") {
+            if query.is_empty() || query.starts_with(' ') {
+                self.show_photos_by_capture_date(query.trim(), cx);
                 return;
             }
         }
 
-        // Check if it's a complete command
-        if text.starts_with("md ") || text == "md" {
+        // Photo mode entered via command selection filters by whatever is typed
+        if self.cmd_palette_mode == CmdPaletteMode::Photos {
+            self.show_photos_by_capture_date(text, cx);
+            return;
+        }
+
+        // Check if it's a complete command with arguments (Enter runs it as typed)
+        if text.starts_with("md ")
+            || text.starts_with("align ")
+            || text.starts_with("font ")
+            || text.starts_with("view ")
+            || text.starts_with("unview ")
+            || text.starts_with("template ")
+            || text.starts_with("fill ")
+            || text.starts_with("mark ")
+            || text.starts_with("unmark ")
+            || text.starts_with("pages ")
+            || text.starts_with("note ")
+            || text.starts_with("comment ")
+        {
             self.search_results.clear();
             self.selected_result = 0;
             cx.notify();
             return;
         }
 
-        // Search canvas items (empty string shows all items)
+        // Commands, saved views and canvas items ranked together by match
+        // quality and past use (empty text shows views and items by use)
+        let mut matches = Vec::new();
+        if !text.is_empty() {
+            matches.extend(PALETTE_COMMANDS.iter().filter_map(|(id, cmd, desc)| {
+                PaletteMatch::find(text, *id, "", &format!("{} - {}", cmd, desc), command_key(cmd))
+            }));
+
+            // Registered commands, including user-defined external ones
+            let registered = self.command_registry.search(text, self);
+            self.palette_command_ids = registered
+                .iter()
+                .map(|(cmd, _)| cmd.id().to_string())
+                .collect();
+            matches.extend(registered.into_iter().enumerate().map(|(index, (cmd, found))| {
+                PaletteMatch::new(
+                    registry_result_id(index),
+                    cmd.name(),
+                    found,
+                    command_key(cmd.id()),
+                )
+            }));
+        }
         if let Some(ref board) = self.board {
-            if text.is_empty() {
-                // Show all searchable items when no search text
-                self.search_results = board
-                    .items
-                    .iter()
-                    .filter(|item| item.content.is_searchable())
-                    .map(|item| (item.id, item.content.display_name()))
-                    .collect();
-            } else {
-                self.search_results = board.find_items(text);
+            matches.extend(board.bookmarks.iter().enumerate().filter_map(|(index, b)| {
+                PaletteMatch::find(
+                    text,
+                    bookmark_result_id(index),
+                    "View: ",
+                    &b.name,
+                    view_key(&board.id, &b.name),
+                )
+            }));
+            matches.extend(board.find_items(text).into_iter().map(|(id, name, found)| {
+                PaletteMatch::new(id, name, found, item_key(&board.id, id))
+            }));
+        }
+
+        self.search_results = self.palette_history.rank(matches, unix_now());
+        self.selected_result = 0;
+        cx.notify();
+    }
+
+    /// List dated images matching `query`, oldest first rather than ranked.
+    /// Dates are read in the background, so the list fills in once they arrive.
+    fn show_photos_by_capture_date(&mut self, query: &str, cx: &mut Context<Self>) {
+        if self.cmd_palette_mode != CmdPaletteMode::Photos {
+            self.cmd_palette_mode = CmdPaletteMode::Photos;
+            self.refresh_capture_dates();
+        }
+        self.photos_query = query.to_string();
+        self.search_results = match (&self.board, &self.capture_dates) {
+            (Some(board), Some(dates)) if dates.board_id == board.id => board
+                .find_images_by_capture_date(&dates.dates, query)
+                .into_iter()
+                .map(|(id, label, found)| {
+                    SearchResult::new(id, label).with_positions(found.positions)
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.selected_result = 0;
+        cx.notify();
+    }
+
+    /// Read the open board's capture dates in the background, unless they're
+    /// current or already being read
+    pub fn refresh_capture_dates(&mut self) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        if self.capture_dates_loading.as_ref() == Some(&board.id) || self.capture_dates_current() {
+            return;
+        }
+
+        let board_id = board.id.clone();
+        let read_at = Instant::now();
+        let images = board.image_paths();
+        let tx = self.capture_dates_tx.clone();
+        self.capture_dates_loading = Some(board_id.clone());
+        self.background.spawn(
+            "capture-dates",
+            move || Ok(exif::capture_dates(&images)),
+            move |result| {
+                let _ = tx.send(CaptureDates {
+                    board_id,
+                    read_at,
+                    dates: result.unwrap_or_default(),
+                });
+            },
+        );
+    }
+
+    /// Whether the capture dates were read since the open board last changed
+    pub fn capture_dates_current(&self) -> bool {
+        match (&self.board, &self.capture_dates) {
+            (Some(board), Some(dates)) => {
+                dates.board_id == board.id && dates.read_at >= board.last_change()
+            }
+            _ => false,
+        }
+    }
+
+    /// Keep capture dates read in the background, then refresh the photo list
+    /// or run the arrange waiting for them
+    pub fn poll_capture_dates(&mut self, cx: &mut Context<Self>) {
+        while let Ok(dates) = self.capture_dates_rx.try_recv() {
+            cx.notify();
+            if self.capture_dates_loading.as_ref() == Some(&dates.board_id) {
+                self.capture_dates_loading = None;
+            }
+            // The board was closed while they were read
+            if self.board.as_ref().is_none_or(|board| board.id != dates.board_id) {
+                continue;
+            }
+            self.capture_dates = Some(dates);
+
+            if let Some(kind) = self.pending_date_arrange.take() {
+                self.arrange_with_capture_dates(kind, cx);
+            }
+            if self.command_palette.is_some() && self.cmd_palette_mode == CmdPaletteMode::Photos {
+                let query = self.photos_query.clone();
+                self.show_photos_by_capture_date(&query, cx);
+            }
+        }
+    }
+
+    /// Available themes fuzzy-matching `filter`, ranked with past use
+    fn rank_themes(&self, filter: &str, cx: &App) -> Vec<SearchResult> {
+        let matches = Settings::available_themes(cx)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(idx, name)| {
+                let key = theme_key(&name);
+                PaletteMatch::find(filter, idx as u64, "", &name, key)
+            })
+            .collect();
+        self.palette_history.rank(matches, unix_now())
+    }
+
+    /// Remember that a palette command was run, for frecency ranking
+    fn record_palette_use(&mut self, command: &str) {
+        let board_id = self.board.as_ref().map(|b| b.id.as_str()).unwrap_or("");
+        let key = if let Some(Ok(item_id)) = command.strip_prefix("__jump:").map(str::parse::<u64>)
+        {
+            item_key(board_id, item_id)
+        } else if let Some(id) = command.strip_prefix("__command:") {
+            command_key(id)
+        } else if let Some(theme_name) = command.strip_prefix("__theme:") {
+            theme_key(theme_name)
+        } else if let Some(Ok(index)) = command.strip_prefix("__view:").map(str::parse::<usize>) {
+            match self.board.as_ref().and_then(|b| b.bookmarks.get(index)) {
+                Some(bookmark) => view_key(board_id, &bookmark.name),
+                None => return,
             }
-            self.selected_result = 0;
         } else {
-            self.search_results.clear();
+            let word = command.split_whitespace().next().unwrap_or("");
+            if !PALETTE_COMMANDS.iter().any(|(_, cmd, _)| *cmd == word) {
+                return;
+            }
+            command_key(word)
+        };
+
+        self.palette_history.record(&key, unix_now());
+        if let Some(path) = PaletteHistory::default_path()
+            && let Err(e) = self.palette_history.save(&path)
+        {
+            tracing::warn!("Failed to save palette history: {}", e);
         }
-        cx.notify();
     }
 
     /// Enter theme selection mode in command palette
@@ -248,7 +376,7 @@ impl Humanboard {
         // Handle theme mode
         if self.cmd_palette_mode == CmdPaletteMode::Themes {
             if !self.search_results.is_empty() {
-                let (_, theme_name) = &self.search_results[self.selected_result];
+                let theme_name = &self.search_results[self.selected_result].name;
                 self.pending_command = Some(format!("__theme:{}", theme_name));
             }
             self.command_palette = None;
@@ -261,22 +389,28 @@ impl Humanboard {
 
         // If we have search results selected, check if it's a command or an item
         if !self.search_results.is_empty() {
-            let (item_id, _) = &self.search_results[self.selected_result];
+            let item_id = &self.search_results[self.selected_result].id;
 
             // Check for special command IDs (u64::MAX - N for commands)
             const CMD_THEME: u64 = u64::MAX - 1;
             const CMD_MD: u64 = u64::MAX - 2;
+            const CMD_ALIGN: u64 = u64::MAX - 3;
+            const CMD_FONT: u64 = u64::MAX - 4;
+            const CMD_PRESENT: u64 = u64::MAX - 5;
+            const CMD_FIT: u64 = u64::MAX - 6;
+            const CMD_FOCUS: u64 = u64::MAX - 7;
+            const CMD_VIEW: u64 = u64::MAX - 8;
+            const CMD_TEMPLATE: u64 = u64::MAX - 9;
+            const CMD_FILL: u64 = u64::MAX - 10;
+            const CMD_TAKEN: u64 = u64::MAX - 11;
+            const CMD_MARK: u64 = u64::MAX - 12;
+            const CMD_PAGES: u64 = u64::MAX - 13;
 
             match *item_id {
                 CMD_THEME => {
                     // Enter theme mode directly
                     self.cmd_palette_mode = CmdPaletteMode::Themes;
-                    let themes = Settings::available_themes(cx);
-                    self.search_results = themes
-                        .into_iter()
-                        .enumerate()
-                        .map(|(idx, name)| (idx as u64, name))
-                        .collect();
+                    self.search_results = self.rank_themes("", cx);
                     self.selected_result = 0;
                     cx.notify();
                     return; // Don't close palette, stay in theme mode
@@ -284,9 +418,50 @@ impl Humanboard {
                 CMD_MD => {
                     self.pending_command = Some("md".to_string());
                 }
+                CMD_ALIGN => {
+                    self.pending_command = Some("align".to_string());
+                }
+                CMD_FONT => {
+                    self.pending_command = Some("font".to_string());
+                }
+                CMD_PRESENT => {
+                    self.pending_command = Some("present".to_string());
+                }
+                CMD_FIT => {
+                    self.pending_command = Some("fit".to_string());
+                }
+                CMD_FOCUS => {
+                    self.pending_command = Some("focus".to_string());
+                }
+                CMD_VIEW => {
+                    self.pending_command = Some("view".to_string());
+                }
+                CMD_TEMPLATE => {
+                    self.pending_command = Some("template".to_string());
+                }
+                CMD_FILL => {
+                    self.pending_command = Some("fill".to_string());
+                }
+                CMD_MARK => {
+                    self.pending_command = Some("mark".to_string());
+                }
+                CMD_PAGES => {
+                    self.pending_command = Some("pages".to_string());
+                }
+                CMD_TAKEN => {
+                    // List photos by date in place, like theme mode
+                    self.show_photos_by_capture_date("", cx);
+                    return;
+                }
                 _ => {
-                    // Regular item - jump to it
-                    self.pending_command = Some(format!("__jump:{}", item_id));
+                    let command_id = registry_index(*item_id)
+                        .and_then(|index| self.palette_command_ids.get(index));
+                    self.pending_command = Some(match (bookmark_index(*item_id), command_id) {
+                        (Some(index), _) => format!("__view:{}", index),
+                        (None, Some(id)) => format!("__command:{}", id),
+                        // Regular item - jump to it
+                        (None, None) => format!("__jump:{}", item_id),
+                    });
                 }
             }
         } else {
@@ -308,6 +483,7 @@ impl Humanboard {
     pub fn process_pending_command(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(command) = self.pending_command.take() {
             let command = command.trim();
+            self.record_palette_use(command);
 
             // Handle jump command (from search result selection)
             if command.starts_with("__jump:") {
@@ -318,6 +494,12 @@ impl Humanboard {
                 {
                     self.jump_to_item(item_id, window, cx);
                 }
+            } else if let Some(id) = command.strip_prefix("__command:") {
+                if let Some(cmd) = self.command_registry.get(id)
+                    && cmd.is_enabled(self)
+                {
+                    cmd.execute(self, window, cx);
+                }
             } else if command.starts_with("__theme:") {
                 let theme_name = command.strip_prefix("__theme:").unwrap_or("");
                 if !theme_name.is_empty() {
@@ -328,6 +510,75 @@ impl Humanboard {
                 self.create_markdown_note(name.to_string(), window, cx);
             } else if command == "md" {
                 self.create_markdown_note("Untitled".to_string(), window, cx);
+            } else if command == "align" {
+                self.cycle_textbox_alignment(cx);
+            } else if let Some(name) = command.strip_prefix("align ") {
+                match TextAlignment::from_name(name) {
+                    Some(alignment) => self.set_textbox_alignment(alignment, cx),
+                    None => self.toast_manager.push(Toast::error(format!(
+                        "Unknown alignment '{}' (use left, center or right)",
+                        name.trim()
+                    ))),
+                }
+            } else if command == "font" {
+                self.set_textbox_font(None, cx);
+            } else if let Some(family) = command.strip_prefix("font ") {
+                let family = family.trim();
+                let family = (!family.is_empty()).then(|| family.to_string());
+                self.set_textbox_font(family, cx);
+            } else if command == "present" {
+                self.start_presentation(window, cx);
+            } else if command == "fit" {
+                self.zoom_to_fit(window, cx);
+            } else if command == "focus" {
+                self.zoom_to_selection(window, cx);
+            } else if command == "view" {
+                self.save_bookmark(None, cx);
+            } else if let Some(name) = command.strip_prefix("view ") {
+                let name = name.trim();
+                self.save_bookmark((!name.is_empty()).then(|| name.to_string()), cx);
+            } else if let Some(name) = command.strip_prefix("unview ") {
+                self.remove_bookmark(name.trim(), cx);
+            } else if command == "template" {
+                self.save_board_as_template(None, cx);
+            } else if let Some(name) = command.strip_prefix("template ") {
+                let name = name.trim();
+                self.save_board_as_template((!name.is_empty()).then(|| name.to_string()), cx);
+            } else if command == "fill" {
+                self.set_fill_color(None, cx);
+            } else if let Some(color) = command.strip_prefix("fill ") {
+                match crate::theme_editor::normalize_hex(color) {
+                    Some(hex) => self.set_fill_color(Some(hex), cx),
+                    None => self.toast_manager.push(Toast::error(format!(
+                        "Invalid colour '{}' (use #rrggbb)",
+                        color.trim()
+                    ))),
+                }
+            } else if command == "mark" {
+                self.toast_manager.push(Toast::info(
+                    "Type mark <time> <note>, e.g. mark 1:23 Chorus starts",
+                ));
+            } else if let Some(args) = command.strip_prefix("mark ") {
+                self.mark_selected_audio(args, cx);
+            } else if let Some(time) = command.strip_prefix("unmark ") {
+                self.unmark_selected_audio(time, cx);
+            } else if command == "pages" {
+                self.open_pdf_page_picker(cx);
+            } else if let Some(pages) = command.strip_prefix("pages ") {
+                self.place_selected_pdf_pages(pages, cx);
+            } else if command == "note" {
+                self.set_pending_pdf_note("", cx);
+            } else if let Some(text) = command.strip_prefix("note ") {
+                self.set_pending_pdf_note(text, cx);
+            } else if command == "comment" {
+                self.submit_pending_comment("", cx);
+            } else if let Some(text) = command.strip_prefix("comment ") {
+                self.submit_pending_comment(text, cx);
+            } else if let Some(Ok(index)) = command
+                .strip_prefix("__view:")
+                .map(|index| index.parse::<usize>())
+            {
+                self.go_to_bookmark(index, cx);
             }
         }
     }
@@ -357,6 +608,8 @@ impl Humanboard {
                 self.pan_animation = Some(PanAnimation {
                     start_offset: board.canvas_offset,
                     target_offset,
+                    start_zoom: board.zoom,
+                    target_zoom: board.zoom,
                     start_time: Instant::now(),
                     duration: Duration::from_millis(300),
                 });
@@ -391,10 +644,14 @@ impl Humanboard {
                     px(start_x + (target_x - start_x) * eased),
                     px(start_y + (target_y - start_y) * eased),
                 );
+                board.zoom = anim.start_zoom + (anim.target_zoom - anim.start_zoom) * eased;
             }
 
             if progress >= 1.0 {
-                // Animation complete
+                // Animation complete - persist the new viewport
+                if let Some(ref mut board) = self.board {
+                    board.mark_dirty();
+                }
                 self.pan_animation = None;
                 return false;
             }
@@ -497,6 +754,16 @@ impl Humanboard {
         if let Some(item) = clipboard {
             if let Some(text) = item.text() {
                 let text = text.trim();
+                // A `#` hex colour fills the selected shapes and text boxes;
+                // bare hex digits could just as well be a word or a number
+                if text.starts_with('#') {
+                    if let Some(hex) = crate::theme_editor::normalize_hex(text) {
+                        if self.fill_selection(Some(hex)) > 0 {
+                            cx.notify();
+                            return;
+                        }
+                    }
+                }
                 // Check if it's a URL
                 if text.starts_with("http://") || text.starts_with("https://") {
                     // Get center of window for paste position
@@ -514,3 +781,90 @@ impl Humanboard {
         }
     }
 }
+
+/// Commands offered by the palette: (result id, command, description).
+/// IDs count down from u64::MAX to avoid collision with item IDs.
+const PALETTE_COMMANDS: &[(u64, &str, &str)] = &[
+    (u64::MAX - 1, "theme", "Change theme"),
+    (u64::MAX - 2, "md", "Create markdown note"),
+    (u64::MAX - 3, "align", "Align text (left, center, right)"),
+    (u64::MAX - 4, "font", "Set text font (empty for default)"),
+    (u64::MAX - 5, "present", "Present frames"),
+    (u64::MAX - 6, "fit", "Zoom to fit all items"),
+    (u64::MAX - 7, "focus", "Zoom to selection"),
+    (
+        u64::MAX - 8,
+        "view",
+        "Save current view (view <name>, unview <name>)",
+    ),
+    (
+        u64::MAX - 9,
+        "template",
+        "Save board as template (template <name>)",
+    ),
+    (
+        u64::MAX - 10,
+        "fill",
+        "Fill shapes and text boxes (fill #rrggbb, empty for none)",
+    ),
+    (
+        u64::MAX - 11,
+        "taken",
+        "Find photos by capture date (taken 2024-05)",
+    ),
+    (
+        u64::MAX - 12,
+        "mark",
+        "Mark a time on the selected audio (mark 1:23 <note>, unmark 1:23)",
+    ),
+    (
+        u64::MAX - 13,
+        "pages",
+        "Place pages of the selected PDF (pages 1-5, 8, empty to pick)",
+    ),
+];
+
+// Palette history keys for each kind of result
+fn command_key(command: &str) -> String {
+    format!("cmd:{}", command)
+}
+
+fn theme_key(name: &str) -> String {
+    format!("theme:{}", name)
+}
+
+fn view_key(board_id: &str, name: &str) -> String {
+    format!("view:{}:{}", board_id, name)
+}
+
+fn item_key(board_id: &str, item_id: u64) -> String {
+    format!("item:{}:{}", board_id, item_id)
+}
+
+/// Search result IDs below this are saved views (counting down from it)
+const BOOKMARK_RESULT_BASE: u64 = u64::MAX - 1000;
+
+fn bookmark_result_id(index: usize) -> u64 {
+    BOOKMARK_RESULT_BASE - index as u64
+}
+
+fn bookmark_index(result_id: u64) -> Option<usize> {
+    BOOKMARK_RESULT_BASE
+        .checked_sub(result_id)
+        .filter(|&index| index < 1000)
+        .map(|index| index as usize)
+}
+
+/// Search result IDs below this are registry commands (counting down from it)
+const REGISTRY_RESULT_BASE: u64 = BOOKMARK_RESULT_BASE - 1000;
+
+fn registry_result_id(index: usize) -> u64 {
+    REGISTRY_RESULT_BASE - index as u64
+}
+
+fn registry_index(result_id: u64) -> Option<usize> {
+    REGISTRY_RESULT_BASE
+        .checked_sub(result_id)
+        .filter(|&index| index < 1000)
+        .map(|index| index as usize)
+}
@@ -0,0 +1,189 @@
+//! Comment methods - starting, replying to and resolving comment threads,
+//! and the comments sidebar.
+
+use super::{CommentsPanel, Humanboard, PendingComment};
+use crate::comments::{self, Comment, CommentAnchor};
+use crate::constants::{COMMENTS_SIDEBAR_WIDTH, HEADER_HEIGHT};
+use crate::notifications::Toast;
+use crate::render::dock::DOCK_WIDTH;
+use gpui::*;
+
+impl Humanboard {
+    /// Show or hide the comments sidebar
+    pub fn toggle_comments(&mut self, cx: &mut Context<Self>) {
+        if self.comments_panel.take().is_none() {
+            self.comments_panel = Some(CommentsPanel::default());
+        }
+        cx.notify();
+    }
+
+    /// Show the comments sidebar, highlighting a thread
+    pub fn open_comments(&mut self, focused: Option<u64>, cx: &mut Context<Self>) {
+        let panel = self
+            .comments_panel
+            .get_or_insert_with(CommentsPanel::default);
+        panel.focused = focused;
+        // A resolved thread is only listed alongside the other resolved ones
+        let resolved = focused.is_some_and(|id| {
+            self.board
+                .as_ref()
+                .and_then(|b| b.comment_threads.iter().find(|t| t.id == id))
+                .is_some_and(|t| t.resolved)
+        });
+        if resolved {
+            panel.show_resolved = true;
+        }
+        cx.notify();
+    }
+
+    pub fn close_comments(&mut self, cx: &mut Context<Self>) {
+        self.comments_panel = None;
+        cx.notify();
+    }
+
+    /// List resolved threads in the sidebar too, or stop listing them
+    pub fn toggle_resolved_comments(&mut self, cx: &mut Context<Self>) {
+        if let Some(ref mut panel) = self.comments_panel {
+            panel.show_resolved = !panel.show_resolved;
+            cx.notify();
+        }
+    }
+
+    /// Start a thread on the selected item, or at the middle of the canvas in
+    /// view when there isn't exactly one item selected
+    pub fn add_comment(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let anchor = match (self.selected_items.len(), self.selected_items.iter().next()) {
+            (1, Some(&id)) => CommentAnchor::Item(id),
+            _ => {
+                let (width, height) = self.comment_view_size(window);
+                let center = board.screen_to_canvas(point(
+                    px(DOCK_WIDTH + width / 2.0),
+                    px(HEADER_HEIGHT + height / 2.0),
+                ));
+                CommentAnchor::Point(f32::from(center.x), f32::from(center.y))
+            }
+        };
+        self.prompt_comment(PendingComment::New(anchor), window, cx);
+    }
+
+    /// Reply to a thread, typing the reply in the command palette
+    pub fn reply_to_comment_thread(
+        &mut self,
+        id: u64,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.prompt_comment(PendingComment::Reply(id), window, cx);
+    }
+
+    /// Open the command palette for the text of a comment
+    fn prompt_comment(
+        &mut self,
+        pending: PendingComment,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let focused = match pending {
+            PendingComment::Reply(id) => Some(id),
+            PendingComment::New(_) => None,
+        };
+        self.open_comments(focused, cx);
+        if let Some(ref mut panel) = self.comments_panel {
+            panel.pending = Some(pending);
+        }
+
+        self.show_command_palette(window, cx);
+        if let Some(ref input) = self.command_palette {
+            input.update(cx, |state, cx| state.set_value("comment ", window, cx));
+        }
+        // Enter runs the typed command rather than a search result
+        self.search_results.clear();
+        self.selected_result = 0;
+    }
+
+    /// Post the text typed for the comment being written
+    pub fn submit_pending_comment(&mut self, text: &str, cx: &mut Context<Self>) {
+        let pending = self
+            .comments_panel
+            .as_mut()
+            .and_then(|panel| panel.pending.take());
+        let Some(pending) = pending else {
+            self.toast_manager
+                .push(Toast::info("Choose Add Comment or Reply first"));
+            cx.notify();
+            return;
+        };
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+
+        let author = comments::author_name(&crate::settings::app_settings().author_name);
+        let comment = Comment::new(author, text);
+        let focused = match pending {
+            PendingComment::New(anchor) => Some(board.add_comment_thread(anchor, comment)),
+            PendingComment::Reply(id) => board.reply_to_thread(id, comment).then_some(id),
+        };
+        if let Some(ref mut panel) = self.comments_panel {
+            panel.focused = focused;
+        }
+        cx.notify();
+    }
+
+    /// Resolve or reopen a thread
+    pub fn set_comment_thread_resolved(&mut self, id: u64, resolved: bool, cx: &mut Context<Self>) {
+        if let Some(ref mut board) = self.board {
+            board.set_thread_resolved(id, resolved);
+            cx.notify();
+        }
+    }
+
+    /// Center the canvas on what a thread is about, and highlight the thread
+    pub fn jump_to_comment_thread(&mut self, id: u64, window: &mut Window, cx: &mut Context<Self>) {
+        let (width, height) = self.comment_view_size(window);
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+        let Some(anchor) = board
+            .comment_threads
+            .iter()
+            .find(|t| t.id == id)
+            .map(|t| t.anchor)
+        else {
+            return;
+        };
+
+        let screen_size = size(px(width), px(height));
+        match anchor {
+            CommentAnchor::Item(item_id) if board.get_item(item_id).is_some() => {
+                board.center_on_item(item_id, screen_size);
+                self.selected_items.clear();
+                self.selected_items.insert(item_id);
+            }
+            CommentAnchor::Item(_) => {
+                self.toast_manager
+                    .push(Toast::info("The item for this thread was deleted"));
+            }
+            CommentAnchor::Point(x, y) => board.center_on_point((x, y), screen_size),
+        }
+        if let Some(ref mut panel) = self.comments_panel {
+            panel.focused = Some(id);
+        }
+        cx.notify();
+    }
+
+    /// Size of the canvas area left uncovered by the comments sidebar
+    fn comment_view_size(&self, window: &Window) -> (f32, f32) {
+        let (width, height) = self.canvas_area_size(window);
+        match self.comments_panel {
+            Some(_) => ((width - COMMENTS_SIDEBAR_WIDTH).max(0.0), height),
+            None => (width, height),
+        }
+    }
+}
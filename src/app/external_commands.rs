@@ -0,0 +1,177 @@
+//! External command methods - run user-defined commands on the selection
+
+use super::{ExternalCommandResult, Humanboard, RunningExternalCommand};
+use crate::error::ExternalCommandError;
+use crate::external_commands::{self, ExternalCommandConfig};
+use crate::notifications::Toast;
+use crate::types::CanvasItem;
+use gpui::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Space between the selection and items added beside it, and between those items
+const ADDED_ITEM_GAP: f32 = 40.0;
+
+impl Humanboard {
+    /// Register the external commands declared in the user's settings,
+    /// replacing previous ones
+    pub fn register_external_commands(&mut self) {
+        let configs = crate::settings::app_settings().external_commands;
+        external_commands::register_all(&mut self.command_registry, &configs);
+    }
+
+    /// Run an external command on its own thread on the selected items it applies to
+    pub fn run_external_command(&mut self, config: &ExternalCommandConfig, cx: &mut Context<Self>) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let items: Vec<CanvasItem> = board
+            .items
+            .iter()
+            .filter(|item| self.selected_items.contains(&item.id))
+            .filter(|item| config.applies_to(&item.content))
+            .cloned()
+            .collect();
+        if items.is_empty() {
+            self.toast_manager.push(Toast::info(format!(
+                "Select items for \"{}\" first",
+                config.name
+            )));
+            cx.notify();
+            return;
+        }
+
+        // New items go to the right of the selection, top-aligned
+        let right = items
+            .iter()
+            .map(|item| item.position.0 + item.size.0)
+            .fold(f32::MIN, f32::max);
+        let top = items
+            .iter()
+            .map(|item| item.position.1)
+            .fold(f32::MAX, f32::min);
+
+        let board_id = board.id.clone();
+        let name = config.name.clone();
+        let command = config.command.clone();
+        let timeout = config.timeout();
+        let tx = self.external_command_tx.clone();
+        let run_id = self
+            .running_external_commands
+            .iter()
+            .map(|r| r.run_id + 1)
+            .max()
+            .unwrap_or(0);
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        // Commands can run for minutes, so they get their own thread rather
+        // than tying up the background executor's workers
+        let thread_cancel = cancel.clone();
+        let thread_name = name.clone();
+        let spawned = std::thread::Builder::new()
+            .name(format!("external_command:{}", name))
+            .spawn(move || {
+                let result = external_commands::run(&command, &items, timeout, &thread_cancel);
+                let _ = tx.send(ExternalCommandResult {
+                    board_id,
+                    name: thread_name,
+                    anchor: (right + ADDED_ITEM_GAP, top),
+                    run_id,
+                    result,
+                });
+            });
+        if let Err(e) = spawned {
+            self.toast_manager
+                .push(Toast::error(format!("Couldn't run \"{}\": {}", name, e)));
+            cx.notify();
+            return;
+        }
+
+        self.toast_manager
+            .push(Toast::info(format!("Running \"{}\"...", name)));
+        self.running_external_commands.push(RunningExternalCommand {
+            run_id,
+            name,
+            cancel,
+        });
+        cx.notify();
+    }
+
+    /// Kill every external command that's still running
+    pub fn cancel_external_commands(&mut self, cx: &mut Context<Self>) {
+        for running in &self.running_external_commands {
+            running.cancel.store(true, Ordering::Relaxed);
+        }
+        let message = match self.running_external_commands.as_slice() {
+            [] => "No external commands are running".to_string(),
+            [only] => format!("Cancelling \"{}\"...", only.name),
+            all => format!("Cancelling {} external commands...", all.len()),
+        };
+        self.toast_manager.push(Toast::info(message));
+        cx.notify();
+    }
+
+    /// Apply the output of finished external commands to the board
+    pub fn poll_external_commands(&mut self, cx: &mut Context<Self>) {
+        while let Ok(done) = self.external_command_rx.try_recv() {
+            cx.notify();
+            self.running_external_commands
+                .retain(|r| r.run_id != done.run_id);
+
+            let output = match done.result {
+                Ok(output) => output,
+                Err(ExternalCommandError::Cancelled) => {
+                    self.toast_manager
+                        .push(Toast::info(format!("\"{}\" was cancelled", done.name)));
+                    continue;
+                }
+                Err(e) => {
+                    tracing::error!("External command \"{}\" failed: {}", done.name, e);
+                    self.toast_manager
+                        .push(Toast::error(format!("\"{}\" failed: {}", done.name, e)));
+                    continue;
+                }
+            };
+            if output.is_empty() {
+                self.toast_manager
+                    .push(Toast::success(format!("\"{}\" finished", done.name)));
+                continue;
+            }
+
+            let Some(board) = self.board.as_mut().filter(|b| b.id == done.board_id) else {
+                self.toast_manager.push(Toast::warning(format!(
+                    "\"{}\" finished after its board was closed; changes were discarded",
+                    done.name
+                )));
+                continue;
+            };
+
+            // Stack items without a position below each other beside the selection
+            let (x, mut y) = done.anchor;
+            let added = output
+                .add
+                .into_iter()
+                .map(|new| {
+                    let position = new.position.unwrap_or_else(|| {
+                        let size = new.size.unwrap_or_else(|| new.content.default_size());
+                        let position = (x, y);
+                        y += size.1 + ADDED_ITEM_GAP;
+                        position
+                    });
+                    (new.content, position, new.size)
+                })
+                .collect();
+
+            let (added_ids, updated) = board.apply_item_changes(added, output.update);
+            if !added_ids.is_empty() {
+                self.selected_items = added_ids.iter().copied().collect();
+            }
+            self.toast_manager.push(Toast::success(format!(
+                "\"{}\" added {} and updated {} item(s)",
+                done.name,
+                added_ids.len(),
+                updated
+            )));
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! File drop methods - adding dropped or opened files and folders to the board
+
+use super::{DroppedFilesResult, Humanboard};
+use crate::board;
+use crate::notifications::Toast;
+use gpui::*;
+use std::path::PathBuf;
+
+impl Humanboard {
+    /// Add files and folders dropped at a screen position. They're read, and
+    /// copied for iCloud boards, in the background, then added in one step.
+    pub fn drop_files(
+        &mut self,
+        position: Point<Pixels>,
+        paths: Vec<PathBuf>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        if paths.is_empty() {
+            return;
+        }
+
+        let canvas_pos = board.screen_to_canvas(position);
+        let origin = (f32::from(canvas_pos.x), f32::from(canvas_pos.y));
+        let board_id = board.id.clone();
+        let copy_into = board.copy_target();
+        let folder_options = crate::settings::app_settings().folder_import;
+        let tx = self.dropped_files_tx.clone();
+
+        if paths.iter().any(|path| path.is_dir()) {
+            self.toast_manager.push(Toast::info("Importing folder..."));
+        }
+        self.background.spawn(
+            "file_drop",
+            move || {
+                Ok(board::prepare_file_drop(
+                    paths,
+                    &folder_options,
+                    copy_into.as_deref(),
+                ))
+            },
+            move |result| {
+                let _ = tx.send(DroppedFilesResult {
+                    board_id,
+                    origin,
+                    result,
+                });
+            },
+        );
+        cx.notify();
+    }
+
+    /// Add finished drops to the board
+    pub fn poll_dropped_files(&mut self, cx: &mut Context<Self>) {
+        while let Ok(done) = self.dropped_files_rx.try_recv() {
+            cx.notify();
+
+            let prepared = match done.result {
+                Ok(prepared) => prepared,
+                Err(e) => {
+                    self.toast_manager.push(Toast::error(format!(
+                        "Couldn't add the dropped files: {}",
+                        e
+                    )));
+                    continue;
+                }
+            };
+            for error in &prepared.errors {
+                self.toast_manager.push(Toast::error(error.clone()));
+            }
+            let skipped = prepared.skipped;
+
+            let Some(board) = self.board.as_mut().filter(|b| b.id == done.board_id) else {
+                self.toast_manager.push(Toast::warning(
+                    "The board was closed before the dropped files were read; nothing was added",
+                ));
+                continue;
+            };
+            let added = board.apply_file_drop(done.origin, prepared);
+
+            // Count items rather than paths, since a folder adds many
+            if !added.is_empty() {
+                self.toast_manager.push(Toast::success(match added.len() {
+                    1 => "Added 1 item".to_string(),
+                    n => format!("Added {} items", n),
+                }));
+            }
+            if skipped > 0 {
+                self.toast_manager.push(Toast::warning(format!(
+                    "Left out {} more file(s) - raise \"max_items\" in the folder_import setting to add them",
+                    skipped
+                )));
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Image editing methods - rotating, flipping, cropping and inspecting image items.
+
+use super::{BakedImagesResult, CropEditing, Humanboard, ImageInfoView};
+use crate::exif;
+use crate::image_edit::{self, CropRect, ImageAction, ImageEdit, OrientationKey};
+use crate::notifications::Toast;
+use crate::types::{CanvasItem, ItemContent};
+use gpui::*;
+use std::collections::HashSet;
+
+impl Humanboard {
+    /// Run an image action on the selected images
+    pub fn edit_images(&mut self, action: ImageAction, cx: &mut Context<Self>) {
+        let images = self.selected_images();
+        if images.is_empty() {
+            self.toast_manager
+                .push(Toast::info("Select an image first"));
+            cx.notify();
+            return;
+        }
+
+        match action {
+            ImageAction::Crop => {
+                if let [image] = images.as_slice() {
+                    self.start_crop(image);
+                } else {
+                    self.toast_manager
+                        .push(Toast::info("Select a single image to crop"));
+                }
+            }
+            ImageAction::Bake => self.bake_images(images),
+            _ => {
+                let edited = images
+                    .into_iter()
+                    .map(|item| transformed(item, action))
+                    .collect();
+                self.replace_images(edited);
+            }
+        }
+        cx.notify();
+    }
+
+    /// Make the copies turned and flipped images render from, in the background
+    pub fn ensure_oriented_images(&mut self) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let missing: HashSet<OrientationKey> = board
+            .items
+            .iter()
+            .filter_map(|item| match &item.content {
+                ItemContent::Image(path, edit) => image_edit::orientation_key(path, edit),
+                _ => None,
+            })
+            .filter(|key| !self.images_orienting.contains(key))
+            .filter(|key| image_edit::cached_oriented(key).is_none())
+            .collect();
+
+        for key in missing {
+            self.images_orienting.insert(key.clone());
+            let tx = self.oriented_tx.clone();
+            let oriented = key.clone();
+            self.background.spawn(
+                "image-orient",
+                move || {
+                    image_edit::orient(&key);
+                    Ok(())
+                },
+                move |_| {
+                    let _ = tx.send(oriented);
+                },
+            );
+        }
+    }
+
+    /// Redraw images whose oriented copies are ready
+    pub fn poll_oriented_images(&mut self, cx: &mut Context<Self>) {
+        while let Ok(key) = self.oriented_rx.try_recv() {
+            self.images_orienting.remove(&key);
+            cx.notify();
+        }
+    }
+
+    /// Selected image items, in board order
+    pub fn selected_images(&self) -> Vec<CanvasItem> {
+        let Some(ref board) = self.board else {
+            return Vec::new();
+        };
+        board
+            .items
+            .iter()
+            .filter(|item| self.selected_items.contains(&item.id))
+            .filter(|item| matches!(item.content, ItemContent::Image(..)))
+            .cloned()
+            .collect()
+    }
+
+    /// Swap edited items into the board as one undoable change
+    fn replace_images(&mut self, edited: Vec<CanvasItem>) {
+        if let Some(ref mut board) = self.board {
+            board.apply_item_changes(Vec::new(), edited);
+        }
+    }
+
+    /// Write each image's edits out to a new file in the board's `files/`,
+    /// in the background
+    fn bake_images(&mut self, images: Vec<CanvasItem>) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let board_id = board.id.clone();
+        let files_dir = board.files_dir();
+        let tx = self.baked_images_tx.clone();
+        self.background.spawn(
+            "image-bake",
+            move || {
+                let baked: Vec<_> = images
+                    .into_iter()
+                    .filter_map(|item| match item.content {
+                        ItemContent::Image(path, edit) => Some((
+                            item.id,
+                            image_edit::bake(&path, &edit, &files_dir).map_err(|e| e.to_string()),
+                        )),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(baked)
+            },
+            move |result| {
+                if let Ok(baked) = result {
+                    let _ = tx.send(BakedImagesResult { board_id, baked });
+                }
+            },
+        );
+    }
+
+    /// Point baked images at their new files as one undoable change
+    pub fn poll_baked_images(&mut self, cx: &mut Context<Self>) {
+        while let Ok(done) = self.baked_images_rx.try_recv() {
+            cx.notify();
+            // The board was closed while it ran
+            let Some(board) = self.board.as_ref().filter(|b| b.id == done.board_id) else {
+                continue;
+            };
+
+            let mut baked = Vec::new();
+            for (id, result) in done.baked {
+                let new_path = match result {
+                    Ok(new_path) => new_path,
+                    Err(e) => {
+                        self.toast_manager
+                            .push(Toast::error(format!("Couldn't save edited image: {}", e)));
+                        continue;
+                    }
+                };
+                // Skip images deleted or replaced while they were baking
+                let Some(mut item) = board.get_item(id).cloned() else {
+                    continue;
+                };
+                if let ItemContent::Image(ref mut path, ref mut edit) = item.content {
+                    *path = new_path;
+                    *edit = ImageEdit::default();
+                    baked.push(item);
+                }
+            }
+
+            if !baked.is_empty() {
+                self.toast_manager.push(Toast::success(format!(
+                    "Saved {} edited image(s) to the board's files",
+                    baked.len()
+                )));
+                self.replace_images(baked);
+            }
+        }
+    }
+
+    /// Enter crop mode for an image item
+    pub fn start_crop(&mut self, item: &CanvasItem) {
+        let ItemContent::Image(ref path, ref edit) = item.content else {
+            return;
+        };
+        // Uncropped images are letterboxed inside their item, cropped ones fill it
+        let image_rect = if edit.crop.is_some() {
+            CropRect::FULL
+        } else {
+            image::image_dimensions(path)
+                .map(|(width, height)| {
+                    let (width, height) = if edit.is_sideways() {
+                        (height, width)
+                    } else {
+                        (width, height)
+                    };
+                    image_edit::contain_rect(item.size, (width as f32, height as f32))
+                })
+                .unwrap_or(CropRect::FULL)
+        };
+
+        self.finish_layout_animation();
+        self.selected_items.clear();
+        self.selected_items.insert(item.id);
+        self.crop_editing = Some(CropEditing {
+            item_id: item.id,
+            image_rect,
+            selection: None,
+            drag_start: None,
+        });
+        self.toast_manager.push(Toast::info(
+            "Drag over the image to crop - Enter to apply, Escape to cancel",
+        ));
+    }
+
+    /// Apply the crop being drawn, if it's big enough
+    pub fn commit_crop(&mut self, cx: &mut Context<Self>) {
+        let Some(crop) = self.crop_editing.take() else {
+            return;
+        };
+        cx.notify();
+        let Some(selection) = crop.selection.filter(CropRect::is_usable) else {
+            return;
+        };
+        let Some(mut item) = self
+            .board
+            .as_ref()
+            .and_then(|board| board.get_item(crop.item_id).cloned())
+        else {
+            return;
+        };
+        let ItemContent::Image(_, ref mut edit) = item.content else {
+            return;
+        };
+
+        edit.crop_to(selection);
+        let (position, size) =
+            image_edit::sub_bounds(item.position, item.size, crop.image_rect.compose(selection));
+        item.position = position;
+        item.size = size;
+        self.replace_images(vec![item]);
+    }
+
+    /// Leave crop mode without changing the image
+    pub fn cancel_crop(&mut self, cx: &mut Context<Self>) {
+        if self.crop_editing.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Open the info card for the selected image, or close it if open
+    pub fn toggle_image_info(&mut self, cx: &mut Context<Self>) {
+        cx.notify();
+        if self.image_info.take().is_some() {
+            return;
+        }
+        let images = self.selected_images();
+        let [image] = images.as_slice() else {
+            self.toast_manager
+                .push(Toast::info("Select a single image to see its info"));
+            return;
+        };
+        let ItemContent::Image(ref path, _) = image.content else {
+            return;
+        };
+        let Some(info) = exif::info(path) else {
+            self.toast_manager
+                .push(Toast::error("Couldn't read the image file"));
+            return;
+        };
+        self.image_info = Some(ImageInfoView {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            fields: info.fields(),
+        });
+    }
+
+    /// Screen position as a fraction of the visible image being cropped
+    pub(crate) fn crop_point(&self, position: Point<Pixels>) -> Option<(f32, f32)> {
+        let crop = self.crop_editing.as_ref()?;
+        let item = self.board.as_ref()?.get_item(crop.item_id)?;
+        let canvas = self.screen_to_canvas(position, crate::constants::HEADER_HEIGHT);
+        let ((x, y), (width, height)) =
+            image_edit::sub_bounds(item.position, item.size, crop.image_rect);
+        Some((
+            (f32::from(canvas.x) - x) / width,
+            (f32::from(canvas.y) - y) / height,
+        ))
+    }
+}
+
+/// An image item after a rotate, flip or reset
+fn transformed(mut item: CanvasItem, action: ImageAction) -> CanvasItem {
+    let ItemContent::Image(ref path, ref mut edit) = item.content else {
+        return item;
+    };
+    let mut bounds = (item.position, item.size);
+    match action {
+        ImageAction::RotateClockwise => {
+            edit.rotate_clockwise();
+            bounds = image_edit::turned_bounds(bounds.0, bounds.1);
+        }
+        ImageAction::RotateCounterclockwise => {
+            edit.rotate_counterclockwise();
+            bounds = image_edit::turned_bounds(bounds.0, bounds.1);
+        }
+        ImageAction::FlipHorizontal => edit.flip_horizontal(),
+        ImageAction::FlipVertical => edit.flip_vertical(),
+        ImageAction::Reset => {
+            // Back to the file as imported, upright per its EXIF orientation
+            let upright = ImageEdit::from_exif(path);
+            if let Some(crop) = edit.crop {
+                bounds = image_edit::uncropped_bounds(bounds.0, bounds.1, crop);
+            }
+            if edit.is_sideways() != upright.is_sideways() {
+                bounds = image_edit::turned_bounds(bounds.0, bounds.1);
+            }
+            *edit = upright;
+        }
+        ImageAction::Crop | ImageAction::Bake => {}
+    }
+    (item.position, item.size) = bounds;
+    item
+}
@@ -0,0 +1,196 @@
+//! Import and export methods - boards to and from other apps' formats
+
+use super::{BoardFormat, FilePrompt, Humanboard};
+use crate::comments;
+use crate::excalidraw;
+use crate::json_canvas;
+use crate::notifications::Toast;
+use gpui::*;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, TryRecvError};
+
+impl Humanboard {
+    /// Ask for a file to import as a new board
+    pub fn prompt_import_board(&mut self, cx: &mut Context<Self>) {
+        let paths_rx = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Import".into()),
+        });
+
+        // Poll for the answer in render, like file drops
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(paths))) = paths_rx.await {
+                    if let Some(path) = paths.into_iter().next() {
+                        let _ = tx.send(FilePrompt::Import(path));
+                    }
+                }
+            })
+            .detach();
+        self.file_prompt_rx = Some(rx);
+    }
+
+    /// Ask where to export the open board
+    pub fn prompt_export_board(&mut self, format: BoardFormat, cx: &mut Context<Self>) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let name = self
+            .board_index
+            .get_board(&board.id)
+            .map(|meta| meta.name.clone())
+            .unwrap_or_else(|| "Board".to_string());
+        let suggested_name = format!("{}.{}", name, format.extension());
+        let directory = dirs::document_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let path_rx = cx.prompt_for_new_path(&directory, Some(&suggested_name));
+        let (tx, rx) = mpsc::channel();
+        cx.background_executor()
+            .spawn(async move {
+                if let Ok(Ok(Some(path))) = path_rx.await {
+                    let _ = tx.send(FilePrompt::Export(format, path));
+                }
+            })
+            .detach();
+        self.file_prompt_rx = Some(rx);
+    }
+
+    /// Act on a file picked in an import or export dialog
+    pub fn poll_file_prompts(&mut self, cx: &mut Context<Self>) {
+        let Some(ref rx) = self.file_prompt_rx else {
+            return;
+        };
+        let prompt = match rx.try_recv() {
+            Ok(prompt) => prompt,
+            Err(TryRecvError::Empty) => return,
+            // Dialog cancelled
+            Err(TryRecvError::Disconnected) => {
+                self.file_prompt_rx = None;
+                return;
+            }
+        };
+        self.file_prompt_rx = None;
+
+        match prompt {
+            FilePrompt::Import(path) => self.import_board(&path, cx),
+            FilePrompt::Export(format, path) => self.export_board(format, &path, cx),
+        }
+        cx.notify();
+    }
+
+    /// Import a file as a new board named after it, and open the board
+    pub fn import_board(&mut self, path: &Path, cx: &mut Context<Self>) {
+        let Some(format) = BoardFormat::from_path(path) else {
+            self.toast_manager.push(Toast::error(format!(
+                "Can't import {}: unsupported file type",
+                path.display()
+            )));
+            return;
+        };
+        // Parse before creating the board, so a bad file leaves nothing behind
+        let source = match format {
+            BoardFormat::JsonCanvas => json_canvas::read(path).map(ImportSource::JsonCanvas),
+            BoardFormat::Excalidraw => excalidraw::read(path).map(ImportSource::Excalidraw),
+        };
+        let source = match source {
+            Ok(source) => source,
+            Err(e) => {
+                tracing::error!("Failed to import {}: {}", path.display(), e);
+                self.toast_manager
+                    .push(Toast::error(format!("Import failed: {}", e)));
+                return;
+            }
+        };
+
+        if self.board.is_some() {
+            self.go_home(cx);
+        }
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Imported Board".to_string());
+        let metadata = self.board_index.create_board(name);
+
+        let (state, skipped) = match source {
+            ImportSource::JsonCanvas(canvas) => {
+                let (state, skipped) =
+                    json_canvas::to_board_state(&canvas, &json_canvas::vault_root(path));
+                let note = (skipped > 0).then(|| format!("{} couldn't be imported", skipped));
+                (state, note)
+            }
+            ImportSource::Excalidraw(scene) => {
+                // Embedded images become files of the new board
+                let files_dir = metadata.board_dir().join("files");
+                let (state, unsupported) = excalidraw::to_board_state(&scene, &files_dir);
+                let note = (!unsupported.is_empty()).then(|| format!("skipped {}", unsupported));
+                (state, note)
+            }
+        };
+        if let Err(e) = state.save_to_path(&metadata.board_path()) {
+            // Drop the new board along with any files already written for it
+            self.board_index.permanently_delete_board(&metadata.id);
+            self.toast_manager
+                .push(Toast::error(format!("Import failed: {}", e)));
+            return;
+        }
+
+        let mut message = format!(
+            "Imported {} item(s) from {}",
+            state.items.len(),
+            format.name()
+        );
+        if let Some(skipped) = skipped {
+            message.push_str(&format!("; {}", skipped));
+        }
+        self.toast_manager.push(Toast::success(message));
+        self.open_board(metadata.id, cx);
+    }
+
+    /// Export the open board to `path`
+    pub fn export_board(&mut self, format: BoardFormat, path: &Path, cx: &mut Context<Self>) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        // Comment threads go along as sticky notes beside what they're on
+        let items = comments::export_items(&board.items, &board.comment_threads);
+        let exported = match format {
+            BoardFormat::JsonCanvas => {
+                json_canvas::export_file(&items, path).map(|skipped| {
+                    (skipped > 0)
+                        .then(|| format!("{} item(s) have no JSON Canvas equivalent", skipped))
+                })
+            }
+            BoardFormat::Excalidraw => {
+                excalidraw::export_file(&items, path).map(|unsupported| {
+                    (!unsupported.is_empty()).then(|| format!("left out {}", unsupported))
+                })
+            }
+        };
+        match exported {
+            Ok(skipped) => {
+                let mut message = format!("Exported to {}", path.display());
+                if let Some(skipped) = skipped {
+                    message.push_str(&format!("; {}", skipped));
+                }
+                self.toast_manager.push(Toast::success(message));
+            }
+            Err(e) => {
+                tracing::error!("Failed to export to {}: {}", path.display(), e);
+                self.toast_manager
+                    .push(Toast::error(format!("Export failed: {}", e)));
+            }
+        }
+        cx.notify();
+    }
+}
+
+/// A parsed file waiting for its board to be created
+enum ImportSource {
+    JsonCanvas(json_canvas::JsonCanvas),
+    Excalidraw(excalidraw::Scene),
+}
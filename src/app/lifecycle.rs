@@ -4,15 +4,20 @@ use super::{AppView, CmdPaletteMode, CountdownState, Humanboard, SettingsTab, St
 use crate::animations::ModalAnimationState;
 use crate::background::BackgroundExecutor;
 use crate::board_index::BoardIndex;
+use crate::command_palette::PaletteHistory;
+use crate::command_registry::create_default_registry;
 use crate::focus::FocusManager;
 use crate::hit_testing::HitTester;
+use crate::media::LiveMedia;
 use crate::notifications::{Toast, ToastManager};
 use crate::perf::PerfMonitor;
 use crate::settings::Settings;
 use crate::settings_watcher::{SettingsEvent, SettingsWatcher};
-use crate::types::ToolType;
+use crate::templates::TemplateChoice;
+use crate::types::{ShapeType, ToolType};
 use gpui::*;
 use std::collections::{HashMap, HashSet};
+use std::sync::mpsc;
 use std::time::Instant;
 
 impl Humanboard {
@@ -26,7 +31,19 @@ impl Humanboard {
             AppView::Onboarding
         };
 
-        Self {
+        let (dropped_files_tx, dropped_files_rx) = mpsc::channel();
+        let (external_command_tx, external_command_rx) = mpsc::channel();
+        let (template_tx, template_rx) = mpsc::channel();
+        let (palette_tx, palette_rx) = mpsc::channel();
+        let (poster_tx, poster_rx) = mpsc::channel();
+        let (capture_dates_tx, capture_dates_rx) = mpsc::channel();
+        let (baked_images_tx, baked_images_rx) = mpsc::channel();
+        let (oriented_tx, oriented_rx) = mpsc::channel();
+        let (waveform_tx, waveform_rx) = mpsc::channel();
+        let (pdf_pages_tx, pdf_pages_rx) = mpsc::channel();
+        let (pdf_annotator_tx, pdf_annotator_rx) = mpsc::channel();
+
+        let mut this = Self {
             view: initial_view,
             board_index,
             editing_board_id: None,
@@ -35,6 +52,8 @@ impl Humanboard {
             show_create_board_modal: false,
             create_board_input: None,
             create_board_location: StorageLocation::default(),
+            create_board_template: TemplateChoice::default(),
+            saved_templates: Vec::new(),
             create_board_backdrop_clicked: false,
             show_trash: false,
             board: None,
@@ -68,12 +87,52 @@ impl Humanboard {
             dragging_pane_splitter: false,
             last_drop_pos: None,
             file_drop_rx: None,
+            dropped_files_tx,
+            dropped_files_rx,
+            file_prompt_rx: None,
             show_shortcuts: false,
+            show_minimap: false,
+            minimap_dragging: false,
             command_palette: None,
             pending_command: None,
             search_results: Vec::new(),
             selected_result: 0,
             cmd_palette_mode: CmdPaletteMode::default(),
+            palette_history: PaletteHistory::default_path()
+                .map(|p| PaletteHistory::load(&p))
+                .unwrap_or_default(),
+            command_registry: create_default_registry(),
+            palette_command_ids: Vec::new(),
+            external_command_tx,
+            external_command_rx,
+            running_external_commands: Vec::new(),
+            template_tx,
+            template_rx,
+            palette_tx,
+            palette_rx,
+            poster_tx,
+            poster_rx,
+            posters_loading: HashSet::new(),
+            capture_dates_tx,
+            capture_dates_rx,
+            capture_dates: None,
+            capture_dates_loading: None,
+            pending_date_arrange: None,
+            photos_query: String::new(),
+            baked_images_tx,
+            baked_images_rx,
+            oriented_tx,
+            oriented_rx,
+            images_orienting: HashSet::new(),
+            waveform_tx,
+            waveform_rx,
+            waveforms_loading: HashSet::new(),
+            pdf_pages_tx,
+            pdf_pages_rx,
+            pdf_annotator_tx,
+            pdf_annotator_rx,
+            live_media: LiveMedia::default(),
+            pending_seeks: HashMap::new(),
             youtube_webviews: HashMap::new(),
             audio_webviews: HashMap::new(),
             video_webviews: HashMap::new(),
@@ -86,6 +145,7 @@ impl Humanboard {
             settings_theme_scroll: ScrollHandle::new(),
             settings_font_index: 0,
             settings_font_scroll: ScrollHandle::new(),
+            theme_editor: None,
             modal_focus_index: 0,
 
             toast_manager: ToastManager::new(),
@@ -93,20 +153,35 @@ impl Humanboard {
             preview_right_tab_scroll: ScrollHandle::new(),
             cmd_palette_scroll: ScrollHandle::new(),
             pan_animation: None,
+            layout_animation: None,
+            presentation: None,
             modal_animations: ModalAnimationState::default(),
             selected_tool: ToolType::default(),
+            selected_shape: ShapeType::default(),
             drawing_start: None,
             drawing_current: None,
+            stroke_points: Vec::new(),
+            erase_ops: Vec::new(),
             editing_textbox_id: None,
             textbox_input: None,
             pending_textbox_drag: None,
+            crop_editing: None,
+            image_info: None,
+            pdf_page_picker: None,
+            pdf_annotator: None,
+            comments_panel: None,
             hit_tester: HitTester::new(),
             perf_monitor: PerfMonitor::new(),
             background: BackgroundExecutor::with_default_workers(),
             settings_watcher: crate::settings_watcher::default_settings_path()
                 .and_then(|p| SettingsWatcher::new(p).ok()),
+            keymap_watcher: crate::keymap::keymap_path().and_then(|p| SettingsWatcher::new(p).ok()),
             countdown: Some(CountdownState::until_midnight()),
-        }
+        };
+
+        this.reload_keymap(cx);
+        this.register_external_commands();
+        this
     }
 
     /// Check for settings file changes and reload if needed.
@@ -117,7 +192,11 @@ impl Humanboard {
                     SettingsEvent::Modified | SettingsEvent::Created => {
                         tracing::info!("Settings file changed, reloading...");
                         // Reload settings
+                        if let Err(e) = crate::settings::init_settings() {
+                            tracing::error!("Failed to reload user settings: {}", e);
+                        }
                         self.settings = Settings::load();
+                        self.register_external_commands();
                         self.toast_manager.push(Toast::info("Settings reloaded"));
                         cx.notify();
                     }
@@ -134,6 +213,37 @@ impl Humanboard {
         }
     }
 
+    /// Check for keymap file changes and rebuild the keybindings if needed.
+    pub fn check_keymap_reload(&mut self, cx: &mut Context<Self>) {
+        let Some(event) = self.keymap_watcher.as_mut().and_then(|w| w.poll()) else {
+            return;
+        };
+
+        match event {
+            SettingsEvent::Modified | SettingsEvent::Created | SettingsEvent::Deleted => {
+                tracing::info!("Keymap file changed, reloading...");
+                if self.reload_keymap(cx) {
+                    self.toast_manager.push(Toast::info("Keymap reloaded"));
+                }
+                cx.notify();
+            }
+            SettingsEvent::Error(e) => {
+                tracing::error!("Keymap watch error: {}", e);
+            }
+        }
+    }
+
+    /// Layer the user keymap over the default bindings, reporting any problems
+    /// as toasts. Returns true if the keymap loaded without problems.
+    pub fn reload_keymap(&mut self, cx: &mut Context<Self>) -> bool {
+        let issues = crate::keymap::reload(cx);
+        for issue in &issues {
+            tracing::warn!("Keymap: {}", issue);
+            self.toast_manager.push(Toast::warning(issue.to_string()));
+        }
+        issues.is_empty()
+    }
+
     /// Returns true if a code editor is currently in edit mode
     pub fn is_code_editing(&self) -> bool {
         self.preview
@@ -0,0 +1,337 @@
+//! Media methods - posters, waveforms and markers, activating players and
+//! the player budget.
+
+use super::Humanboard;
+use crate::constants::HEADER_HEIGHT;
+use crate::media::{self, MediaSource};
+use crate::notifications::Toast;
+use crate::types::{CanvasItem, ItemContent};
+use crate::waveform::{self, AudioMarker};
+use gpui::*;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// How far either side of a marker, in screen pixels, still hits it
+const MARKER_HIT_RADIUS: f32 = 6.0;
+
+impl Humanboard {
+    /// Load posters for media items that don't have one yet, in the background
+    pub fn ensure_media_posters(&mut self) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let missing: Vec<MediaSource> = board
+            .items
+            .iter()
+            .filter_map(|item| MediaSource::of(&item.content))
+            .filter(|source| !self.posters_loading.contains(source))
+            .filter(|source| media::cached_poster(source).is_none())
+            .collect();
+
+        for source in missing {
+            self.posters_loading.insert(source.clone());
+            let tx = self.poster_tx.clone();
+            let loaded = source.clone();
+            self.background.spawn(
+                "media-poster",
+                move || {
+                    media::load_poster(&source);
+                    Ok(())
+                },
+                move |_| {
+                    let _ = tx.send(loaded);
+                },
+            );
+        }
+    }
+
+    /// Redraw media items whose posters have loaded
+    pub fn poll_media_posters(&mut self, cx: &mut Context<Self>) {
+        while let Ok(source) = self.poster_rx.try_recv() {
+            self.posters_loading.remove(&source);
+            cx.notify();
+        }
+    }
+
+    /// Decode waveforms for audio items that don't have one yet, in the background
+    pub fn ensure_waveforms(&mut self) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let missing: HashSet<PathBuf> = board
+            .items
+            .iter()
+            .filter_map(|item| match &item.content {
+                ItemContent::Audio(path, _) => Some(path.clone()),
+                _ => None,
+            })
+            .filter(|path| !self.waveforms_loading.contains(path))
+            .filter(|path| waveform::cached_waveform(path).is_none())
+            .collect();
+
+        for path in missing {
+            self.waveforms_loading.insert(path.clone());
+            let tx = self.waveform_tx.clone();
+            let loaded = path.clone();
+            self.background.spawn(
+                "audio-waveform",
+                move || {
+                    waveform::load_waveform(&path);
+                    Ok(())
+                },
+                move |_| {
+                    let _ = tx.send(loaded);
+                },
+            );
+        }
+    }
+
+    /// Redraw audio items whose waveforms have been decoded
+    pub fn poll_waveforms(&mut self, cx: &mut Context<Self>) {
+        while let Ok(path) = self.waveform_rx.try_recv() {
+            self.waveforms_loading.remove(&path);
+            cx.notify();
+        }
+    }
+
+    /// Keep the activated media within the player budget, unloading
+    /// off-screen players first
+    pub fn enforce_media_budget(&mut self, visible: &HashSet<u64>) {
+        let Some(ref board) = self.board else {
+            self.live_media.clear();
+            return;
+        };
+        let existing: HashSet<u64> = board.items.iter().map(|item| item.id).collect();
+        let budget = crate::settings::app_settings().max_media_players;
+        self.live_media.enforce(&existing, visible, budget);
+    }
+
+    /// Load the player for a media item
+    pub fn activate_media(&mut self, item_id: u64, cx: &mut Context<Self>) {
+        self.live_media.activate(item_id);
+        cx.notify();
+    }
+
+    /// Play an audio item from `seconds`, loading its player first if needed
+    pub fn seek_audio(&mut self, item_id: u64, seconds: f64, cx: &mut Context<Self>) {
+        match self.audio_webviews.get(&item_id) {
+            Some(player) => player.seek(seconds, cx),
+            None => {
+                self.pending_seeks.insert(item_id, seconds);
+            }
+        }
+        self.live_media.activate(item_id);
+        cx.notify();
+    }
+
+    /// The time under a screen point on an audio item's waveform, and how
+    /// many seconds either side of it still hit a marker
+    pub(crate) fn waveform_time_at(
+        &self,
+        item_id: u64,
+        position: Point<Pixels>,
+    ) -> Option<(f64, f64)> {
+        let board = self.board.as_ref()?;
+        let item = board.get_item(item_id)?;
+        let ItemContent::Audio(ref path, _) = item.content else {
+            return None;
+        };
+        let waveform = waveform::cached_waveform(path).filter(|w| !w.is_empty())?;
+
+        let canvas = self.screen_to_canvas(position, HEADER_HEIGHT);
+        let x = f32::from(canvas.x) - item.position.0;
+        let y = f32::from(canvas.y) - item.position.1;
+        if y < item.size.1 - waveform::strip_height(item.size.1) || y > item.size.1 {
+            return None;
+        }
+
+        let track = (item.size.0 - 2.0 * waveform::WAVEFORM_INSET).max(1.0) * board.zoom;
+        let tolerance = waveform.seconds_at(MARKER_HIT_RADIUS / track);
+        Some((
+            waveform.seconds_at(waveform::strip_fraction(x, item.size.0)),
+            tolerance,
+        ))
+    }
+
+    /// The marker under a screen point on an audio item's waveform
+    pub(crate) fn audio_marker_at(
+        &self,
+        item_id: u64,
+        position: Point<Pixels>,
+    ) -> Option<AudioMarker> {
+        let (seconds, tolerance) = self.waveform_time_at(item_id, position)?;
+        let item = self.board.as_ref()?.get_item(item_id)?;
+        let ItemContent::Audio(_, ref markers) = item.content else {
+            return None;
+        };
+        waveform::marker_near(markers, seconds, tolerance).cloned()
+    }
+
+    /// Open the palette to mark the time under a screen point on an audio
+    /// item's waveform, with the note of any marker already there
+    pub(crate) fn prompt_audio_marker(
+        &mut self,
+        item_id: u64,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((seconds, _)) = self.waveform_time_at(item_id, position) else {
+            return;
+        };
+        let (seconds, note) = match self.audio_marker_at(item_id, position) {
+            Some(marker) => (marker.seconds, marker.note),
+            None => (seconds, String::new()),
+        };
+        let command = format!("mark {} {}", waveform::format_timestamp(seconds), note);
+
+        self.selected_items.clear();
+        self.selected_items.insert(item_id);
+        self.show_command_palette(window, cx);
+        if let Some(ref input) = self.command_palette {
+            input.update(cx, |state, cx| state.set_value(command, window, cx));
+        }
+        // Enter runs the typed command rather than a search result
+        self.search_results.clear();
+        self.selected_result = 0;
+    }
+
+    /// Add a marker to the selected audio item, or change the note of the
+    /// one at that time, from `mark` arguments (`1:23 Chorus starts`)
+    pub fn mark_selected_audio(&mut self, args: &str, cx: &mut Context<Self>) {
+        let args = args.trim();
+        let (time, note) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        let Some(seconds) = waveform::parse_timestamp(time) else {
+            self.toast_manager.push(Toast::error(format!(
+                "Invalid time '{}' (use 1:23 or 83)",
+                time
+            )));
+            cx.notify();
+            return;
+        };
+        let note = note.trim().to_string();
+        self.update_audio_markers(cx, |markers, duration| {
+            if duration.is_some_and(|duration| seconds > duration) {
+                return Err(format!(
+                    "{} is past the end",
+                    waveform::format_timestamp(seconds)
+                ));
+            }
+            waveform::set_marker(markers, seconds, note);
+            Ok(format!("Marked {}", waveform::format_timestamp(seconds)))
+        });
+    }
+
+    /// Remove the marker at a time (`1:23`) from the selected audio item
+    pub fn unmark_selected_audio(&mut self, time: &str, cx: &mut Context<Self>) {
+        let Some(seconds) = waveform::parse_timestamp(time) else {
+            self.toast_manager.push(Toast::error(format!(
+                "Invalid time '{}' (use 1:23 or 83)",
+                time.trim()
+            )));
+            cx.notify();
+            return;
+        };
+        self.update_audio_markers(cx, |markers, _| {
+            match waveform::remove_marker(markers, seconds) {
+                Some(marker) => Ok(format!(
+                    "Removed the marker at {}",
+                    waveform::format_timestamp(marker.seconds)
+                )),
+                None => Err(format!(
+                    "No marker at {}",
+                    waveform::format_timestamp(seconds)
+                )),
+            }
+        });
+    }
+
+    /// Change the markers of the single selected audio item as one undoable
+    /// step. `change` gets the item's duration, if it's been decoded, and
+    /// returns the message to show.
+    fn update_audio_markers(
+        &mut self,
+        cx: &mut Context<Self>,
+        change: impl FnOnce(&mut Vec<AudioMarker>, Option<f64>) -> Result<String, String>,
+    ) {
+        let selected: Vec<&CanvasItem> = self
+            .board
+            .iter()
+            .flat_map(|board| board.items.iter())
+            .filter(|item| self.selected_items.contains(&item.id))
+            .filter(|item| matches!(item.content, ItemContent::Audio(..)))
+            .collect();
+        let mut item = match selected.as_slice() {
+            [item] => (*item).clone(),
+            [] => {
+                self.toast_manager
+                    .push(Toast::info("Select an audio item first"));
+                cx.notify();
+                return;
+            }
+            _ => {
+                self.toast_manager
+                    .push(Toast::info("Select a single audio item"));
+                cx.notify();
+                return;
+            }
+        };
+
+        let ItemContent::Audio(ref path, ref mut markers) = item.content else {
+            return;
+        };
+        let duration = waveform::cached_waveform(path)
+            .filter(|w| !w.is_empty())
+            .map(|w| w.duration.as_secs_f64());
+        match change(markers, duration) {
+            Ok(message) => {
+                if let Some(ref mut board) = self.board {
+                    board.apply_item_changes(Vec::new(), vec![item]);
+                }
+                self.toast_manager.push(Toast::success(message));
+            }
+            Err(message) => self.toast_manager.push(Toast::error(message)),
+        }
+        cx.notify();
+    }
+
+    /// Load players for the selected media items
+    pub fn play_selected_media(&mut self, cx: &mut Context<Self>) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let selected: Vec<u64> = board
+            .items
+            .iter()
+            .filter(|item| self.selected_items.contains(&item.id))
+            .filter(|item| MediaSource::of(&item.content).is_some())
+            .map(|item| item.id)
+            .collect();
+
+        if selected.is_empty() {
+            self.toast_manager
+                .push(Toast::info("Select an audio, video or YouTube item first"));
+        }
+        for id in selected {
+            self.live_media.activate(id);
+        }
+        cx.notify();
+    }
+
+    /// Unload every media player, leaving their posters
+    pub fn unload_media(&mut self, cx: &mut Context<Self>) {
+        let count = self.live_media.len();
+        self.live_media.clear();
+        if count == 0 {
+            self.toast_manager
+                .push(Toast::info("No media players are loaded"));
+        } else {
+            self.toast_manager.push(Toast::success(format!(
+                "Unloaded {} media player{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            )));
+        }
+        cx.notify();
+    }
+}
@@ -6,27 +6,53 @@
 //! - `lifecycle` - Initialization and cleanup methods
 //! - `board_management` - Board CRUD operations
 //! - `settings_methods` - Theme, font, and settings management
+//! - `theme_editor` - Forking, previewing and saving themes
 //! - `command_palette_methods` - Command palette functionality
 //! - `preview_core` - Core preview panel operations
 //! - `preview_webviews` - YouTube, Audio, Video webview management
+//! - `media` - Media posters, waveforms and markers, activating players and the player budget
+//! - `pdf_pages` - The PDF page picker and placing pages on the canvas
+//! - `pdf_annotations` - Annotating PDF pages and quoting highlights on the canvas
+//! - `comments` - Comment threads on items and canvas points, and the comments sidebar
 //! - `preview_tabs` - Tab close, drag, and history management
 //! - `preview_panes` - Tab switching and pane split management
 //! - `preview_search` - Find in file functionality
 //! - `textbox` - Textbox editing and utility methods
+//! - `presentation` - Presentation mode navigation
+//! - `viewport` - Zoom to fit, zoom to selection and viewport bookmarks
+//! - `import_export` - Importing and exporting other apps' board formats
+//! - `file_drop` - Adding dropped or opened files and folders in the background
+//! - `arrange` - Animated automatic layouts of the selection or board
+//! - `image_editing` - Rotating, flipping and cropping image items
+//! - `palette` - Colour palettes from images, and fill colours
 
 mod types;
 mod state;
 mod lifecycle;
 mod board_management;
 mod settings_methods;
+mod theme_editor;
 mod command_palette_methods;
+mod external_commands;
+mod import_export;
+mod file_drop;
 mod preview_core;
 mod preview_webviews;
+mod media;
+mod pdf_pages;
+mod pdf_annotations;
+mod comments;
 mod preview_tabs;
 mod preview_panes;
 mod preview_search;
 mod textbox;
+mod presentation;
+mod viewport;
+mod arrange;
+mod image_editing;
+mod palette;
 mod error_recovery;
 
 pub use types::*;
 pub use state::Humanboard;
+pub use theme_editor::resolved_theme_color;
@@ -0,0 +1,129 @@
+//! Palette methods - extracting colour swatches from images and filling with them.
+
+use super::{Humanboard, PaletteResult};
+use crate::notifications::Toast;
+use crate::palette::{self, PALETTE_SIZE};
+use crate::types::ItemContent;
+use gpui::*;
+
+impl Humanboard {
+    /// Extract the dominant colours of each selected image in the background
+    pub fn extract_palettes(&mut self, cx: &mut Context<Self>) {
+        let images = self.selected_images();
+        if images.is_empty() {
+            self.toast_manager
+                .push(Toast::info("Select an image first"));
+            cx.notify();
+            return;
+        }
+        let Some(ref board) = self.board else {
+            return;
+        };
+
+        for image in images {
+            let ItemContent::Image(path, edit) = image.content else {
+                continue;
+            };
+            let board_id = board.id.clone();
+            let tx = self.palette_tx.clone();
+            self.background.spawn(
+                &format!("palette:{}", image.id),
+                move || palette::extract(&path, &edit, PALETTE_SIZE).map_err(|e| e.to_string()),
+                move |result| {
+                    let _ = tx.send(PaletteResult {
+                        board_id,
+                        item_id: image.id,
+                        result,
+                    });
+                },
+            );
+        }
+        cx.notify();
+    }
+
+    /// Place finished palettes as swatch rows under their images
+    pub fn poll_palettes(&mut self, cx: &mut Context<Self>) {
+        while let Ok(done) = self.palette_rx.try_recv() {
+            cx.notify();
+
+            let colors = match done.result {
+                Ok(colors) => colors,
+                Err(e) => {
+                    self.toast_manager
+                        .push(Toast::error(format!("Couldn't read image colours: {}", e)));
+                    continue;
+                }
+            };
+            // The board was closed or the image deleted while it ran
+            let Some(board) = self.board.as_mut().filter(|b| b.id == done.board_id) else {
+                continue;
+            };
+            let Some(image) = board.get_item(done.item_id).cloned() else {
+                continue;
+            };
+            if colors.is_empty() {
+                self.toast_manager
+                    .push(Toast::info("No colours found in the image"));
+                continue;
+            }
+
+            let (added_ids, _) =
+                board.apply_item_changes(palette::swatches(&image, &colors), Vec::new());
+            self.selected_items = added_ids.into_iter().collect();
+            self.toast_manager.push(Toast::success(format!(
+                "Added {} colour swatches - copy one to get its hex code",
+                colors.len()
+            )));
+        }
+    }
+
+    /// Set or clear the fill of the selected shapes and text boxes
+    pub fn set_fill_color(&mut self, color: Option<String>, cx: &mut Context<Self>) {
+        if self.fill_selection(color) == 0 {
+            self.toast_manager
+                .push(Toast::info("Select a shape or text box to fill"));
+        }
+        cx.notify();
+    }
+
+    /// Fill the selected shapes and text boxes as one undoable change.
+    /// Returns how many items were changed.
+    pub fn fill_selection(&mut self, color: Option<String>) -> usize {
+        let Some(ref mut board) = self.board else {
+            return 0;
+        };
+        let filled: Vec<_> = board
+            .items
+            .iter()
+            .filter(|item| self.selected_items.contains(&item.id))
+            .filter_map(|item| {
+                let mut item = item.clone();
+                match item.content {
+                    ItemContent::Shape {
+                        ref mut fill_color, ..
+                    }
+                    | ItemContent::TextBox {
+                        ref mut fill_color, ..
+                    } => *fill_color = color.clone(),
+                    _ => return None,
+                }
+                Some(item)
+            })
+            .collect();
+        board.apply_item_changes(Vec::new(), filled).1
+    }
+
+    /// Fill colour of the one selected item, such as a palette swatch
+    pub fn selected_fill_color(&self) -> Option<String> {
+        if self.selected_items.len() != 1 {
+            return None;
+        }
+        let id = self.selected_items.iter().next()?;
+        match &self.board.as_ref()?.get_item(*id)?.content {
+            ItemContent::Shape { fill_color, .. } | ItemContent::TextBox { fill_color, .. } => {
+                fill_color.clone()
+            }
+            _ => None,
+        }
+    }
+}
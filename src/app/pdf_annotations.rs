@@ -0,0 +1,415 @@
+//! PDF annotation methods - the annotator, its tools and quoting highlights
+//! on the canvas.
+
+use super::{FocusedPane, Humanboard, PdfAnnotator, PdfAnnotatorPage, PendingNote, PreviewTab};
+use crate::notifications::Toast;
+use crate::pdf_annotations::{self, AnnotationKind, AnnotationTool, PageRect};
+use crate::pdf_thumbnail::{self, PLACED_PAGE_WIDTH};
+use crate::render::overlays::annotator_page_frame;
+use crate::types::ItemContent;
+use gpui::*;
+use std::path::PathBuf;
+
+/// Drags smaller than this, as a fraction of the page, count as clicks
+const MIN_DRAG: f32 = 0.005;
+
+/// How close to a note, as a fraction of the page, a click picks it
+const NOTE_HIT_RADIUS: f32 = 0.02;
+
+impl Humanboard {
+    /// Annotate the PDF in the focused preview tab, or the selected PDF or
+    /// PDF page
+    pub fn annotate_pdf(&mut self, cx: &mut Context<Self>) {
+        match self.annotatable_pdf() {
+            Some((path, page)) => self.open_pdf_annotator(path, page, cx),
+            None => {
+                self.toast_manager
+                    .push(Toast::info("Open or select a PDF to annotate"));
+                cx.notify();
+            }
+        }
+    }
+
+    /// The PDF, and page, that annotating would start on
+    fn annotatable_pdf(&self) -> Option<(PathBuf, usize)> {
+        let tab = self.preview.as_ref().and_then(|preview| {
+            if preview.is_pane_split && preview.focused_pane == FocusedPane::Right {
+                preview.right_tabs.get(preview.right_active_tab)
+            } else {
+                preview.tabs.get(preview.active_tab)
+            }
+        });
+        if let Some(PreviewTab::Pdf { path, .. }) = tab {
+            return Some((path.clone(), 1));
+        }
+
+        let board = self.board.as_ref()?;
+        let mut selected = board
+            .items
+            .iter()
+            .filter(|item| self.selected_items.contains(&item.id));
+        match (selected.next().map(|item| &item.content), selected.next()) {
+            (Some(ItemContent::Pdf { path, .. }), None) => Some((path.clone(), 1)),
+            (Some(ItemContent::PdfPage { path, page, .. }), None) => Some((path.clone(), *page)),
+            _ => None,
+        }
+    }
+
+    /// Open the annotator on a page of a PDF
+    pub fn open_pdf_annotator(&mut self, path: PathBuf, page: usize, cx: &mut Context<Self>) {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "PDF".to_string());
+        self.pdf_annotator = Some(PdfAnnotator {
+            path,
+            name,
+            page: page.max(1),
+            page_count: None,
+            image: None,
+            error: None,
+            tool: AnnotationTool::default(),
+            drag: None,
+            pending_note: None,
+        });
+        self.render_annotator_page();
+        cx.notify();
+    }
+
+    pub fn close_pdf_annotator(&mut self, cx: &mut Context<Self>) {
+        self.pdf_annotator = None;
+        cx.notify();
+    }
+
+    pub fn set_annotation_tool(&mut self, tool: AnnotationTool, cx: &mut Context<Self>) {
+        if let Some(ref mut annotator) = self.pdf_annotator {
+            annotator.tool = tool;
+            annotator.drag = None;
+            cx.notify();
+        }
+    }
+
+    /// Show another page in the annotator, if the PDF has it
+    pub fn go_to_annotator_page(&mut self, page: usize, cx: &mut Context<Self>) {
+        let Some(ref mut annotator) = self.pdf_annotator else {
+            return;
+        };
+        let last = annotator.page_count.unwrap_or(1).max(1);
+        let page = page.clamp(1, last);
+        if page == annotator.page {
+            return;
+        }
+        annotator.page = page;
+        annotator.image = None;
+        annotator.error = None;
+        annotator.drag = None;
+        self.render_annotator_page();
+        cx.notify();
+    }
+
+    /// Show the page an annotation is on
+    pub fn show_pdf_annotation(&mut self, id: u64, cx: &mut Context<Self>) {
+        let page = self
+            .board
+            .as_ref()
+            .and_then(|board| board.pdf_annotations.iter().find(|a| a.id == id))
+            .map(|annotation| annotation.page);
+        if let Some(page) = page {
+            self.go_to_annotator_page(page, cx);
+        }
+    }
+
+    /// Render the annotator's page in the background
+    fn render_annotator_page(&self) {
+        let Some(ref annotator) = self.pdf_annotator else {
+            return;
+        };
+        let path = annotator.path.clone();
+        let render_path = path.clone();
+        let page = annotator.page;
+        let tx = self.pdf_annotator_tx.clone();
+        self.background.spawn(
+            &format!("pdf-annotator:{}", page),
+            move || {
+                let count =
+                    pdf_thumbnail::pdf_page_count(&render_path).map_err(|e| e.to_string())?;
+                let rendered =
+                    pdf_thumbnail::render_pdf_pages(&render_path, &[page], PLACED_PAGE_WIDTH)
+                        .map_err(|e| e.to_string())?;
+                let rendered = rendered
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| format!("There's no page {}", page))?;
+                Ok((count, rendered))
+            },
+            move |result| {
+                let _ = tx.send(PdfAnnotatorPage { path, page, result });
+            },
+        );
+    }
+
+    /// Show pages rendered for the annotator
+    pub fn poll_pdf_annotator(&mut self, cx: &mut Context<Self>) {
+        while let Ok(done) = self.pdf_annotator_rx.try_recv() {
+            cx.notify();
+            // The annotator was closed, or moved to another page, while it ran
+            let Some(annotator) = self
+                .pdf_annotator
+                .as_mut()
+                .filter(|a| a.path == done.path && a.page == done.page)
+            else {
+                continue;
+            };
+            match done.result {
+                Ok((count, rendered)) => {
+                    annotator.page_count = Some(count);
+                    annotator.image = Some(rendered);
+                }
+                Err(e) => annotator.error = Some(e),
+            }
+        }
+    }
+
+    /// A window position as a fraction of the page shown in the annotator
+    fn annotator_point(&self, position: Point<Pixels>, window: &Window) -> Option<(f32, f32)> {
+        let image = self.pdf_annotator.as_ref()?.image.as_ref()?;
+        let (x, y, width, height) = annotator_page_frame(window.bounds().size, image.size);
+        Some((
+            (f32::from(position.x) - x) / width,
+            (f32::from(position.y) - y) / height,
+        ))
+    }
+
+    /// Start a highlight or box, or place or pick a note
+    pub fn annotator_mouse_down(
+        &mut self,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(point) = self
+            .annotator_point(position, window)
+            .filter(|&(x, y)| (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y))
+        else {
+            return;
+        };
+        let Some(ref mut annotator) = self.pdf_annotator else {
+            return;
+        };
+        if annotator.tool != AnnotationTool::Note {
+            annotator.drag = Some((point, point));
+            cx.notify();
+            return;
+        }
+
+        // Clicking an existing note changes its text
+        let page = annotator.page;
+        let existing = self.board.as_ref().and_then(|board| {
+            board.pdf_annotations.iter().find_map(|a| match &a.kind {
+                AnnotationKind::Note { point: at, text }
+                    if a.path == annotator.path
+                        && a.page == page
+                        && (at.0 - point.0).hypot(at.1 - point.1) <= NOTE_HIT_RADIUS =>
+                {
+                    Some((a.id, text.clone()))
+                }
+                _ => None,
+            })
+        });
+        match existing {
+            Some((id, text)) => self.prompt_pdf_note(PendingNote::Edit(id), &text, window, cx),
+            None => self.prompt_pdf_note(PendingNote::New { page, point }, "", window, cx),
+        }
+    }
+
+    /// Change the text of a note from the annotation list
+    pub fn edit_pdf_note(&mut self, id: u64, window: &mut Window, cx: &mut Context<Self>) {
+        let text = self
+            .board
+            .as_ref()
+            .and_then(|board| board.pdf_annotations.iter().find(|a| a.id == id))
+            .and_then(|a| match &a.kind {
+                AnnotationKind::Note { text, .. } => Some(text.clone()),
+                _ => None,
+            });
+        if let Some(text) = text {
+            self.prompt_pdf_note(PendingNote::Edit(id), &text, window, cx);
+        }
+    }
+
+    /// Ask for a note's text in the command palette, as `note <text>`
+    fn prompt_pdf_note(
+        &mut self,
+        pending: PendingNote,
+        text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref mut annotator) = self.pdf_annotator else {
+            return;
+        };
+        annotator.pending_note = Some(pending);
+
+        let command = format!("note {}", text);
+        self.show_command_palette(window, cx);
+        if let Some(ref input) = self.command_palette {
+            input.update(cx, |state, cx| state.set_value(command, window, cx));
+        }
+        // Enter runs the typed command rather than a search result
+        self.search_results.clear();
+        self.selected_result = 0;
+    }
+
+    pub fn annotator_mouse_move(
+        &mut self,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let dragging = self
+            .pdf_annotator
+            .as_ref()
+            .is_some_and(|a| a.drag.is_some());
+        if !dragging {
+            return;
+        }
+        let Some(point) = self.annotator_point(position, window) else {
+            return;
+        };
+        if let Some(drag) = self.pdf_annotator.as_mut().and_then(|a| a.drag.as_mut()) {
+            drag.1 = point;
+        }
+        cx.notify();
+    }
+
+    /// Finish the highlight or box being dragged out
+    pub fn annotator_mouse_up(&mut self, cx: &mut Context<Self>) {
+        let Some(ref mut annotator) = self.pdf_annotator else {
+            return;
+        };
+        let Some((start, end)) = annotator.drag.take() else {
+            return;
+        };
+        cx.notify();
+        let rect = PageRect::from_corners(start, end);
+        if rect.width < MIN_DRAG && rect.height < MIN_DRAG {
+            return;
+        }
+        let path = annotator.path.clone();
+        let page = annotator.page;
+
+        let kind = match annotator.tool {
+            AnnotationTool::Box => AnnotationKind::Box { rect },
+            AnnotationTool::Highlight => {
+                let selected = pdf_thumbnail::page_chars(&path, page)
+                    .map(|chars| pdf_annotations::select_text(&chars, rect));
+                match selected {
+                    Ok(Some((text, rects))) => AnnotationKind::Highlight { rects, text },
+                    Ok(None) => {
+                        self.toast_manager
+                            .push(Toast::info("There's no text there to highlight"));
+                        return;
+                    }
+                    Err(e) => {
+                        self.toast_manager.push(Toast::error(format!(
+                            "Couldn't read the page's text: {}",
+                            e
+                        )));
+                        return;
+                    }
+                }
+            }
+            AnnotationTool::Note => return,
+        };
+        if let Some(ref mut board) = self.board {
+            board.add_pdf_annotation(path, page, kind);
+        }
+    }
+
+    /// Give the note waiting in the annotator its text, from `note <text>`
+    pub fn set_pending_pdf_note(&mut self, text: &str, cx: &mut Context<Self>) {
+        let pending = self.pdf_annotator.as_mut().and_then(|annotator| {
+            let pending = annotator.pending_note.take()?;
+            Some((annotator.path.clone(), pending))
+        });
+        let Some((path, pending)) = pending else {
+            self.toast_manager
+                .push(Toast::info("Click a PDF page with the Note tool first"));
+            cx.notify();
+            return;
+        };
+        let text = text.trim().to_string();
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+        match pending {
+            PendingNote::New { .. } if text.is_empty() => {}
+            PendingNote::New { page, point } => {
+                board.add_pdf_annotation(path, page, AnnotationKind::Note { point, text });
+            }
+            // Clearing a note's text removes it
+            PendingNote::Edit(id) if text.is_empty() => {
+                board.remove_pdf_annotation(id);
+            }
+            PendingNote::Edit(id) => {
+                board.set_pdf_note_text(id, text);
+            }
+        }
+        cx.notify();
+    }
+
+    pub fn remove_pdf_annotation(&mut self, id: u64, cx: &mut Context<Self>) {
+        if let Some(ref mut board) = self.board {
+            board.remove_pdf_annotation(id);
+            cx.notify();
+        }
+    }
+
+    /// Add a highlight to the canvas as a quote that links to its page,
+    /// beside the page or PDF if they're on the board
+    pub fn send_highlight_to_canvas(
+        &mut self,
+        id: u64,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+        let highlight = board
+            .pdf_annotations
+            .iter()
+            .find(|a| a.id == id)
+            .and_then(|a| match &a.kind {
+                AnnotationKind::Highlight { text, .. } => {
+                    Some((a.path.clone(), a.page, text.clone()))
+                }
+                _ => None,
+            });
+        let Some((path, page, text)) = highlight else {
+            return;
+        };
+
+        let size = pdf_annotations::quote_size(&text);
+        let position =
+            pdf_annotations::quote_anchor(&board.items, &path, page).unwrap_or_else(|| {
+                // Otherwise in the middle of the canvas in view
+                let window_size = window.bounds().size;
+                let center = board
+                    .screen_to_canvas(point(window_size.width / 2.0, window_size.height / 2.0));
+                (
+                    f32::from(center.x) - size.0 / 2.0,
+                    f32::from(center.y) - size.1 / 2.0,
+                )
+            });
+        let content = pdf_annotations::quote_content(&path, page, &text);
+        let (added_ids, _) =
+            board.apply_item_changes(vec![(content, position, Some(size))], Vec::new());
+        self.selected_items = added_ids.into_iter().collect();
+        self.toast_manager.push(Toast::success(format!(
+            "Quoted {} on the canvas",
+            pdf_annotations::page_label(&path, page)
+        )));
+        cx.notify();
+    }
+}
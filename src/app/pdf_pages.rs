@@ -0,0 +1,205 @@
+//! PDF page methods - the page picker and placing pages on the canvas.
+
+use super::{Humanboard, PdfPagePicker, PdfPagesResult};
+use crate::notifications::Toast;
+use crate::pdf_pages;
+use crate::pdf_thumbnail::{self, PAGE_PICKER_WIDTH, PLACED_PAGE_WIDTH};
+use crate::types::{CanvasItem, ItemContent};
+use gpui::*;
+use std::collections::BTreeSet;
+
+impl Humanboard {
+    /// Open the page picker for the selected PDF, rendering its page
+    /// thumbnails in the background
+    pub fn open_pdf_page_picker(&mut self, cx: &mut Context<Self>) {
+        let Some(pdf) = self.selected_pdf(cx) else {
+            return;
+        };
+        self.pdf_page_picker = Some(PdfPagePicker {
+            item_id: pdf.id,
+            name: pdf.content.display_name(),
+            pages: None,
+            error: None,
+            selected: BTreeSet::new(),
+            anchor: None,
+        });
+        self.render_pdf_pages(&pdf, "all".to_string(), true);
+        cx.notify();
+    }
+
+    pub fn close_pdf_page_picker(&mut self, cx: &mut Context<Self>) {
+        self.pdf_page_picker = None;
+        cx.notify();
+    }
+
+    /// Select or unselect a page in the picker. Extending selects every page
+    /// from the last one clicked.
+    pub fn toggle_picker_page(&mut self, page: usize, extend: bool, cx: &mut Context<Self>) {
+        let Some(ref mut picker) = self.pdf_page_picker else {
+            return;
+        };
+        match picker.anchor.filter(|_| extend) {
+            Some(anchor) => picker.selected.extend(anchor.min(page)..=anchor.max(page)),
+            None => {
+                if !picker.selected.remove(&page) {
+                    picker.selected.insert(page);
+                }
+            }
+        }
+        picker.anchor = Some(page);
+        cx.notify();
+    }
+
+    /// Select every page in the picker, or none if they all are
+    pub fn toggle_all_picker_pages(&mut self, cx: &mut Context<Self>) {
+        let Some(ref mut picker) = self.pdf_page_picker else {
+            return;
+        };
+        let count = picker.pages.as_ref().map_or(0, Vec::len);
+        if picker.selected.len() == count {
+            picker.selected.clear();
+        } else {
+            picker.selected = (1..=count).collect();
+        }
+        picker.anchor = None;
+        cx.notify();
+    }
+
+    /// Place the pages chosen in the picker and close it
+    pub fn place_picked_pages(&mut self, cx: &mut Context<Self>) {
+        let Some(picker) = self.pdf_page_picker.take() else {
+            return;
+        };
+        let pdf = self
+            .board
+            .as_ref()
+            .and_then(|board| board.get_item(picker.item_id))
+            .cloned();
+        if let Some(pdf) = pdf {
+            let pages: Vec<usize> = picker.selected.into_iter().collect();
+            self.render_pdf_pages(&pdf, pdf_pages::format_page_ranges(&pages), false);
+        }
+        cx.notify();
+    }
+
+    /// Place pages of the selected PDF beside it, from a list such as `1-5, 8`
+    pub fn place_selected_pdf_pages(&mut self, spec: &str, cx: &mut Context<Self>) {
+        let Some(pdf) = self.selected_pdf(cx) else {
+            return;
+        };
+        self.render_pdf_pages(&pdf, spec.to_string(), false);
+        cx.notify();
+    }
+
+    /// The single selected PDF item, after telling the user if there isn't one
+    fn selected_pdf(&mut self, cx: &mut Context<Self>) -> Option<CanvasItem> {
+        let selected: Vec<&CanvasItem> = self
+            .board
+            .iter()
+            .flat_map(|board| board.items.iter())
+            .filter(|item| self.selected_items.contains(&item.id))
+            .filter(|item| matches!(item.content, ItemContent::Pdf { .. }))
+            .collect();
+        match selected.as_slice() {
+            [item] => Some((*item).clone()),
+            [] => {
+                self.toast_manager.push(Toast::info("Select a PDF first"));
+                cx.notify();
+                None
+            }
+            _ => {
+                self.toast_manager.push(Toast::info("Select a single PDF"));
+                cx.notify();
+                None
+            }
+        }
+    }
+
+    /// Render the `spec` pages of a PDF item in the background, as picker
+    /// thumbnails or as pages to place
+    fn render_pdf_pages(&mut self, pdf: &CanvasItem, spec: String, for_picker: bool) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let ItemContent::Pdf { ref path, .. } = pdf.content else {
+            return;
+        };
+        let path = path.clone();
+        let board_id = board.id.clone();
+        let item_id = pdf.id;
+        let width = if for_picker {
+            PAGE_PICKER_WIDTH
+        } else {
+            PLACED_PAGE_WIDTH
+        };
+        let tx = self.pdf_pages_tx.clone();
+        self.background.spawn(
+            &format!("pdf-pages:{}", item_id),
+            move || {
+                let count = pdf_thumbnail::pdf_page_count(&path).map_err(|e| e.to_string())?;
+                let pages = pdf_pages::parse_page_ranges(&spec, count)?;
+                pdf_thumbnail::render_pdf_pages(&path, &pages, width).map_err(|e| e.to_string())
+            },
+            move |result| {
+                let _ = tx.send(PdfPagesResult {
+                    board_id,
+                    item_id,
+                    for_picker,
+                    result,
+                });
+            },
+        );
+    }
+
+    /// Show rendered thumbnails in the picker, and place rendered pages
+    /// beside their PDF
+    pub fn poll_pdf_pages(&mut self, cx: &mut Context<Self>) {
+        while let Ok(done) = self.pdf_pages_rx.try_recv() {
+            cx.notify();
+
+            if done.for_picker {
+                // The picker was closed, or opened on another PDF, while it ran
+                let Some(picker) = self
+                    .pdf_page_picker
+                    .as_mut()
+                    .filter(|picker| picker.item_id == done.item_id)
+                else {
+                    continue;
+                };
+                match done.result {
+                    Ok(pages) => picker.pages = Some(pages),
+                    Err(e) => picker.error = Some(e),
+                }
+                continue;
+            }
+
+            let pages = match done.result {
+                Ok(pages) => pages,
+                Err(e) => {
+                    self.toast_manager
+                        .push(Toast::error(format!("Couldn't place pages: {}", e)));
+                    continue;
+                }
+            };
+            // The board was closed or the PDF deleted while it ran
+            let Some(board) = self.board.as_mut().filter(|b| b.id == done.board_id) else {
+                continue;
+            };
+            let Some(pdf) = board.get_item(done.item_id).cloned() else {
+                continue;
+            };
+            let ItemContent::Pdf { ref path, .. } = pdf.content else {
+                continue;
+            };
+
+            let (added_ids, _) =
+                board.apply_item_changes(pdf_pages::page_items(&pdf, path, &pages), Vec::new());
+            self.selected_items = added_ids.into_iter().collect();
+            self.toast_manager.push(Toast::success(format!(
+                "Placed {} page{}",
+                pages.len(),
+                if pages.len() == 1 { "" } else { "s" }
+            )));
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Presentation mode - stepping through frames and bookmarks full-screen.
+
+use super::{Humanboard, PresentationState};
+use crate::constants::PRESENTATION_TRANSITION_MS;
+use crate::focus::FocusContext;
+use crate::notifications::Toast;
+use crate::presentation::{PRESENTATION_MARGIN, fit_viewport, presentation_stops};
+use gpui::*;
+use std::time::Duration;
+
+impl Humanboard {
+    /// Enter presentation mode at the first stop
+    pub fn start_presentation(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let viewport = self.canvas_area_size(window);
+        let Some(ref board) = self.board else {
+            return;
+        };
+
+        let stops = presentation_stops(&board.items, &board.bookmarks, viewport);
+        let (saved_offset, saved_zoom) = (board.canvas_offset, board.zoom);
+        if stops.is_empty() {
+            self.toast_manager
+                .push(Toast::info("Nothing to present - add some items first"));
+            cx.notify();
+            return;
+        }
+
+        if self.editing_textbox_id.is_some() {
+            self.finish_textbox_editing_with_window(window, cx);
+        }
+        self.command_palette = None;
+        self.selected_tool = crate::types::ToolType::Select;
+        self.marquee_start = None;
+        self.marquee_current = None;
+
+        self.presentation = Some(PresentationState {
+            stops,
+            current: 0,
+            saved_offset,
+            saved_zoom,
+        });
+        self.focus.focus(FocusContext::Presentation, window);
+        self.show_presentation_stop(0, window, cx);
+    }
+
+    /// Leave presentation mode and restore the viewport from before it started
+    pub fn exit_presentation(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(presentation) = self.presentation.take() else {
+            return;
+        };
+
+        self.pan_animation = None;
+        if let Some(ref mut board) = self.board {
+            board.canvas_offset = presentation.saved_offset;
+            board.zoom = presentation.saved_zoom;
+            board.mark_dirty();
+        }
+        self.focus.focus(FocusContext::Canvas, window);
+        cx.notify();
+    }
+
+    pub fn presentation_next(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(index) = self
+            .presentation
+            .as_ref()
+            .map(|p| p.current + 1)
+            .filter(|&i| i < self.presentation_len())
+        {
+            self.show_presentation_stop(index, window, cx);
+        }
+    }
+
+    pub fn presentation_prev(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(index) = self
+            .presentation
+            .as_ref()
+            .and_then(|p| p.current.checked_sub(1))
+        {
+            self.show_presentation_stop(index, window, cx);
+        }
+    }
+
+    fn presentation_len(&self) -> usize {
+        self.presentation.as_ref().map_or(0, |p| p.stops.len())
+    }
+
+    /// Move the camera to a stop
+    fn show_presentation_stop(
+        &mut self,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref mut presentation) = self.presentation else {
+            return;
+        };
+        let Some(stop) = presentation.stops.get(index) else {
+            return;
+        };
+        presentation.current = index;
+
+        // Presentation hides all chrome, so the whole window is the viewport
+        let size = window.bounds().size;
+        let viewport = (f32::from(size.width), f32::from(size.height));
+        let (offset, zoom) = fit_viewport(stop.bounds, viewport, PRESENTATION_MARGIN);
+        self.animate_viewport(
+            point(px(offset.0), px(offset.1)),
+            zoom,
+            Duration::from_millis(PRESENTATION_TRANSITION_MS),
+            cx,
+        );
+    }
+}
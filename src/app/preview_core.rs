@@ -1,6 +1,7 @@
 //! Core preview panel methods - opening, PDF/code webviews, markdown/code editing.
 
 use super::{FocusedPane, Humanboard, PreviewPanel, PreviewTab, SplitDirection, TabMeta};
+use crate::constants::PDF_TAB_FOOTER_HEIGHT;
 use crate::focus::FocusContext;
 use crate::pdf_webview::PdfWebView;
 use gpui::*;
@@ -14,6 +15,36 @@ impl Humanboard {
         self.open_preview_internal(path, false, window, cx);
     }
 
+    /// Open a PDF in the preview at a page, starting at 1
+    pub fn open_pdf_at_page(
+        &mut self,
+        path: PathBuf,
+        page: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.open_preview(path.clone(), window, cx);
+        let Some(ref mut preview) = self.preview else {
+            return;
+        };
+        let tab = preview
+            .tabs
+            .iter_mut()
+            .chain(preview.right_tabs.iter_mut())
+            .find(|tab| tab.path() == &path);
+        if let Some(PreviewTab::Pdf {
+            webview,
+            page: pending,
+            ..
+        }) = tab
+        {
+            match webview {
+                Some(wv) => wv.go_to_page(page, cx),
+                None => *pending = Some(page),
+            }
+        }
+    }
+
     /// Open a file as a preview (temporary) tab - will replace existing preview tab
     pub fn open_as_preview_tab(
         &mut self,
@@ -63,6 +94,7 @@ impl Humanboard {
             PreviewTab::Pdf {
                 path: path.clone(),
                 webview: None,
+                page: None,
                 meta,
             }
         } else if let Some(language) = crate::types::language_from_extension(ext) {
@@ -82,6 +114,7 @@ impl Humanboard {
             PreviewTab::Pdf {
                 path: path.clone(),
                 webview: None,
+                page: None,
                 meta,
             }
         };
@@ -242,9 +275,9 @@ impl Humanboard {
 
             // Ensure all PDF tabs in left pane have their WebViews created and positioned
             for (idx, tab) in preview.tabs.iter_mut().enumerate() {
-                if let PreviewTab::Pdf { path, webview, .. } = tab {
+                if let PreviewTab::Pdf { path, webview, page, .. } = tab {
                     if webview.is_none() {
-                        match PdfWebView::new(path.clone(), window, cx) {
+                        match PdfWebView::new(path.clone(), page.take(), window, cx) {
                             Ok(wv) => {
                                 if idx != active_tab {
                                     wv.hide(cx);
@@ -263,7 +296,14 @@ impl Humanboard {
 
                     if let Some(wv) = webview {
                         if idx == active_tab {
-                            wv.set_bounds(left_pane_x, left_pane_y, left_pane_w, left_pane_h, cx);
+                            // Leave room for the annotations bar under the PDF
+                            wv.set_bounds(
+                                left_pane_x,
+                                left_pane_y,
+                                left_pane_w,
+                                left_pane_h - PDF_TAB_FOOTER_HEIGHT,
+                                cx,
+                            );
                             wv.show(cx);
                         } else {
                             wv.hide(cx);
@@ -275,9 +315,9 @@ impl Humanboard {
             // Ensure all PDF tabs in right pane have their WebViews created and positioned (when split)
             if is_pane_split {
                 for (idx, tab) in preview.right_tabs.iter_mut().enumerate() {
-                    if let PreviewTab::Pdf { path, webview, .. } = tab {
+                    if let PreviewTab::Pdf { path, webview, page, .. } = tab {
                         if webview.is_none() {
-                            match PdfWebView::new(path.clone(), window, cx) {
+                            match PdfWebView::new(path.clone(), page.take(), window, cx) {
                                 Ok(wv) => {
                                     if idx != right_active_tab {
                                         wv.hide(cx);
@@ -300,7 +340,7 @@ impl Humanboard {
                                     right_pane_x,
                                     right_pane_y,
                                     right_pane_w,
-                                    right_pane_h,
+                                    right_pane_h - PDF_TAB_FOOTER_HEIGHT,
                                     cx,
                                 );
                                 wv.show(cx);
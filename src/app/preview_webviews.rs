@@ -1,16 +1,27 @@
 //! Webview management - YouTube, Audio, Video webviews and visibility updates.
+//!
+//! Media webviews are only created for activated items in view; see `crate::media`.
 
 use super::{Humanboard, PreviewTab, SplitDirection};
 use crate::audio_webview::AudioWebView;
+use crate::constants::COMMENTS_SIDEBAR_WIDTH;
+use crate::render::dock::DOCK_WIDTH;
 use crate::video_webview::VideoWebView;
 use crate::youtube_webview::YouTubeWebView;
 use gpui::*;
+use std::collections::HashSet;
 use tracing::error;
 
 impl Humanboard {
-    /// Ensure YouTube webviews are created for YouTube items.
+    /// Ensure YouTube webviews exist for activated YouTube items in view, and
+    /// drop those of items that were deactivated or deleted.
     /// Returns a list of error messages for any webviews that failed to create.
-    pub fn ensure_youtube_webviews(&mut self, window: &mut Window, cx: &mut App) -> Vec<String> {
+    pub fn ensure_youtube_webviews(
+        &mut self,
+        visible: &HashSet<u64>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<String> {
         use crate::types::ItemContent;
         let mut errors = Vec::new();
 
@@ -19,27 +30,30 @@ impl Humanboard {
             return errors;
         };
 
-        // Collect YouTube item IDs and video IDs
+        // Collect activated YouTube item IDs and video IDs
         let youtube_items: Vec<(u64, String)> = board
             .items
             .iter()
             .filter_map(|item| {
                 if let ItemContent::YouTube(video_id) = &item.content {
                     Some((item.id, video_id.clone()))
+                        .filter(|(id, _)| self.live_media.is_active(*id))
                 } else {
                     None
                 }
             })
             .collect();
 
-        // Create WebViews for new YouTube items
+        // Create WebViews for new YouTube items once they're in view
         for (item_id, video_id) in &youtube_items {
-            if !self.youtube_webviews.contains_key(item_id) {
+            if !self.youtube_webviews.contains_key(item_id) && visible.contains(item_id) {
                 match YouTubeWebView::new(video_id.clone(), window, cx) {
                     Ok(webview) => {
                         self.youtube_webviews.insert(*item_id, webview);
                     }
                     Err(e) => {
+                        // Back to the poster rather than retrying every frame
+                        self.live_media.deactivate(*item_id);
                         errors.push(format!("Failed to load YouTube video: {}", e));
                         error!(
                             "Failed to create YouTube WebView for video {}: {}",
@@ -50,9 +64,8 @@ impl Humanboard {
             }
         }
 
-        // Remove WebViews for deleted items (hide before dropping to prevent orphaned UI)
-        let youtube_ids: std::collections::HashSet<u64> =
-            youtube_items.iter().map(|(id, _)| *id).collect();
+        // Remove WebViews for deactivated or deleted items (hide before dropping to prevent orphaned UI)
+        let youtube_ids: HashSet<u64> = youtube_items.iter().map(|(id, _)| *id).collect();
         let ids_to_remove: Vec<u64> = self
             .youtube_webviews
             .keys()
@@ -67,9 +80,15 @@ impl Humanboard {
         errors
     }
 
-    /// Ensure Audio webviews are created for Audio items.
+    /// Ensure Audio webviews exist for activated Audio items in view, and
+    /// drop those of items that were deactivated or deleted.
     /// Returns a list of error messages for any webviews that failed to create.
-    pub fn ensure_audio_webviews(&mut self, window: &mut Window, cx: &mut App) -> Vec<String> {
+    pub fn ensure_audio_webviews(
+        &mut self,
+        visible: &HashSet<u64>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<String> {
         use crate::types::ItemContent;
         let mut errors = Vec::new();
 
@@ -78,27 +97,30 @@ impl Humanboard {
             return errors;
         };
 
-        // Collect Audio item IDs and paths
+        // Collect activated Audio item IDs and paths
         let audio_items: Vec<(u64, std::path::PathBuf)> = board
             .items
             .iter()
             .filter_map(|item| {
-                if let ItemContent::Audio(path) = &item.content {
-                    Some((item.id, path.clone()))
+                if let ItemContent::Audio(path, _) = &item.content {
+                    Some((item.id, path.clone())).filter(|(id, _)| self.live_media.is_active(*id))
                 } else {
                     None
                 }
             })
             .collect();
 
-        // Create WebViews for new Audio items
+        // Create WebViews for new Audio items once they're in view
         for (item_id, path) in &audio_items {
-            if !self.audio_webviews.contains_key(item_id) {
-                match AudioWebView::new(path.clone(), window, cx) {
+            if !self.audio_webviews.contains_key(item_id) && visible.contains(item_id) {
+                let start = self.pending_seeks.remove(item_id);
+                match AudioWebView::new(path.clone(), start, window, cx) {
                     Ok(webview) => {
                         self.audio_webviews.insert(*item_id, webview);
                     }
                     Err(e) => {
+                        // Back to the poster rather than retrying every frame
+                        self.live_media.deactivate(*item_id);
                         let filename = path.file_name()
                             .and_then(|n| n.to_str())
                             .unwrap_or("audio file");
@@ -109,9 +131,8 @@ impl Humanboard {
             }
         }
 
-        // Remove WebViews for deleted items (hide before dropping to prevent orphaned UI)
-        let audio_ids: std::collections::HashSet<u64> =
-            audio_items.iter().map(|(id, _)| *id).collect();
+        // Remove WebViews for deactivated or deleted items (hide before dropping to prevent orphaned UI)
+        let audio_ids: HashSet<u64> = audio_items.iter().map(|(id, _)| *id).collect();
         let ids_to_remove: Vec<u64> = self
             .audio_webviews
             .keys()
@@ -126,9 +147,15 @@ impl Humanboard {
         errors
     }
 
-    /// Ensure Video webviews are created for Video items.
+    /// Ensure Video webviews exist for activated Video items in view, and
+    /// drop those of items that were deactivated or deleted.
     /// Returns a list of error messages for any webviews that failed to create.
-    pub fn ensure_video_webviews(&mut self, window: &mut Window, cx: &mut App) -> Vec<String> {
+    pub fn ensure_video_webviews(
+        &mut self,
+        visible: &HashSet<u64>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<String> {
         use crate::types::ItemContent;
         let mut errors = Vec::new();
 
@@ -137,27 +164,29 @@ impl Humanboard {
             return errors;
         };
 
-        // Collect Video item IDs and paths
+        // Collect activated Video item IDs and paths
         let video_items: Vec<(u64, std::path::PathBuf)> = board
             .items
             .iter()
             .filter_map(|item| {
                 if let ItemContent::Video(path) = &item.content {
-                    Some((item.id, path.clone()))
+                    Some((item.id, path.clone())).filter(|(id, _)| self.live_media.is_active(*id))
                 } else {
                     None
                 }
             })
             .collect();
 
-        // Create WebViews for new Video items
+        // Create WebViews for new Video items once they're in view
         for (item_id, path) in &video_items {
-            if !self.video_webviews.contains_key(item_id) {
+            if !self.video_webviews.contains_key(item_id) && visible.contains(item_id) {
                 match VideoWebView::new(path.clone(), window, cx) {
                     Ok(webview) => {
                         self.video_webviews.insert(*item_id, webview);
                     }
                     Err(e) => {
+                        // Back to the poster rather than retrying every frame
+                        self.live_media.deactivate(*item_id);
                         let filename = path.file_name()
                             .and_then(|n| n.to_str())
                             .unwrap_or("video file");
@@ -168,9 +197,8 @@ impl Humanboard {
             }
         }
 
-        // Remove WebViews for deleted items (hide before dropping to prevent orphaned UI)
-        let video_ids: std::collections::HashSet<u64> =
-            video_items.iter().map(|(id, _)| *id).collect();
+        // Remove WebViews for deactivated or deleted items (hide before dropping to prevent orphaned UI)
+        let video_ids: HashSet<u64> = video_items.iter().map(|(id, _)| *id).collect();
         let ids_to_remove: Vec<u64> = self
             .video_webviews
             .keys()
@@ -185,13 +213,82 @@ impl Humanboard {
         errors
     }
 
+    /// Items entirely inside the canvas area, clear of the header and footer.
+    /// Webviews don't clip, so only these can show one.
+    pub fn fully_visible_items(&self, window: &Window) -> HashSet<u64> {
+        let Some(ref board) = self.board else {
+            return HashSet::new();
+        };
+
+        let bounds = window.bounds();
+        let window_width = f32::from(bounds.size.width);
+        let window_height = f32::from(bounds.size.height);
+
+        // Presentation mode hides the header, footer and preview panel
+        let presenting = self.presentation.is_some();
+
+        // Account for preview panel if open
+        let (canvas_width, canvas_height) = if presenting {
+            (window_width, window_height)
+        } else if let Some(ref preview) = self.preview {
+            match preview.split {
+                SplitDirection::Vertical => ((1.0 - preview.size) * window_width, window_height),
+                SplitDirection::Horizontal => (window_width, (1.0 - preview.size) * window_height),
+            }
+        } else {
+            (window_width, window_height)
+        };
+
+        // The comments sidebar covers the right of the canvas area; item
+        // positions below leave out the dock, so it's taken off here too
+        let canvas_width = if !presenting && self.comments_panel.is_some() {
+            canvas_width - COMMENTS_SIDEBAR_WIDTH - DOCK_WIDTH
+        } else {
+            canvas_width
+        };
+
+        // Header offset
+        let header_height = if presenting { 0.0 } else { 40.0 };
+        let canvas_top = header_height;
+        let footer_height = if presenting { 0.0 } else { 28.0 };
+        let canvas_bottom = canvas_height - footer_height;
+
+        let zoom = board.zoom;
+        let offset_x = f32::from(board.canvas_offset.x);
+        let offset_y = f32::from(board.canvas_offset.y);
+
+        board
+            .items
+            .iter()
+            .filter(|item| {
+                let item_x = item.position.0 * zoom + offset_x;
+                let item_y = item.position.1 * zoom + offset_y + header_height;
+                let item_w = item.size.0 * zoom;
+                let item_h = item.size.1 * zoom;
+
+                let overlaps_header = item_y < canvas_top;
+                let overlaps_footer = item_y + item_h > canvas_bottom;
+                let overlaps_left = item_x < 0.0;
+                let overlaps_right = item_x + item_w > canvas_width;
+
+                !overlaps_header && !overlaps_footer && !overlaps_left && !overlaps_right
+            })
+            .map(|item| item.id)
+            .collect()
+    }
+
     /// Update webview visibility based on canvas viewport
     /// Hides webviews that are scrolled out of view to prevent z-index issues
     pub fn update_webview_visibility(&mut self, window: &mut Window, cx: &mut App) {
         let Some(ref board) = self.board else { return };
 
-        // Hide all webviews when settings modal or shortcuts overlay is open
-        if self.show_settings || self.show_shortcuts {
+        // Hide all webviews when a modal, overlay or card is open
+        if self.show_settings
+            || self.show_shortcuts
+            || self.image_info.is_some()
+            || self.pdf_page_picker.is_some()
+            || self.pdf_annotator.is_some()
+        {
             for (_, webview) in &self.youtube_webviews {
                 webview.webview().update(cx, |wv, _| wv.hide());
             }
@@ -215,47 +312,12 @@ impl Humanboard {
             return;
         }
 
-        let bounds = window.bounds();
-        let window_width = f32::from(bounds.size.width);
-        let window_height = f32::from(bounds.size.height);
-
-        // Account for preview panel if open
-        let (canvas_width, canvas_height) = if let Some(ref preview) = self.preview {
-            match preview.split {
-                SplitDirection::Vertical => ((1.0 - preview.size) * window_width, window_height),
-                SplitDirection::Horizontal => (window_width, (1.0 - preview.size) * window_height),
-            }
-        } else {
-            (window_width, window_height)
-        };
-
-        // Header offset
-        let header_height = 40.0;
-        let canvas_top = header_height;
-
-        let zoom = board.zoom;
-        let offset_x = f32::from(board.canvas_offset.x);
-        let offset_y = f32::from(board.canvas_offset.y);
+        let presenting = self.presentation.is_some();
+        let visible = self.fully_visible_items(window);
 
         // Check each item with a webview
         for item in &board.items {
-            let item_x = item.position.0 * zoom + offset_x;
-            let item_y = item.position.1 * zoom + offset_y + header_height;
-            let item_w = item.size.0 * zoom;
-            let item_h = item.size.1 * zoom;
-
-            // Check if item is visible and not overlapping UI chrome
-            // Webviews don't clip, so hide if any edge goes outside canvas bounds
-            let footer_height = 28.0;
-            let canvas_bottom = canvas_height - footer_height;
-
-            let overlaps_header = item_y < canvas_top;
-            let overlaps_footer = item_y + item_h > canvas_bottom;
-            let overlaps_left = item_x < 0.0;
-            let overlaps_right = item_x + item_w > canvas_width;
-
-            let is_visible =
-                !overlaps_header && !overlaps_footer && !overlaps_left && !overlaps_right;
+            let is_visible = visible.contains(&item.id);
 
             // Update YouTube webview visibility
             if let Some(webview) = self.youtube_webviews.get(&item.id) {
@@ -298,7 +360,7 @@ impl Humanboard {
                     webview: Some(wv), ..
                 } = tab
                 {
-                    if idx == preview.active_tab {
+                    if idx == preview.active_tab && !presenting {
                         wv.webview().update(cx, |view, _| view.show());
                     } else {
                         wv.webview().update(cx, |view, _| view.hide());
@@ -1,13 +1,21 @@
-//! Settings-related methods - theme, font, dropdowns, shortcuts
+//! Settings-related methods - theme, font, canvas, dropdowns, shortcuts
 
 use super::{Humanboard, SettingsTab};
 use crate::focus::FocusContext;
+use crate::settings::SettingsSource;
 use gpui::*;
 use gpui_component::ActiveTheme;
 
+/// Grid size change per step in the settings modal
+const GRID_SIZE_STEP: f32 = 5.0;
+const MIN_GRID_SIZE: f32 = 5.0;
+const MAX_GRID_SIZE: f32 = 200.0;
+
 impl Humanboard {
     pub fn toggle_settings(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.show_settings {
+            // Drop any unsaved theme edits
+            self.cancel_theme_editor(cx);
             // Start fade-out animation
             self.modal_animations.close_settings();
             // Force focus back to canvas when closing settings
@@ -129,6 +137,12 @@ impl Humanboard {
         cx.notify();
     }
 
+    pub fn toggle_minimap(&mut self, cx: &mut Context<Self>) {
+        self.show_minimap = !self.show_minimap;
+        self.minimap_dragging = false;
+        cx.notify();
+    }
+
     /// Toggle high contrast mode for accessibility
     pub fn toggle_high_contrast(&mut self, cx: &mut Context<Self>) {
         let current = crate::settings::is_high_contrast();
@@ -158,6 +172,59 @@ impl Humanboard {
         crate::settings::is_high_contrast()
     }
 
+    /// Turn "this board only" canvas settings on or off for the open board
+    pub fn set_board_only_settings(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        if let Err(e) = crate::settings::set_board_overrides(enabled) {
+            tracing::error!("Failed to change board settings: {}", e);
+            self.toast_manager
+                .push(crate::notifications::Toast::error(format!(
+                    "Couldn't change board settings: {}",
+                    e
+                )));
+        }
+        cx.notify();
+    }
+
+    /// Apply a canvas setting change to the open board's settings when it has
+    /// its own, otherwise to the user's settings
+    fn update_canvas_setting(
+        &mut self,
+        updater: impl FnOnce(&mut crate::settings::SettingsContent),
+        cx: &mut Context<Self>,
+    ) {
+        let source = if self.board.is_some() && crate::settings::has_board_settings() {
+            SettingsSource::Board
+        } else {
+            SettingsSource::User
+        };
+
+        if let Err(e) = crate::settings::update_setting(source, updater) {
+            tracing::error!("Failed to save canvas settings: {}", e);
+        }
+        cx.notify();
+    }
+
+    /// Set the canvas background, or follow the theme with `None`
+    pub fn set_canvas_background(&mut self, color: Option<String>, cx: &mut Context<Self>) {
+        let background = color.unwrap_or_else(|| crate::settings::THEME_BACKGROUND.to_string());
+        self.update_canvas_setting(|content| content.canvas_background = Some(background), cx);
+    }
+
+    pub fn set_show_grid(&mut self, show: bool, cx: &mut Context<Self>) {
+        self.update_canvas_setting(|content| content.show_grid = Some(show), cx);
+    }
+
+    pub fn set_snap_to_grid(&mut self, snap: bool, cx: &mut Context<Self>) {
+        self.update_canvas_setting(|content| content.snap_to_grid = Some(snap), cx);
+    }
+
+    /// Change the grid size by `steps` increments of `GRID_SIZE_STEP`
+    pub fn step_grid_size(&mut self, steps: f32, cx: &mut Context<Self>) {
+        let grid_size = crate::settings::canvas_settings().grid_size;
+        let grid_size = (grid_size + steps * GRID_SIZE_STEP).clamp(MIN_GRID_SIZE, MAX_GRID_SIZE);
+        self.update_canvas_setting(|content| content.grid_size = Some(grid_size), cx);
+    }
+
     pub fn set_settings_tab(&mut self, tab: SettingsTab, cx: &mut Context<Self>) {
         self.settings_tab = tab;
         cx.notify();
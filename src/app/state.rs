@@ -1,25 +1,35 @@
 //! Application state - the Humanboard struct definition.
 
-use super::{CmdPaletteMode, CountdownState, PreviewPanel, SettingsTab, StorageLocation};
+use super::{
+    BakedImagesResult, CaptureDates, CmdPaletteMode, CountdownState, DroppedFilesResult,
+    ExternalCommandResult, FilePrompt, PaletteResult, PdfAnnotatorPage, PdfPagesResult,
+    PreviewPanel, RunningExternalCommand, SavedTemplateResult, SettingsTab, StorageLocation,
+};
 use crate::animations::ModalAnimationState;
 use crate::audio_webview::AudioWebView;
+use crate::auto_layout::LayoutKind;
 use crate::background::BackgroundExecutor;
-use crate::board::Board;
+use crate::board::{Board, UndoOperation};
 use crate::board_index::BoardIndex;
+use crate::command_palette::{PaletteHistory, SearchResult};
+use crate::command_registry::CommandRegistry;
 use crate::focus::FocusManager;
 use crate::hit_testing::HitTester;
+use crate::image_edit::OrientationKey;
+use crate::media::{LiveMedia, MediaSource};
 use crate::notifications::ToastManager;
 use crate::perf::PerfMonitor;
 use crate::settings::Settings;
 use crate::settings_watcher::SettingsWatcher;
-use crate::types::ToolType;
+use crate::templates::{SavedTemplate, TemplateChoice};
+use crate::types::{ShapeType, ToolType};
 use crate::video_webview::VideoWebView;
 use crate::youtube_webview::YouTubeWebView;
 use gpui::*;
 use gpui_component::input::InputState;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::mpsc::Receiver;
+use std::sync::mpsc::{Receiver, Sender};
 use std::time::{Duration, Instant};
 
 use super::PanAnimation;
@@ -38,6 +48,8 @@ pub struct Humanboard {
     pub show_create_board_modal: bool,
     pub create_board_input: Option<Entity<InputState>>,
     pub create_board_location: StorageLocation,
+    pub create_board_template: TemplateChoice,
+    pub saved_templates: Vec<SavedTemplate>,
     pub create_board_backdrop_clicked: bool,
 
     // Trash visibility on landing page
@@ -79,14 +91,72 @@ pub struct Humanboard {
     pub dragging_pane_splitter: bool, // Dragging the splitter between split panes
     pub last_drop_pos: Option<Point<Pixels>>,
     pub file_drop_rx: Option<Receiver<(Point<Pixels>, Vec<PathBuf>)>>,
+    pub dropped_files_tx: Sender<DroppedFilesResult>, // Drops read and copied in the background
+    pub dropped_files_rx: Receiver<DroppedFilesResult>,
+    pub file_prompt_rx: Option<Receiver<FilePrompt>>, // Import/export file picked in a dialog
 
     // UI overlays
     pub show_shortcuts: bool,
+    pub show_minimap: bool,
+    pub minimap_dragging: bool, // Panning the canvas by dragging in the minimap
     pub command_palette: Option<Entity<InputState>>, // Command palette input
     pub pending_command: Option<String>, // Command to execute (deferred until we have window access)
-    pub search_results: Vec<(u64, String)>, // Search results: (item_id, display_name)
+    pub search_results: Vec<SearchResult>, // Search results with matched positions
     pub selected_result: usize,          // Currently selected search result index
     pub cmd_palette_mode: CmdPaletteMode, // Current mode: items or themes
+    pub palette_history: PaletteHistory, // Past palette use, for frecency ranking
+    pub command_registry: CommandRegistry, // Built-in and external commands
+    pub palette_command_ids: Vec<String>, // Registry command IDs behind the current results
+
+    // External commands running on their own threads report back here
+    pub external_command_tx: Sender<ExternalCommandResult>,
+    pub external_command_rx: Receiver<ExternalCommandResult>,
+    pub running_external_commands: Vec<RunningExternalCommand>,
+
+    // Templates saved in the background report back here
+    pub template_tx: Sender<SavedTemplateResult>,
+    pub template_rx: Receiver<SavedTemplateResult>,
+
+    // Colour palettes extracted in the background report back here
+    pub palette_tx: Sender<PaletteResult>,
+    pub palette_rx: Receiver<PaletteResult>,
+
+    // Media posters loaded in the background report back here
+    pub poster_tx: Sender<MediaSource>,
+    pub poster_rx: Receiver<MediaSource>,
+    pub posters_loading: HashSet<MediaSource>,
+
+    // EXIF capture dates read in the background report back here
+    pub capture_dates_tx: Sender<CaptureDates>,
+    pub capture_dates_rx: Receiver<CaptureDates>,
+    pub capture_dates: Option<CaptureDates>, // Latest dates read for the open board
+    pub capture_dates_loading: Option<String>, // Board whose dates are being read
+    pub pending_date_arrange: Option<LayoutKind>, // Arrange waiting for those dates
+    pub photos_query: String, // Filter of the palette's photos-by-date list
+
+    // Image edits baked into new files in the background report back here
+    pub baked_images_tx: Sender<BakedImagesResult>,
+    pub baked_images_rx: Receiver<BakedImagesResult>,
+
+    // Turned and flipped images oriented in the background report back here
+    pub oriented_tx: Sender<OrientationKey>,
+    pub oriented_rx: Receiver<OrientationKey>,
+    pub images_orienting: HashSet<OrientationKey>,
+
+    // Audio waveforms decoded in the background report back here
+    pub waveform_tx: Sender<PathBuf>,
+    pub waveform_rx: Receiver<PathBuf>,
+    pub waveforms_loading: HashSet<PathBuf>,
+
+    // PDF pages rendered in the background report back here
+    pub pdf_pages_tx: Sender<PdfPagesResult>,
+    pub pdf_pages_rx: Receiver<PdfPagesResult>,
+    pub pdf_annotator_tx: Sender<PdfAnnotatorPage>,
+    pub pdf_annotator_rx: Receiver<PdfAnnotatorPage>,
+
+    // Media items activated for playback; only these get webviews
+    pub live_media: LiveMedia,
+    pub pending_seeks: HashMap<u64, f64>, // Where audio players start once loaded (item ID -> seconds)
 
     // YouTube WebViews (keyed by item ID)
     pub youtube_webviews: HashMap<u64, YouTubeWebView>,
@@ -106,6 +176,7 @@ pub struct Humanboard {
     pub settings_theme_scroll: ScrollHandle,
     pub settings_font_index: usize,
     pub settings_font_scroll: ScrollHandle,
+    pub theme_editor: Option<super::ThemeEditorState>,
 
     // Modal focus trap state
     pub modal_focus_index: usize, // Current focus index within modal (for Tab cycling)
@@ -122,17 +193,27 @@ pub struct Humanboard {
 
     // Pan animation state
     pub pan_animation: Option<PanAnimation>,
+    pub layout_animation: Option<super::LayoutAnimation>,
+    pub presentation: Option<super::PresentationState>,
 
     // Modal animation state
     pub modal_animations: ModalAnimationState,
 
     // Tool dock state
     pub selected_tool: ToolType,
+    pub selected_shape: ShapeType, // Shape placed by the Shape tool
     pub drawing_start: Option<Point<Pixels>>, // Start position for drawing shapes/arrows
     pub drawing_current: Option<Point<Pixels>>, // Current position while drawing (for preview)
+    pub stroke_points: Vec<(f32, f32)>, // Canvas-space trail of the pen/highlighter stroke being drawn
+    pub erase_ops: Vec<UndoOperation>,      // Changes made by the current eraser drag (one undo step)
     pub editing_textbox_id: Option<u64>,      // ID of textbox being edited
     pub textbox_input: Option<Entity<gpui_component::input::InputState>>, // Input for editing textbox
     pub pending_textbox_drag: Option<(u64, Point<Pixels>)>, // Deferred drag for textboxes (to allow double-click)
+    pub crop_editing: Option<super::CropEditing>, // Image item being cropped
+    pub image_info: Option<super::ImageInfoView>, // Image info card being shown
+    pub pdf_page_picker: Option<super::PdfPagePicker>, // PDF whose pages are being picked
+    pub pdf_annotator: Option<super::PdfAnnotator>, // PDF being annotated
+    pub comments_panel: Option<super::CommentsPanel>, // Comments sidebar, when open
 
     // Hit testing
     pub hit_tester: HitTester,
@@ -146,6 +227,9 @@ pub struct Humanboard {
     // Settings file watcher for hot-reload
     pub settings_watcher: Option<SettingsWatcher>,
 
+    // Keymap file watcher for hot-reload
+    pub keymap_watcher: Option<SettingsWatcher>,
+
     // Home screen countdown state
     pub countdown: Option<CountdownState>,
 }
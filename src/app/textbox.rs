@@ -1,8 +1,9 @@
 //! Textbox editing and utility methods.
 
 use super::Humanboard;
+use crate::board::UndoOperation;
 use crate::focus::FocusContext;
-use crate::types::ItemContent;
+use crate::types::{ItemContent, TextAlignment, TextFormat};
 use gpui::*;
 use gpui_component::input::InputState;
 use std::time::{Duration, Instant};
@@ -10,7 +11,7 @@ use std::time::{Duration, Instant};
 impl Humanboard {
     // ==================== TextBox Editing Methods ====================
 
-    /// Start editing a textbox inline on the canvas
+    /// Start editing a textbox (or a shape/arrow label) inline on the canvas
     pub fn start_textbox_editing(
         &mut self,
         item_id: u64,
@@ -25,29 +26,36 @@ impl Humanboard {
         self.resize_start_pos = None;
         self.resize_start_font_size = None;
 
-        // Get the current text from the item
+        // Get the current text and format from the item (labels are always plain)
         let current_text = if let Some(ref board) = self.board {
             board.get_item(item_id).and_then(|item| {
-                if let ItemContent::TextBox { text, .. } = &item.content {
-                    Some(text.clone())
-                } else {
-                    None
-                }
+                let text = item.content.editable_text()?.to_string();
+                let format = match &item.content {
+                    ItemContent::TextBox { format, .. } => *format,
+                    _ => TextFormat::Plain,
+                };
+                Some((text, format))
             })
         } else {
             None
         };
 
-        if let Some(text) = current_text {
+        if let Some((text, format)) = current_text {
             // Count lines to position cursor at end
             let lines: Vec<&str> = text.lines().collect();
             let last_line = lines.len().saturating_sub(1) as u32;
             let last_char = lines.last().map(|l| l.len() as u32).unwrap_or(0);
 
+            // Markdown boxes get syntax highlighting for their raw source
+            let language = match format {
+                TextFormat::Plain => "plaintext",
+                TextFormat::Markdown => "markdown",
+            };
+
             // Create the input with multiline support (code_editor enables multiline)
             let input = cx.new(|cx| {
                 InputState::new(window, cx)
-                    .code_editor("plaintext") // Enable multiline editing
+                    .code_editor(language) // Enable multiline editing
                     .line_number(false) // No line numbers for textbox
                     .soft_wrap(true) // Wrap long lines
                     .default_value(text)
@@ -80,10 +88,7 @@ impl Humanboard {
                                 let new_text = input.read(cx).text().to_string();
                                 if let Some(ref mut board) = this.board {
                                     if let Some(item) = board.get_item_mut(item_id) {
-                                        if let ItemContent::TextBox { text, .. } = &mut item.content
-                                        {
-                                            *text = new_text;
-                                        }
+                                        item.content.set_editable_text(new_text);
                                     }
                                     board.mark_dirty();
                                 }
@@ -117,9 +122,7 @@ impl Humanboard {
 
                 if let Some(ref mut board) = self.board {
                     if let Some(item) = board.get_item_mut(item_id) {
-                        if let ItemContent::TextBox { text, .. } = &mut item.content {
-                            *text = new_text;
-                        }
+                        item.content.set_editable_text(new_text);
                     }
                     board.push_history();
                     if let Err(e) = board.flush_save() {
@@ -150,9 +153,7 @@ impl Humanboard {
 
                 if let Some(ref mut board) = self.board {
                     if let Some(item) = board.get_item_mut(item_id) {
-                        if let ItemContent::TextBox { text, .. } = &mut item.content {
-                            *text = new_text;
-                        }
+                        item.content.set_editable_text(new_text);
                     }
                     board.push_history();
                     if let Err(e) = board.flush_save() {
@@ -195,6 +196,142 @@ impl Humanboard {
         cx.notify();
     }
 
+    // ==================== TextBox Formatting Methods ====================
+
+    /// Wrap the current selection in the textbox being edited with a markdown marker
+    /// (e.g. `**` for bold). If the selection is already wrapped, the marker is removed.
+    /// Plain text boxes are switched to markdown so the formatting renders.
+    pub fn wrap_textbox_selection(
+        &mut self,
+        marker: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(item_id), Some(input)) = (self.editing_textbox_id, self.textbox_input.clone())
+        else {
+            return;
+        };
+
+        // Shape and arrow labels are plain text
+        let is_textbox = self.board.as_ref().is_some_and(|board| {
+            board
+                .get_item(item_id)
+                .is_some_and(|item| matches!(item.content, ItemContent::TextBox { .. }))
+        });
+        if !is_textbox {
+            return;
+        }
+
+        input.update(cx, |state, cx| {
+            let Some(selection) = state.selected_text_range(false, window, cx) else {
+                return;
+            };
+            let range = selection.range;
+            // Nothing selected, so there's nothing to wrap
+            if range.is_empty() {
+                return;
+            }
+            let mut adjusted = None;
+            let selected = state
+                .text_for_range(range.clone(), &mut adjusted, window, cx)
+                .unwrap_or_default();
+
+            let replacement = if selected.len() >= marker.len() * 2
+                && selected.starts_with(marker)
+                && selected.ends_with(marker)
+            {
+                selected[marker.len()..selected.len() - marker.len()].to_string()
+            } else {
+                format!("{marker}{selected}{marker}")
+            };
+            state.replace_text_in_range(Some(range), &replacement, window, cx);
+        });
+
+        if let Some(ref mut board) = self.board {
+            if let Some(item) = board.get_item_mut(item_id) {
+                if let ItemContent::TextBox { format, .. } = &mut item.content {
+                    *format = TextFormat::Markdown;
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    /// Set the horizontal alignment of the edited or selected text boxes
+    pub fn set_textbox_alignment(&mut self, alignment: TextAlignment, cx: &mut Context<Self>) {
+        self.modify_styled_textboxes(cx, |content| {
+            if let ItemContent::TextBox { align, .. } = content {
+                *align = alignment;
+            }
+        });
+    }
+
+    /// Advance the alignment of the edited or selected text boxes to the next option
+    pub fn cycle_textbox_alignment(&mut self, cx: &mut Context<Self>) {
+        let current = self.board.as_ref().and_then(|board| {
+            let id = self
+                .editing_textbox_id
+                .or_else(|| self.selected_items.iter().copied().next())?;
+            match &board.get_item(id)?.content {
+                ItemContent::TextBox { align, .. } => Some(*align),
+                _ => None,
+            }
+        });
+        self.set_textbox_alignment(current.unwrap_or_default().next(), cx);
+    }
+
+    /// Set the font family of the edited or selected text boxes (`None` restores the default)
+    pub fn set_textbox_font(&mut self, family: Option<String>, cx: &mut Context<Self>) {
+        self.modify_styled_textboxes(cx, |content| {
+            if let ItemContent::TextBox { font_family, .. } = content {
+                *font_family = family.clone();
+            }
+        });
+    }
+
+    /// Apply a style change to the text box being edited, or to every selected text box.
+    /// Records a single undoable batch for the change.
+    fn modify_styled_textboxes(
+        &mut self,
+        cx: &mut Context<Self>,
+        mut apply: impl FnMut(&mut ItemContent),
+    ) {
+        let targets: Vec<u64> = match self.editing_textbox_id {
+            Some(id) => vec![id],
+            None => self.selected_items.iter().copied().collect(),
+        };
+
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+
+        let mut ops = Vec::new();
+        for id in targets {
+            if let Some(item) = board.get_item_mut(id) {
+                if !matches!(item.content, ItemContent::TextBox { .. }) {
+                    continue;
+                }
+                let old_item = item.clone();
+                apply(&mut item.content);
+                ops.push(UndoOperation::ModifyItem {
+                    old_item,
+                    new_item: item.clone(),
+                });
+            }
+        }
+
+        if ops.is_empty() {
+            return;
+        }
+        if ops.len() == 1 {
+            board.push_operation(ops.remove(0));
+        } else {
+            board.push_operation(UndoOperation::Batch(ops));
+        }
+        board.mark_dirty();
+        cx.notify();
+    }
+
     // ==================== Utility Methods ====================
 
     pub fn update_fps(&mut self) {
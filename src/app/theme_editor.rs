@@ -0,0 +1,191 @@
+//! Theme editor methods - fork the current theme, preview edits and save it
+
+use super::{Humanboard, ThemeEditorState};
+use crate::render::canvas::ContentTypeColors;
+use crate::theme_editor::{self, ThemeColorSlot, ThemeDraft};
+use gpui::*;
+use gpui_component::input::{InputEvent, InputState};
+use gpui_component::theme::Theme;
+use gpui_component::ActiveTheme;
+use std::rc::Rc;
+
+/// The colour shown on screen for `slot` under `theme`
+pub fn resolved_theme_color(slot: ThemeColorSlot, theme: &Theme) -> Hsla {
+    let items = ContentTypeColors::from_theme(theme);
+    match slot {
+        ThemeColorSlot::Background => theme.background,
+        ThemeColorSlot::Foreground => theme.foreground,
+        ThemeColorSlot::Primary => theme.primary,
+        ThemeColorSlot::Accent => theme.accent,
+        ThemeColorSlot::Border => theme.border,
+        ThemeColorSlot::Muted => theme.muted,
+        ThemeColorSlot::Canvas => theme_editor::item_colors(theme.theme_name())
+            .canvas
+            .as_deref()
+            .and_then(|hex| Rgba::try_from(hex).ok())
+            .map(Hsla::from)
+            .unwrap_or(theme.background),
+        ThemeColorSlot::Video => items.video,
+        ThemeColorSlot::Audio => items.audio,
+        ThemeColorSlot::Text => items.text,
+        ThemeColorSlot::Pdf => items.pdf,
+        ThemeColorSlot::Link => items.link,
+        ThemeColorSlot::YouTube => items.youtube,
+    }
+}
+
+impl Humanboard {
+    /// Open the theme editor on a copy of the current theme
+    pub fn open_theme_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let base = if cx.theme().mode.is_dark() {
+            cx.theme().dark_theme.clone()
+        } else {
+            cx.theme().light_theme.clone()
+        };
+        let draft = ThemeDraft::fork(&base, theme_editor::item_colors(&base.name));
+        let selected = ThemeColorSlot::default();
+        let hex = draft
+            .color(selected)
+            .map(str::to_string)
+            .unwrap_or_else(|| theme_editor::hsla_to_hex(resolved_theme_color(selected, cx.theme())));
+
+        let name = draft.name().to_string();
+        let name_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Theme name")
+                .default_value(name)
+        });
+        let hex_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("#rrggbb")
+                .default_value(hex)
+        });
+
+        cx.subscribe(&name_input, |this, input, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                let name = input.read(cx).text().to_string();
+                if let Some(ref mut editor) = this.theme_editor {
+                    editor.draft.set_name(&name);
+                    editor.error = None;
+                }
+                this.preview_theme_draft(cx);
+            }
+        })
+        .detach();
+
+        cx.subscribe(&hex_input, |this, input, event: &InputEvent, cx| {
+            let text = input.read(cx).text().to_string();
+            let Some(ref mut editor) = this.theme_editor else {
+                return;
+            };
+            match event {
+                // Apply as soon as the text is a valid colour
+                InputEvent::Change => {
+                    if editor.draft.set_color(editor.selected, &text).is_ok() {
+                        editor.error = None;
+                        this.preview_theme_draft(cx);
+                    }
+                }
+                InputEvent::PressEnter { .. } => {
+                    if let Err(e) = editor.draft.set_color(editor.selected, &text) {
+                        editor.error = Some(e.to_string());
+                        cx.notify();
+                    }
+                }
+                _ => {}
+            }
+        })
+        .detach();
+
+        self.theme_editor = Some(ThemeEditorState {
+            draft,
+            selected,
+            name_input,
+            hex_input,
+            error: None,
+        });
+        self.preview_theme_draft(cx);
+    }
+
+    /// Choose which colour the hex input edits
+    pub fn select_theme_color(
+        &mut self,
+        slot: ThemeColorSlot,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let resolved = theme_editor::hsla_to_hex(resolved_theme_color(slot, cx.theme()));
+        let Some(ref mut editor) = self.theme_editor else {
+            return;
+        };
+        editor.selected = slot;
+        editor.error = None;
+
+        let hex = editor.draft.color(slot).map(str::to_string).unwrap_or(resolved);
+        editor
+            .hex_input
+            .update(cx, |state, cx| state.set_value(hex, window, cx));
+        cx.notify();
+    }
+
+    /// Show the draft on screen as if it were the active theme
+    fn preview_theme_draft(&mut self, cx: &mut Context<Self>) {
+        let Some(ref editor) = self.theme_editor else {
+            return;
+        };
+        theme_editor::set_item_colors(editor.draft.name(), editor.draft.item_colors.clone());
+
+        let config = Rc::new(editor.draft.config.clone());
+        Theme::global_mut(cx).mode = config.mode;
+        Theme::global_mut(cx).apply_config(&config);
+        cx.refresh_windows();
+    }
+
+    /// Save the draft as a new user theme and switch to it
+    pub fn save_theme_draft(&mut self, cx: &mut Context<Self>) {
+        let Some(ref mut editor) = self.theme_editor else {
+            return;
+        };
+        let Some(themes_dir) = crate::settings_watcher::themes_dir_path() else {
+            editor.error = Some("No themes directory available".to_string());
+            cx.notify();
+            return;
+        };
+
+        let existing = crate::settings::Settings::available_themes(cx);
+        match editor.draft.save(&themes_dir, &existing) {
+            Ok(path) => {
+                tracing::info!("Saved theme to {:?}", path);
+                let name = editor.draft.name().to_string();
+                self.theme_editor = None;
+
+                // The registry picks the file up from its directory watch
+                theme_editor::reload_item_colors(&themes_dir);
+                self.settings.theme = name.clone();
+                self.settings.save();
+                self.toast_manager
+                    .push(crate::notifications::Toast::success(format!(
+                        "Saved theme \"{}\"",
+                        name
+                    )));
+            }
+            Err(e) => {
+                tracing::error!("Failed to save theme: {}", e);
+                editor.error = Some(e.to_string());
+            }
+        }
+        cx.notify();
+    }
+
+    /// Close the theme editor without saving, restoring the current theme
+    pub fn cancel_theme_editor(&mut self, cx: &mut Context<Self>) {
+        if self.theme_editor.take().is_none() {
+            return;
+        }
+        if let Some(themes_dir) = crate::settings_watcher::themes_dir_path() {
+            theme_editor::reload_item_colors(&themes_dir);
+        }
+        self.settings.apply_theme(cx);
+        cx.notify();
+    }
+}
@@ -34,6 +34,7 @@ pub enum CmdPaletteMode {
     #[default]
     Items, // Searching canvas items
     Themes, // Selecting theme
+    Photos, // Listing photos by capture date
 }
 
 /// Tab in the settings modal
@@ -118,6 +119,8 @@ pub enum PreviewTab {
     Pdf {
         path: PathBuf,
         webview: Option<PdfWebView>,
+        /// Page to open at once the webview is created
+        page: Option<usize>,
         meta: TabMeta,
     },
     Markdown {
@@ -331,14 +334,265 @@ impl PreviewPanel {
     }
 }
 
-/// Animation state for smooth panning to a target position
+/// Animation state for smooth panning (and zooming) to a target viewport
 pub struct PanAnimation {
     pub start_offset: Point<Pixels>,
     pub target_offset: Point<Pixels>,
+    pub start_zoom: f32,
+    pub target_zoom: f32,
     pub start_time: Instant,
     pub duration: Duration,
 }
 
+/// Items animating into the places an automatic layout gave them
+pub struct LayoutAnimation {
+    /// The items as they were, for animating from and for undo
+    pub from: Vec<crate::types::CanvasItem>,
+    /// The same items where the layout puts them
+    pub to: Vec<crate::types::CanvasItem>,
+    pub start_time: Instant,
+    pub duration: Duration,
+}
+
+/// An image item being cropped on the canvas
+pub struct CropEditing {
+    pub item_id: u64,
+    /// Where the visible image sits inside the item, in fractions of its size
+    pub image_rect: crate::image_edit::CropRect,
+    /// The area to keep, in fractions of the visible image
+    pub selection: Option<crate::image_edit::CropRect>,
+    /// Where the current drag started, in fractions of the visible image
+    pub drag_start: Option<(f32, f32)>,
+}
+
+/// State while a board is being presented
+pub struct PresentationState {
+    /// Stops in presentation order
+    pub stops: Vec<crate::presentation::PresentationStop>,
+    /// Index of the stop currently shown
+    pub current: usize,
+    /// Viewport restored when the presentation ends
+    pub saved_offset: Point<Pixels>,
+    pub saved_zoom: f32,
+}
+
+/// State while the theme editor is open in the settings modal
+pub struct ThemeEditorState {
+    /// The theme being edited, previewed live
+    pub draft: crate::theme_editor::ThemeDraft,
+    /// Colour the hex input edits
+    pub selected: crate::theme_editor::ThemeColorSlot,
+    pub name_input: Entity<InputState>,
+    pub hex_input: Entity<InputState>,
+    /// Problem with the last edit, shown under the inputs
+    pub error: Option<String>,
+}
+
+/// Outcome of an external command run in the background
+pub struct ExternalCommandResult {
+    /// Board the command ran on; changes are dropped if it was closed
+    pub board_id: String,
+    /// Command name, for notifications
+    pub name: String,
+    /// Where added items without a position are stacked, beside the selection
+    pub anchor: (f32, f32),
+    /// ID of the run in `Humanboard::running_external_commands`
+    pub run_id: u64,
+    pub result: Result<
+        crate::external_commands::ExternalCommandOutput,
+        crate::error::ExternalCommandError,
+    >,
+}
+
+/// An external command that's still running
+pub struct RunningExternalCommand {
+    pub run_id: u64,
+    pub name: String,
+    /// Set to have the command killed
+    pub cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+/// An image's size and EXIF details, shown in the info overlay
+pub struct ImageInfoView {
+    /// File name, for the title
+    pub name: String,
+    /// Labelled values, dimensions first
+    pub fields: Vec<(&'static str, String)>,
+}
+
+/// The page picker for a PDF item
+pub struct PdfPagePicker {
+    /// PDF item the pages are placed beside
+    pub item_id: u64,
+    /// File name, for the title
+    pub name: String,
+    /// Page thumbnails, once rendered
+    pub pages: Option<Vec<crate::pdf_thumbnail::RenderedPage>>,
+    /// Why the thumbnails couldn't be rendered
+    pub error: Option<String>,
+    /// Chosen page numbers
+    pub selected: std::collections::BTreeSet<usize>,
+    /// Last page clicked, where shift-click ranges start
+    pub anchor: Option<usize>,
+}
+
+/// Pages of a PDF rendered in the background
+pub struct PdfPagesResult {
+    /// Board the PDF is on; pages are dropped if it was closed
+    pub board_id: String,
+    /// PDF item the pages belong to
+    pub item_id: u64,
+    /// Thumbnails for the picker rather than pages to place
+    pub for_picker: bool,
+    pub result: crate::background::TaskResult<Vec<crate::pdf_thumbnail::RenderedPage>>,
+}
+
+/// A PDF open for annotating, one page at a time
+pub struct PdfAnnotator {
+    pub path: PathBuf,
+    /// File name, for the title
+    pub name: String,
+    /// Page shown, from 1
+    pub page: usize,
+    /// Number of pages, once the PDF has been read
+    pub page_count: Option<usize>,
+    /// The page shown, once rendered
+    pub image: Option<crate::pdf_thumbnail::RenderedPage>,
+    /// Why the page couldn't be rendered
+    pub error: Option<String>,
+    pub tool: crate::pdf_annotations::AnnotationTool,
+    /// Corners of the area being dragged out, as fractions of the page
+    pub drag: Option<((f32, f32), (f32, f32))>,
+    /// Note waiting for its text from the command palette
+    pub pending_note: Option<PendingNote>,
+}
+
+/// A note whose text is being typed in the command palette
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PendingNote {
+    /// A new note at a point on a page
+    New { page: usize, point: (f32, f32) },
+    /// An existing note, by annotation ID
+    Edit(u64),
+}
+
+/// A page of the PDF being annotated, rendered in the background
+pub struct PdfAnnotatorPage {
+    pub path: PathBuf,
+    pub page: usize,
+    /// The document's page count and the rendered page
+    pub result: crate::background::TaskResult<(usize, crate::pdf_thumbnail::RenderedPage)>,
+}
+
+/// The comments sidebar
+#[derive(Clone, Debug, Default)]
+pub struct CommentsPanel {
+    /// Thread to highlight, such as one just jumped to
+    pub focused: Option<u64>,
+    /// Whether resolved threads are listed too
+    pub show_resolved: bool,
+    /// Comment waiting for its text from the command palette
+    pub pending: Option<PendingComment>,
+}
+
+/// A comment whose text is being typed in the command palette
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PendingComment {
+    /// The first comment of a new thread
+    New(crate::comments::CommentAnchor),
+    /// A reply to a thread, by ID
+    Reply(u64),
+}
+
+/// Files and folders dropped on a board, read and copied in the background
+pub struct DroppedFilesResult {
+    /// Board they were dropped on; they're dropped if it was closed
+    pub board_id: String,
+    /// Canvas point the drop landed on
+    pub origin: (f32, f32),
+    pub result: crate::background::TaskResult<crate::board::PreparedDrop>,
+}
+
+/// EXIF capture dates of a board's images, read in the background
+pub struct CaptureDates {
+    /// Board the images are on
+    pub board_id: String,
+    /// When the images were listed for reading; later board changes make the
+    /// dates stale
+    pub read_at: std::time::Instant,
+    /// Capture dates of the dated images, by item ID
+    pub dates: std::collections::HashMap<u64, String>,
+}
+
+/// Image edits written out to new files in the background
+pub struct BakedImagesResult {
+    /// Board the images are on; nothing changes if it was closed
+    pub board_id: String,
+    /// Each image's item ID and its new file, or why it couldn't be written
+    pub baked: Vec<(u64, Result<std::path::PathBuf, String>)>,
+}
+
+/// A board saved as a template in the background
+pub struct SavedTemplateResult {
+    /// Name the template was saved under
+    pub name: String,
+    pub result: crate::background::TaskResult<crate::templates::SavedTemplate>,
+}
+
+/// Colours extracted from an image in the background
+pub struct PaletteResult {
+    /// Board the image is on; swatches are dropped if it was closed
+    pub board_id: String,
+    pub item_id: u64,
+    pub result: crate::background::TaskResult<Vec<crate::palette::Rgb>>,
+}
+
+/// Other apps' board formats that can be imported and exported
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardFormat {
+    /// Obsidian's open `.canvas` format
+    JsonCanvas,
+    /// Excalidraw's `.excalidraw` scenes
+    Excalidraw,
+}
+
+impl BoardFormat {
+    pub const ALL: [BoardFormat; 2] = [BoardFormat::JsonCanvas, BoardFormat::Excalidraw];
+
+    /// Human-readable name
+    pub fn name(self) -> &'static str {
+        match self {
+            BoardFormat::JsonCanvas => "JSON Canvas",
+            BoardFormat::Excalidraw => "Excalidraw",
+        }
+    }
+
+    /// File extension, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            BoardFormat::JsonCanvas => crate::json_canvas::EXTENSION,
+            BoardFormat::Excalidraw => crate::excalidraw::EXTENSION,
+        }
+    }
+
+    /// The format a file is in, judged by its extension
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+        Self::ALL
+            .into_iter()
+            .find(|format| ext.eq_ignore_ascii_case(format.extension()))
+    }
+}
+
+/// A file picked in a native open or save dialog
+#[derive(Clone, Debug)]
+pub enum FilePrompt {
+    /// Import this file as a new board
+    Import(PathBuf),
+    /// Export the open board to this file
+    Export(BoardFormat, PathBuf),
+}
+
 /// State for the countdown timer on the home screen
 #[derive(Clone, Debug)]
 pub struct CountdownState {
@@ -0,0 +1,146 @@
+//! Viewport navigation - zoom to fit, zoom to selection and bookmarks.
+
+use super::{Humanboard, PanAnimation, SplitDirection};
+use crate::constants::{FOOTER_HEIGHT, HEADER_HEIGHT, PAN_ANIMATION_DURATION, ZOOM_TO_FIT_MARGIN};
+use crate::notifications::Toast;
+use crate::presentation::{fit_viewport, items_bounds};
+use crate::render::dock::DOCK_WIDTH;
+use crate::types::CanvasItem;
+use gpui::*;
+use std::time::{Duration, Instant};
+
+impl Humanboard {
+    /// Size of the visible canvas area, excluding chrome and the preview panel
+    pub fn canvas_area_size(&self, window: &Window) -> (f32, f32) {
+        let size = window.bounds().size;
+        let mut width = f32::from(size.width) - DOCK_WIDTH;
+        let mut height = f32::from(size.height) - HEADER_HEIGHT - FOOTER_HEIGHT;
+        if let Some(ref preview) = self.preview {
+            match preview.split {
+                SplitDirection::Vertical => width *= 1.0 - preview.size,
+                SplitDirection::Horizontal => height *= 1.0 - preview.size,
+            }
+        }
+        (width, height)
+    }
+
+    /// Move the camera to a viewport, animated unless reduced motion is requested
+    pub fn animate_viewport(
+        &mut self,
+        target_offset: Point<Pixels>,
+        target_zoom: f32,
+        duration: Duration,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+
+        if crate::settings::app_settings().should_reduce_motion() {
+            self.pan_animation = None;
+            board.canvas_offset = target_offset;
+            board.zoom = target_zoom;
+            board.mark_dirty();
+        } else {
+            self.pan_animation = Some(PanAnimation {
+                start_offset: board.canvas_offset,
+                target_offset,
+                start_zoom: board.zoom,
+                target_zoom,
+                start_time: Instant::now(),
+                duration,
+            });
+        }
+        cx.notify();
+    }
+
+    /// Zoom and pan so every item on the board is visible
+    pub fn zoom_to_fit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(bounds) = self.board.as_ref().and_then(|b| items_bounds(&b.items)) else {
+            return;
+        };
+        self.zoom_to_bounds(bounds, window, cx);
+    }
+
+    /// Zoom and pan so the selected items fill the canvas
+    pub fn zoom_to_selection(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ref board) = self.board else {
+            return;
+        };
+        let selected: Vec<CanvasItem> = board
+            .items
+            .iter()
+            .filter(|item| self.selected_items.contains(&item.id))
+            .cloned()
+            .collect();
+
+        match items_bounds(&selected) {
+            Some(bounds) => self.zoom_to_bounds(bounds, window, cx),
+            None => {
+                self.toast_manager
+                    .push(Toast::info("Select items to zoom to them"));
+                cx.notify();
+            }
+        }
+    }
+
+    fn zoom_to_bounds(
+        &mut self,
+        bounds: (f32, f32, f32, f32),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let viewport = self.canvas_area_size(window);
+        let (offset, zoom) = fit_viewport(bounds, viewport, ZOOM_TO_FIT_MARGIN);
+        self.animate_viewport(
+            point(px(offset.0), px(offset.1)),
+            zoom,
+            Duration::from_secs_f64(PAN_ANIMATION_DURATION),
+            cx,
+        );
+    }
+
+    /// Save the current viewport as a named bookmark
+    pub fn save_bookmark(&mut self, name: Option<String>, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+        let name = name.unwrap_or_else(|| format!("View {}", board.bookmarks.len() + 1));
+        let index = board.save_bookmark(&name);
+
+        let message = if index < 9 {
+            format!("Saved view '{}' (press {} to return)", name, index + 1)
+        } else {
+            format!("Saved view '{}'", name)
+        };
+        self.toast_manager.push(Toast::success(message));
+        cx.notify();
+    }
+
+    /// Delete a bookmark by name
+    pub fn remove_bookmark(&mut self, name: &str, cx: &mut Context<Self>) {
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+        if !board.remove_bookmark(name) {
+            self.toast_manager
+                .push(Toast::error(format!("No saved view named '{}'", name)));
+        }
+        cx.notify();
+    }
+
+    /// Animate to the bookmark at `index`, if there is one
+    pub fn go_to_bookmark(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(bookmark) = self.board.as_ref().and_then(|b| b.bookmarks.get(index)) else {
+            return;
+        };
+        let target_offset = point(px(bookmark.canvas_offset.0), px(bookmark.canvas_offset.1));
+        let zoom = bookmark.zoom;
+        self.animate_viewport(
+            target_offset,
+            zoom,
+            Duration::from_secs_f64(PAN_ANIMATION_DURATION),
+            cx,
+        );
+    }
+}
@@ -44,7 +44,13 @@ pub struct AudioWebView {
 }
 
 impl AudioWebView {
-    pub fn new(audio_path: PathBuf, window: &mut Window, cx: &mut App) -> Result<Self, String> {
+    /// Create a player for `audio_path`, starting playback at `start` seconds if given
+    pub fn new(
+        audio_path: PathBuf,
+        start: Option<f64>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<Self, String> {
         let port = PORT_COUNTER.fetch_add(1, Ordering::SeqCst);
         let audio_path_clone = audio_path.clone();
         let file_name = audio_path
@@ -261,6 +267,19 @@ impl AudioWebView {
             const pct = (e.clientX - rect.left) / rect.width;
             audio.currentTime = pct * audio.duration;
         }};
+
+        // Called from the canvas when a waveform marker is clicked
+        window.seekTo = t => {{
+            audio.currentTime = t;
+            audio.play();
+            playIcon.classList.add('hidden');
+            pauseIcon.classList.remove('hidden');
+        }};
+
+        const start = parseFloat(new URLSearchParams(location.search).get('t'));
+        if (start >= 0) {{
+            audio.addEventListener('loadedmetadata', () => seekTo(start), {{ once: true }});
+        }}
     </script>
 </body>
 </html>"##,
@@ -308,7 +327,10 @@ impl AudioWebView {
 
         std::thread::sleep(std::time::Duration::from_millis(100));
 
-        let url = format!("http://127.0.0.1:{}/", port);
+        let url = match start {
+            Some(start) => format!("http://127.0.0.1:{}/?t={}", port, start.max(0.0)),
+            None => format!("http://127.0.0.1:{}/", port),
+        };
 
         #[cfg(any(
             target_os = "macos",
@@ -345,6 +367,16 @@ impl AudioWebView {
         self.webview_entity.update(cx, |wv, _| wv.hide());
     }
 
+    /// Jump to `seconds` and play from there
+    pub fn seek(&self, seconds: f64, cx: &mut App) {
+        let script = format!("seekTo({})", seconds.max(0.0));
+        self.webview_entity.update(cx, |wv, _| {
+            if let Err(e) = wv.raw().evaluate_script(&script) {
+                error!("Failed to seek audio player: {:?}", e);
+            }
+        });
+    }
+
     fn serve_audio_file(path: &PathBuf, request: tiny_http::Request) {
         let mut file = match std::fs::File::open(path) {
             Ok(f) => f,
@@ -0,0 +1,634 @@
+//! Automatic layout - rearranges items as image walls, grids or graphs.
+//!
+//! Wall and grid layouts ignore arrows. Tree and force-directed layouts treat
+//! each arrow whose ends touch two arranged items as an edge between them.
+//! Arrows attached to moved items are re-routed between their new positions.
+//! Layouts keep the arranged items' top-left corner where it was. Everything
+//! here is pure so it can be tested without a window.
+
+use crate::types::{arrow_bounds, CanvasItem, ItemContent, ShapeType};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Space between arranged items
+pub const GAP: f32 = 24.0;
+/// Row height justified rows start from before they are scaled to fit
+pub const ROW_HEIGHT: f32 = 240.0;
+/// Width of a masonry column
+pub const COLUMN_WIDTH: f32 = 240.0;
+/// Space between tree levels
+pub const LEVEL_GAP: f32 = 96.0;
+/// How far outside an item an arrow end can be and still count as attached
+pub const ATTACH_TOLERANCE: f32 = 24.0;
+/// Width over height that wall and grid layouts aim for
+const ASPECT: f32 = 1.6;
+const FORCE_ITERATIONS: usize = 300;
+const OVERLAP_PASSES: usize = 50;
+
+/// The available layouts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutKind {
+    /// Fixed-width columns, each item going to the shortest one
+    Masonry,
+    /// Rows of equal height, scaled to a common width
+    Justified,
+    /// Uniform cells sized to the largest item
+    Grid,
+    /// Top-down tree following arrows
+    Tree,
+    /// Spring simulation pulling connected items together
+    Force,
+}
+
+impl LayoutKind {
+    /// Every layout, in the order shown in the command palette
+    pub const ALL: [LayoutKind; 5] = [
+        LayoutKind::Masonry,
+        LayoutKind::Justified,
+        LayoutKind::Grid,
+        LayoutKind::Tree,
+        LayoutKind::Force,
+    ];
+
+    /// Human-readable name
+    pub fn name(self) -> &'static str {
+        match self {
+            LayoutKind::Masonry => "Masonry",
+            LayoutKind::Justified => "Justified Rows",
+            LayoutKind::Grid => "Grid",
+            LayoutKind::Tree => "Tree",
+            LayoutKind::Force => "Force-Directed Graph",
+        }
+    }
+
+    /// Identifier used in command IDs
+    pub fn slug(self) -> &'static str {
+        match self {
+            LayoutKind::Masonry => "masonry",
+            LayoutKind::Justified => "justified",
+            LayoutKind::Grid => "grid",
+            LayoutKind::Tree => "tree",
+            LayoutKind::Force => "force",
+        }
+    }
+
+    /// Whether items are placed in sequence, so sorting them changes the result
+    pub fn is_ordered(self) -> bool {
+        matches!(
+            self,
+            LayoutKind::Masonry | LayoutKind::Justified | LayoutKind::Grid
+        )
+    }
+}
+
+/// Whether an item takes part in layouts; arrows, strokes and frames don't
+pub fn is_arrangeable(item: &CanvasItem) -> bool {
+    !matches!(
+        item.content,
+        ItemContent::Arrow { .. }
+            | ItemContent::Stroke { .. }
+            | ItemContent::Shape {
+                shape_type: ShapeType::Frame,
+                ..
+            }
+    )
+}
+
+/// Arrange the selected items, or every item when nothing is selected.
+///
+/// Returns the new state of each item that changed, including re-routed arrows.
+pub fn arrange(
+    kind: LayoutKind,
+    items: &[CanvasItem],
+    selection: &HashSet<u64>,
+) -> Vec<CanvasItem> {
+    arrange_sorted(kind, items, selection, &HashMap::new())
+}
+
+/// Arrange like [`arrange`], with items that have a sort key (such as a
+/// photo's capture date) placed first, in key order.
+pub fn arrange_sorted(
+    kind: LayoutKind,
+    items: &[CanvasItem],
+    selection: &HashSet<u64>,
+    keys: &HashMap<u64, String>,
+) -> Vec<CanvasItem> {
+    let mut nodes: Vec<&CanvasItem> = items
+        .iter()
+        .filter(|item| is_arrangeable(item))
+        .filter(|item| selection.is_empty() || selection.contains(&item.id))
+        .collect();
+    if nodes.len() < 2 {
+        return Vec::new();
+    }
+    // Reading order, so walls and grids keep a familiar sequence
+    nodes.sort_by(|a, b| {
+        a.position
+            .1
+            .total_cmp(&b.position.1)
+            .then(a.position.0.total_cmp(&b.position.0))
+    });
+    // Stable, so items with equal or no keys stay in reading order
+    nodes.sort_by_key(|node| {
+        let key = keys.get(&node.id);
+        (key.is_none(), key)
+    });
+
+    let edges = edges(items, &nodes);
+    let rects: Vec<Rect> = match kind {
+        LayoutKind::Masonry => masonry(&nodes),
+        LayoutKind::Justified => justified(&nodes),
+        LayoutKind::Grid => grid(&nodes),
+        LayoutKind::Tree => tree(&nodes, &edges),
+        LayoutKind::Force => force(&nodes, &edges),
+    };
+    let rects = anchor(&nodes, rects);
+
+    let moved: Vec<(Rect, Rect)> = nodes
+        .iter()
+        .zip(&rects)
+        .map(|(node, rect)| (Rect::of(node), *rect))
+        .collect();
+
+    let mut changed: Vec<CanvasItem> = nodes
+        .iter()
+        .zip(&rects)
+        .filter(|(node, rect)| Rect::of(node) != **rect)
+        .map(|(node, rect)| {
+            let mut item = (*node).clone();
+            item.position = (rect.x, rect.y);
+            item.size = (rect.w, rect.h);
+            item
+        })
+        .collect();
+    changed.extend(reroute_arrows(items, &moved));
+    changed
+}
+
+/// An item's state part way through animating from `from` to `to`.
+///
+/// Arrows move their endpoints so they never flip while animating.
+pub fn interpolate(from: &CanvasItem, to: &CanvasItem, t: f32) -> CanvasItem {
+    let lerp = |a: (f32, f32), b: (f32, f32)| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+    let mut item = to.clone();
+    if let (Some((from_start, from_end)), Some((to_start, to_end))) =
+        (from.arrow_endpoints(), to.arrow_endpoints())
+    {
+        set_arrow_endpoints(
+            &mut item,
+            lerp(from_start, to_start),
+            lerp(from_end, to_end),
+        );
+    } else {
+        item.position = lerp(from.position, to.position);
+        item.size = lerp(from.size, to.size);
+    }
+    item
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+impl Rect {
+    fn of(item: &CanvasItem) -> Self {
+        Self {
+            x: item.position.0,
+            y: item.position.1,
+            w: item.size.0,
+            h: item.size.1,
+        }
+    }
+
+    fn center(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+
+    fn contains(&self, point: (f32, f32), margin: f32) -> bool {
+        point.0 >= self.x - margin
+            && point.0 <= self.x + self.w + margin
+            && point.1 >= self.y - margin
+            && point.1 <= self.y + self.h + margin
+    }
+
+    /// Where the line from the centre towards `point` leaves the rectangle
+    fn boundary_towards(&self, point: (f32, f32)) -> (f32, f32) {
+        let (cx, cy) = self.center();
+        let (dx, dy) = (point.0 - cx, point.1 - cy);
+        let scale_x = if dx == 0.0 {
+            f32::INFINITY
+        } else {
+            self.w / 2.0 / dx.abs()
+        };
+        let scale_y = if dy == 0.0 {
+            f32::INFINITY
+        } else {
+            self.h / 2.0 / dy.abs()
+        };
+        let scale = scale_x.min(scale_y).min(1.0);
+        (cx + dx * scale, cy + dy * scale)
+    }
+}
+
+/// Index of the smallest node an arrow end touches
+fn attached(point: (f32, f32), nodes: &[Rect]) -> Option<usize> {
+    nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, rect)| rect.contains(point, ATTACH_TOLERANCE))
+        .min_by(|(_, a), (_, b)| (a.w * a.h).total_cmp(&(b.w * b.h)))
+        .map(|(i, _)| i)
+}
+
+/// Arrows between two different nodes, as (from, to) node indices
+fn edges(items: &[CanvasItem], nodes: &[&CanvasItem]) -> Vec<(usize, usize)> {
+    let rects: Vec<Rect> = nodes.iter().map(|node| Rect::of(node)).collect();
+    items
+        .iter()
+        .filter_map(CanvasItem::arrow_endpoints)
+        .filter_map(|(start, end)| Some((attached(start, &rects)?, attached(end, &rects)?)))
+        .filter(|(from, to)| from != to)
+        .collect()
+}
+
+/// Re-route arrows with an end on a moved node, keeping free ends in place.
+///
+/// `moved` holds each arranged node's rect before and after the layout.
+fn reroute_arrows(items: &[CanvasItem], moved: &[(Rect, Rect)]) -> Vec<CanvasItem> {
+    let old_rects: Vec<Rect> = moved.iter().map(|(old, _)| *old).collect();
+
+    items
+        .iter()
+        .filter_map(|arrow| {
+            let (start, end) = arrow.arrow_endpoints()?;
+            let from = attached(start, &old_rects);
+            let to = attached(end, &old_rects);
+            let moves = |i: &usize| moved[*i].0 != moved[*i].1;
+            if !from.iter().chain(&to).any(moves) {
+                return None;
+            }
+            let new_rect = |i: Option<usize>| i.map(|i| moved[i].1);
+            let (new_start, new_end) = match (from, to) {
+                // Both ends on one item: move the arrow along with it
+                (Some(a), Some(b)) if a == b => {
+                    let (old, new) = moved[a];
+                    let shift = |p: (f32, f32)| (p.0 + new.x - old.x, p.1 + new.y - old.y);
+                    (shift(start), shift(end))
+                }
+                _ => match (new_rect(from), new_rect(to)) {
+                    (Some(from), Some(to)) => (
+                        from.boundary_towards(to.center()),
+                        to.boundary_towards(from.center()),
+                    ),
+                    (Some(from), None) => (from.boundary_towards(end), end),
+                    (None, Some(to)) => (start, to.boundary_towards(start)),
+                    (None, None) => return None,
+                },
+            };
+            let mut arrow = arrow.clone();
+            set_arrow_endpoints(&mut arrow, new_start, new_end);
+            Some(arrow)
+        })
+        .collect()
+}
+
+fn set_arrow_endpoints(arrow: &mut CanvasItem, start: (f32, f32), end: (f32, f32)) {
+    if let ItemContent::Arrow { end_offset, .. } = &mut arrow.content {
+        *end_offset = (end.0 - start.0, end.1 - start.1);
+    }
+    let (position, size) = arrow_bounds(start, end);
+    arrow.position = position;
+    arrow.size = size;
+}
+
+/// Move laid-out rects so their top-left corner matches the nodes' original one
+fn anchor(nodes: &[&CanvasItem], mut rects: Vec<Rect>) -> Vec<Rect> {
+    let min = |values: &mut dyn Iterator<Item = f32>| values.fold(f32::INFINITY, f32::min);
+    let dx = min(&mut nodes.iter().map(|n| n.position.0)) - min(&mut rects.iter().map(|r| r.x));
+    let dy = min(&mut nodes.iter().map(|n| n.position.1)) - min(&mut rects.iter().map(|r| r.y));
+    for rect in &mut rects {
+        rect.x += dx;
+        rect.y += dy;
+    }
+    rects
+}
+
+/// Width over height, guarding against degenerate sizes
+fn aspect(node: &CanvasItem) -> f32 {
+    (node.size.0 / node.size.1.max(1.0)).max(0.01)
+}
+
+fn masonry(nodes: &[&CanvasItem]) -> Vec<Rect> {
+    let heights: Vec<f32> = nodes.iter().map(|n| COLUMN_WIDTH / aspect(n)).collect();
+    let total: f32 = heights.iter().map(|h| h + GAP).sum();
+    // Enough columns that the wall ends up roughly ASPECT times wider than tall
+    let columns =
+        ((ASPECT * total / (COLUMN_WIDTH + GAP)).sqrt().round() as usize).clamp(1, nodes.len());
+
+    let mut bottoms = vec![0.0_f32; columns];
+    heights
+        .iter()
+        .map(|&h| {
+            let (column, top) = bottoms
+                .iter()
+                .copied()
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap_or((0, 0.0));
+            bottoms[column] = top + h + GAP;
+            Rect {
+                x: column as f32 * (COLUMN_WIDTH + GAP),
+                y: top,
+                w: COLUMN_WIDTH,
+                h,
+            }
+        })
+        .collect()
+}
+
+fn justified(nodes: &[&CanvasItem]) -> Vec<Rect> {
+    let widths: Vec<f32> = nodes.iter().map(|n| ROW_HEIGHT * aspect(n)).collect();
+    let total: f32 = widths.iter().map(|w| w + GAP).sum();
+    let rows = (total / (ASPECT * (ROW_HEIGHT + GAP))).sqrt().max(1.0);
+    let widest = widths.iter().copied().fold(0.0, f32::max);
+    let row_width = (total / rows).max(widest);
+
+    let mut rects = Vec::with_capacity(nodes.len());
+    let mut row: Vec<f32> = Vec::new();
+    let mut y = 0.0;
+    for (i, &width) in widths.iter().enumerate() {
+        row.push(width);
+        let filled: f32 = row.iter().sum::<f32>() + GAP * (row.len() - 1) as f32;
+        let last = i == widths.len() - 1;
+        if filled < row_width && !last {
+            continue;
+        }
+        // Scale full rows to the common width; a short last row keeps its height
+        let gaps = GAP * (row.len() - 1) as f32;
+        let scale = if filled >= row_width {
+            (row_width - gaps) / (filled - gaps)
+        } else {
+            1.0
+        };
+        let mut x = 0.0;
+        for width in row.drain(..) {
+            rects.push(Rect {
+                x,
+                y,
+                w: width * scale,
+                h: ROW_HEIGHT * scale,
+            });
+            x += width * scale + GAP;
+        }
+        y += ROW_HEIGHT * scale + GAP;
+    }
+    rects
+}
+
+fn grid(nodes: &[&CanvasItem]) -> Vec<Rect> {
+    let cell_w = nodes.iter().map(|n| n.size.0).fold(0.0, f32::max);
+    let cell_h = nodes.iter().map(|n| n.size.1).fold(0.0, f32::max);
+    // Columns for a grid about ASPECT times wider than tall
+    let columns = ((nodes.len() as f32 * ASPECT * (cell_h + GAP) / (cell_w + GAP))
+        .sqrt()
+        .round() as usize)
+        .clamp(1, nodes.len());
+
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| {
+            let cell_x = (i % columns) as f32 * (cell_w + GAP);
+            let cell_y = (i / columns) as f32 * (cell_h + GAP);
+            Rect {
+                x: cell_x + (cell_w - node.size.0) / 2.0,
+                y: cell_y + (cell_h - node.size.1) / 2.0,
+                w: node.size.0,
+                h: node.size.1,
+            }
+        })
+        .collect()
+}
+
+fn tree(nodes: &[&CanvasItem], edges: &[(usize, usize)]) -> Vec<Rect> {
+    let count = nodes.len();
+    // Left-to-right order of roots and children follows their current position
+    let mut by_x: Vec<usize> = (0..count).collect();
+    by_x.sort_by(|&a, &b| nodes[a].position.0.total_cmp(&nodes[b].position.0));
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); count];
+    let mut has_parent = vec![false; count];
+    for &n in &by_x {
+        for &(from, to) in edges {
+            if to == n && !outgoing[from].contains(&n) {
+                outgoing[from].push(n);
+                has_parent[n] = true;
+            }
+        }
+    }
+
+    // Breadth-first from the roots, so each node hangs under the first parent
+    // that reaches it; nodes only reachable through cycles start their own tree
+    let mut layout = TreeLayout {
+        nodes,
+        children: vec![Vec::new(); count],
+        depth: vec![usize::MAX; count],
+        widths: vec![0.0; count],
+        level_tops: Vec::new(),
+        level_heights: Vec::new(),
+        rects: nodes.iter().map(|n| Rect::of(n)).collect(),
+    };
+    let mut roots = Vec::new();
+    let starts = by_x.iter().filter(|&&n| !has_parent[n]).chain(&by_x);
+    for &start in starts {
+        if layout.depth[start] != usize::MAX {
+            continue;
+        }
+        layout.depth[start] = 0;
+        roots.push(start);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for &child in &outgoing[node] {
+                if layout.depth[child] == usize::MAX {
+                    layout.depth[child] = layout.depth[node] + 1;
+                    layout.children[node].push(child);
+                    queue.push_back(child);
+                }
+            }
+        }
+    }
+
+    // Each level is as tall as its tallest item
+    let levels = layout.depth.iter().max().map_or(0, |d| d + 1);
+    layout.level_heights = vec![0.0; levels];
+    for (n, &d) in layout.depth.iter().enumerate() {
+        layout.level_heights[d] = layout.level_heights[d].max(nodes[n].size.1);
+    }
+    let mut top = 0.0;
+    for height in &layout.level_heights {
+        layout.level_tops.push(top);
+        top += height + LEVEL_GAP;
+    }
+
+    let mut x = 0.0;
+    for root in roots {
+        let width = layout.measure(root);
+        layout.place(root, x);
+        x += width + GAP * 2.0;
+    }
+    layout.rects
+}
+
+/// Working state of the tree layout
+struct TreeLayout<'a> {
+    nodes: &'a [&'a CanvasItem],
+    children: Vec<Vec<usize>>,
+    depth: Vec<usize>,
+    /// Width of each node's subtree
+    widths: Vec<f32>,
+    level_tops: Vec<f32>,
+    level_heights: Vec<f32>,
+    rects: Vec<Rect>,
+}
+
+impl TreeLayout<'_> {
+    /// Work out the width of `n`'s subtree
+    fn measure(&mut self, n: usize) -> f32 {
+        let children = self.children[n].clone();
+        let children_width = self.children_width(&children, |layout, c| layout.measure(c));
+        self.widths[n] = self.nodes[n].size.0.max(children_width);
+        self.widths[n]
+    }
+
+    fn children_width(
+        &mut self,
+        children: &[usize],
+        mut width: impl FnMut(&mut Self, usize) -> f32,
+    ) -> f32 {
+        let total: f32 = children.iter().map(|&c| width(self, c) + GAP).sum();
+        (total - GAP).max(0.0)
+    }
+
+    /// Place `n`'s subtree with its left edge at `left`, centring parents over children
+    fn place(&mut self, n: usize, left: f32) {
+        let (w, h) = self.nodes[n].size;
+        let d = self.depth[n];
+        self.rects[n] = Rect {
+            x: left + (self.widths[n] - w) / 2.0,
+            y: self.level_tops[d] + (self.level_heights[d] - h) / 2.0,
+            w,
+            h,
+        };
+        let children = self.children[n].clone();
+        let children_width = self.children_width(&children, |layout, c| layout.widths[c]);
+        let mut x = left + (self.widths[n] - children_width) / 2.0;
+        for child in children {
+            self.place(child, x);
+            x += self.widths[child] + GAP;
+        }
+    }
+}
+
+fn force(nodes: &[&CanvasItem], edges: &[(usize, usize)]) -> Vec<Rect> {
+    let count = nodes.len();
+    let mut centers: Vec<(f32, f32)> = nodes.iter().map(|n| Rect::of(n).center()).collect();
+    // Ideal distance between connected items: about one item apart
+    let k = nodes.iter().map(|n| n.size.0.max(n.size.1)).sum::<f32>() / count as f32 + GAP * 2.0;
+    let mut temperature = k * 2.0;
+    let cooling = temperature / FORCE_ITERATIONS as f32;
+
+    for _ in 0..FORCE_ITERATIONS {
+        let mut shifts = vec![(0.0_f32, 0.0_f32); count];
+        for a in 0..count {
+            for b in (a + 1)..count {
+                let (dx, dy, dist) = separation(centers[a], centers[b], a);
+                let push = k * k / dist;
+                shifts[a].0 += dx / dist * push;
+                shifts[a].1 += dy / dist * push;
+                shifts[b].0 -= dx / dist * push;
+                shifts[b].1 -= dy / dist * push;
+            }
+        }
+        for &(a, b) in edges {
+            let (dx, dy, dist) = separation(centers[a], centers[b], a);
+            let pull = dist * dist / k;
+            shifts[a].0 -= dx / dist * pull;
+            shifts[a].1 -= dy / dist * pull;
+            shifts[b].0 += dx / dist * pull;
+            shifts[b].1 += dy / dist * pull;
+        }
+        for (center, shift) in centers.iter_mut().zip(&shifts) {
+            let length = (shift.0 * shift.0 + shift.1 * shift.1).sqrt().max(0.01);
+            let step = length.min(temperature);
+            center.0 += shift.0 / length * step;
+            center.1 += shift.1 / length * step;
+        }
+        temperature = (temperature - cooling).max(1.0);
+    }
+
+    let mut rects: Vec<Rect> = nodes
+        .iter()
+        .zip(&centers)
+        .map(|(n, c)| Rect {
+            x: c.0 - n.size.0 / 2.0,
+            y: c.1 - n.size.1 / 2.0,
+            w: n.size.0,
+            h: n.size.1,
+        })
+        .collect();
+    remove_overlaps(&mut rects);
+    rects
+}
+
+/// Offset from `b` to `a` and its length, never zero so forces stay finite
+fn separation(a: (f32, f32), b: (f32, f32), seed: usize) -> (f32, f32, f32) {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    let dist = (dx * dx + dy * dy).sqrt();
+    if dist > 0.01 {
+        return (dx, dy, dist);
+    }
+    // Coincident items are pushed apart in a direction that depends on the item
+    let angle = seed as f32 * 2.4;
+    (angle.cos(), angle.sin(), 1.0)
+}
+
+/// Push overlapping rects apart along the axis where they overlap least
+fn remove_overlaps(rects: &mut [Rect]) {
+    for _ in 0..OVERLAP_PASSES {
+        let mut moved = false;
+        for a in 0..rects.len() {
+            for b in (a + 1)..rects.len() {
+                let (ra, rb) = (rects[a], rects[b]);
+                let overlap_x = (ra.x + ra.w + GAP).min(rb.x + rb.w + GAP) - ra.x.max(rb.x);
+                let overlap_y = (ra.y + ra.h + GAP).min(rb.y + rb.h + GAP) - ra.y.max(rb.y);
+                if overlap_x <= 0.0 || overlap_y <= 0.0 {
+                    continue;
+                }
+                moved = true;
+                let (ca, cb) = (ra.center(), rb.center());
+                if overlap_x < overlap_y {
+                    let shift = if ca.0 < cb.0 {
+                        overlap_x / 2.0
+                    } else {
+                        -overlap_x / 2.0
+                    };
+                    rects[a].x -= shift;
+                    rects[b].x += shift;
+                } else {
+                    let shift = if ca.1 < cb.1 {
+                        overlap_y / 2.0
+                    } else {
+                        -overlap_y / 2.0
+                    };
+                    rects[a].y -= shift;
+                    rects[b].y += shift;
+                }
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+}
@@ -4,14 +4,19 @@
 //! including items, undo/redo history, and debounced saving.
 
 use crate::board_index::BoardIndex;
+use crate::comments::{self, Comment, CommentAnchor, CommentThread};
 use crate::error::BoardError;
-use crate::types::{CanvasItem, ItemContent};
+use crate::folder_import::{self, FolderImportOptions};
+use crate::fuzzy::{fuzzy_match, FuzzyMatch};
+use crate::pdf_annotations::{self, AnnotationKind, PdfAnnotation};
+use crate::strokes;
+use crate::types::{CanvasItem, ItemContent, StrokeKind, ViewportBookmark};
 use crate::validation::validate_items;
 use gpui::{point, px, Pixels, Point, Size};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, info_span, trace, warn};
 
@@ -30,6 +35,15 @@ pub struct BoardState {
     pub zoom: f32,
     pub items: Vec<CanvasItem>,
     pub next_item_id: u64,
+    /// Named viewports, in the order they were saved
+    #[serde(default)]
+    pub bookmarks: Vec<ViewportBookmark>,
+    /// Highlights, notes and boxes on PDF pages
+    #[serde(default)]
+    pub pdf_annotations: Vec<PdfAnnotation>,
+    /// Comment threads on items and canvas points
+    #[serde(default)]
+    pub comment_threads: Vec<CommentThread>,
 }
 
 /// A single undoable operation (delta-based)
@@ -64,13 +78,27 @@ pub enum UndoOperation {
         old_item: CanvasItem,
         new_item: CanvasItem,
     },
+    /// Add an annotation to a PDF page
+    AddPdfAnnotation(PdfAnnotation),
+    /// Remove an annotation from a PDF page (stores it for undo)
+    RemovePdfAnnotation(PdfAnnotation),
+    /// Change an annotation (stores old and new states)
+    ModifyPdfAnnotation {
+        old_annotation: PdfAnnotation,
+        new_annotation: PdfAnnotation,
+    },
     /// Batch of operations (for multi-item actions like file drop)
     Batch(Vec<UndoOperation>),
 }
 
 impl UndoOperation {
     /// Apply this operation to the board (for redo)
-    pub fn apply(&self, items: &mut Vec<CanvasItem>, items_index: &mut HashMap<u64, usize>) {
+    pub fn apply(
+        &self,
+        items: &mut Vec<CanvasItem>,
+        items_index: &mut HashMap<u64, usize>,
+        annotations: &mut Vec<PdfAnnotation>,
+    ) {
         match self {
             UndoOperation::AddItem(item) => {
                 items_index.insert(item.id, items.len());
@@ -118,16 +146,32 @@ impl UndoOperation {
                     items[idx] = new_item.clone();
                 }
             }
+            UndoOperation::AddPdfAnnotation(annotation) => {
+                annotations.push(annotation.clone());
+            }
+            UndoOperation::RemovePdfAnnotation(annotation) => {
+                annotations.retain(|a| a.id != annotation.id);
+            }
+            UndoOperation::ModifyPdfAnnotation { new_annotation, .. } => {
+                if let Some(a) = annotations.iter_mut().find(|a| a.id == new_annotation.id) {
+                    *a = new_annotation.clone();
+                }
+            }
             UndoOperation::Batch(ops) => {
                 for op in ops {
-                    op.apply(items, items_index);
+                    op.apply(items, items_index, annotations);
                 }
             }
         }
     }
 
     /// Reverse this operation (for undo)
-    pub fn reverse(&self, items: &mut Vec<CanvasItem>, items_index: &mut HashMap<u64, usize>) {
+    pub fn reverse(
+        &self,
+        items: &mut Vec<CanvasItem>,
+        items_index: &mut HashMap<u64, usize>,
+        annotations: &mut Vec<PdfAnnotation>,
+    ) {
         match self {
             UndoOperation::AddItem(item) => {
                 // Undo add = remove
@@ -176,10 +220,23 @@ impl UndoOperation {
                     items[idx] = old_item.clone();
                 }
             }
+            UndoOperation::AddPdfAnnotation(annotation) => {
+                // Undo add = remove
+                annotations.retain(|a| a.id != annotation.id);
+            }
+            UndoOperation::RemovePdfAnnotation(annotation) => {
+                // Undo remove = add back
+                annotations.push(annotation.clone());
+            }
+            UndoOperation::ModifyPdfAnnotation { old_annotation, .. } => {
+                if let Some(a) = annotations.iter_mut().find(|a| a.id == old_annotation.id) {
+                    *a = old_annotation.clone();
+                }
+            }
             UndoOperation::Batch(ops) => {
                 // Reverse in opposite order
                 for op in ops.iter().rev() {
-                    op.reverse(items, items_index);
+                    op.reverse(items, items_index, annotations);
                 }
             }
         }
@@ -196,6 +253,24 @@ enum HistoryEntry {
 }
 
 impl BoardState {
+    /// A board holding `items`, scrolled so their top-left corner is in view
+    pub fn from_items(items: Vec<CanvasItem>) -> Self {
+        let min = items
+            .iter()
+            .map(|item| item.position)
+            .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1)))
+            .unwrap_or((0.0, 0.0));
+        Self {
+            canvas_offset: (120.0 - min.0, 160.0 - min.1),
+            zoom: 1.0,
+            next_item_id: items.iter().map(|item| item.id + 1).max().unwrap_or(0),
+            items,
+            bookmarks: Vec::new(),
+            pdf_annotations: Vec::new(),
+            comment_threads: Vec::new(),
+        }
+    }
+
     /// Save board state to a file path.
     ///
     /// Returns Ok(()) on success, or a BoardError on failure.
@@ -258,6 +333,15 @@ pub struct Board {
 
     pub next_item_id: u64,
 
+    /// Saved viewports (not part of undo history)
+    pub bookmarks: Vec<ViewportBookmark>,
+
+    /// PDF annotations
+    pub pdf_annotations: Vec<PdfAnnotation>,
+
+    /// Comment threads (not part of undo history)
+    pub comment_threads: Vec<CommentThread>,
+
     // Delta-based history using VecDeque for O(1) front removal
     history: VecDeque<HistoryEntry>,
     history_index: usize,
@@ -306,6 +390,9 @@ impl Board {
                 items: state.items,
                 items_index,
                 next_item_id: state.next_item_id,
+                bookmarks: state.bookmarks,
+                pdf_annotations: state.pdf_annotations,
+                comment_threads: state.comment_threads,
                 history: VecDeque::new(),
                 history_index: 0,
                 ops_since_snapshot: 0,
@@ -333,6 +420,9 @@ impl Board {
             items: Vec::new(),
             items_index: HashMap::new(),
             next_item_id: 0,
+            bookmarks: Vec::new(),
+            pdf_annotations: Vec::new(),
+            comment_threads: Vec::new(),
             history: VecDeque::new(),
             history_index: 0,
             ops_since_snapshot: 0,
@@ -397,53 +487,151 @@ impl Board {
         id
     }
 
-    /// Handle file drop - batched operation (single history push + save)
-    /// For iCloud boards, files are copied to the board's files/ directory
-    /// so they sync across devices.
+    /// Add and replace items as one undoable change, e.g. from an external command.
+    ///
+    /// `added` holds each new item's content, position and optional size.
+    /// Replacements for unknown IDs are skipped. Returns the IDs of the added
+    /// items and how many items were replaced.
+    pub fn apply_item_changes(
+        &mut self,
+        added: Vec<(ItemContent, (f32, f32), Option<(f32, f32)>)>,
+        replaced: Vec<CanvasItem>,
+    ) -> (Vec<u64>, usize) {
+        let mut ops = Vec::new();
+        let mut added_ids = Vec::with_capacity(added.len());
+
+        for (content, position, size) in added {
+            let id = self.add_item_internal(point(px(position.0), px(position.1)), content);
+            if let Some(item) = self.get_item_mut(id) {
+                if let Some(size) = size {
+                    item.size = size;
+                }
+                ops.push(UndoOperation::AddItem(item.clone()));
+            }
+            added_ids.push(id);
+        }
+
+        let mut replaced_count = 0;
+        for new_item in replaced {
+            if let Some(item) = self.get_item_mut(new_item.id) {
+                let old_item = std::mem::replace(item, new_item.clone());
+                ops.push(UndoOperation::ModifyItem { old_item, new_item });
+                replaced_count += 1;
+            }
+        }
+
+        if !ops.is_empty() {
+            self.push_operation(UndoOperation::Batch(ops));
+            self.mark_dirty();
+        }
+        (added_ids, replaced_count)
+    }
+
+    /// Record items already moved in place, e.g. by an animated layout, as one
+    /// undoable change.
     ///
-    /// Returns a list of error messages for any files that failed to copy.
-    /// The caller should display these to the user via toast notifications.
-    pub fn handle_file_drop(&mut self, position: Point<Pixels>, paths: Vec<PathBuf>) -> Vec<String> {
-        let mut errors = Vec::new();
+    /// `before` holds the items as they were; only their geometry is used, so
+    /// other changes made since are kept. Moves and resizes become
+    /// `MoveItem`/`ResizeItem` operations; re-routed arrows, whose geometry
+    /// lives in their content, become `ModifyItem`. Returns how many items changed.
+    pub fn commit_layout(&mut self, before: &[CanvasItem]) -> usize {
+        let mut ops = Vec::new();
+        let mut changed = 0;
+        for old_item in before {
+            let Some(new_item) = self.get_item(old_item.id) else {
+                continue;
+            };
+            let moved = new_item.position != old_item.position;
+            let resized = new_item.size != old_item.size;
+            let rerouted = new_item.arrow_endpoints() != old_item.arrow_endpoints();
+            if !(moved || resized || rerouted) {
+                continue;
+            }
+            changed += 1;
+            if rerouted {
+                // Undo only the geometry, keeping any other edits
+                let mut undone = new_item.clone();
+                undone.copy_geometry_from(old_item);
+                ops.push(UndoOperation::ModifyItem {
+                    old_item: undone,
+                    new_item: new_item.clone(),
+                });
+                continue;
+            }
+            if moved {
+                ops.push(UndoOperation::MoveItem {
+                    id: old_item.id,
+                    old_pos: old_item.position,
+                    new_pos: new_item.position,
+                });
+            }
+            if resized {
+                ops.push(UndoOperation::ResizeItem {
+                    id: old_item.id,
+                    old_size: old_item.size,
+                    new_size: new_item.size,
+                });
+            }
+        }
 
-        if paths.is_empty() {
-            return errors;
+        if !ops.is_empty() {
+            self.push_operation(UndoOperation::Batch(ops));
+            self.mark_dirty();
         }
+        changed
+    }
 
+    /// Directory dropped files are copied into: the files directory of an
+    /// iCloud board, so they sync across devices. Other boards link to the
+    /// dropped files where they are.
+    pub fn copy_target(&self) -> Option<PathBuf> {
+        self.should_copy_files().then(|| self.files_dir())
+    }
+
+    /// Add the items of a prepared file drop with their top-left at `origin`,
+    /// as one undo step. Loose files are staggered from `origin`, and folders
+    /// laid out as framed grids below them, side by side.
+    ///
+    /// Returns the IDs of the added items.
+    pub fn apply_file_drop(&mut self, origin: (f32, f32), prepared: PreparedDrop) -> Vec<u64> {
         // Stagger offset for multiple files so they don't overlap
         const STAGGER_X: f32 = 30.0;
         const STAGGER_Y: f32 = 30.0;
+        // Space between dropped folders, and between them and loose files
+        const FOLDER_GAP: f32 = 60.0;
 
         let mut added_ids = Vec::new();
-
-        for (i, path) in paths.iter().enumerate() {
-            // For iCloud boards, copy the file to the board's files directory
-            let actual_path = if self.should_copy_files() {
-                match self.copy_file_to_board(path) {
-                    Ok(copied_path) => copied_path,
-                    Err(e) => {
-                        let filename = path.file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("file");
-                        errors.push(format!("Failed to copy '{}': {}", filename, e));
-                        warn!("Failed to copy file to board storage: {}", e);
-                        path.clone()
-                    }
-                }
-            } else {
-                path.clone()
-            };
-
-            let content = ItemContent::from_path(&actual_path);
-            let base_pos = self.screen_to_canvas(position);
+        for (i, content) in prepared.files.into_iter().enumerate() {
             let staggered_pos = point(
-                px(f32::from(base_pos.x) + (i as f32 * STAGGER_X)),
-                px(f32::from(base_pos.y) + (i as f32 * STAGGER_Y)),
+                px(origin.0 + (i as f32 * STAGGER_X)),
+                px(origin.1 + (i as f32 * STAGGER_Y)),
             );
             let id = self.add_item_internal(staggered_pos, content);
             added_ids.push(id);
         }
 
+        let mut folder_x = origin.0;
+        let folder_y = added_ids
+            .iter()
+            .filter_map(|&id| self.get_item(id))
+            .map(|item| item.position.1 + item.size.1 + FOLDER_GAP)
+            .fold(origin.1, f32::max);
+        for placed in prepared.folders {
+            // The folder's own frame comes first
+            let Some(width) = placed.first().map(|(_, _, size)| size.0) else {
+                continue;
+            };
+            for (content, position, size) in placed {
+                let position = point(px(folder_x + position.0), px(folder_y + position.1));
+                let id = self.add_item_internal(position, content);
+                if let Some(item) = self.get_item_mut(id) {
+                    item.size = size;
+                }
+                added_ids.push(id);
+            }
+            folder_x += width + FOLDER_GAP;
+        }
+
         // Create batch operation for all added items
         let ops: Vec<UndoOperation> = added_ids
             .iter()
@@ -460,7 +648,7 @@ impl Board {
         }
         self.mark_dirty();
 
-        errors
+        added_ids
     }
 
     /// Check if files should be copied to the board's storage
@@ -474,63 +662,6 @@ impl Board {
         self.storage_location.base_path().join(&self.id).join("files")
     }
 
-    /// Copy a file to the board's files directory
-    /// Returns the new path to the copied file
-    fn copy_file_to_board(&self, source: &PathBuf) -> Result<PathBuf, std::io::Error> {
-        let files_dir = self.files_dir();
-        std::fs::create_dir_all(&files_dir)?;
-
-        // Get original filename and sanitize it to prevent path traversal attacks
-        let filename = source
-            .file_name()
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "No filename"))?
-            .to_str()
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid filename encoding"))?;
-
-        // Sanitize filename: remove path separators and reject dangerous names
-        let sanitized = sanitize_filename(filename)
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid filename"))?;
-
-        // Generate unique filename if it already exists
-        let mut dest = files_dir.join(&sanitized);
-        if dest.exists() {
-            let path = std::path::Path::new(&sanitized);
-            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
-            let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis()
-                % 100000;
-
-            let new_name = if ext.is_empty() {
-                format!("{}_{}", stem, timestamp)
-            } else {
-                format!("{}_{}.{}", stem, timestamp, ext)
-            };
-            dest = files_dir.join(new_name);
-        }
-
-        // Final safety check: verify destination is within files_dir
-        let canonical_files_dir = files_dir.canonicalize().unwrap_or(files_dir.clone());
-        let canonical_dest = dest.parent()
-            .and_then(|p| p.canonicalize().ok())
-            .map(|p| p.join(dest.file_name().unwrap_or_default()));
-
-        if let Some(ref canonical) = canonical_dest {
-            if !canonical.starts_with(&canonical_files_dir) {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::PermissionDenied,
-                    "Path traversal attempt detected",
-                ));
-            }
-        }
-
-        std::fs::copy(source, &dest)?;
-        info!("Copied file to board storage: {:?} -> {:?}", source, dest);
-        Ok(dest)
-    }
-
     /// Add URL (YouTube or generic link)
     pub fn add_url(&mut self, url: &str, position: Point<Pixels>) {
         use crate::types::extract_youtube_id;
@@ -572,6 +703,96 @@ impl Board {
         self.rebuild_index();
     }
 
+    /// Add a freehand stroke from canvas-space points, sized to fit its path
+    pub fn add_stroke(
+        &mut self,
+        points: &[(f32, f32)],
+        kind: StrokeKind,
+        color: String,
+        width: f32,
+    ) -> Option<u64> {
+        if points.is_empty() {
+            return None;
+        }
+        let id = self.add_stroke_internal(points, kind, color, width);
+        if let Some(item) = self.get_item(id).cloned() {
+            self.push_operation(UndoOperation::AddItem(item));
+        }
+        self.mark_dirty();
+        Some(id)
+    }
+
+    /// Internal stroke add without history - used by `add_stroke` and the eraser
+    fn add_stroke_internal(
+        &mut self,
+        points: &[(f32, f32)],
+        kind: StrokeKind,
+        color: String,
+        width: f32,
+    ) -> u64 {
+        let (position, size, points) = strokes::fit(points, width);
+        let id = self.add_item_internal(
+            point(px(position.0), px(position.1)),
+            ItemContent::Stroke {
+                points,
+                color,
+                width,
+                kind,
+            },
+        );
+        if let Some(item) = self.get_item_mut(id) {
+            item.size = size;
+        }
+        id
+    }
+
+    /// Erase a circle (in canvas coordinates) from every stroke it touches.
+    ///
+    /// Touched strokes are removed and replaced by the pieces that survive.
+    /// Changes are applied without history; the returned operations describe
+    /// them so a whole eraser drag can be pushed as one undoable batch.
+    pub fn erase_strokes(&mut self, center: (f32, f32), radius: f32) -> Vec<UndoOperation> {
+        let hits: Vec<(CanvasItem, Vec<strokes::Polyline>)> = self
+            .items
+            .iter()
+            .filter_map(|item| match &item.content {
+                ItemContent::Stroke { points, .. } => {
+                    let canvas_points = strokes::to_canvas(points, item.position, item.size);
+                    strokes::erase(&canvas_points, center, radius)
+                        .map(|pieces| (item.clone(), pieces))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut ops = Vec::new();
+        for (item, pieces) in hits {
+            let ItemContent::Stroke {
+                color, width, kind, ..
+            } = &item.content
+            else {
+                continue;
+            };
+            let (color, width, kind) = (color.clone(), *width, *kind);
+
+            self.items.retain(|it| it.id != item.id);
+            self.rebuild_index();
+            ops.push(UndoOperation::RemoveItem(item));
+
+            for piece in pieces {
+                let id = self.add_stroke_internal(&piece, kind, color.clone(), width);
+                if let Some(added) = self.get_item(id).cloned() {
+                    ops.push(UndoOperation::AddItem(added));
+                }
+            }
+        }
+
+        if !ops.is_empty() {
+            self.mark_dirty();
+        }
+        ops
+    }
+
     /// Convert screen position to canvas position
     #[inline]
     pub fn screen_to_canvas(&self, screen_pos: Point<Pixels>) -> Point<Pixels> {
@@ -636,6 +857,133 @@ impl Board {
         self.mark_dirty();
     }
 
+    /// Save the current viewport as a bookmark, replacing any bookmark with the same name.
+    ///
+    /// Returns the bookmark's index.
+    pub fn save_bookmark(&mut self, name: &str) -> usize {
+        let bookmark = ViewportBookmark {
+            name: name.to_string(),
+            canvas_offset: (
+                f32::from(self.canvas_offset.x),
+                f32::from(self.canvas_offset.y),
+            ),
+            zoom: self.zoom,
+        };
+        let index = match self.bookmarks.iter().position(|b| b.name == name) {
+            Some(index) => {
+                self.bookmarks[index] = bookmark;
+                index
+            }
+            None => {
+                self.bookmarks.push(bookmark);
+                self.bookmarks.len() - 1
+            }
+        };
+        self.mark_dirty();
+        index
+    }
+
+    /// Remove a bookmark by name, returning whether it existed
+    pub fn remove_bookmark(&mut self, name: &str) -> bool {
+        let before = self.bookmarks.len();
+        self.bookmarks.retain(|b| b.name != name);
+        let removed = self.bookmarks.len() != before;
+        if removed {
+            self.mark_dirty();
+        }
+        removed
+    }
+
+    /// Find bookmarks whose name contains `query` (case-insensitive), as (index, name)
+    pub fn find_bookmarks(&self, query: &str) -> Vec<(usize, String)> {
+        let query_lower = query.to_lowercase();
+        self.bookmarks
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.name.to_lowercase().contains(&query_lower))
+            .map(|(i, b)| (i, b.name.clone()))
+            .collect()
+    }
+
+    /// Annotate a page of a PDF, returning the annotation's ID
+    pub fn add_pdf_annotation(&mut self, path: PathBuf, page: usize, kind: AnnotationKind) -> u64 {
+        let annotation = PdfAnnotation {
+            id: pdf_annotations::next_annotation_id(&self.pdf_annotations),
+            path,
+            page,
+            kind,
+        };
+        let id = annotation.id;
+        self.pdf_annotations.push(annotation.clone());
+        self.push_operation(UndoOperation::AddPdfAnnotation(annotation));
+        self.mark_dirty();
+        id
+    }
+
+    /// Remove a PDF annotation by ID, returning whether it existed
+    pub fn remove_pdf_annotation(&mut self, id: u64) -> bool {
+        let Some(idx) = self.pdf_annotations.iter().position(|a| a.id == id) else {
+            return false;
+        };
+        let annotation = self.pdf_annotations.remove(idx);
+        self.push_operation(UndoOperation::RemovePdfAnnotation(annotation));
+        self.mark_dirty();
+        true
+    }
+
+    /// Change the text of a note on a PDF page, returning whether it exists
+    pub fn set_pdf_note_text(&mut self, id: u64, new_text: String) -> bool {
+        let Some(annotation) = self.pdf_annotations.iter_mut().find(|a| a.id == id) else {
+            return false;
+        };
+        let old_annotation = annotation.clone();
+        let AnnotationKind::Note { text, .. } = &mut annotation.kind else {
+            return false;
+        };
+        *text = new_text;
+        let new_annotation = annotation.clone();
+        self.push_operation(UndoOperation::ModifyPdfAnnotation {
+            old_annotation,
+            new_annotation,
+        });
+        self.mark_dirty();
+        true
+    }
+
+    /// Start a comment thread, returning its ID
+    pub fn add_comment_thread(&mut self, anchor: CommentAnchor, comment: Comment) -> u64 {
+        let id = comments::next_thread_id(&self.comment_threads);
+        self.comment_threads.push(CommentThread {
+            id,
+            anchor,
+            comments: vec![comment],
+            resolved: false,
+        });
+        self.mark_dirty();
+        id
+    }
+
+    /// Add a comment to a thread, reopening it; returns whether the thread exists
+    pub fn reply_to_thread(&mut self, id: u64, comment: Comment) -> bool {
+        let Some(thread) = self.comment_threads.iter_mut().find(|t| t.id == id) else {
+            return false;
+        };
+        thread.comments.push(comment);
+        thread.resolved = false;
+        self.mark_dirty();
+        true
+    }
+
+    /// Resolve or reopen a thread, returning whether it exists
+    pub fn set_thread_resolved(&mut self, id: u64, resolved: bool) -> bool {
+        let Some(thread) = self.comment_threads.iter_mut().find(|t| t.id == id) else {
+            return false;
+        };
+        thread.resolved = resolved;
+        self.mark_dirty();
+        true
+    }
+
     /// Center the viewport on an item by its ID
     /// screen_size is the visible canvas area size
     pub fn center_on_item(&mut self, item_id: u64, screen_size: Size<Pixels>) {
@@ -643,34 +991,77 @@ impl Board {
             // Calculate the center of the item in canvas coordinates
             let item_center_x = item.position.0 + item.size.0 / 2.0;
             let item_center_y = item.position.1 + item.size.1 / 2.0;
+            self.center_on_point((item_center_x, item_center_y), screen_size);
+        }
+    }
 
-            // Calculate offset to center item on screen
-            let screen_center_x = f32::from(screen_size.width) / 2.0;
-            let screen_center_y = f32::from(screen_size.height) / 2.0;
+    /// Center the viewport on a canvas point
+    pub fn center_on_point(&mut self, (x, y): (f32, f32), screen_size: Size<Pixels>) {
+        // Calculate offset to center the point on screen
+        let screen_center_x = f32::from(screen_size.width) / 2.0;
+        let screen_center_y = f32::from(screen_size.height) / 2.0;
 
-            self.canvas_offset = point(
-                px(screen_center_x - item_center_x * self.zoom),
-                px(screen_center_y - item_center_y * self.zoom),
-            );
+        self.canvas_offset = point(
+            px(screen_center_x - x * self.zoom),
+            px(screen_center_y - y * self.zoom),
+        );
 
-            self.mark_dirty();
-        }
+        self.mark_dirty();
     }
 
-    /// Find items matching a search query (searches display names)
-    pub fn find_items(&self, query: &str) -> Vec<(u64, String)> {
-        let query_lower = query.to_lowercase();
+    /// Find items fuzzy-matching a search query (searches display names), best first
+    pub fn find_items(&self, query: &str) -> Vec<(u64, String, FuzzyMatch)> {
+        let mut matches: Vec<_> = self
+            .items
+            .iter()
+            .filter(|item| item.content.is_searchable())
+            .filter_map(|item| {
+                let name = item.content.display_name();
+                let found = fuzzy_match(query, &name)?;
+                Some((item.id, name, found))
+            })
+            .collect();
+
+        // Best match first; stable, so ties keep board order
+        matches.sort_by(|a, b| b.2.score.cmp(&a.2.score));
+        matches
+    }
+
+    /// Image items' IDs and files, e.g. for reading their EXIF in the background
+    pub fn image_paths(&self) -> Vec<(u64, PathBuf)> {
         self.items
             .iter()
-            .filter(|item| {
-                item.content.is_searchable()
-                    && item
-                        .content
-                        .display_name()
-                        .to_lowercase()
-                        .contains(&query_lower)
+            .filter_map(|item| match item.content {
+                ItemContent::Image(ref path, _) => Some((item.id, path.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Images with a capture date in `dates`, oldest first, labelled with the
+    /// date and name and fuzzy-matched against `query` (e.g. `2024-05`)
+    pub fn find_images_by_capture_date(
+        &self,
+        dates: &HashMap<u64, String>,
+        query: &str,
+    ) -> Vec<(u64, String, FuzzyMatch)> {
+        let mut dated: Vec<(String, u64)> = self
+            .items
+            .iter()
+            .filter(|item| matches!(item.content, ItemContent::Image(..)))
+            .filter_map(|item| {
+                let date = dates.get(&item.id)?;
+                Some((format!("{}  {}", date, item.content.display_name()), item.id))
+            })
+            .collect();
+        dated.sort();
+
+        dated
+            .into_iter()
+            .filter_map(|(label, id)| {
+                let found = fuzzy_match(query, &label)?;
+                Some((id, label, found))
             })
-            .map(|item| (item.id, item.content.display_name()))
             .collect()
     }
 
@@ -680,6 +1071,11 @@ impl Board {
         self.last_change = Instant::now();
     }
 
+    /// When the board was last changed
+    pub fn last_change(&self) -> Instant {
+        self.last_change
+    }
+
     /// Check if the board has unsaved changes
     pub fn is_dirty(&self) -> bool {
         self.dirty
@@ -702,9 +1098,9 @@ impl Board {
         }
     }
 
-    /// Try to save, returning any errors
-    pub fn try_save(&self) -> Result<(), BoardError> {
-        let state = BoardState {
+    /// Serializable snapshot of the board's current contents and viewport
+    pub fn to_state(&self) -> BoardState {
+        BoardState {
             canvas_offset: (
                 f32::from(self.canvas_offset.x),
                 f32::from(self.canvas_offset.y),
@@ -712,7 +1108,15 @@ impl Board {
             zoom: self.zoom,
             items: self.items.clone(),
             next_item_id: self.next_item_id,
-        };
+            bookmarks: self.bookmarks.clone(),
+            pdf_annotations: self.pdf_annotations.clone(),
+            comment_threads: self.comment_threads.clone(),
+        }
+    }
+
+    /// Try to save, returning any errors
+    pub fn try_save(&self) -> Result<(), BoardError> {
+        let state = self.to_state();
 
         // Get path from board index (supports custom storage locations)
         let index = BoardIndex::load();
@@ -766,15 +1170,7 @@ impl Board {
 
     /// Create a full snapshot in history (for periodic checkpoints)
     fn create_snapshot(&mut self) {
-        let state = BoardState {
-            canvas_offset: (
-                f32::from(self.canvas_offset.x),
-                f32::from(self.canvas_offset.y),
-            ),
-            zoom: self.zoom,
-            items: self.items.clone(),
-            next_item_id: self.next_item_id,
-        };
+        let state = self.to_state();
         self.history.push_back(HistoryEntry::Snapshot(state));
         self.history_index = self.history.len();
         self.ops_since_snapshot = 0;
@@ -810,7 +1206,11 @@ impl Board {
         match entry {
             Some(HistoryEntry::Operation(op)) => {
                 // Reverse the operation
-                op.reverse(&mut self.items, &mut self.items_index);
+                op.reverse(
+                    &mut self.items,
+                    &mut self.items_index,
+                    &mut self.pdf_annotations,
+                );
                 self.mark_dirty();
                 true
             }
@@ -842,7 +1242,11 @@ impl Board {
         match entry {
             Some(HistoryEntry::Operation(op)) => {
                 // Apply the operation
-                op.apply(&mut self.items, &mut self.items_index);
+                op.apply(
+                    &mut self.items,
+                    &mut self.items_index,
+                    &mut self.pdf_annotations,
+                );
                 self.mark_dirty();
                 true
             }
@@ -861,6 +1265,7 @@ impl Board {
         self.zoom = state.zoom;
         self.items = state.items.clone();
         self.next_item_id = state.next_item_id;
+        self.pdf_annotations = state.pdf_annotations.clone();
         self.rebuild_index();
         self.mark_dirty();
     }
@@ -881,6 +1286,153 @@ impl Board {
     }
 }
 
+/// Files and folders from a drop, read and copied off the UI thread and
+/// ready for `Board::apply_file_drop`
+#[derive(Debug, Default)]
+pub struct PreparedDrop {
+    /// Content of each loose file
+    pub files: Vec<ItemContent>,
+    /// Each folder laid out with its top-left corner at the origin
+    pub folders: Vec<Vec<folder_import::Placed>>,
+    /// Files that couldn't be copied and folders that couldn't be read
+    pub errors: Vec<String>,
+    /// Files left out for going over `FolderImportOptions::max_items`
+    pub skipped: usize,
+}
+
+/// Read dropped files and folders, copying files into `copy_into` when set
+/// (see `Board::copy_target`). Blocks on the file system, so run it in the
+/// background.
+pub fn prepare_file_drop(
+    paths: Vec<PathBuf>,
+    folder_options: &FolderImportOptions,
+    copy_into: Option<&Path>,
+) -> PreparedDrop {
+    let mut prepared = PreparedDrop::default();
+    let (folders, mut files): (Vec<PathBuf>, Vec<PathBuf>) =
+        paths.into_iter().partition(|path| path.is_dir());
+
+    let mut remaining = folder_options.max_items;
+    prepared.skipped += files.len().saturating_sub(remaining);
+    files.truncate(remaining);
+    remaining -= files.len();
+    for path in &files {
+        let path = board_file_path(path, copy_into, &mut prepared.errors);
+        prepared.files.push(ItemContent::from_path(&path));
+    }
+
+    for folder in &folders {
+        let name = folder
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("folder")
+            .to_string();
+        let mut tree = match folder_import::scan(folder, folder_options) {
+            Ok(tree) => tree,
+            Err(e) => {
+                prepared
+                    .errors
+                    .push(format!("Failed to read folder '{}': {}", name, e));
+                warn!("Failed to read dropped folder: {}", e);
+                continue;
+            }
+        };
+        if tree.file_count() == 0 {
+            prepared
+                .errors
+                .push(format!("No files to import in '{}'", name));
+            continue;
+        }
+        prepared.skipped += tree.limit_files(remaining);
+        remaining -= tree.file_count();
+        if tree.file_count() == 0 {
+            continue;
+        }
+
+        let errors = &mut prepared.errors;
+        prepared.folders.push(folder_import::layout(&tree, (0.0, 0.0), &mut |file| {
+            ItemContent::from_path(&board_file_path(file, copy_into, errors))
+        }));
+    }
+
+    prepared
+}
+
+/// Path to use for a dropped file: a copy in `copy_into` when set, falling
+/// back to the original on failure
+fn board_file_path(path: &Path, copy_into: Option<&Path>, errors: &mut Vec<String>) -> PathBuf {
+    let Some(files_dir) = copy_into else {
+        return path.to_path_buf();
+    };
+    match copy_file_to_board(files_dir, path) {
+        Ok(copied_path) => copied_path,
+        Err(e) => {
+            let filename = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("file");
+            errors.push(format!("Failed to copy '{}': {}", filename, e));
+            warn!("Failed to copy file to board storage: {}", e);
+            path.to_path_buf()
+        }
+    }
+}
+
+/// Copy a file to a board's files directory
+/// Returns the new path to the copied file
+fn copy_file_to_board(files_dir: &Path, source: &Path) -> Result<PathBuf, std::io::Error> {
+    std::fs::create_dir_all(files_dir)?;
+
+    // Get original filename and sanitize it to prevent path traversal attacks
+    let filename = source
+        .file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "No filename"))?
+        .to_str()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid filename encoding"))?;
+
+    // Sanitize filename: remove path separators and reject dangerous names
+    let sanitized = sanitize_filename(filename)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid filename"))?;
+
+    // Generate unique filename if it already exists
+    let mut dest = files_dir.join(&sanitized);
+    if dest.exists() {
+        let path = std::path::Path::new(&sanitized);
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
+        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            % 100000;
+
+        let new_name = if ext.is_empty() {
+            format!("{}_{}", stem, timestamp)
+        } else {
+            format!("{}_{}.{}", stem, timestamp, ext)
+        };
+        dest = files_dir.join(new_name);
+    }
+
+    // Final safety check: verify destination is within files_dir
+    let canonical_files_dir = files_dir.canonicalize().unwrap_or_else(|_| files_dir.to_path_buf());
+    let canonical_dest = dest.parent()
+        .and_then(|p| p.canonicalize().ok())
+        .map(|p| p.join(dest.file_name().unwrap_or_default()));
+
+    if let Some(ref canonical) = canonical_dest {
+        if !canonical.starts_with(&canonical_files_dir) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "Path traversal attempt detected",
+            ));
+        }
+    }
+
+    std::fs::copy(source, &dest)?;
+    info!("Copied file to board storage: {:?} -> {:?}", source, dest);
+    Ok(dest)
+}
+
 /// Sanitize a filename to prevent path traversal attacks.
 /// Returns None if the filename is invalid or dangerous.
 fn sanitize_filename(filename: &str) -> Option<String> {
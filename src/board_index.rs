@@ -16,6 +16,7 @@
 //! - **Custom**: User-specified directory
 
 use crate::app::StorageLocation;
+use crate::timestamps::{time_ago, unix_now};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -102,10 +103,7 @@ impl BoardMetadata {
     }
 
     pub fn with_location(name: String, location: StoredLocation) -> Self {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let now = unix_now();
 
         Self {
             id: generate_uuid(),
@@ -124,12 +122,7 @@ impl BoardMetadata {
 
     /// Move this board to trash
     pub fn move_to_trash(&mut self) {
-        self.deleted_at = Some(
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-        );
+        self.deleted_at = Some(unix_now());
     }
 
     /// Restore this board from trash
@@ -140,43 +133,21 @@ impl BoardMetadata {
     /// Get how long ago this was deleted (for display)
     pub fn deleted_ago(&self) -> Option<String> {
         let deleted_at = self.deleted_at?;
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-
-        let diff = now.saturating_sub(deleted_at);
-
-        Some(if diff < 60 {
-            "Just now".to_string()
-        } else if diff < 3600 {
-            format!("{} min ago", diff / 60)
-        } else if diff < 86400 {
-            format!("{} hours ago", diff / 3600)
-        } else {
-            format!("{} days ago", diff / 86400)
-        })
+        Some(time_ago(deleted_at, unix_now()))
     }
 
     /// Check if board should be permanently deleted (older than 30 days)
     pub fn should_purge(&self) -> bool {
         const THIRTY_DAYS: u64 = 30 * 24 * 60 * 60;
         if let Some(deleted_at) = self.deleted_at {
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
-            now.saturating_sub(deleted_at) > THIRTY_DAYS
+            unix_now().saturating_sub(deleted_at) > THIRTY_DAYS
         } else {
             false
         }
     }
 
     pub fn touch(&mut self) {
-        self.updated_at = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        self.updated_at = unix_now();
     }
 
     /// Get the directory for this board
@@ -195,25 +166,7 @@ impl BoardMetadata {
     }
 
     pub fn formatted_date(&self) -> String {
-        // Simple date formatting - just show relative time or date
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-
-        let diff = now.saturating_sub(self.updated_at);
-
-        if diff < 60 {
-            "Just now".to_string()
-        } else if diff < 3600 {
-            format!("{} min ago", diff / 60)
-        } else if diff < 86400 {
-            format!("{} hours ago", diff / 3600)
-        } else if diff < 604800 {
-            format!("{} days ago", diff / 86400)
-        } else {
-            format!("{} weeks ago", diff / 604800)
-        }
+        time_ago(self.updated_at, unix_now())
     }
 }
 
@@ -588,7 +541,7 @@ impl BoardIndex {
 }
 
 /// Simple UUID generation without external dependency
-fn generate_uuid() -> String {
+pub(crate) fn generate_uuid() -> String {
     use std::collections::hash_map::RandomState;
     use std::hash::{BuildHasher, Hasher};
 
@@ -8,11 +8,16 @@
 //! - **Item Search**: Search canvas items by name/content
 //! - **Theme Selection**: Quick theme switching
 //! - **Commands**: Execute various commands (md, theme, etc.)
-//! - **Fuzzy Matching**: Filter results as user types
+//! - **Fuzzy Matching**: Filter results as user types, highlighting what matched
+//! - **Frecency**: Rank often and recently used results higher, across sessions
 
+use crate::fuzzy::{fuzzy_match, FuzzyMatch};
 use crate::settings::Settings;
 use gpui::*;
 use gpui_component::input::InputState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Mode for the command palette - what type of items we're showing.
 #[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
@@ -33,6 +38,8 @@ pub struct SearchResult {
     pub name: String,
     /// Optional subtitle/description
     pub description: Option<String>,
+    /// Byte offsets of the characters in `name` that matched the query
+    pub positions: Vec<usize>,
 }
 
 impl SearchResult {
@@ -41,6 +48,7 @@ impl SearchResult {
             id,
             name: name.into(),
             description: None,
+            positions: Vec::new(),
         }
     }
 
@@ -48,6 +56,145 @@ impl SearchResult {
         self.description = Some(desc.into());
         self
     }
+
+    pub fn with_positions(mut self, positions: Vec<usize>) -> Self {
+        self.positions = positions;
+        self
+    }
+}
+
+/// A result that matched the query, before past use is taken into account
+#[derive(Clone, Debug)]
+pub struct PaletteMatch {
+    pub result: SearchResult,
+    pub score: i32,
+    /// Identifies the result in the palette history
+    pub key: String,
+}
+
+impl PaletteMatch {
+    pub fn new(id: u64, name: impl Into<String>, found: FuzzyMatch, key: impl Into<String>) -> Self {
+        Self {
+            result: SearchResult::new(id, name).with_positions(found.positions),
+            score: found.score,
+            key: key.into(),
+        }
+    }
+
+    /// Fuzzy-match `query` against `name`, shown as `prefix` followed by `name`
+    pub fn find(
+        query: &str,
+        id: u64,
+        prefix: &str,
+        name: &str,
+        key: impl Into<String>,
+    ) -> Option<Self> {
+        let found = fuzzy_match(query, name)?.offset(prefix.len());
+        Some(Self::new(id, format!("{}{}", prefix, name), found, key))
+    }
+}
+
+/// Weight of a use by its age in seconds: recent uses count fully, old ones a little
+const RECENCY_WEIGHTS: [(u64, f32); 4] = [
+    (4 * 24 * 60 * 60, 1.0),
+    (14 * 24 * 60 * 60, 0.7),
+    (31 * 24 * 60 * 60, 0.5),
+    (90 * 24 * 60 * 60, 0.3),
+];
+const OLD_USE_WEIGHT: f32 = 0.1;
+/// Uses counted towards frecency, so a favourite can't bury every new match
+const MAX_COUNTED_USES: u32 = 20;
+/// Ranking points per unit of frecency, relative to fuzzy match scores
+const FRECENCY_WEIGHT: f32 = 4.0;
+/// Least recently used entries are dropped beyond this many
+const MAX_HISTORY_ENTRIES: usize = 500;
+
+/// How often and how recently a palette result was used
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub count: u32,
+    /// Unix time in seconds
+    pub last_used: u64,
+}
+
+/// Past use of palette results, persisted between sessions for frecency ranking
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PaletteHistory {
+    entries: HashMap<String, HistoryEntry>,
+}
+
+impl PaletteHistory {
+    /// Default location of the history file
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|p| p.join("humanboard").join("palette_history.json"))
+    }
+
+    /// Load the history, starting empty if the file is missing or invalid
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    pub fn get(&self, key: &str) -> Option<HistoryEntry> {
+        self.entries.get(key).copied()
+    }
+
+    /// Record a use of the result identified by `key` at `now`
+    pub fn record(&mut self, key: &str, now: u64) {
+        let entry = self.entries.entry(key.to_string()).or_default();
+        entry.count = entry.count.saturating_add(1);
+        entry.last_used = now;
+
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let mut by_age: Vec<(String, u64)> = self
+                .entries
+                .iter()
+                .map(|(key, entry)| (key.clone(), entry.last_used))
+                .collect();
+            by_age.sort_by_key(|(_, last_used)| *last_used);
+            let excess = self.entries.len() - MAX_HISTORY_ENTRIES;
+            for (key, _) in by_age.into_iter().take(excess) {
+                self.entries.remove(&key);
+            }
+        }
+    }
+
+    /// Frecency of a result: its use count weighted by how recently it was last used
+    pub fn frecency(&self, key: &str, now: u64) -> f32 {
+        let Some(entry) = self.entries.get(key) else {
+            return 0.0;
+        };
+        let age = now.saturating_sub(entry.last_used);
+        let weight = RECENCY_WEIGHTS
+            .iter()
+            .find(|(max_age, _)| age <= *max_age)
+            .map(|(_, weight)| *weight)
+            .unwrap_or(OLD_USE_WEIGHT);
+        entry.count.min(MAX_COUNTED_USES) as f32 * weight
+    }
+
+    /// Order matches by fuzzy score plus frecency, best first. Ties keep their order.
+    pub fn rank(&self, matches: Vec<PaletteMatch>, now: u64) -> Vec<SearchResult> {
+        let mut ranked: Vec<(i32, SearchResult)> = matches
+            .into_iter()
+            .map(|m| {
+                let boost = (self.frecency(&m.key, now) * FRECENCY_WEIGHT).round() as i32;
+                (m.score + boost, m.result)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.0.cmp(&a.0));
+        ranked.into_iter().map(|(_, result)| result).collect()
+    }
 }
 
 /// Special command IDs that don't correspond to canvas items.
@@ -182,20 +329,14 @@ impl CommandPalette {
     fn filter_themes(filter: &str, cx: &App) -> Vec<SearchResult> {
         let themes = Settings::available_themes(cx);
 
-        if filter.is_empty() {
-            themes
-                .into_iter()
-                .enumerate()
-                .map(|(idx, name)| SearchResult::new(idx as u64, name))
-                .collect()
-        } else {
-            themes
-                .into_iter()
-                .enumerate()
-                .filter(|(_, name)| name.to_lowercase().contains(&filter.to_lowercase()))
-                .map(|(idx, name)| SearchResult::new(idx as u64, name))
-                .collect()
-        }
+        themes
+            .into_iter()
+            .enumerate()
+            .filter_map(|(idx, name)| {
+                let found = fuzzy_match(filter, &name)?;
+                Some(SearchResult::new(idx as u64, name).with_positions(found.positions))
+            })
+            .collect()
     }
 
     /// Set results from external source (e.g., canvas item search).
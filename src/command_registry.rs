@@ -17,13 +17,14 @@
 //! }
 //! ```
 
+use crate::fuzzy::{fuzzy_match, FuzzyMatch};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 /// A command that can be executed from the command palette or keyboard shortcut.
 pub trait Command: Send + Sync {
     /// Unique identifier for this command (e.g., "file:open", "edit:undo")
-    fn id(&self) -> &'static str;
+    fn id(&self) -> &str;
 
     /// Human-readable name shown in the command palette
     fn name(&self) -> &str;
@@ -63,9 +64,9 @@ pub trait Command: Send + Sync {
 /// Registry for application commands.
 #[derive(Default)]
 pub struct CommandRegistry {
-    commands: HashMap<&'static str, Arc<dyn Command>>,
+    commands: HashMap<String, Arc<dyn Command>>,
     /// Commands sorted by category for display
-    by_category: HashMap<String, Vec<&'static str>>,
+    by_category: HashMap<String, Vec<String>>,
 }
 
 impl CommandRegistry {
@@ -77,12 +78,13 @@ impl CommandRegistry {
         }
     }
 
-    /// Register a command.
+    /// Register a command, replacing any command with the same ID.
     pub fn register<C: Command + 'static>(&mut self, cmd: C) {
-        let id = cmd.id();
+        let id = cmd.id().to_string();
         let category = cmd.category().to_string();
 
-        self.commands.insert(id, Arc::new(cmd));
+        self.unregister(&id);
+        self.commands.insert(id.clone(), Arc::new(cmd));
 
         self.by_category
             .entry(category)
@@ -90,6 +92,18 @@ impl CommandRegistry {
             .push(id);
     }
 
+    /// Remove a command by ID, for commands registered at runtime.
+    pub fn unregister(&mut self, id: &str) -> Option<Arc<dyn Command>> {
+        let cmd = self.commands.remove(id)?;
+        if let Some(ids) = self.by_category.get_mut(cmd.category()) {
+            ids.retain(|other| other != id);
+            if ids.is_empty() {
+                self.by_category.remove(cmd.category());
+            }
+        }
+        Some(cmd)
+    }
+
     /// Get a command by ID.
     pub fn get(&self, id: &str) -> Option<Arc<dyn Command>> {
         self.commands.get(id).cloned()
@@ -101,8 +115,8 @@ impl CommandRegistry {
     }
 
     /// Get all command IDs.
-    pub fn ids(&self) -> impl Iterator<Item = &&'static str> {
-        self.commands.keys()
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.commands.keys().map(String::as_str)
     }
 
     /// Get commands by category.
@@ -122,37 +136,47 @@ impl CommandRegistry {
         self.by_category.keys()
     }
 
-    /// Search commands by query (fuzzy match on name and keywords).
-    pub fn search(&self, query: &str, app: &crate::app::Humanboard) -> Vec<Arc<dyn Command>> {
-        let query_lower = query.to_lowercase();
-
+    /// Search commands by query (fuzzy match on name, keywords, category and id).
+    ///
+    /// Results are ranked best first. Match positions refer to the command name
+    /// and are empty when only a keyword, the category or the id matched, which
+    /// also rank below name matches.
+    pub fn search(
+        &self,
+        query: &str,
+        app: &crate::app::Humanboard,
+    ) -> Vec<(Arc<dyn Command>, FuzzyMatch)> {
         let mut matches: Vec<_> = self
             .commands
             .values()
             .filter(|cmd| cmd.is_enabled(app))
-            .filter(|cmd| {
-                let name_match = cmd.name().to_lowercase().contains(&query_lower);
-                let keyword_match = cmd
+            .filter_map(|cmd| {
+                let secondary = cmd
                     .keywords()
                     .iter()
-                    .any(|k| k.to_lowercase().contains(&query_lower));
-                let category_match = cmd.category().to_lowercase().contains(&query_lower);
-                let id_match = cmd.id().to_lowercase().contains(&query_lower);
-
-                name_match || keyword_match || category_match || id_match
+                    .copied()
+                    .chain([cmd.category(), cmd.id()])
+                    .filter_map(|text| fuzzy_match(query, text))
+                    .map(|m| FuzzyMatch {
+                        score: m.score / 2,
+                        positions: Vec::new(),
+                    })
+                    .max_by_key(|m| m.score);
+
+                let best = match (fuzzy_match(query, cmd.name()), secondary) {
+                    (Some(name), Some(other)) if other.score > name.score => other,
+                    (Some(name), _) => name,
+                    (None, other) => other?,
+                };
+                Some((cmd.clone(), best))
             })
-            .cloned()
             .collect();
 
-        // Sort by relevance (exact name match first, then by name)
-        matches.sort_by(|a, b| {
-            let a_exact = a.name().to_lowercase() == query_lower;
-            let b_exact = b.name().to_lowercase() == query_lower;
-            match (a_exact, b_exact) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name().cmp(b.name()),
-            }
+        matches.sort_by(|(a, a_match), (b, b_match)| {
+            b_match
+                .score
+                .cmp(&a_match.score)
+                .then_with(|| a.name().cmp(b.name()))
         });
 
         matches
@@ -177,7 +201,7 @@ impl CommandRegistry {
 pub struct GoHomeCommand;
 
 impl Command for GoHomeCommand {
-    fn id(&self) -> &'static str {
+    fn id(&self) -> &str {
         "navigation:home"
     }
 
@@ -215,7 +239,7 @@ impl Command for GoHomeCommand {
 pub struct NewBoardCommand;
 
 impl Command for NewBoardCommand {
-    fn id(&self) -> &'static str {
+    fn id(&self) -> &str {
         "board:new"
     }
 
@@ -249,11 +273,569 @@ impl Command for NewBoardCommand {
     }
 }
 
+/// Import another app's board file as a new board
+pub struct ImportBoardCommand;
+
+impl Command for ImportBoardCommand {
+    fn id(&self) -> &str {
+        "board:import"
+    }
+
+    fn name(&self) -> &str {
+        "Import Board..."
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Open a JSON Canvas or Excalidraw file as a new board")
+    }
+
+    fn category(&self) -> &str {
+        "Board"
+    }
+
+    fn is_enabled(&self, _app: &crate::app::Humanboard) -> bool {
+        true
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.prompt_import_board(cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["open", "obsidian", "canvas", "excalidraw"]
+    }
+}
+
+/// Export the open board to another app's format
+pub struct ExportBoardCommand {
+    format: crate::app::BoardFormat,
+    id: String,
+    name: String,
+}
+
+impl ExportBoardCommand {
+    pub fn new(format: crate::app::BoardFormat) -> Self {
+        Self {
+            format,
+            id: format!("board:export:{}", format.extension()),
+            name: format!("Export as {}...", format.name()),
+        }
+    }
+}
+
+impl Command for ExportBoardCommand {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> &str {
+        "Board"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.prompt_export_board(self.format, cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["save", "file"]
+    }
+}
+
+/// Arrange the selection, or the whole board, with an automatic layout
+pub struct ArrangeCommand {
+    kind: crate::auto_layout::LayoutKind,
+    by_capture_date: bool,
+    id: String,
+    name: String,
+}
+
+impl ArrangeCommand {
+    pub fn new(kind: crate::auto_layout::LayoutKind) -> Self {
+        Self {
+            kind,
+            by_capture_date: false,
+            id: format!("arrange:{}", kind.slug()),
+            name: format!("Arrange as {}", kind.name()),
+        }
+    }
+
+    /// Arrange with photos in the order they were taken
+    pub fn by_capture_date(kind: crate::auto_layout::LayoutKind) -> Self {
+        Self {
+            kind,
+            by_capture_date: true,
+            id: format!("arrange:{}-by-date", kind.slug()),
+            name: format!("Arrange as {} by Capture Date", kind.name()),
+        }
+    }
+}
+
+impl Command for ArrangeCommand {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Arrange the selection, or the whole board when nothing is selected")
+    }
+
+    fn category(&self) -> &str {
+        "Arrange"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        if self.by_capture_date {
+            app.arrange_by_capture_date(self.kind, cx);
+        } else {
+            app.arrange(self.kind, cx);
+        }
+    }
+
+    fn keywords(&self) -> &[&str] {
+        if self.by_capture_date {
+            &[
+                "layout",
+                "sort",
+                "date",
+                "taken",
+                "photo",
+                "exif",
+                "chronological",
+            ]
+        } else {
+            &["layout", "organize", "tidy", "auto"]
+        }
+    }
+}
+
+/// Rotate, flip, crop or bake the selected images
+pub struct ImageEditCommand {
+    action: crate::image_edit::ImageAction,
+    id: String,
+}
+
+impl ImageEditCommand {
+    pub fn new(action: crate::image_edit::ImageAction) -> Self {
+        Self {
+            action,
+            id: format!("image:{}", action.slug()),
+        }
+    }
+}
+
+impl Command for ImageEditCommand {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        self.action.name()
+    }
+
+    fn category(&self) -> &str {
+        "Image"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.edit_images(self.action, cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["photo", "picture", "edit", "transform"]
+    }
+}
+
+/// Place a row of the selected images' dominant colours under them
+pub struct ExtractPaletteCommand;
+
+impl Command for ExtractPaletteCommand {
+    fn id(&self) -> &str {
+        "image:palette"
+    }
+
+    fn name(&self) -> &str {
+        "Extract Colour Palette"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Add swatches of the image's dominant colours; copy one for its hex code")
+    }
+
+    fn category(&self) -> &str {
+        "Image"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.extract_palettes(cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["color", "swatch", "moodboard", "sample", "hex"]
+    }
+}
+
+/// Show the selected image's size and EXIF details
+pub struct ImageInfoCommand;
+
+impl Command for ImageInfoCommand {
+    fn id(&self) -> &str {
+        "image:info"
+    }
+
+    fn name(&self) -> &str {
+        "Show Image Info"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Dimensions, capture date, camera and exposure of the selected image")
+    }
+
+    fn category(&self) -> &str {
+        "Image"
+    }
+
+    fn shortcut_hint(&self) -> Option<&str> {
+        Some("Cmd+I")
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.toggle_image_info(cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["exif", "metadata", "camera", "properties", "details"]
+    }
+}
+
+/// Load the players of the selected audio, video and YouTube items
+pub struct PlayMediaCommand;
+
+impl Command for PlayMediaCommand {
+    fn id(&self) -> &str {
+        "media:play"
+    }
+
+    fn name(&self) -> &str {
+        "Play Selected Media"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Replace the posters of the selected media items with their players")
+    }
+
+    fn category(&self) -> &str {
+        "Media"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.play_selected_media(cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["activate", "audio", "video", "youtube", "load", "webview"]
+    }
+}
+
+/// Unload every media player, leaving posters
+pub struct UnloadMediaCommand;
+
+impl Command for UnloadMediaCommand {
+    fn id(&self) -> &str {
+        "media:unload"
+    }
+
+    fn name(&self) -> &str {
+        "Unload All Media Players"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Stop playback and free the webviews of all media items")
+    }
+
+    fn category(&self) -> &str {
+        "Media"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        !app.live_media.is_empty()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.unload_media(cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["stop", "deactivate", "audio", "video", "youtube", "memory", "webview"]
+    }
+}
+
+/// Pick pages of the selected PDF to place on the canvas
+pub struct PlacePdfPagesCommand;
+
+impl Command for PlacePdfPagesCommand {
+    fn id(&self) -> &str {
+        "pdf:place-pages"
+    }
+
+    fn name(&self) -> &str {
+        "Place PDF Pages"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Choose pages of the selected PDF and place them beside it")
+    }
+
+    fn category(&self) -> &str {
+        "Media"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.open_pdf_page_picker(cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["pdf", "slides", "deck", "explode", "split", "page", "range"]
+    }
+}
+
+/// Annotate the PDF being previewed or selected
+pub struct AnnotatePdfCommand;
+
+impl Command for AnnotatePdfCommand {
+    fn id(&self) -> &str {
+        "pdf:annotate"
+    }
+
+    fn name(&self) -> &str {
+        "Annotate PDF"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Highlight, note and box areas of the previewed or selected PDF")
+    }
+
+    fn category(&self) -> &str {
+        "Media"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.annotate_pdf(cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["pdf", "highlight", "note", "comment", "markup", "quote"]
+    }
+}
+
+/// Start a comment thread on the selected item or the canvas in view
+pub struct AddCommentCommand;
+
+impl Command for AddCommentCommand {
+    fn id(&self) -> &str {
+        "comments:add"
+    }
+
+    fn name(&self) -> &str {
+        "Add Comment"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Start a thread on the selected item, or on the canvas in view")
+    }
+
+    fn category(&self) -> &str {
+        "Comments"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.add_comment(window, cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["comment", "discuss", "thread", "feedback", "review"]
+    }
+}
+
+/// Show or hide the comments sidebar
+pub struct ToggleCommentsCommand;
+
+impl Command for ToggleCommentsCommand {
+    fn id(&self) -> &str {
+        "comments:toggle"
+    }
+
+    fn name(&self) -> &str {
+        "Toggle Comments"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Show or hide the open comment threads on this board")
+    }
+
+    fn category(&self) -> &str {
+        "Comments"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.is_some()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.toggle_comments(cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["comments", "threads", "sidebar", "discussion", "resolve"]
+    }
+}
+
+/// Kill the external commands that are still running
+pub struct CancelExternalCommandsCommand;
+
+impl Command for CancelExternalCommandsCommand {
+    fn id(&self) -> &str {
+        "external-commands:cancel"
+    }
+
+    fn name(&self) -> &str {
+        "Cancel External Commands"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Stop the external commands that are still running")
+    }
+
+    fn category(&self) -> &str {
+        "External"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        !app.running_external_commands.is_empty()
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.cancel_external_commands(cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["external", "script", "stop", "kill"]
+    }
+}
+
 /// Toggle settings modal
 pub struct OpenSettingsCommand;
 
 impl Command for OpenSettingsCommand {
-    fn id(&self) -> &'static str {
+    fn id(&self) -> &str {
         "app:settings"
     }
 
@@ -291,7 +873,7 @@ impl Command for OpenSettingsCommand {
 pub struct ShowShortcutsCommand;
 
 impl Command for ShowShortcutsCommand {
-    fn id(&self) -> &'static str {
+    fn id(&self) -> &str {
         "help:shortcuts"
     }
 
@@ -329,7 +911,7 @@ impl Command for ShowShortcutsCommand {
 pub struct UndoCommand;
 
 impl Command for UndoCommand {
-    fn id(&self) -> &'static str {
+    fn id(&self) -> &str {
         "edit:undo"
     }
 
@@ -364,7 +946,7 @@ impl Command for UndoCommand {
 pub struct RedoCommand;
 
 impl Command for RedoCommand {
-    fn id(&self) -> &'static str {
+    fn id(&self) -> &str {
         "edit:redo"
     }
 
@@ -399,7 +981,7 @@ impl Command for RedoCommand {
 pub struct ZoomInCommand;
 
 impl Command for ZoomInCommand {
-    fn id(&self) -> &'static str {
+    fn id(&self) -> &str {
         "view:zoom_in"
     }
 
@@ -437,7 +1019,7 @@ impl Command for ZoomInCommand {
 pub struct ZoomOutCommand;
 
 impl Command for ZoomOutCommand {
-    fn id(&self) -> &'static str {
+    fn id(&self) -> &str {
         "view:zoom_out"
     }
 
@@ -475,7 +1057,7 @@ impl Command for ZoomOutCommand {
 pub struct ZoomResetCommand;
 
 impl Command for ZoomResetCommand {
-    fn id(&self) -> &'static str {
+    fn id(&self) -> &str {
         "view:zoom_reset"
     }
 
@@ -518,11 +1100,45 @@ pub fn create_default_registry() -> CommandRegistry {
 
     // Board
     registry.register(NewBoardCommand);
+    registry.register(ImportBoardCommand);
+    for format in crate::app::BoardFormat::ALL {
+        registry.register(ExportBoardCommand::new(format));
+    }
 
     // Edit
     registry.register(UndoCommand);
     registry.register(RedoCommand);
 
+    // Arrange
+    for kind in crate::auto_layout::LayoutKind::ALL {
+        registry.register(ArrangeCommand::new(kind));
+    }
+    for kind in crate::auto_layout::LayoutKind::ALL {
+        if kind.is_ordered() {
+            registry.register(ArrangeCommand::by_capture_date(kind));
+        }
+    }
+
+    // Image
+    for action in crate::image_edit::ImageAction::ALL {
+        registry.register(ImageEditCommand::new(action));
+    }
+    registry.register(ExtractPaletteCommand);
+    registry.register(ImageInfoCommand);
+
+    // Media
+    registry.register(PlayMediaCommand);
+    registry.register(UnloadMediaCommand);
+    registry.register(PlacePdfPagesCommand);
+    registry.register(AnnotatePdfCommand);
+
+    // Comments
+    registry.register(AddCommentCommand);
+    registry.register(ToggleCommentsCommand);
+
+    // External commands
+    registry.register(CancelExternalCommandsCommand);
+
     // View
     registry.register(ZoomInCommand);
     registry.register(ZoomOutCommand);
@@ -0,0 +1,259 @@
+//! Comments - threads of discussion attached to items or points on a board.
+//!
+//! A thread hangs off an item (and follows it around) or a fixed canvas
+//! point. Threads are resolved rather than deleted, so the discussion stays
+//! with the board. Exports carry threads as sticky notes beside what they
+//! discuss, joined to items by an arrow.
+
+use crate::timestamps::{self, WEEK_SECS, unix_now};
+use crate::types::{ArrowHead, CanvasItem, ItemContent, ShapeType, arrow_bounds};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Width of an exported comment note
+pub const NOTE_WIDTH: f32 = 240.0;
+
+/// Space between an item and its exported comment notes
+pub const NOTE_GAP: f32 = 40.0;
+
+/// Fill of exported comment notes
+const NOTE_COLOR: &str = "#fde68a";
+
+/// Author of comments written before `author_name` is set
+pub const ANONYMOUS: &str = "Anonymous";
+
+/// What a thread is about
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CommentAnchor {
+    /// An item, by ID
+    Item(u64),
+    /// A point on the canvas
+    Point(f32, f32),
+}
+
+/// One message in a thread
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub author: String,
+    pub text: String,
+    /// Unix timestamp when the comment was written
+    pub created_at: u64,
+}
+
+impl Comment {
+    /// A comment written now
+    pub fn new(author: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            author: author.into(),
+            text: text.into(),
+            created_at: unix_now(),
+        }
+    }
+}
+
+/// A thread of comments on an item or canvas point
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommentThread {
+    pub id: u64,
+    pub anchor: CommentAnchor,
+    pub comments: Vec<Comment>,
+    #[serde(default)]
+    pub resolved: bool,
+}
+
+impl CommentThread {
+    /// Time of the latest comment
+    pub fn last_activity(&self) -> u64 {
+        self.comments
+            .iter()
+            .map(|c| c.created_at)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Canvas point the thread's badge sits on: an item's top right corner,
+    /// or the thread's own point. None if its item is gone.
+    pub fn position(&self, items: &[CanvasItem]) -> Option<(f32, f32)> {
+        match self.anchor {
+            CommentAnchor::Item(id) => items
+                .iter()
+                .find(|item| item.id == id)
+                .map(|item| (item.position.0 + item.size.0, item.position.1)),
+            CommentAnchor::Point(x, y) => Some((x, y)),
+        }
+    }
+}
+
+/// ID for a new thread, after every existing one
+pub fn next_thread_id(threads: &[CommentThread]) -> u64 {
+    threads.iter().map(|t| t.id + 1).max().unwrap_or(0)
+}
+
+/// Number of open threads on each item
+pub fn open_counts(threads: &[CommentThread]) -> HashMap<u64, usize> {
+    let mut counts = HashMap::new();
+    for thread in threads.iter().filter(|t| !t.resolved) {
+        if let CommentAnchor::Item(id) = thread.anchor {
+            *counts.entry(id).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Threads to list: open ones first, each group most recently active first
+pub fn sorted_threads(threads: &[CommentThread], include_resolved: bool) -> Vec<&CommentThread> {
+    let mut listed: Vec<&CommentThread> = threads
+        .iter()
+        .filter(|t| include_resolved || !t.resolved)
+        .collect();
+    listed.sort_by(|a, b| {
+        a.resolved
+            .cmp(&b.resolved)
+            .then(b.last_activity().cmp(&a.last_activity()))
+            .then(b.id.cmp(&a.id))
+    });
+    listed
+}
+
+/// Name comments are signed with: the `author_name` setting, or `Anonymous`
+/// until it's set. Boards are shared and exported, so the login name isn't used.
+pub fn author_name(setting: &str) -> String {
+    let setting = setting.trim();
+    if setting.is_empty() {
+        ANONYMOUS.to_string()
+    } else {
+        setting.to_string()
+    }
+}
+
+/// What a thread is on, for the comments sidebar
+pub fn anchor_label(anchor: CommentAnchor, items: &[CanvasItem]) -> String {
+    match anchor {
+        CommentAnchor::Item(id) => items
+            .iter()
+            .find(|item| item.id == id)
+            .map(|item| item.content.display_name())
+            .unwrap_or_else(|| "Deleted item".to_string()),
+        CommentAnchor::Point(x, y) => format!("Canvas ({:.0}, {:.0})", x, y),
+    }
+}
+
+/// When a comment was written: how long before `now` for the past week,
+/// such as `5 min ago`, and its date after that
+pub fn written_at(timestamp: u64, now: u64) -> String {
+    if now.saturating_sub(timestamp) < WEEK_SECS {
+        timestamps::time_ago(timestamp, now)
+    } else {
+        format_date(timestamp)
+    }
+}
+
+/// A timestamp as a UTC date and time, such as `2026-10-18 14:03 UTC`
+pub fn format_date(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let secs = timestamp % 86400;
+
+    // Civil date from days since 1970-01-01, after Howard Hinnant
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
+/// A thread as plain text, one paragraph per comment
+pub fn thread_text(thread: &CommentThread) -> String {
+    let mut paragraphs: Vec<String> = thread
+        .comments
+        .iter()
+        .map(|c| format!("{} · {}\n{}", c.author, format_date(c.created_at), c.text))
+        .collect();
+    if thread.resolved {
+        paragraphs.insert(0, "Resolved".to_string());
+    }
+    paragraphs.join("\n\n")
+}
+
+/// Items to export for a board with comments: its own items, then a sticky
+/// note per thread, beside its item with an arrow to it or at its point.
+/// Threads on deleted items are left out.
+pub fn export_items(items: &[CanvasItem], threads: &[CommentThread]) -> Vec<CanvasItem> {
+    let mut exported = items.to_vec();
+    let mut next_id = items.iter().map(|i| i.id + 1).max().unwrap_or(0);
+    // Where the next note beside each item goes
+    let mut stack: HashMap<u64, f32> = HashMap::new();
+
+    let mut ordered: Vec<&CommentThread> = threads.iter().collect();
+    ordered.sort_by_key(|t| t.id);
+    for thread in ordered {
+        let text = thread_text(thread);
+        let height = note_height(&text);
+        let note = |id, position| CanvasItem {
+            id,
+            position,
+            size: (NOTE_WIDTH, height),
+            content: ItemContent::Shape {
+                shape_type: ShapeType::StickyNote,
+                fill_color: Some(NOTE_COLOR.to_string()),
+                border_color: String::new(),
+                border_width: 0.0,
+                label: Some(text.clone()),
+            },
+        };
+
+        match thread.anchor {
+            CommentAnchor::Point(x, y) => {
+                exported.push(note(next_id, (x, y)));
+                next_id += 1;
+            }
+            CommentAnchor::Item(item_id) => {
+                let Some(item) = items.iter().find(|i| i.id == item_id) else {
+                    continue;
+                };
+                let right = item.position.0 + item.size.0;
+                let y = stack.entry(item_id).or_insert(item.position.1);
+                let position = (right + NOTE_GAP, *y);
+                *y += height + NOTE_GAP / 2.0;
+                exported.push(note(next_id, position));
+
+                // From the note's left edge back to the item's right edge
+                let arrow_y = (position.1 + 20.0).min(item.position.1 + item.size.1);
+                let (start, end) = ((position.0, position.1 + 20.0), (right, arrow_y));
+                let (arrow_position, arrow_size) = arrow_bounds(start, end);
+                exported.push(CanvasItem {
+                    id: next_id + 1,
+                    position: arrow_position,
+                    size: arrow_size,
+                    content: ItemContent::Arrow {
+                        end_offset: (end.0 - start.0, end.1 - start.1),
+                        color: String::new(),
+                        thickness: 2.0,
+                        head_style: ArrowHead::Arrow,
+                        label: None,
+                    },
+                });
+                next_id += 2;
+            }
+        }
+    }
+    exported
+}
+
+/// Height of an exported note, taller for longer threads
+fn note_height(text: &str) -> f32 {
+    let lines: usize = text.lines().map(|line| 1 + line.chars().count() / 30).sum();
+    (lines as f32 * 20.0 + 40.0).clamp(120.0, 600.0)
+}
@@ -19,6 +19,12 @@ pub const FOOTER_HEIGHT: f32 = 28.0;
 /// Width of the preview splitter drag handle in pixels
 pub const SPLITTER_WIDTH: f32 = 16.0;
 
+/// Width of the comments sidebar in pixels
+pub const COMMENTS_SIDEBAR_WIDTH: f32 = 320.0;
+
+/// Height of the annotations bar under a PDF preview tab in pixels
+pub const PDF_TAB_FOOTER_HEIGHT: f32 = 36.0;
+
 /// Minimum hit area for interactive elements
 pub const MIN_HIT_AREA: f32 = 8.0;
 
@@ -60,6 +66,12 @@ pub const MAX_HISTORY_STATES: usize = 50;
 /// Pan animation duration in seconds
 pub const PAN_ANIMATION_DURATION: f64 = 0.3;
 
+/// Duration of items moving into an automatic layout in seconds
+pub const LAYOUT_ANIMATION_DURATION: f64 = 0.4;
+
+/// Duration of the camera move between presentation stops in milliseconds
+pub const PRESENTATION_TRANSITION_MS: u64 = 500;
+
 // ============================================================================
 // Zoom & Pan
 // ============================================================================
@@ -76,6 +88,9 @@ pub const DEFAULT_ZOOM: f32 = 1.0;
 /// Zoom step for scroll wheel
 pub const ZOOM_STEP: f32 = 0.1;
 
+/// Fraction of the canvas left empty around items for zoom to fit / zoom to selection
+pub const ZOOM_TO_FIT_MARGIN: f32 = 0.05;
+
 // ============================================================================
 // Preview Panel
 // ============================================================================
@@ -123,3 +138,12 @@ pub const MIN_BORDER_WIDTH: f32 = 0.0;
 
 /// Maximum shape border width
 pub const MAX_BORDER_WIDTH: f32 = 50.0;
+
+/// Minimum freehand stroke width
+pub const MIN_STROKE_WIDTH: f32 = 0.5;
+
+/// Maximum freehand stroke width
+pub const MAX_STROKE_WIDTH: f32 = 64.0;
+
+/// Eraser radius in screen pixels
+pub const ERASER_RADIUS: f32 = 10.0;
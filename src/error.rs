@@ -66,6 +66,98 @@ pub enum SettingsError {
     LockPoisoned(String),
 }
 
+/// Errors that can occur when saving or applying board templates
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    #[error("Template not found: {0}")]
+    NotFound(String),
+
+    #[error("Failed to copy template files: {0}")]
+    CopyFailed(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Board(#[from] BoardError),
+}
+
+/// Errors that can occur when authoring themes
+#[derive(Error, Debug)]
+pub enum ThemeError {
+    #[error("Invalid colour \"{0}\", expected #rrggbb")]
+    InvalidColor(String),
+
+    #[error("Theme name can't be empty")]
+    EmptyName,
+
+    #[error("A theme named \"{0}\" already exists")]
+    NameTaken(String),
+
+    #[error("Failed to save theme to {path}: {source}")]
+    SaveFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to serialize theme: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+/// Errors that can occur when running user-defined external commands
+#[derive(Error, Debug)]
+pub enum ExternalCommandError {
+    #[error("Failed to start \"{command}\": {source}")]
+    SpawnFailed {
+        command: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to run command: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Command failed ({status}): {stderr}")]
+    Failed { status: String, stderr: String },
+
+    #[error("Command timed out after {seconds}s")]
+    TimedOut { seconds: u64 },
+
+    #[error("Command was cancelled")]
+    Cancelled,
+
+    #[error("Failed to serialize selection: {0}")]
+    Serialize(#[source] serde_json::Error),
+
+    #[error("Invalid command output: {0}")]
+    InvalidOutput(#[source] serde_json::Error),
+
+    #[error("Invalid command output: {0}")]
+    InvalidItem(String),
+}
+
+/// Errors that can occur when importing or exporting other board formats
+#[derive(Error, Debug)]
+pub enum ImportExportError {
+    #[error("Failed to read {path}: {source}")]
+    ReadFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to write {path}: {source}")]
+    WriteFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid file: {0}")]
+    Parse(#[source] serde_json::Error),
+
+    #[error("Failed to serialize board: {0}")]
+    Serialize(#[source] serde_json::Error),
+}
+
 /// Errors that can occur with media operations
 #[derive(Error, Debug)]
 pub enum MediaError {
@@ -79,9 +171,19 @@ pub enum MediaError {
         source: std::io::Error,
     },
 
+    #[error("Failed to write file {path}: {source}")]
+    WriteFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error("Failed to process image: {0}")]
     ImageError(#[from] image::ImageError),
 
+    #[error("Failed to decode audio: {0}")]
+    AudioError(#[from] symphonia::core::errors::Error),
+
     #[error("Failed to create webview: {0}")]
     WebviewError(String),
 
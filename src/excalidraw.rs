@@ -0,0 +1,887 @@
+//! Excalidraw - import and export of `.excalidraw` scenes.
+//!
+//! Rectangles, ellipses and diamonds become shapes, text becomes text boxes
+//! (or the label of the shape or arrow it's bound to), arrows and lines become
+//! arrows, freedraw becomes pen strokes, frames become frames and embedded
+//! images are written out as image files. Stroke and fill colours carry over;
+//! rotation, bends in arrows and the hand-drawn style don't. Anything without
+//! an equivalent is counted in an [`Unsupported`] summary instead of being
+//! dropped silently.
+
+use crate::board::BoardState;
+use crate::error::ImportExportError;
+use crate::image_edit::ImageEdit;
+use crate::strokes;
+use crate::types::{
+    arrow_bounds, ArrowHead, CanvasItem, ItemContent, ShapeType, StrokeKind, TextAlignment,
+    TextFormat,
+};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// File extension of Excalidraw scenes
+pub const EXTENSION: &str = "excalidraw";
+
+/// Excalidraw's default stroke colour, used for items that follow the theme
+const DEFAULT_STROKE: &str = "#1e1e1e";
+
+/// Background colour of unfilled elements
+const TRANSPARENT: &str = "transparent";
+
+/// Roundness type Excalidraw gives rounded rectangles
+const ADAPTIVE_RADIUS: u32 = 3;
+
+/// A whole `.excalidraw` file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scene {
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub elements: Vec<SceneElement>,
+    #[serde(default)]
+    pub app_state: serde_json::Value,
+    /// Embedded binary files, keyed by file ID
+    #[serde(default)]
+    pub files: BTreeMap<String, BinaryFile>,
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Self {
+            kind: "excalidraw".to_string(),
+            version: 2,
+            source: "humanboard".to_string(),
+            elements: Vec::new(),
+            app_state: serde_json::json!({ "viewBackgroundColor": "#ffffff" }),
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+/// An element of a type this module understands, or any other
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SceneElement {
+    Known(Box<Element>),
+    Other(OtherElement),
+}
+
+/// An element of a type with no Humanboard equivalent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtherElement {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub is_deleted: bool,
+}
+
+/// Properties every element has
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Element {
+    pub id: String,
+    #[serde(flatten)]
+    pub kind: ElementKind,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Rotation in radians
+    pub angle: f32,
+    pub stroke_color: String,
+    pub background_color: String,
+    pub fill_style: String,
+    pub stroke_width: f32,
+    pub stroke_style: String,
+    pub roughness: f32,
+    /// 0 to 100
+    pub opacity: f32,
+    pub group_ids: Vec<String>,
+    pub frame_id: Option<String>,
+    pub roundness: Option<Roundness>,
+    pub seed: i64,
+    pub version: u32,
+    pub version_nonce: i64,
+    pub is_deleted: bool,
+    pub bound_elements: Option<Vec<BoundElement>>,
+    pub updated: u64,
+    pub link: Option<String>,
+    pub locked: bool,
+}
+
+impl Default for Element {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            kind: ElementKind::Rectangle,
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            angle: 0.0,
+            stroke_color: DEFAULT_STROKE.to_string(),
+            background_color: TRANSPARENT.to_string(),
+            fill_style: "solid".to_string(),
+            stroke_width: 2.0,
+            stroke_style: "solid".to_string(),
+            roughness: 1.0,
+            opacity: 100.0,
+            group_ids: Vec::new(),
+            frame_id: None,
+            roundness: None,
+            seed: 1,
+            version: 1,
+            version_nonce: 0,
+            is_deleted: false,
+            bound_elements: None,
+            updated: 1,
+            link: None,
+            locked: false,
+        }
+    }
+}
+
+/// The type-specific part of an element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ElementKind {
+    Rectangle,
+    Ellipse,
+    Diamond,
+    Text(TextData),
+    Arrow(LinearData),
+    Line(LinearData),
+    Freedraw(FreedrawData),
+    Image(ImageData),
+    Frame(FrameData),
+}
+
+impl ElementKind {
+    /// The `type` Excalidraw gives this kind of element
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ElementKind::Rectangle => "rectangle",
+            ElementKind::Ellipse => "ellipse",
+            ElementKind::Diamond => "diamond",
+            ElementKind::Text(_) => "text",
+            ElementKind::Arrow(_) => "arrow",
+            ElementKind::Line(_) => "line",
+            ElementKind::Freedraw(_) => "freedraw",
+            ElementKind::Image(_) => "image",
+            ElementKind::Frame(_) => "frame",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TextData {
+    pub text: String,
+    pub font_size: f32,
+    pub font_family: u32,
+    pub text_align: String,
+    pub vertical_align: String,
+    /// Shape or arrow the text is the label of
+    pub container_id: Option<String>,
+    pub original_text: String,
+    pub line_height: f32,
+}
+
+impl Default for TextData {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            font_size: 20.0,
+            font_family: 1,
+            text_align: "left".to_string(),
+            vertical_align: "top".to_string(),
+            container_id: None,
+            original_text: String::new(),
+            line_height: 1.25,
+        }
+    }
+}
+
+/// Points of an arrow or line, relative to the element's position
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LinearData {
+    pub points: Vec<(f32, f32)>,
+    pub start_binding: Option<serde_json::Value>,
+    pub end_binding: Option<serde_json::Value>,
+    pub start_arrowhead: Option<String>,
+    pub end_arrowhead: Option<String>,
+}
+
+/// Points of a freehand line, relative to the element's position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FreedrawData {
+    pub points: Vec<(f32, f32)>,
+    pub pressures: Vec<f32>,
+    pub simulate_pressure: bool,
+}
+
+impl Default for FreedrawData {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            pressures: Vec::new(),
+            simulate_pressure: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ImageData {
+    /// Key into the scene's `files`
+    pub file_id: Option<String>,
+    pub status: String,
+    pub scale: (f32, f32),
+}
+
+impl Default for ImageData {
+    fn default() -> Self {
+        Self {
+            file_id: None,
+            status: "saved".to_string(),
+            scale: (1.0, 1.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrameData {
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Roundness {
+    #[serde(rename = "type")]
+    pub kind: u32,
+}
+
+/// Another element attached to this one, such as its label
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoundElement {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// A file embedded in the scene as a data URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryFile {
+    pub id: String,
+    pub mime_type: String,
+    #[serde(rename = "dataURL")]
+    pub data_url: String,
+    #[serde(default)]
+    pub created: u64,
+}
+
+/// Element or item types left out of an import or export, with counts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Unsupported(pub BTreeMap<String, usize>);
+
+impl Unsupported {
+    pub fn add(&mut self, kind: impl Into<String>) {
+        *self.0.entry(kind.into()).or_default() += 1;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn total(&self) -> usize {
+        self.0.values().sum()
+    }
+
+    pub fn count(&self, kind: &str) -> usize {
+        self.0.get(kind).copied().unwrap_or(0)
+    }
+}
+
+/// Lists the skipped kinds, e.g. "2 embeddable, 1 iframe"
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self
+            .0
+            .iter()
+            .map(|(kind, count)| format!("{} {}", count, kind))
+            .collect();
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// Read and parse a `.excalidraw` file
+pub fn read(path: &Path) -> Result<Scene, ImportExportError> {
+    let json = fs::read_to_string(path).map_err(|source| ImportExportError::ReadFailed {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_json::from_str(&json).map_err(ImportExportError::Parse)
+}
+
+/// Write a scene to `path`
+pub fn write(scene: &Scene, path: &Path) -> Result<(), ImportExportError> {
+    let json = serde_json::to_string_pretty(scene).map_err(ImportExportError::Serialize)?;
+    fs::write(path, json).map_err(|source| ImportExportError::WriteFailed {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Export items to a `.excalidraw` file, returning what was left out
+pub fn export_file(items: &[CanvasItem], path: &Path) -> Result<Unsupported, ImportExportError> {
+    let (scene, unsupported) = from_items(items);
+    write(&scene, path)?;
+    Ok(unsupported)
+}
+
+/// Position, size and content of an item before it gets an id
+type Part = ((f32, f32), (f32, f32), ItemContent);
+
+/// Convert a scene to board contents, writing embedded images into `files_dir`.
+///
+/// Frames go to the back; everything else keeps the scene's stacking order.
+/// Deleted elements are ignored.
+pub fn to_board_state(scene: &Scene, files_dir: &Path) -> (BoardState, Unsupported) {
+    let mut unsupported = Unsupported::default();
+    let mut elements: Vec<&Element> = Vec::new();
+    for element in &scene.elements {
+        match element {
+            SceneElement::Known(element) if !element.is_deleted => elements.push(element),
+            SceneElement::Other(other) if !other.is_deleted => unsupported.add(&other.kind),
+            _ => {}
+        }
+    }
+
+    // Text bound to a shape or arrow becomes that item's label
+    let containers: HashSet<&str> = elements
+        .iter()
+        .filter(|e| !matches!(e.kind, ElementKind::Text(_)))
+        .map(|e| e.id.as_str())
+        .collect();
+    let labels: HashMap<&str, &str> = elements
+        .iter()
+        .copied()
+        .filter_map(|e| match &e.kind {
+            ElementKind::Text(TextData {
+                container_id: Some(container),
+                text,
+                ..
+            }) if containers.contains(container.as_str()) => {
+                Some((container.as_str(), text.as_str()))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut frames: Vec<Part> = Vec::new();
+    let mut parts: Vec<Part> = Vec::new();
+    for element in elements {
+        let label = labels.get(element.id.as_str()).map(|l| l.to_string());
+        let part = match &element.kind {
+            ElementKind::Text(TextData {
+                container_id: Some(container),
+                ..
+            }) if labels.contains_key(container.as_str()) => continue,
+            ElementKind::Frame(frame) => {
+                frames.push((
+                    (element.x, element.y),
+                    (element.width, element.height),
+                    shape_content(ShapeType::Frame, None, String::new(), 2.0, frame.name.clone()),
+                ));
+                continue;
+            }
+            _ => element_part(element, label, scene, files_dir),
+        };
+        match part {
+            Some(part) => parts.push(part),
+            None => unsupported.add(element.kind.type_name()),
+        }
+    }
+
+    let items = frames
+        .into_iter()
+        .chain(parts)
+        .enumerate()
+        .map(|(id, (position, size, content))| CanvasItem {
+            id: id as u64,
+            position,
+            size,
+            content,
+        })
+        .collect();
+    (BoardState::from_items(items), unsupported)
+}
+
+/// The item an element imports as; `None` if it can't be
+fn element_part(
+    element: &Element,
+    label: Option<String>,
+    scene: &Scene,
+    files_dir: &Path,
+) -> Option<Part> {
+    let position = (element.x, element.y);
+    let size = (element.width, element.height);
+    let fill = (element.background_color != TRANSPARENT).then(|| element.background_color.clone());
+    let border = element.stroke_color.clone();
+
+    let content = match &element.kind {
+        ElementKind::Rectangle => {
+            let shape_type = if element.roundness.is_some() {
+                ShapeType::RoundedRect
+            } else {
+                ShapeType::Rectangle
+            };
+            shape_content(shape_type, fill, border, element.stroke_width, label)
+        }
+        ElementKind::Ellipse => {
+            shape_content(ShapeType::Ellipse, fill, border, element.stroke_width, label)
+        }
+        ElementKind::Diamond => {
+            shape_content(ShapeType::Diamond, fill, border, element.stroke_width, label)
+        }
+        ElementKind::Text(text) => ItemContent::TextBox {
+            text: text.text.clone(),
+            font_size: text.font_size,
+            color: element.stroke_color.clone(),
+            format: TextFormat::Plain,
+            align: TextAlignment::from_name(&text.text_align).unwrap_or_default(),
+            font_family: None,
+            fill_color: None,
+        },
+        ElementKind::Arrow(linear) | ElementKind::Line(linear) => {
+            if linear.points.len() < 2 {
+                return None;
+            }
+            let (first, last) = (linear.points[0], linear.points[linear.points.len() - 1]);
+            let start = (element.x + first.0, element.y + first.1);
+            let end = (element.x + last.0, element.y + last.1);
+            let head_style = match &element.kind {
+                ElementKind::Arrow(_) => arrow_head(linear.end_arrowhead.as_deref()),
+                _ => ArrowHead::None,
+            };
+            let (position, size) = arrow_bounds(start, end);
+            return Some((
+                position,
+                size,
+                ItemContent::Arrow {
+                    end_offset: (end.0 - start.0, end.1 - start.1),
+                    color: element.stroke_color.clone(),
+                    thickness: element.stroke_width,
+                    head_style,
+                    label,
+                },
+            ));
+        }
+        ElementKind::Freedraw(freedraw) => {
+            if freedraw.points.is_empty() {
+                return None;
+            }
+            let points: Vec<(f32, f32)> = freedraw
+                .points
+                .iter()
+                .map(|p| (element.x + p.0, element.y + p.1))
+                .collect();
+            let kind = if element.opacity < 100.0 {
+                StrokeKind::Highlighter
+            } else {
+                StrokeKind::Pen
+            };
+            let (position, size, points) = strokes::fit(&points, element.stroke_width);
+            return Some((
+                position,
+                size,
+                ItemContent::Stroke {
+                    points,
+                    color: element.stroke_color.clone(),
+                    width: element.stroke_width,
+                    kind,
+                },
+            ));
+        }
+        ElementKind::Image(image) => {
+            let file = scene.files.get(image.file_id.as_deref()?)?;
+            ItemContent::Image(write_embedded_file(file, files_dir)?, ImageEdit::default())
+        }
+        ElementKind::Frame(_) => return None,
+    };
+    Some((position, size, content))
+}
+
+fn arrow_head(name: Option<&str>) -> ArrowHead {
+    match name {
+        None => ArrowHead::None,
+        Some("dot" | "circle" | "circle_outline") => ArrowHead::Circle,
+        Some("diamond" | "diamond_outline") => ArrowHead::Diamond,
+        Some(_) => ArrowHead::Arrow,
+    }
+}
+
+fn shape_content(
+    shape_type: ShapeType,
+    fill: Option<String>,
+    border: String,
+    border_width: f32,
+    label: Option<String>,
+) -> ItemContent {
+    ItemContent::Shape {
+        shape_type,
+        fill_color: fill,
+        border_color: border,
+        border_width,
+        label,
+    }
+}
+
+const IMAGE_TYPES: [(&str, &str); 6] = [
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+    ("bmp", "image/bmp"),
+];
+
+/// Decode an embedded image into `files_dir`, named after its file ID
+fn write_embedded_file(file: &BinaryFile, files_dir: &Path) -> Option<std::path::PathBuf> {
+    let (header, data) = file.data_url.strip_prefix("data:")?.split_once(',')?;
+    let mime = header.split(';').next().filter(|m| !m.is_empty()).unwrap_or(&file.mime_type);
+    let (ext, _) = IMAGE_TYPES.iter().find(|(_, m)| *m == mime)?;
+    let bytes = if header.ends_with(";base64") {
+        base64::engine::general_purpose::STANDARD.decode(data).ok()?
+    } else {
+        urlencoding::decode_binary(data.as_bytes()).into_owned()
+    };
+
+    let name: String = file
+        .id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    let path = files_dir.join(format!("{}.{}", name, ext));
+    fs::create_dir_all(files_dir).ok()?;
+    fs::write(&path, bytes).ok()?;
+    Some(path)
+}
+
+/// Convert board items to a scene, embedding image files.
+///
+/// Links and YouTube videos become linked, labelled rectangles. Media and
+/// document items, shapes Excalidraw can't draw and images whose files are
+/// missing are left out and counted.
+pub fn from_items(items: &[CanvasItem]) -> (Scene, Unsupported) {
+    let mut scene = Scene::default();
+    let mut unsupported = Unsupported::default();
+
+    for item in items {
+        let id = format!("{:016x}", item.id);
+        let base = Element {
+            id: id.clone(),
+            x: item.position.0,
+            y: item.position.1,
+            width: item.size.0,
+            height: item.size.1,
+            seed: item.id as i64 + 1,
+            ..Element::default()
+        };
+
+        let (element, label) = match &item.content {
+            ItemContent::Shape {
+                shape_type: ShapeType::Frame,
+                label,
+                ..
+            } => (
+                Element {
+                    kind: ElementKind::Frame(FrameData {
+                        name: label.clone(),
+                    }),
+                    ..base
+                },
+                None,
+            ),
+            ItemContent::Shape {
+                shape_type,
+                fill_color,
+                border_color,
+                border_width,
+                label,
+            } => {
+                let kind = match shape_type {
+                    ShapeType::Rectangle | ShapeType::RoundedRect | ShapeType::StickyNote => {
+                        ElementKind::Rectangle
+                    }
+                    ShapeType::Ellipse => ElementKind::Ellipse,
+                    ShapeType::Diamond => ElementKind::Diamond,
+                    other => {
+                        unsupported.add(other.name().to_lowercase());
+                        continue;
+                    }
+                };
+                let rounded = matches!(shape_type, ShapeType::RoundedRect | ShapeType::StickyNote);
+                (
+                    Element {
+                        kind,
+                        stroke_color: stroke_color(border_color),
+                        background_color: fill_color
+                            .clone()
+                            .unwrap_or_else(|| TRANSPARENT.to_string()),
+                        stroke_width: *border_width,
+                        roundness: rounded.then_some(Roundness {
+                            kind: ADAPTIVE_RADIUS,
+                        }),
+                        ..base
+                    },
+                    label.clone(),
+                )
+            }
+            ItemContent::Arrow {
+                color,
+                thickness,
+                head_style,
+                label,
+                ..
+            } => {
+                let Some((start, end)) = item.arrow_endpoints() else {
+                    continue;
+                };
+                let offset = (end.0 - start.0, end.1 - start.1);
+                let end_arrowhead = match head_style {
+                    ArrowHead::None => None,
+                    ArrowHead::Arrow => Some("arrow"),
+                    ArrowHead::Diamond => Some("diamond"),
+                    ArrowHead::Circle => Some("circle"),
+                };
+                let linear = LinearData {
+                    points: vec![(0.0, 0.0), offset],
+                    end_arrowhead: end_arrowhead.map(str::to_string),
+                    ..LinearData::default()
+                };
+                // Headless arrows are how lines are drawn, so export them as lines
+                let kind = match head_style {
+                    ArrowHead::None => ElementKind::Line(linear),
+                    _ => ElementKind::Arrow(linear),
+                };
+                (
+                    Element {
+                        kind,
+                        x: start.0,
+                        y: start.1,
+                        width: offset.0.abs(),
+                        height: offset.1.abs(),
+                        stroke_color: stroke_color(color),
+                        stroke_width: *thickness,
+                        ..base
+                    },
+                    label.clone(),
+                )
+            }
+            ItemContent::TextBox {
+                text,
+                font_size,
+                color,
+                align,
+                ..
+            } => (
+                text_element(base, text, *font_size, stroke_color(color), *align, None),
+                None,
+            ),
+            ItemContent::Text(text) => (
+                text_element(base, text, 20.0, DEFAULT_STROKE.to_string(), TextAlignment::Left, None),
+                None,
+            ),
+            ItemContent::Link(url) => (
+                Element {
+                    link: Some(url.clone()),
+                    ..base
+                },
+                Some(url.clone()),
+            ),
+            ItemContent::YouTube(video_id) => {
+                let url = format!("https://www.youtube.com/watch?v={}", video_id);
+                (
+                    Element {
+                        link: Some(url.clone()),
+                        ..base
+                    },
+                    Some(url),
+                )
+            }
+            ItemContent::Stroke {
+                points,
+                color,
+                width,
+                kind,
+            } => {
+                // Freedraw points are relative to the first one, which sits at x, y
+                let points = strokes::to_canvas(points, item.position, item.size);
+                let Some(&origin) = points.first() else {
+                    continue;
+                };
+                let points: Vec<(f32, f32)> = points
+                    .iter()
+                    .map(|p| (p.0 - origin.0, p.1 - origin.1))
+                    .collect();
+                // The first point is (0, 0), so the extremes start there
+                let (min, max) = points.iter().fold(((0.0, 0.0), (0.0, 0.0)), |(min, max), p| {
+                    (
+                        (f32::min(min.0, p.0), f32::min(min.1, p.1)),
+                        (f32::max(max.0, p.0), f32::max(max.1, p.1)),
+                    )
+                });
+                (
+                    Element {
+                        kind: ElementKind::Freedraw(FreedrawData {
+                            points,
+                            ..FreedrawData::default()
+                        }),
+                        x: origin.0,
+                        y: origin.1,
+                        width: max.0 - min.0,
+                        height: max.1 - min.1,
+                        stroke_color: stroke_color(color),
+                        stroke_width: *width,
+                        opacity: kind.opacity() * 100.0,
+                        ..base
+                    },
+                    None,
+                )
+            }
+            ItemContent::Image(path, _)
+            | ItemContent::PdfPage {
+                image: Some(path), ..
+            } => match embed_file(path) {
+                Some(file) => {
+                    let file_id = file.id.clone();
+                    scene.files.insert(file_id.clone(), file);
+                    (
+                        Element {
+                            kind: ElementKind::Image(ImageData {
+                                file_id: Some(file_id),
+                                ..ImageData::default()
+                            }),
+                            ..base
+                        },
+                        None,
+                    )
+                }
+                None => {
+                    unsupported.add("image (missing file)");
+                    continue;
+                }
+            },
+            other => {
+                unsupported.add(other.type_name());
+                continue;
+            }
+        };
+
+        match label {
+            Some(label) => {
+                // Bound text is centred in its container
+                let label_id = format!("{}-label", id);
+                let mut container = element;
+                container.bound_elements = Some(vec![BoundElement {
+                    id: label_id.clone(),
+                    kind: "text".to_string(),
+                }]);
+                let text_base = Element {
+                    id: label_id,
+                    x: container.x,
+                    y: container.y,
+                    width: container.width,
+                    height: container.height,
+                    seed: container.seed,
+                    ..Element::default()
+                };
+                let text = text_element(
+                    text_base,
+                    &label,
+                    20.0,
+                    container.stroke_color.clone(),
+                    TextAlignment::Center,
+                    Some(id),
+                );
+                scene.elements.push(SceneElement::Known(Box::new(container)));
+                scene.elements.push(SceneElement::Known(Box::new(text)));
+            }
+            None => scene.elements.push(SceneElement::Known(Box::new(element))),
+        }
+    }
+
+    (scene, unsupported)
+}
+
+/// Theme-following colours (empty) export as Excalidraw's default stroke
+fn stroke_color(color: &str) -> String {
+    if color.is_empty() {
+        DEFAULT_STROKE.to_string()
+    } else {
+        color.to_string()
+    }
+}
+
+fn text_element(
+    base: Element,
+    text: &str,
+    font_size: f32,
+    color: String,
+    align: TextAlignment,
+    container_id: Option<String>,
+) -> Element {
+    let text_align = match align {
+        TextAlignment::Left => "left",
+        TextAlignment::Center => "center",
+        TextAlignment::Right => "right",
+    };
+    let vertical_align = if container_id.is_some() { "middle" } else { "top" };
+    Element {
+        kind: ElementKind::Text(TextData {
+            text: text.to_string(),
+            font_size,
+            text_align: text_align.to_string(),
+            vertical_align: vertical_align.to_string(),
+            container_id,
+            original_text: text.to_string(),
+            ..TextData::default()
+        }),
+        stroke_color: color,
+        ..base
+    }
+}
+
+/// Read an image file into an embedded file keyed by its content hash
+fn embed_file(path: &Path) -> Option<BinaryFile> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    let ext = if ext == "jpeg" { "jpg".to_string() } else { ext };
+    let (_, mime) = IMAGE_TYPES.iter().find(|(e, _)| *e == ext)?;
+    let bytes = fs::read(path).ok()?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    let id = format!("{:x}", hasher.finalize())[..40].to_string();
+    let data = base64::engine::general_purpose::STANDARD.encode(&bytes);
+    Some(BinaryFile {
+        id,
+        mime_type: mime.to_string(),
+        data_url: format!("data:{};base64,{}", mime, data),
+        created: 1,
+    })
+}
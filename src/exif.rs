@@ -0,0 +1,427 @@
+//! EXIF metadata for image items - orientation, capture date, camera and GPS.
+//!
+//! The `image` crate's decoders hand back the raw, TIFF-structured EXIF block;
+//! the handful of tags shown in the app are parsed from it here. Reads are
+//! cached per file until it changes; search and layouts get their capture
+//! dates from a background read.
+
+use image::metadata::Orientation;
+use image::{ImageDecoder, ImageReader};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// IFD0 tags
+const TAG_MAKE: u16 = 0x010f;
+const TAG_MODEL: u16 = 0x0110;
+const TAG_ORIENTATION: u16 = 0x0112;
+const TAG_DATE_TIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_GPS_IFD: u16 = 0x8825;
+// Exif IFD tags
+const TAG_EXPOSURE_TIME: u16 = 0x829a;
+const TAG_F_NUMBER: u16 = 0x829d;
+const TAG_ISO: u16 = 0x8827;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+const TAG_FOCAL_LENGTH: u16 = 0x920a;
+const TAG_LENS_MODEL: u16 = 0xa434;
+// GPS IFD tags
+const TAG_LATITUDE_REF: u16 = 1;
+const TAG_LATITUDE: u16 = 2;
+const TAG_LONGITUDE_REF: u16 = 3;
+const TAG_LONGITUDE: u16 = 4;
+const TAG_ALTITUDE_REF: u16 = 5;
+const TAG_ALTITUDE: u16 = 6;
+
+/// The EXIF fields the app understands
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Exif {
+    /// Raw EXIF orientation, 1-8
+    pub orientation: Option<u16>,
+    /// When the photo was taken, as `YYYY-MM-DD HH:MM:SS` so it sorts as text
+    pub captured: Option<String>,
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub lens: Option<String>,
+    /// Exposure time in seconds, as a fraction
+    pub exposure_time: Option<(u32, u32)>,
+    pub f_number: Option<f64>,
+    pub iso: Option<u32>,
+    /// Focal length in millimetres
+    pub focal_length: Option<f64>,
+    /// Latitude and longitude in degrees, negative south and west
+    pub gps: Option<(f64, f64)>,
+    /// Altitude in metres, negative below sea level
+    pub altitude: Option<f64>,
+}
+
+impl Exif {
+    /// Parse a raw EXIF block (starting with the TIFF header)
+    pub fn parse(data: &[u8]) -> Option<Exif> {
+        let tiff = Tiff::new(data)?;
+        let ifd0 = tiff.ifd(tiff.u32(4)? as usize)?;
+        let exif_ifd = find(&ifd0, TAG_EXIF_IFD)
+            .and_then(|e| tiff.ifd(tiff.number(e)? as usize))
+            .unwrap_or_default();
+        let gps_ifd = find(&ifd0, TAG_GPS_IFD)
+            .and_then(|e| tiff.ifd(tiff.number(e)? as usize))
+            .unwrap_or_default();
+
+        let text = |ifd: &[Entry], tag| find(ifd, tag).and_then(|e| tiff.ascii(e));
+        let ratio = |ifd: &[Entry], tag| {
+            let (n, d) = *find(ifd, tag).and_then(|e| tiff.rationals(e))?.first()?;
+            (d != 0).then(|| n as f64 / d as f64)
+        };
+        let degrees = |tag, ref_tag, negative| {
+            let parts = find(&gps_ifd, tag).and_then(|e| tiff.rationals(e))?;
+            let [d, m, s] = parts[..] else {
+                return None;
+            };
+            let value = [d, m, s]
+                .iter()
+                .zip([1.0, 60.0, 3600.0])
+                .map(|(&(n, d), scale)| {
+                    if d == 0 {
+                        0.0
+                    } else {
+                        n as f64 / d as f64 / scale
+                    }
+                })
+                .sum::<f64>();
+            let sign = if text(&gps_ifd, ref_tag).as_deref() == Some(negative) {
+                -1.0
+            } else {
+                1.0
+            };
+            Some(sign * value)
+        };
+
+        let gps = degrees(TAG_LATITUDE, TAG_LATITUDE_REF, "S").zip(degrees(
+            TAG_LONGITUDE,
+            TAG_LONGITUDE_REF,
+            "W",
+        ));
+        let below_sea_level = find(&gps_ifd, TAG_ALTITUDE_REF)
+            .and_then(|e| tiff.number(e))
+            .is_some_and(|r| r == 1);
+        let altitude = ratio(&gps_ifd, TAG_ALTITUDE).map(|a| if below_sea_level { -a } else { a });
+
+        Some(Exif {
+            orientation: find(&ifd0, TAG_ORIENTATION)
+                .and_then(|e| tiff.number(e))
+                .map(|o| o as u16),
+            captured: text(&exif_ifd, TAG_DATE_TIME_ORIGINAL)
+                .or_else(|| text(&ifd0, TAG_DATE_TIME))
+                .and_then(|date| normalize_date(&date)),
+            make: text(&ifd0, TAG_MAKE),
+            model: text(&ifd0, TAG_MODEL),
+            lens: text(&exif_ifd, TAG_LENS_MODEL),
+            exposure_time: find(&exif_ifd, TAG_EXPOSURE_TIME)
+                .and_then(|e| tiff.rationals(e)?.first().copied())
+                .filter(|&(_, d)| d != 0),
+            f_number: ratio(&exif_ifd, TAG_F_NUMBER),
+            iso: find(&exif_ifd, TAG_ISO).and_then(|e| tiff.number(e)),
+            focal_length: ratio(&exif_ifd, TAG_FOCAL_LENGTH),
+            gps,
+            altitude,
+        })
+    }
+
+    /// How the stored pixels have to be turned to display upright
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+            .and_then(|o| Orientation::from_exif(o as u8))
+            .unwrap_or(Orientation::NoTransforms)
+    }
+
+    /// Camera make and model, without the make repeated when the model has it
+    pub fn camera(&self) -> Option<String> {
+        match (&self.make, &self.model) {
+            (Some(make), Some(model)) if model.starts_with(make.as_str()) => Some(model.clone()),
+            (Some(make), Some(model)) => Some(format!("{} {}", make, model)),
+            (make, model) => make.clone().or_else(|| model.clone()),
+        }
+    }
+
+    /// Labelled, human-readable fields for the image info view
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = Vec::new();
+        if let Some(ref captured) = self.captured {
+            fields.push(("Taken", captured.clone()));
+        }
+        if let Some(camera) = self.camera() {
+            fields.push(("Camera", camera));
+        }
+        if let Some(ref lens) = self.lens {
+            fields.push(("Lens", lens.clone()));
+        }
+        if let Some((n, d)) = self.exposure_time {
+            let exposure = if n >= d {
+                format!("{} s", trim_number(n as f64 / d as f64))
+            } else {
+                format!("1/{} s", trim_number(d as f64 / n.max(1) as f64))
+            };
+            fields.push(("Exposure", exposure));
+        }
+        if let Some(f) = self.f_number {
+            fields.push(("Aperture", format!("f/{}", trim_number(f))));
+        }
+        if let Some(iso) = self.iso {
+            fields.push(("ISO", iso.to_string()));
+        }
+        if let Some(focal) = self.focal_length {
+            fields.push(("Focal length", format!("{} mm", trim_number(focal))));
+        }
+        if let Some((lat, lon)) = self.gps {
+            let ns = if lat < 0.0 { 'S' } else { 'N' };
+            let ew = if lon < 0.0 { 'W' } else { 'E' };
+            fields.push((
+                "Location",
+                format!("{:.5}° {}, {:.5}° {}", lat.abs(), ns, lon.abs(), ew),
+            ));
+        }
+        if let Some(altitude) = self.altitude {
+            fields.push(("Altitude", format!("{:.0} m", altitude)));
+        }
+        fields
+    }
+}
+
+/// An image file's pixel size and EXIF data
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImageInfo {
+    /// Size of the stored pixels, before orientation
+    pub width: u32,
+    pub height: u32,
+    /// Empty when the file has no EXIF block
+    pub exif: Exif,
+}
+
+impl ImageInfo {
+    /// Labelled fields for the info view, starting with the upright size
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let (width, height) = if is_sideways(self.exif.orientation()) {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        let mut fields = vec![("Dimensions", format!("{} × {}", width, height))];
+        fields.extend(self.exif.fields());
+        fields
+    }
+}
+
+/// Read an image's size and EXIF data from its header
+pub fn read(path: &Path) -> Option<ImageInfo> {
+    let mut decoder = ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
+        .ok()?;
+    let (width, height) = decoder.dimensions();
+    let exif = decoder
+        .exif_metadata()
+        .ok()
+        .flatten()
+        .and_then(|data| Exif::parse(&data))
+        .unwrap_or_default();
+    Some(ImageInfo {
+        width,
+        height,
+        exif,
+    })
+}
+
+/// Files read so far, with the modification time they were read at
+static INFO_CACHE: Lazy<Mutex<HashMap<PathBuf, CachedInfo>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+type CachedInfo = (Option<SystemTime>, Option<ImageInfo>);
+
+/// [`read`], cached until the file changes
+pub fn info(path: &Path) -> Option<ImageInfo> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some((_, info)) = INFO_CACHE
+        .lock()
+        .get(path)
+        .filter(|(at, _)| *at == modified)
+    {
+        return info.clone();
+    }
+    let info = read(path);
+    INFO_CACHE
+        .lock()
+        .insert(path.to_path_buf(), (modified, info.clone()));
+    info
+}
+
+/// When an image was taken, as `YYYY-MM-DD HH:MM:SS`
+pub fn capture_date(path: &Path) -> Option<String> {
+    info(path)?.exif.captured
+}
+
+/// Capture dates of the dated images in `images`, by item ID. This reads
+/// every file, so it belongs on a background thread.
+pub fn capture_dates(images: &[(u64, PathBuf)]) -> HashMap<u64, String> {
+    images
+        .iter()
+        .filter_map(|(id, path)| Some((*id, capture_date(path)?)))
+        .collect()
+}
+
+/// Whether an orientation swaps width and height
+pub fn is_sideways(orientation: Orientation) -> bool {
+    matches!(
+        orientation,
+        Orientation::Rotate90
+            | Orientation::Rotate270
+            | Orientation::Rotate90FlipH
+            | Orientation::Rotate270FlipH
+    )
+}
+
+/// `2024:05:04 14:22:01` as `2024-05-04 14:22:01`; blank dates are dropped
+fn normalize_date(date: &str) -> Option<String> {
+    let date = date.trim();
+    let bytes = date.as_bytes();
+    let valid = bytes.len() >= 19
+        && bytes[..19].iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b':' || *b == b'-',
+            10 => *b == b' ',
+            13 | 16 => *b == b':',
+            _ => b.is_ascii_digit(),
+        });
+    if !valid || date.starts_with("0000") {
+        return None;
+    }
+    Some(format!("{}-{}-{}", &date[..4], &date[5..7], &date[8..19]))
+}
+
+/// A number without trailing zeros, to one decimal place
+fn trim_number(value: f64) -> String {
+    let text = format!("{:.1}", value);
+    text.strip_suffix(".0").unwrap_or(&text).to_string()
+}
+
+/// A directory entry: tag, value type, value count and where the value is
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    tag: u16,
+    kind: u16,
+    count: u32,
+    at: usize,
+}
+
+fn find(ifd: &[Entry], tag: u16) -> Option<Entry> {
+    ifd.iter().find(|e| e.tag == tag).copied()
+}
+
+/// A TIFF-structured EXIF block in either byte order
+struct Tiff<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let little_endian = match data.get(..4)? {
+            [b'I', b'I', 42, 0] => true,
+            [b'M', b'M', 0, 42] => false,
+            _ => return None,
+        };
+        Some(Self {
+            data,
+            little_endian,
+        })
+    }
+
+    fn u16(&self, at: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(at..at + 2)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, at: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(at..at + 4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    /// The entries of the directory at `offset`
+    fn ifd(&self, offset: usize) -> Option<Vec<Entry>> {
+        let count = self.u16(offset)? as usize;
+        (0..count)
+            .map(|i| {
+                let start = offset + 2 + i * 12;
+                let kind = self.u16(start + 2)?;
+                let count = self.u32(start + 4)?;
+                let size = type_size(kind).saturating_mul(count as usize);
+                // Values of up to four bytes are stored in the entry itself
+                let at = if size <= 4 {
+                    start + 8
+                } else {
+                    self.u32(start + 8)? as usize
+                };
+                Some(Entry {
+                    tag: self.u16(start)?,
+                    kind,
+                    count,
+                    at,
+                })
+            })
+            .collect()
+    }
+
+    /// A BYTE, SHORT or LONG value
+    fn number(&self, entry: Entry) -> Option<u32> {
+        match entry.kind {
+            1 => self.data.get(entry.at).map(|&b| b as u32),
+            3 => self.u16(entry.at).map(u32::from),
+            4 => self.u32(entry.at),
+            _ => None,
+        }
+    }
+
+    /// An ASCII value, trimmed of its terminator and padding
+    fn ascii(&self, entry: Entry) -> Option<String> {
+        if entry.kind != 2 {
+            return None;
+        }
+        let bytes = self.data.get(entry.at..entry.at + entry.count as usize)?;
+        let text = String::from_utf8_lossy(bytes);
+        let text = text.trim_end_matches('\0').trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    /// RATIONAL values as numerator and denominator pairs
+    fn rationals(&self, entry: Entry) -> Option<Vec<(u32, u32)>> {
+        if entry.kind != 5 {
+            return None;
+        }
+        (0..entry.count as usize)
+            .map(|i| {
+                let at = entry.at + i * 8;
+                Some((self.u32(at)?, self.u32(at + 4)?))
+            })
+            .collect()
+    }
+}
+
+/// Bytes per value of a TIFF type
+fn type_size(kind: u16) -> usize {
+    match kind {
+        3 | 8 => 2,
+        4 | 9 | 11 => 4,
+        5 | 10 | 12 => 8,
+        _ => 1,
+    }
+}
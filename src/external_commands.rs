@@ -0,0 +1,401 @@
+//! External commands - user-defined shell commands that operate on the selection.
+//!
+//! Commands are declared in the user's own settings file (project and board
+//! settings can't add them) and show up in the command palette:
+//!
+//! ```json
+//! "external_commands": [
+//!   { "name": "Upscale Images", "command": "upscale-board-images", "item_types": ["image"], "timeout_secs": 600 },
+//!   { "name": "Open in IDE", "command": "jq -r '.[].content.Code.path' | xargs code", "item_types": ["code"] }
+//! ]
+//! ```
+//!
+//! A command runs through the shell on its own thread, with the selected
+//! `CanvasItem`s it applies to written to its stdin as a JSON array. It's killed,
+//! along with anything it started, if it runs longer than `timeout_secs` (two
+//! minutes by default) or is cancelled from the command palette. It may print a JSON object on
+//! stdout describing items to add or modify; empty output changes nothing.
+//! Output with non-finite or non-positive geometry, or that updates an item
+//! twice, is rejected; other bad properties are fixed as when loading a board:
+//!
+//! ```json
+//! {
+//!   "add": [{ "content": { "Image": "/tmp/upscaled.png" } }],
+//!   "update": [{ "id": 3, "position": [0, 0], "size": [400, 300], "content": { "Text": "Done" } }]
+//! }
+//! ```
+
+use crate::command_registry::{Command, CommandRegistry};
+use crate::error::ExternalCommandError;
+use crate::types::{CanvasItem, ItemContent};
+use crate::validation::{ValidationConstraints, validate_item, validate_items};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::process::{Child, Command as Process, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Registry IDs of external commands start with this
+pub const ID_PREFIX: &str = "external:";
+
+/// How long a command may run when its config doesn't say
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How often a running command is checked for exit, timeout or cancellation
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// An external command as declared in settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalCommandConfig {
+    /// Name shown in the command palette
+    pub name: String,
+    /// Shell command line to run
+    pub command: String,
+    /// Item types the command applies to (e.g. "image", "code"); empty for any
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub item_types: Vec<String>,
+    /// Seconds the command may run before it's killed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl ExternalCommandConfig {
+    /// Whether the command applies to items with this content
+    pub fn applies_to(&self, content: &ItemContent) -> bool {
+        self.item_types.is_empty()
+            || self
+                .item_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(content.type_name()))
+    }
+
+    /// How long the command may run before it's killed
+    pub fn timeout(&self) -> Duration {
+        self.timeout_secs
+            .map_or(DEFAULT_TIMEOUT, Duration::from_secs)
+    }
+}
+
+/// An item an external command wants added to the board
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewItem {
+    pub content: ItemContent,
+    /// Canvas position; placed beside the selection when omitted
+    #[serde(default)]
+    pub position: Option<(f32, f32)>,
+    /// Size; the content type's default when omitted
+    #[serde(default)]
+    pub size: Option<(f32, f32)>,
+}
+
+/// Changes an external command printed on stdout
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExternalCommandOutput {
+    #[serde(default)]
+    pub add: Vec<NewItem>,
+    /// Replacements for existing items, matched by ID
+    #[serde(default)]
+    pub update: Vec<CanvasItem>,
+}
+
+impl ExternalCommandOutput {
+    /// Parse command output; empty output means no changes
+    pub fn parse(stdout: &str) -> Result<Self, ExternalCommandError> {
+        if stdout.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let output: Self =
+            serde_json::from_str(stdout).map_err(ExternalCommandError::InvalidOutput)?;
+        output.validated()
+    }
+
+    /// Reject geometry the board can't hold and duplicate updates, and fix
+    /// other invalid properties the way loading a board does
+    fn validated(mut self) -> Result<Self, ExternalCommandError> {
+        let mut ids = HashSet::new();
+        for item in &self.update {
+            if !ids.insert(item.id) {
+                return Err(ExternalCommandError::InvalidItem(format!(
+                    "item {} is updated more than once",
+                    item.id
+                )));
+            }
+            check_geometry(Some(item.position), Some(item.size))?;
+        }
+        for new in &self.add {
+            check_geometry(new.position, new.size)?;
+        }
+
+        validate_items(&mut self.update);
+        let constraints = ValidationConstraints::default();
+        for new in &mut self.add {
+            // Only the content and any size given are the command's; the board
+            // places and sizes the rest
+            let mut item = CanvasItem {
+                id: 0,
+                position: (0.0, 0.0),
+                size: new
+                    .size
+                    .unwrap_or((constraints.min_item_width, constraints.min_item_height)),
+                content: new.content.clone(),
+            };
+            validate_item(&mut item, &constraints);
+            new.content = item.content;
+            if new.size.is_some() {
+                new.size = Some(item.size);
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.update.is_empty()
+    }
+}
+
+/// Positions must be finite and sizes finite and positive
+fn check_geometry(
+    position: Option<(f32, f32)>,
+    size: Option<(f32, f32)>,
+) -> Result<(), ExternalCommandError> {
+    if let Some((x, y)) = position.filter(|&(x, y)| !x.is_finite() || !y.is_finite()) {
+        return Err(ExternalCommandError::InvalidItem(format!(
+            "position ({}, {}) isn't finite",
+            x, y
+        )));
+    }
+    if let Some((w, h)) =
+        size.filter(|&(w, h)| !(w.is_finite() && h.is_finite() && w > 0.0 && h > 0.0))
+    {
+        return Err(ExternalCommandError::InvalidItem(format!(
+            "size ({}, {}) isn't finite and positive",
+            w, h
+        )));
+    }
+    Ok(())
+}
+
+fn shell(command: &str) -> Process {
+    #[cfg(windows)]
+    {
+        let mut process = Process::new("cmd");
+        process.args(["/C", command]);
+        process
+    }
+    #[cfg(not(windows))]
+    {
+        use std::os::unix::process::CommandExt;
+
+        let mut process = Process::new("sh");
+        process.args(["-c", command]);
+        // Lead a new process group so the command can be stopped along with
+        // anything it starts in the background
+        process.process_group(0);
+        process
+    }
+}
+
+/// Kill a command started by `shell` and every process it started
+fn kill(child: &mut Child) {
+    #[cfg(windows)]
+    {
+        let _ = Process::new("taskkill")
+            .args(["/F", "/T", "/PID", &child.id().to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+    #[cfg(not(windows))]
+    {
+        // The shell leads its process group, so the group's ID is its PID.
+        // SAFETY: kill only sends a signal and has no memory-safety requirements.
+        unsafe {
+            libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Why a running command should be stopped, if it should
+fn stop_reason(
+    started: Instant,
+    timeout: Duration,
+    cancel: &AtomicBool,
+) -> Option<ExternalCommandError> {
+    if cancel.load(Ordering::Relaxed) {
+        Some(ExternalCommandError::Cancelled)
+    } else if started.elapsed() >= timeout {
+        Some(ExternalCommandError::TimedOut {
+            seconds: timeout.as_secs(),
+        })
+    } else {
+        None
+    }
+}
+
+/// Run `command` with `items` as JSON on stdin, blocking until it exits and
+/// its output is closed.
+///
+/// The command is killed once it has run for `timeout`, or when `cancel` is set.
+pub fn run(
+    command: &str,
+    items: &[CanvasItem],
+    timeout: Duration,
+    cancel: &AtomicBool,
+) -> Result<ExternalCommandOutput, ExternalCommandError> {
+    let input = serde_json::to_vec(items).map_err(ExternalCommandError::Serialize)?;
+
+    let mut child = shell(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|source| ExternalCommandError::SpawnFailed {
+            command: command.to_string(),
+            source,
+        })?;
+
+    // Write and read from other threads so a command that prints before
+    // reading all of stdin can't deadlock on a full pipe. Commands needn't
+    // read stdin, so a broken pipe is fine.
+    if let Some(mut stdin) = child.stdin.take() {
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    let stdout = child.stdout.take().map(read_to_end);
+    let stderr = child.stderr.take().map(read_to_end);
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if let Some(error) = stop_reason(started, timeout, cancel) {
+            kill(&mut child);
+            return Err(error);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+
+    // Something the command left running in the background can hold its
+    // output open, so the readers get the rest of the same time limit
+    while ![&stdout, &stderr]
+        .iter()
+        .all(|reader| reader.as_ref().is_none_or(JoinHandle::is_finished))
+    {
+        if let Some(error) = stop_reason(started, timeout, cancel) {
+            kill(&mut child);
+            return Err(error);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    let stdout = joined(stdout);
+    if !status.success() {
+        return Err(ExternalCommandError::Failed {
+            status: status.to_string(),
+            stderr: String::from_utf8_lossy(&joined(stderr)).trim().to_string(),
+        });
+    }
+    ExternalCommandOutput::parse(&String::from_utf8_lossy(&stdout))
+}
+
+/// Read a pipe to its end on another thread
+fn read_to_end(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// What a pipe reader thread read, or nothing
+fn joined(reader: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    reader
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default()
+}
+
+/// A registered external command
+pub struct ExternalCommand {
+    id: String,
+    config: ExternalCommandConfig,
+}
+
+impl ExternalCommand {
+    pub fn new(config: ExternalCommandConfig) -> Self {
+        Self {
+            id: format!("{}{}", ID_PREFIX, config.name),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &ExternalCommandConfig {
+        &self.config
+    }
+}
+
+impl Command for ExternalCommand {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some(&self.config.command)
+    }
+
+    fn category(&self) -> &str {
+        "External"
+    }
+
+    fn is_enabled(&self, app: &crate::app::Humanboard) -> bool {
+        app.board.as_ref().is_some_and(|board| {
+            app.selected_items
+                .iter()
+                .filter_map(|&id| board.get_item(id))
+                .any(|item| self.config.applies_to(&item.content))
+        })
+    }
+
+    fn execute(
+        &self,
+        app: &mut crate::app::Humanboard,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<crate::app::Humanboard>,
+    ) {
+        app.run_external_command(&self.config, cx);
+    }
+
+    fn keywords(&self) -> &[&str] {
+        &["external", "script", "run"]
+    }
+}
+
+/// Replace the external commands in `registry` with the ones in `configs`
+pub fn register_all(registry: &mut CommandRegistry, configs: &[ExternalCommandConfig]) {
+    let stale: Vec<String> = registry
+        .ids()
+        .filter(|id| id.starts_with(ID_PREFIX))
+        .map(str::to_string)
+        .collect();
+    for id in stale {
+        registry.unregister(&id);
+    }
+
+    for config in configs {
+        if config.name.trim().is_empty() || config.command.trim().is_empty() {
+            warn!("Skipping external command without a name or command: {:?}", config);
+            continue;
+        }
+        registry.register(ExternalCommand::new(config.clone()));
+    }
+}
@@ -65,6 +65,8 @@ pub enum FocusContext {
     Canvas,
     /// Canvas with active input (textbox editing) - subset of shortcuts active
     CanvasInputActive,
+    /// Canvas while presenting - only presentation navigation is active
+    Presentation,
 }
 
 /// Key context string constants for use in keybindings.
@@ -88,6 +90,8 @@ impl FocusContext {
     pub const KEY_CANVAS: &'static str = "Canvas";
     /// Key context for Canvas with active input
     pub const KEY_CANVAS_INPUT_ACTIVE: &'static str = "CanvasInputActive";
+    /// Key context for presentation mode
+    pub const KEY_PRESENTATION: &'static str = "Presentation";
 }
 
 impl FocusContext {
@@ -103,6 +107,7 @@ impl FocusContext {
             FocusContext::Landing => Self::KEY_LANDING,
             FocusContext::Canvas => Self::KEY_CANVAS,
             FocusContext::CanvasInputActive => Self::KEY_CANVAS_INPUT_ACTIVE,
+            FocusContext::Presentation => Self::KEY_PRESENTATION,
         }
     }
 
@@ -118,6 +123,7 @@ impl FocusContext {
             FocusContext::Landing => 3,
             FocusContext::Canvas => 2,
             FocusContext::CanvasInputActive => 1, // Same level as Canvas but distinct
+            FocusContext::Presentation => 2,      // Replaces the canvas while presenting
         }
     }
 
@@ -132,6 +138,7 @@ impl FocusContext {
             FocusContext::Landing,
             FocusContext::Canvas,
             FocusContext::CanvasInputActive,
+            FocusContext::Presentation,
         ]
     }
 
@@ -271,7 +278,9 @@ impl FocusManager {
     /// Get the FocusHandle for a specific context.
     pub fn handle_for(&self, context: FocusContext) -> &FocusHandle {
         match context {
-            FocusContext::Canvas | FocusContext::CanvasInputActive => &self.canvas,
+            FocusContext::Canvas | FocusContext::CanvasInputActive | FocusContext::Presentation => {
+                &self.canvas
+            }
             FocusContext::CommandPalette => &self.command_palette,
             FocusContext::TextboxEditing => &self.textbox_editing,
             FocusContext::CodeEditor => &self.code_editor,
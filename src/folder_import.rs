@@ -0,0 +1,272 @@
+//! Folder import - lays out a dropped or opened folder as a structured board.
+//!
+//! The folder is walked recursively. Its files are placed in a grid inside a
+//! frame labelled with the folder's name, and each subfolder becomes a nested
+//! frame below them. A drop imports at most `max_items` files; the rest are
+//! left out and reported. Options come from the `folder_import` setting:
+//!
+//! ```json
+//! "folder_import": { "extensions": ["png", "jpg", "md"], "max_depth": 2, "sort": "modified", "max_items": 200 }
+//! ```
+
+use crate::types::{ItemContent, ShapeType};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Size of a grid cell; larger items are scaled down to fit
+pub const CELL_SIZE: (f32, f32) = (320.0, 240.0);
+/// Space between cells and between sibling frames
+pub const GAP: f32 = 24.0;
+/// Space between a frame's border and its contents
+pub const PADDING: f32 = 24.0;
+/// Room left above a nested frame for its title
+const TITLE_SPACE: f32 = 32.0;
+
+/// An item to add: its content, position and size
+pub type Placed = (ItemContent, (f32, f32), (f32, f32));
+
+/// Order of files (and folders) within a folder
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FolderSort {
+    /// Alphabetical, ignoring case
+    #[default]
+    Name,
+    /// Most recently modified first
+    Modified,
+    /// Grouped by extension, then by name
+    Type,
+}
+
+/// Options for importing a folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FolderImportOptions {
+    /// Extensions to import, without the dot; empty imports every file
+    pub extensions: Vec<String>,
+    /// Levels of subfolders to walk; 0 imports only the folder's own files
+    pub max_depth: usize,
+    pub sort: FolderSort,
+    /// Most files a single drop imports
+    pub max_items: usize,
+}
+
+impl Default for FolderImportOptions {
+    fn default() -> Self {
+        Self {
+            extensions: Vec::new(),
+            max_depth: 3,
+            sort: FolderSort::Name,
+            max_items: 500,
+        }
+    }
+}
+
+impl FolderImportOptions {
+    /// Whether a file passes the extension filter
+    pub fn accepts(&self, path: &Path) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+            return false;
+        };
+        self.extensions
+            .iter()
+            .any(|wanted| wanted.trim_start_matches('.').eq_ignore_ascii_case(ext))
+    }
+}
+
+/// A walked folder with its files and non-empty subfolders, sorted
+#[derive(Debug, Clone, PartialEq)]
+pub struct FolderTree {
+    pub name: String,
+    pub files: Vec<PathBuf>,
+    pub folders: Vec<FolderTree>,
+}
+
+impl FolderTree {
+    /// Number of files in this folder and all its subfolders
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+            + self
+                .folders
+                .iter()
+                .map(FolderTree::file_count)
+                .sum::<usize>()
+    }
+
+    /// Keep only the first `max` files, in layout order, dropping subfolders
+    /// left empty. Returns how many files were dropped.
+    pub fn limit_files(&mut self, max: usize) -> usize {
+        let mut dropped = self.files.len().saturating_sub(max);
+        self.files.truncate(max);
+        let mut remaining = max - self.files.len();
+        for folder in &mut self.folders {
+            dropped += folder.limit_files(remaining);
+            remaining -= folder.file_count();
+        }
+        self.folders.retain(|folder| folder.file_count() > 0);
+        dropped
+    }
+}
+
+/// Walk `root`, skipping hidden entries and files the options filter out.
+///
+/// Unreadable subfolders are skipped; only an unreadable root is an error.
+pub fn scan(root: &Path, options: &FolderImportOptions) -> io::Result<FolderTree> {
+    scan_dir(root, 0, options)
+}
+
+fn scan_dir(dir: &Path, depth: usize, options: &FolderImportOptions) -> io::Result<FolderTree> {
+    let mut files = Vec::new();
+    let mut folders = Vec::new();
+
+    for entry in fs::read_dir(dir)?.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        // Symlinked folders aren't followed, so links can't loop
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        if is_dir {
+            if depth >= options.max_depth {
+                continue;
+            }
+            match scan_dir(&path, depth + 1, options) {
+                Ok(tree) if tree.file_count() > 0 => folders.push((path, tree)),
+                Ok(_) => {}
+                Err(e) => warn!("Skipping unreadable folder {:?}: {}", path, e),
+            }
+        } else if path.is_file() && options.accepts(&path) {
+            files.push(path);
+        }
+    }
+
+    sort_paths(&mut files, options.sort, |path| path);
+    sort_paths(&mut folders, options.sort, |(path, _)| path);
+
+    Ok(FolderTree {
+        name: display_name(dir),
+        files,
+        folders: folders.into_iter().map(|(_, tree)| tree).collect(),
+    })
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Sort by name, then stably by the chosen key so ties stay alphabetical
+fn sort_paths<T>(entries: &mut [T], sort: FolderSort, path_of: impl Fn(&T) -> &PathBuf) {
+    entries.sort_by_cached_key(|e| display_name(path_of(e)).to_lowercase());
+    match sort {
+        FolderSort::Name => {}
+        FolderSort::Modified => entries.sort_by_cached_key(|e| {
+            Reverse(fs::metadata(path_of(e)).and_then(|m| m.modified()).ok())
+        }),
+        FolderSort::Type => entries.sort_by_cached_key(|e| {
+            path_of(e)
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+                .unwrap_or_default()
+        }),
+    }
+}
+
+/// Lay out a walked folder with its top-left corner at `origin`.
+///
+/// Returns each item's content, position and size, frames before the items
+/// inside them so they draw behind. The first entry is the folder's own frame.
+/// `content_for` turns a file into an item, e.g. after copying it.
+pub fn layout(
+    tree: &FolderTree,
+    origin: (f32, f32),
+    content_for: &mut impl FnMut(&Path) -> ItemContent,
+) -> Vec<Placed> {
+    let mut placed = Vec::new();
+    place(tree, origin, content_for, &mut placed);
+    placed
+}
+
+/// Place a folder's frame and contents, returning the frame size
+fn place(
+    tree: &FolderTree,
+    origin: (f32, f32),
+    content_for: &mut impl FnMut(&Path) -> ItemContent,
+    placed: &mut Vec<Placed>,
+) -> (f32, f32) {
+    let frame_index = placed.len();
+    placed.push((frame(&tree.name), origin, (0.0, 0.0)));
+
+    let left = origin.0 + PADDING;
+    let mut y = origin.1 + PADDING;
+    let mut width: f32 = 0.0;
+
+    let columns = grid_columns(tree.files.len());
+    for (i, path) in tree.files.iter().enumerate() {
+        let content = content_for(path);
+        let size = fit(content.default_size(), CELL_SIZE);
+        let cell = (
+            left + (i % columns) as f32 * (CELL_SIZE.0 + GAP),
+            y + (i / columns) as f32 * (CELL_SIZE.1 + GAP),
+        );
+        // Centre the item in its cell
+        let position = (
+            cell.0 + (CELL_SIZE.0 - size.0) / 2.0,
+            cell.1 + (CELL_SIZE.1 - size.1) / 2.0,
+        );
+        placed.push((content, position, size));
+    }
+    if !tree.files.is_empty() {
+        let rows = tree.files.len().div_ceil(columns);
+        width = columns as f32 * (CELL_SIZE.0 + GAP) - GAP;
+        y += rows as f32 * (CELL_SIZE.1 + GAP) - GAP;
+    }
+
+    // Subfolders go below the files, in rows of frames
+    let columns = grid_columns(tree.folders.len());
+    for row in tree.folders.chunks(columns) {
+        y += TITLE_SPACE;
+        let mut x = left;
+        let mut row_height: f32 = 0.0;
+        for folder in row {
+            let size = place(folder, (x, y), content_for, placed);
+            x += size.0 + GAP;
+            row_height = row_height.max(size.1);
+        }
+        width = width.max(x - GAP - left);
+        y += row_height;
+    }
+
+    let size = (width + PADDING * 2.0, y - origin.1 + PADDING);
+    placed[frame_index].2 = size;
+    size
+}
+
+/// Columns for a roughly square grid of `count` cells
+fn grid_columns(count: usize) -> usize {
+    ((count as f32).sqrt().ceil() as usize).max(1)
+}
+
+/// Scale `size` down to fit in `max`, keeping its aspect ratio
+fn fit(size: (f32, f32), max: (f32, f32)) -> (f32, f32) {
+    let scale = (max.0 / size.0).min(max.1 / size.1).min(1.0);
+    (size.0 * scale, size.1 * scale)
+}
+
+fn frame(label: &str) -> ItemContent {
+    ItemContent::Shape {
+        shape_type: ShapeType::Frame,
+        fill_color: None,
+        border_color: String::new(),
+        border_width: 2.0,
+        label: Some(label.to_string()),
+    }
+}
@@ -0,0 +1,168 @@
+//! Fuzzy matching - subsequence scoring with match positions for highlighting.
+//!
+//! A query matches a candidate when its characters appear in order anywhere
+//! in the candidate, ignoring case and whitespace in the query ("zmi" matches
+//! "Zoom In"). Among the possible alignments the best-scoring one is chosen:
+//! matches at the start of words and runs of consecutive matches score higher,
+//! skipped characters cost a little.
+
+use std::ops::Range;
+
+/// Base score for each matched character
+const SCORE_MATCH: i32 = 16;
+/// Bonus for matching the first character of the candidate
+const BONUS_FIRST_CHAR: i32 = 8;
+/// Bonus for matching the first character of a word
+const BONUS_WORD_START: i32 = 24;
+/// Bonus for matching an uppercase letter after a lowercase one (camelCase)
+const BONUS_CAMEL_CASE: i32 = 16;
+/// Bonus for matching right after the previous matched character
+const BONUS_CONSECUTIVE: i32 = 12;
+/// Cost of each candidate character skipped between two matches
+const PENALTY_GAP: i32 = 1;
+/// Cost of each candidate character before the first match, up to a cap
+const PENALTY_LEADING: i32 = 1;
+const MAX_LEADING_PENALTY: i32 = 8;
+
+/// A successful fuzzy match
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better; only comparable between matches of the same query
+    pub score: i32,
+    /// Byte offsets of the matched characters in the candidate, ascending
+    pub positions: Vec<usize>,
+}
+
+impl FuzzyMatch {
+    /// Shift the positions by `offset` bytes, for a candidate shown after a prefix
+    pub fn offset(mut self, offset: usize) -> Self {
+        for position in &mut self.positions {
+            *position += offset;
+        }
+        self
+    }
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Bonus for matching the character at `index`, based on what precedes it
+fn position_bonus(chars: &[(usize, char)], index: usize) -> i32 {
+    let Some(prev) = index.checked_sub(1).map(|i| chars[i].1) else {
+        return BONUS_WORD_START + BONUS_FIRST_CHAR;
+    };
+    let current = chars[index].1;
+
+    if !prev.is_alphanumeric() && current.is_alphanumeric() {
+        BONUS_WORD_START
+    } else if prev.is_lowercase() && current.is_uppercase() {
+        BONUS_CAMEL_CASE
+    } else {
+        0
+    }
+}
+
+/// Fuzzy-match `query` against `candidate`.
+///
+/// An empty query matches everything with a score of zero.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(fold)
+        .collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch::default());
+    }
+
+    let chars: Vec<(usize, char)> = candidate.char_indices().collect();
+    let (m, n) = (query.len(), chars.len());
+    if m > n {
+        return None;
+    }
+    let folded: Vec<char> = chars.iter().map(|&(_, c)| fold(c)).collect();
+
+    // scores[i * n + j]: best score with query[i] matched at candidate[j];
+    // from[i * n + j]: where query[i - 1] was matched on that best path
+    let mut scores = vec![None::<i32>; m * n];
+    let mut from = vec![0usize; m * n];
+
+    for j in 0..n {
+        if folded[j] == query[0] {
+            let leading = (j as i32 * PENALTY_LEADING).min(MAX_LEADING_PENALTY);
+            scores[j] = Some(SCORE_MATCH + position_bonus(&chars, j) - leading);
+        }
+    }
+
+    for i in 1..m {
+        let (prev_row, row) = scores.split_at_mut(i * n);
+        let prev_row = &prev_row[(i - 1) * n..];
+        let row = &mut row[..n];
+
+        // Best of prev_row[k] + PENALTY_GAP * k over k <= j - 2, so a gap
+        // from k to j costs PENALTY_GAP * (j - 1 - k)
+        let mut best_gapped: Option<(i32, usize)> = None;
+
+        for j in i..n {
+            if j >= 2
+                && let Some(score) = prev_row[j - 2]
+            {
+                let adjusted = score + PENALTY_GAP * (j as i32 - 2);
+                if best_gapped.is_none_or(|(best, _)| adjusted > best) {
+                    best_gapped = Some((adjusted, j - 2));
+                }
+            }
+
+            if folded[j] != query[i] {
+                continue;
+            }
+
+            let consecutive = prev_row[j - 1].map(|score| (score + BONUS_CONSECUTIVE, j - 1));
+            let gapped = best_gapped
+                .map(|(adjusted, k)| (adjusted - PENALTY_GAP * (j as i32 - 1), k));
+            let best = match (consecutive, gapped) {
+                (Some(a), Some(b)) => Some(if a.0 >= b.0 { a } else { b }),
+                (a, b) => a.or(b),
+            };
+
+            if let Some((score, k)) = best {
+                row[j] = Some(score + SCORE_MATCH + position_bonus(&chars, j));
+                from[i * n + j] = k;
+            }
+        }
+    }
+
+    let last_row = &scores[(m - 1) * n..];
+    let (mut j, score) = last_row
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|s| (j, s)))
+        .max_by_key(|&(j, score)| (score, std::cmp::Reverse(j)))?;
+
+    let mut positions = vec![0; m];
+    for i in (0..m).rev() {
+        positions[i] = chars[j].0;
+        if i > 0 {
+            j = from[i * n + j];
+        }
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Merge matched byte positions in `text` into ranges of whole characters
+pub fn highlight_ranges(text: &str, positions: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for &start in positions {
+        let Some(c) = text.get(start..).and_then(|rest| rest.chars().next()) else {
+            continue;
+        };
+        let end = start + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == start => last.end = end,
+            _ => ranges.push(start..end),
+        }
+    }
+    ranges
+}
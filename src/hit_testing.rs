@@ -13,10 +13,13 @@
 //! - **Item body**: The main clickable area of an item
 //! - **Resize corner**: Bottom-right corner for resizing
 //! - **Shape border**: For shape items, only the border is clickable
+//! - **Stroke path**: Freehand strokes are only clickable along their path
 //! - **Splitter**: The divider between canvas and preview panel
 
 use crate::constants::{DOCK_WIDTH, FOOTER_HEIGHT, HEADER_HEIGHT, MIN_HIT_AREA, SPLITTER_WIDTH};
-use crate::types::ItemContent;
+use crate::shapes::hit_outline;
+use crate::strokes::hit_stroke;
+use crate::types::{ItemContent, ShapeType};
 use gpui::*;
 
 /// The result of a hit test on the canvas.
@@ -99,8 +102,17 @@ pub struct HitTestItem {
 pub enum HitTestContentType {
     /// Standard item (images, PDFs, etc.)
     Standard,
-    /// Shape with border - only border is clickable
-    Shape { border_width: f32 },
+    /// Shape with border - only the outline is clickable
+    Shape {
+        shape_type: ShapeType,
+        border_width: f32,
+    },
+    /// Freehand stroke - only the path itself is clickable
+    Stroke {
+        /// Points normalised to the item bounds
+        points: Vec<(f32, f32)>,
+        width: f32,
+    },
     /// Arrow or line
     Arrow,
     /// Text box
@@ -111,9 +123,33 @@ impl HitTestContentType {
     /// Create from ItemContent reference.
     pub fn from_content(content: &ItemContent) -> Self {
         match content {
-            ItemContent::Shape { border_width, .. } => HitTestContentType::Shape {
+            // Frames hold other items, so they stay click-through even with a title
+            ItemContent::Shape {
+                shape_type: ShapeType::Frame,
+                border_width,
+                ..
+            } => HitTestContentType::Shape {
+                shape_type: ShapeType::Frame,
                 border_width: *border_width,
             },
+            // A labelled shape or sticky note is clickable anywhere so its text can be reached
+            ItemContent::Shape { label: Some(_), .. }
+            | ItemContent::Shape {
+                shape_type: ShapeType::StickyNote,
+                ..
+            } => HitTestContentType::Standard,
+            ItemContent::Shape {
+                shape_type,
+                border_width,
+                ..
+            } => HitTestContentType::Shape {
+                shape_type: *shape_type,
+                border_width: *border_width,
+            },
+            ItemContent::Stroke { points, width, .. } => HitTestContentType::Stroke {
+                points: points.clone(),
+                width: *width,
+            },
             ItemContent::Arrow { .. } => HitTestContentType::Arrow,
             ItemContent::TextBox { .. } => HitTestContentType::TextBox,
             _ => HitTestContentType::Standard,
@@ -262,15 +298,21 @@ impl HitTester {
             });
         }
 
-        // Special handling for shapes - only hit on border
-        if let HitTestContentType::Shape { border_width } = item.content_type {
+        // Special handling for shapes - only hit on the outline
+        if let HitTestContentType::Shape {
+            shape_type,
+            border_width,
+        } = item.content_type
+        {
             let border_hit_area = (border_width * zoom).max(self.config.min_border_hit_area);
-            let near_left = mx - scaled_x < border_hit_area;
-            let near_right = (scaled_x + scaled_width) - mx < border_hit_area;
-            let near_top = my - scaled_y < border_hit_area;
-            let near_bottom = (scaled_y + scaled_height) - my < border_hit_area;
-
-            if near_left || near_right || near_top || near_bottom {
+            let on_outline = hit_outline(
+                shape_type,
+                (scaled_width, scaled_height),
+                (mx - scaled_x, my - scaled_y),
+                border_hit_area,
+            );
+
+            if on_outline {
                 return Some(ItemHit {
                     item_id: item.id,
                     area: ItemHitArea::ShapeBorder,
@@ -281,6 +323,18 @@ impl HitTester {
             }
         }
 
+        // Strokes are hit along their path so items underneath stay reachable
+        if let HitTestContentType::Stroke { points, width } = &item.content_type {
+            let tolerance = (width * zoom / 2.0).max(self.config.min_border_hit_area);
+            let local: Vec<(f32, f32)> = points
+                .iter()
+                .map(|&(x, y)| (x * scaled_width, y * scaled_height))
+                .collect();
+            if !hit_stroke(&local, (mx - scaled_x, my - scaled_y), tolerance) {
+                return None;
+            }
+        }
+
         // Standard item body hit
         Some(ItemHit {
             item_id: item.id,
@@ -0,0 +1,508 @@
+//! Non-destructive image edits - crop, quarter-turn rotation and flips.
+//!
+//! An [`ImageEdit`] is stored alongside an image item's path and applied at
+//! render time; the source file is never touched. The displayed image is
+//! built in a fixed order: the source is flipped, then rotated clockwise in
+//! quarter turns (the *oriented* image), then cropped.
+//!
+//! Orientation is baked into a cached PNG in the temp directory, written in
+//! the background, so the canvas only has to render an ordinary image.
+//! Cropping is done while rendering,
+//! and [`bake`] writes the fully edited result out as a new file. Imported
+//! photos start from their EXIF orientation, so they show upright.
+
+use crate::error::MediaError;
+use image::metadata::Orientation;
+use image::{DynamicImage, GenericImageView};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tempfile::NamedTempFile;
+use tracing::{debug, warn};
+
+/// Smallest crop, as a fraction of the image, so a crop can't collapse
+pub const MIN_CROP: f32 = 0.02;
+
+/// A rectangle in fractions (0-1) of the oriented image
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CropRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl CropRect {
+    /// The whole image
+    pub const FULL: CropRect = CropRect {
+        x: 0.0,
+        y: 0.0,
+        width: 1.0,
+        height: 1.0,
+    };
+
+    /// The rectangle spanned by two corner points, kept inside the image
+    pub fn from_corners(a: (f32, f32), b: (f32, f32)) -> Self {
+        let (x0, x1) = (a.0.min(b.0).clamp(0.0, 1.0), a.0.max(b.0).clamp(0.0, 1.0));
+        let (y0, y1) = (a.1.min(b.1).clamp(0.0, 1.0), a.1.max(b.1).clamp(0.0, 1.0));
+        CropRect {
+            x: x0,
+            y: y0,
+            width: x1 - x0,
+            height: y1 - y0,
+        }
+    }
+
+    /// Whether the rectangle is big enough to crop to
+    pub fn is_usable(&self) -> bool {
+        self.width >= MIN_CROP && self.height >= MIN_CROP
+    }
+
+    /// The part of this rectangle covered by `inner`, given in this
+    /// rectangle's own fractions
+    pub fn compose(&self, inner: CropRect) -> CropRect {
+        CropRect {
+            x: self.x + inner.x * self.width,
+            y: self.y + inner.y * self.height,
+            width: inner.width * self.width,
+            height: inner.height * self.height,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        *self == CropRect::FULL
+    }
+
+    /// The same area after the image turns a quarter clockwise
+    fn rotated_clockwise(&self) -> CropRect {
+        CropRect {
+            x: 1.0 - self.y - self.height,
+            y: self.x,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// The same area after the image turns a quarter counterclockwise
+    fn rotated_counterclockwise(&self) -> CropRect {
+        CropRect {
+            x: self.y,
+            y: 1.0 - self.x - self.width,
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+/// How an image item is cropped, rotated and flipped on the canvas
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageEdit {
+    /// Clockwise quarter turns, 0-3
+    pub quarter_turns: u8,
+    /// Mirror the source left to right
+    pub flip_horizontal: bool,
+    /// Mirror the source top to bottom
+    pub flip_vertical: bool,
+    /// Visible part of the oriented image, `None` for all of it
+    pub crop: Option<CropRect>,
+}
+
+impl ImageEdit {
+    /// The edit that turns stored pixels with an EXIF orientation upright
+    pub fn from_orientation(orientation: Orientation) -> Self {
+        let mut edit = Self::default();
+        match orientation {
+            Orientation::NoTransforms => {}
+            Orientation::Rotate90 => edit.rotate_clockwise(),
+            Orientation::Rotate180 => {
+                edit.rotate_clockwise();
+                edit.rotate_clockwise();
+            }
+            Orientation::Rotate270 => edit.rotate_counterclockwise(),
+            Orientation::FlipHorizontal => edit.flip_horizontal(),
+            Orientation::FlipVertical => edit.flip_vertical(),
+            Orientation::Rotate90FlipH => {
+                edit.rotate_clockwise();
+                edit.flip_horizontal();
+            }
+            Orientation::Rotate270FlipH => {
+                edit.rotate_counterclockwise();
+                edit.flip_horizontal();
+            }
+        }
+        edit
+    }
+
+    /// The edit an image file starts with: upright, going by its EXIF data
+    pub fn from_exif(path: &Path) -> Self {
+        crate::exif::info(path)
+            .map(|info| Self::from_orientation(info.exif.orientation()))
+            .unwrap_or_default()
+    }
+
+    /// True when the image is shown exactly as stored
+    pub fn is_identity(&self) -> bool {
+        !self.is_oriented() && self.crop.is_none()
+    }
+
+    /// True when the image is rotated or flipped
+    pub fn is_oriented(&self) -> bool {
+        !self.quarter_turns.is_multiple_of(4) || self.flip_horizontal || self.flip_vertical
+    }
+
+    /// True when the image is turned on its side, swapping width and height
+    pub fn is_sideways(&self) -> bool {
+        self.quarter_turns % 2 == 1
+    }
+
+    /// The visible part of the oriented image
+    pub fn crop_rect(&self) -> CropRect {
+        self.crop.unwrap_or(CropRect::FULL)
+    }
+
+    /// Turn the displayed image a quarter clockwise
+    pub fn rotate_clockwise(&mut self) {
+        self.quarter_turns = (self.quarter_turns + 1) % 4;
+        self.crop = self.crop.map(|c| c.rotated_clockwise());
+    }
+
+    /// Turn the displayed image a quarter counterclockwise
+    pub fn rotate_counterclockwise(&mut self) {
+        self.quarter_turns = (self.quarter_turns + 3) % 4;
+        self.crop = self.crop.map(|c| c.rotated_counterclockwise());
+    }
+
+    /// Mirror the displayed image left to right
+    pub fn flip_horizontal(&mut self) {
+        // Flips apply before rotation, so a sideways image flips the other axis
+        if !self.is_sideways() {
+            self.flip_horizontal = !self.flip_horizontal;
+        } else {
+            self.flip_vertical = !self.flip_vertical;
+        }
+        if let Some(crop) = self.crop.as_mut() {
+            crop.x = 1.0 - crop.x - crop.width;
+        }
+    }
+
+    /// Mirror the displayed image top to bottom
+    pub fn flip_vertical(&mut self) {
+        if !self.is_sideways() {
+            self.flip_vertical = !self.flip_vertical;
+        } else {
+            self.flip_horizontal = !self.flip_horizontal;
+        }
+        if let Some(crop) = self.crop.as_mut() {
+            crop.y = 1.0 - crop.y - crop.height;
+        }
+    }
+
+    /// Narrow the crop to `inner`, given in fractions of the current crop
+    pub fn crop_to(&mut self, inner: CropRect) {
+        let crop = self.crop_rect().compose(inner);
+        self.crop = (!crop.is_full()).then_some(crop);
+    }
+
+    /// Flip and rotate an image, leaving the crop out
+    pub fn orient(&self, image: DynamicImage) -> DynamicImage {
+        let mut image = image;
+        if self.flip_horizontal {
+            image = image.fliph();
+        }
+        if self.flip_vertical {
+            image = image.flipv();
+        }
+        match self.quarter_turns % 4 {
+            1 => image.rotate90(),
+            2 => image.rotate180(),
+            3 => image.rotate270(),
+            _ => image,
+        }
+    }
+
+    /// Apply the whole edit to an image
+    pub fn apply(&self, image: DynamicImage) -> DynamicImage {
+        let image = self.orient(image);
+        let Some(crop) = self.crop else {
+            return image;
+        };
+        let (width, height) = image.dimensions();
+        let x = ((crop.x * width as f32).round() as u32).min(width - 1);
+        let y = ((crop.y * height as f32).round() as u32).min(height - 1);
+        let crop_width = ((crop.width * width as f32).round() as u32).clamp(1, width - x);
+        let crop_height = ((crop.height * height as f32).round() as u32).clamp(1, height - y);
+        image.crop_imm(x, y, crop_width, crop_height)
+    }
+}
+
+/// Where an image shown with "contain" fit sits inside its item, in fractions
+/// of the item's size
+pub fn contain_rect(item_size: (f32, f32), image_size: (f32, f32)) -> CropRect {
+    let scale = (item_size.0 / image_size.0).min(item_size.1 / image_size.1);
+    let width = (image_size.0 * scale / item_size.0).min(1.0);
+    let height = (image_size.1 * scale / item_size.1).min(1.0);
+    CropRect {
+        x: (1.0 - width) / 2.0,
+        y: (1.0 - height) / 2.0,
+        width,
+        height,
+    }
+}
+
+/// The part of an item's bounds covered by `rect`, given in fractions of it
+pub fn sub_bounds(
+    position: (f32, f32),
+    size: (f32, f32),
+    rect: CropRect,
+) -> ((f32, f32), (f32, f32)) {
+    (
+        (position.0 + rect.x * size.0, position.1 + rect.y * size.1),
+        (rect.width * size.0, rect.height * size.1),
+    )
+}
+
+/// Bounds showing all of the oriented image for an item cropped to `crop`,
+/// at the same scale
+pub fn uncropped_bounds(
+    position: (f32, f32),
+    size: (f32, f32),
+    crop: CropRect,
+) -> ((f32, f32), (f32, f32)) {
+    let full = (size.0 / crop.width, size.1 / crop.height);
+    (
+        (position.0 - crop.x * full.0, position.1 - crop.y * full.1),
+        full,
+    )
+}
+
+/// Bounds turned a quarter about their centre
+pub fn turned_bounds(position: (f32, f32), size: (f32, f32)) -> ((f32, f32), (f32, f32)) {
+    let center = (position.0 + size.0 / 2.0, position.1 + size.1 / 2.0);
+    (
+        (center.0 - size.1 / 2.0, center.1 - size.0 / 2.0),
+        (size.1, size.0),
+    )
+}
+
+/// Source path and when it was last modified, quarter turns and flips
+pub type OrientationKey = (PathBuf, Option<SystemTime>, u8, bool, bool);
+
+/// Oriented copies already on disk
+static ORIENTED: Lazy<Mutex<HashMap<OrientationKey, PathBuf>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The oriented copy an image needs, or `None` if it's shown as it is.
+/// Editing the source gives it a new key.
+pub fn orientation_key(path: &Path, edit: &ImageEdit) -> Option<OrientationKey> {
+    if !edit.is_oriented() {
+        return None;
+    }
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    Some((
+        path.to_path_buf(),
+        modified,
+        edit.quarter_turns % 4,
+        edit.flip_horizontal,
+        edit.flip_vertical,
+    ))
+}
+
+/// The file `orient` made for `key`, if it has run
+pub fn cached_oriented(key: &OrientationKey) -> Option<PathBuf> {
+    ORIENTED.lock().get(key).cloned()
+}
+
+/// The file to render for an image: the source itself, or its oriented copy.
+/// `None` while the copy hasn't been made.
+pub fn oriented_path(path: &Path, edit: &ImageEdit) -> Option<PathBuf> {
+    match orientation_key(path, edit) {
+        Some(key) => cached_oriented(&key),
+        None => Some(path.to_path_buf()),
+    }
+}
+
+/// Make the oriented copy for `key`. This decodes and encodes the whole image,
+/// so it runs in the background. Falls back to the source if that fails.
+pub fn orient(key: &OrientationKey) {
+    let (path, _, quarter_turns, flip_horizontal, flip_vertical) = key;
+    let edit = ImageEdit {
+        quarter_turns: *quarter_turns,
+        flip_horizontal: *flip_horizontal,
+        flip_vertical: *flip_vertical,
+        crop: None,
+    };
+    let oriented = write_oriented(path, &edit).unwrap_or_else(|| path.clone());
+    ORIENTED.lock().insert(key.clone(), oriented);
+}
+
+fn write_oriented(path: &Path, edit: &ImageEdit) -> Option<PathBuf> {
+    let cache_dir = std::env::temp_dir().join("humanboard").join("image_edits");
+    std::fs::create_dir_all(&cache_dir).ok()?;
+
+    let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_path.to_string_lossy().as_bytes());
+    let path_hash = format!("{:x}", hasher.finalize());
+    let cached_path = cache_dir.join(format!(
+        "{}_r{}{}{}.png",
+        &path_hash[..16],
+        edit.quarter_turns % 4,
+        if edit.flip_horizontal { "h" } else { "" },
+        if edit.flip_vertical { "v" } else { "" },
+    ));
+
+    // Reuse a copy made after the source last changed
+    let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    let fresh = modified(&cached_path)
+        .zip(modified(path))
+        .is_some_and(|(cached, source)| cached >= source);
+    if fresh {
+        return Some(cached_path);
+    }
+
+    let image = match image::open(path) {
+        Ok(image) => image,
+        Err(e) => {
+            warn!("Failed to open image {:?} for editing: {}", path, e);
+            return None;
+        }
+    };
+    let oriented = edit.orient(image);
+
+    // Atomic write, as for PDF thumbnails
+    let temp_file = NamedTempFile::new_in(&cache_dir).ok()?;
+    if let Err(e) = oriented.write_to(
+        &mut std::io::BufWriter::new(temp_file.as_file()),
+        image::ImageFormat::Png,
+    ) {
+        warn!("Failed to encode oriented image: {}", e);
+        return None;
+    }
+    if let Err(e) = temp_file.persist(&cached_path) {
+        warn!(
+            "Failed to persist oriented image to {:?}: {}",
+            cached_path, e
+        );
+        return None;
+    }
+
+    debug!("Oriented image cached: {:?}", cached_path);
+    Some(cached_path)
+}
+
+/// Write the edited image to a new file in `files_dir`, named after the
+/// source. Returns the new file's path.
+pub fn bake(path: &Path, edit: &ImageEdit, files_dir: &Path) -> Result<PathBuf, MediaError> {
+    let image = image::open(path)?;
+    let edited = edit.apply(image);
+
+    std::fs::create_dir_all(files_dir).map_err(|source| MediaError::WriteFailed {
+        path: files_dir.to_path_buf(),
+        source,
+    })?;
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "image".to_string());
+    // Keep the source's format when the image crate can write it
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .filter(|e| image::ImageFormat::from_extension(e).is_some_and(|f| f.writing_enabled()))
+        .unwrap_or("png")
+        .to_lowercase();
+
+    let mut dest = files_dir.join(format!("{}-edited.{}", stem, extension));
+    let mut n = 2;
+    while dest.exists() {
+        dest = files_dir.join(format!("{}-edited-{}.{}", stem, n, extension));
+        n += 1;
+    }
+
+    edited.save(&dest)?;
+    Ok(dest)
+}
+
+/// Images without edits are saved as a bare path, as they were before edits
+/// existed; edited ones as `[path, edit]`.
+pub(crate) fn serialize_image<S: Serializer>(
+    path: &PathBuf,
+    edit: &ImageEdit,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if edit.is_identity() {
+        path.serialize(serializer)
+    } else {
+        (path, edit).serialize(serializer)
+    }
+}
+
+pub(crate) fn deserialize_image<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(PathBuf, ImageEdit), D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Path(PathBuf),
+        Edited(PathBuf, ImageEdit),
+    }
+
+    Ok(match Stored::deserialize(deserializer)? {
+        Stored::Path(path) => (path, ImageEdit::default()),
+        Stored::Edited(path, edit) => (path, edit),
+    })
+}
+
+/// Image editing actions offered in the command palette
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageAction {
+    RotateClockwise,
+    RotateCounterclockwise,
+    FlipHorizontal,
+    FlipVertical,
+    Crop,
+    Reset,
+    Bake,
+}
+
+impl ImageAction {
+    pub const ALL: [ImageAction; 7] = [
+        ImageAction::RotateClockwise,
+        ImageAction::RotateCounterclockwise,
+        ImageAction::FlipHorizontal,
+        ImageAction::FlipVertical,
+        ImageAction::Crop,
+        ImageAction::Reset,
+        ImageAction::Bake,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ImageAction::RotateClockwise => "Rotate Image Clockwise",
+            ImageAction::RotateCounterclockwise => "Rotate Image Counterclockwise",
+            ImageAction::FlipHorizontal => "Flip Image Horizontally",
+            ImageAction::FlipVertical => "Flip Image Vertically",
+            ImageAction::Crop => "Crop Image",
+            ImageAction::Reset => "Reset Image Edits",
+            ImageAction::Bake => "Save Edited Image as New File",
+        }
+    }
+
+    /// Used in the command ID
+    pub fn slug(self) -> &'static str {
+        match self {
+            ImageAction::RotateClockwise => "rotate-cw",
+            ImageAction::RotateCounterclockwise => "rotate-ccw",
+            ImageAction::FlipHorizontal => "flip-horizontal",
+            ImageAction::FlipVertical => "flip-vertical",
+            ImageAction::Crop => "crop",
+            ImageAction::Reset => "reset",
+            ImageAction::Bake => "bake",
+        }
+    }
+}
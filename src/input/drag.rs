@@ -2,6 +2,7 @@
 
 use crate::app::{Humanboard, SplitDirection};
 use crate::constants::HEADER_HEIGHT;
+use crate::image_edit::CropRect;
 use crate::render::dock::DOCK_WIDTH;
 use crate::types::ItemContent;
 use gpui::*;
@@ -92,6 +93,17 @@ impl Humanboard {
             return;
         }
 
+        // Drawing a crop rectangle
+        if let Some(start) = self.crop_editing.as_ref().and_then(|crop| crop.drag_start) {
+            if let Some(current) = self.crop_point(event.position) {
+                if let Some(ref mut crop) = self.crop_editing {
+                    crop.selection = Some(CropRect::from_corners(start, current));
+                }
+                cx.notify();
+            }
+            return;
+        }
+
         let Some(ref mut board) = self.board else {
             return;
         };
@@ -178,6 +190,10 @@ impl Humanboard {
                     (f32::from(event.position.y - offset.y) - canvas_offset_y - header_offset)
                         / zoom;
 
+                // Snap the dragged item; the rest of a group keeps its relative position
+                let canvas_settings = crate::settings::canvas_settings();
+                let (new_x, new_y) = (canvas_settings.snap(new_x), canvas_settings.snap(new_y));
+
                 let old_pos = board.get_item(item_id).map(|i| i.position);
 
                 if let Some((old_x, old_y)) = old_pos {
@@ -220,6 +236,9 @@ impl Humanboard {
         } else if self.drawing_start.is_some() {
             // Update drawing preview position
             self.drawing_current = Some(event.position);
+            if self.selected_tool.is_freehand() {
+                self.freehand_point(event.position);
+            }
             cx.notify();
         }
     }
@@ -0,0 +1,73 @@
+//! Freehand drawing - pen and highlighter strokes and the stroke eraser.
+
+use crate::app::Humanboard;
+use crate::board::UndoOperation;
+use crate::constants::{ERASER_RADIUS, HEADER_HEIGHT};
+use crate::strokes::{self, MIN_POINT_SPACING};
+use crate::types::ToolType;
+use gpui::*;
+
+impl Humanboard {
+    /// Feed a pointer position to the active freehand tool
+    pub(crate) fn freehand_point(&mut self, position: Point<Pixels>) {
+        if self.selected_tool == ToolType::Eraser {
+            self.erase_at(position);
+            return;
+        }
+
+        // Record in canvas space so panning or zooming mid-stroke doesn't move
+        // the points already drawn
+        let Some(zoom) = self.board.as_ref().map(|board| board.zoom) else {
+            return;
+        };
+        let canvas = self.screen_to_canvas(position, HEADER_HEIGHT);
+        let canvas = (f32::from(canvas.x), f32::from(canvas.y));
+        let far_enough = self.stroke_points.last().is_none_or(|last| {
+            let (dx, dy) = (canvas.0 - last.0, canvas.1 - last.1);
+            (dx * dx + dy * dy).sqrt() * zoom >= MIN_POINT_SPACING
+        });
+        if far_enough {
+            self.stroke_points.push(canvas);
+        }
+    }
+
+    /// Erase strokes under the pointer, remembering the changes for undo
+    fn erase_at(&mut self, position: Point<Pixels>) {
+        let center = self.screen_to_canvas(position, HEADER_HEIGHT);
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+
+        let radius = ERASER_RADIUS / board.zoom;
+        let ops = board.erase_strokes((f32::from(center.x), f32::from(center.y)), radius);
+        for op in &ops {
+            if let UndoOperation::RemoveItem(item) = op {
+                self.selected_items.remove(&item.id);
+            }
+        }
+        self.erase_ops.extend(ops);
+    }
+
+    /// Commit the stroke being drawn, or the eraser drag, as a single undo step
+    pub(crate) fn finish_freehand(&mut self) {
+        let points = std::mem::take(&mut self.stroke_points);
+        let erase_ops = std::mem::take(&mut self.erase_ops);
+
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+
+        if !erase_ops.is_empty() {
+            board.push_operation(UndoOperation::Batch(erase_ops));
+        }
+
+        if let Some(kind) = self.selected_tool.stroke_kind() {
+            board.add_stroke(
+                &strokes::finish(&points),
+                kind,
+                kind.default_color().to_string(),
+                kind.default_width(),
+            );
+        }
+    }
+}
@@ -8,9 +8,11 @@
 //! - `mouse_down` - Mouse down event handling (selection, drag/resize start)
 //! - `mouse_up` - Mouse up event handling (finalize operations, create items)
 //! - `drag` - Mouse move handling (drag, resize, pan operations)
+//! - `freehand` - Pen, highlighter and eraser strokes
 //! - `transform` - Canvas transformations (scroll, zoom, coordinate conversion)
 
 mod mouse_down;
 mod mouse_up;
 mod drag;
+mod freehand;
 mod transform;
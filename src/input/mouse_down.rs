@@ -2,7 +2,11 @@
 
 use crate::app::{Humanboard, SplitDirection};
 use crate::constants::{HEADER_HEIGHT, SPLITTER_WIDTH};
+use crate::hit_testing::HitTestContentType;
+use crate::media::MediaSource;
 use crate::render::dock::DOCK_WIDTH;
+use crate::shapes::hit_outline;
+use crate::strokes::hit_stroke;
 use crate::types::{ItemContent, ToolType};
 use gpui::*;
 
@@ -55,17 +59,36 @@ impl Humanboard {
             }
         }
 
+        // In crop mode a drag over the image marks the area to keep, and a
+        // click anywhere else applies the crop
+        if self.crop_editing.is_some() {
+            let start = self
+                .crop_point(mouse_pos)
+                .filter(|&(x, y)| (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y));
+            match start {
+                Some(start) => {
+                    if let Some(ref mut crop) = self.crop_editing {
+                        crop.drag_start = Some(start);
+                        crop.selection = None;
+                    }
+                }
+                None => self.commit_crop(cx),
+            }
+            cx.notify();
+            return;
+        }
+
         let header_offset = HEADER_HEIGHT;
         let dock_offset = DOCK_WIDTH;
 
         // If a drawing tool is selected, prioritize drawing over item selection
-        if matches!(
-            self.selected_tool,
-            ToolType::Text | ToolType::Arrow | ToolType::Shape
-        ) {
+        if self.selected_tool != ToolType::Select {
             self.drawing_start = Some(mouse_pos);
             self.drawing_current = Some(mouse_pos);
             self.selected_items.clear();
+            if self.selected_tool.is_freehand() {
+                self.freehand_point(mouse_pos);
+            }
             cx.notify();
             return;
         }
@@ -95,14 +118,33 @@ impl Humanboard {
                     return false;
                 }
 
-                // For Shape items, only select if clicking on the border (not interior)
-                if let ItemContent::Shape { border_width, .. } = &item.content {
+                // For Shape items, only select if clicking on the outline (not interior),
+                // unless the shape has a label or is a sticky note
+                if let HitTestContentType::Shape {
+                    shape_type,
+                    border_width,
+                } = HitTestContentType::from_content(&item.content)
+                {
                     let border_hit_area = (border_width * board.zoom).max(8.0);
-                    let near_left = mx - scaled_x < border_hit_area;
-                    let near_right = (scaled_x + scaled_width) - mx < border_hit_area;
-                    let near_top = my - scaled_y < border_hit_area;
-                    let near_bottom = (scaled_y + scaled_height) - my < border_hit_area;
-                    return near_left || near_right || near_top || near_bottom;
+                    return hit_outline(
+                        shape_type,
+                        (scaled_width, scaled_height),
+                        (mx - scaled_x, my - scaled_y),
+                        border_hit_area,
+                    );
+                }
+
+                // Strokes are only selectable along their path
+                if let ItemContent::Stroke { points, width, .. } = &item.content {
+                    let local: Vec<(f32, f32)> = points
+                        .iter()
+                        .map(|&(x, y)| (x * scaled_width, y * scaled_height))
+                        .collect();
+                    return hit_stroke(
+                        &local,
+                        (mx - scaled_x, my - scaled_y),
+                        (width * board.zoom / 2.0).max(8.0),
+                    );
                 }
 
                 true
@@ -124,18 +166,66 @@ impl Humanboard {
                 self.selected_items.insert(item_id);
             }
 
-            // Handle double-click for preview or TextBox editing
+            // Clicking a marker on an audio waveform plays from it
+            let marker = if event.click_count == 1 && !event.modifiers.shift {
+                self.audio_marker_at(item_id, mouse_pos)
+            } else {
+                None
+            };
+            if let Some(marker) = marker {
+                self.seek_audio(item_id, marker.seconds, cx);
+                return;
+            }
+
+            // Handle double-click for preview, TextBox editing, shape/arrow labels or media
             if event.click_count == 2 {
-                let is_textbox = board
+                let is_editable = board
                     .get_item(item_id)
-                    .map(|item| matches!(&item.content, ItemContent::TextBox { .. }))
+                    .map(|item| item.content.editable_text().is_some())
                     .unwrap_or(false);
 
-                if is_textbox {
+                if is_editable {
                     self.start_textbox_editing(item_id, window, cx);
                     return;
                 }
 
+                let image = board
+                    .get_item(item_id)
+                    .filter(|item| matches!(item.content, ItemContent::Image(..)))
+                    .cloned();
+                if let Some(image) = image {
+                    self.start_crop(&image);
+                    cx.notify();
+                    return;
+                }
+
+                // Double-clicking an audio waveform marks that time
+                if self.waveform_time_at(item_id, mouse_pos).is_some() {
+                    self.prompt_audio_marker(item_id, mouse_pos, window, cx);
+                    return;
+                }
+
+                // Media posters load their player
+                let is_media = board
+                    .get_item(item_id)
+                    .is_some_and(|item| MediaSource::of(&item.content).is_some());
+                if is_media {
+                    self.activate_media(item_id, cx);
+                    return;
+                }
+
+                // Placed PDF pages open the preview at their page
+                let pdf_page = board
+                    .get_item(item_id)
+                    .and_then(|item| match &item.content {
+                        ItemContent::PdfPage { path, page, .. } => Some((path.clone(), *page)),
+                        _ => None,
+                    });
+                if let Some((path, page)) = pdf_page {
+                    self.open_pdf_at_page(path, page, window, cx);
+                    return;
+                }
+
                 let content_path = board
                     .get_item(item_id)
                     .and_then(|item| match &item.content {
@@ -204,7 +294,12 @@ impl Humanboard {
                         self.selected_items.clear();
                     }
                 }
-                ToolType::Text | ToolType::Arrow | ToolType::Shape => {
+                ToolType::Text
+                | ToolType::Arrow
+                | ToolType::Shape
+                | ToolType::Pen
+                | ToolType::Highlighter
+                | ToolType::Eraser => {
                     self.drawing_start = Some(mouse_pos);
                     self.drawing_current = Some(mouse_pos);
                 }
@@ -3,7 +3,7 @@
 use crate::app::Humanboard;
 use crate::constants::{DEFAULT_FONT_SIZE, HEADER_HEIGHT};
 use crate::render::dock::DOCK_WIDTH;
-use crate::types::{ArrowHead, ItemContent, ShapeType, ToolType};
+use crate::types::{ArrowHead, ItemContent, ShapeType, TextAlignment, TextFormat, ToolType};
 use gpui::*;
 
 impl Humanboard {
@@ -13,6 +13,14 @@ impl Humanboard {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        // Finish drawing a crop rectangle, it's applied with Enter
+        if let Some(ref mut crop) = self.crop_editing {
+            if crop.drag_start.take().is_some() {
+                cx.notify();
+                return;
+            }
+        }
+
         // Only push history on mouse up if we were dragging/resizing
         let was_modifying = self.dragging_item.is_some() || self.resizing_item.is_some();
 
@@ -72,6 +80,13 @@ impl Humanboard {
             }
         }
 
+        // Finalize pen/highlighter strokes and eraser drags; the tool stays active
+        if self.drawing_start.is_some() && self.selected_tool.is_freehand() {
+            self.finish_freehand();
+            self.drawing_start = None;
+            self.drawing_current = None;
+        }
+
         // Finalize arrow/shape/text drawing
         if let Some(start) = self.drawing_start {
             let end = event.position;
@@ -80,8 +95,10 @@ impl Humanboard {
             let screen_width = (f32::from(end.x) - f32::from(start.x)).abs();
             let screen_height = (f32::from(end.y) - f32::from(start.y)).abs();
 
-            // Only create if dragged at least 10 pixels
-            if screen_width < 10.0 && screen_height < 10.0 {
+            // Only create if dragged at least 10 pixels; a click with the shape
+            // tool places the shape at its default size instead
+            let is_click = screen_width < 10.0 && screen_height < 10.0;
+            if is_click && self.selected_tool != ToolType::Shape {
                 self.drawing_start = None;
                 self.drawing_current = None;
                 self.selected_tool = ToolType::Select;
@@ -102,8 +119,21 @@ impl Humanboard {
             let pos_x = start_x.min(end_x);
             let pos_y = start_y.min(end_y);
 
-            match self.selected_tool {
-                ToolType::Arrow => {
+            // Lines are headless arrows, so like arrows they run between the
+            // drag's endpoints; a click draws one at the line's default length
+            let line_head = match self.selected_tool {
+                ToolType::Arrow => Some(ArrowHead::Arrow),
+                ToolType::Shape if self.selected_shape == ShapeType::Line => Some(ArrowHead::None),
+                _ => None,
+            };
+            let (end_x, end_y) = if is_click {
+                (start_x + ShapeType::Line.default_size().0, start_y)
+            } else {
+                (end_x, end_y)
+            };
+
+            match (self.selected_tool, line_head) {
+                (_, Some(head_style)) => {
                     if let Some(ref mut board) = self.board {
                         let box_x = start_x.min(end_x);
                         let box_y = start_y.min(end_y);
@@ -120,7 +150,8 @@ impl Humanboard {
                                 end_offset,
                                 color: "".to_string(),
                                 thickness: 2.0,
-                                head_style: ArrowHead::Arrow,
+                                head_style,
+                                label: None,
                             },
                         );
                         if let Some(item) = board.get_item_mut(id) {
@@ -130,25 +161,32 @@ impl Humanboard {
                         self.selected_items.insert(id);
                     }
                 }
-                ToolType::Shape => {
+                (ToolType::Shape, None) => {
                     if let Some(ref mut board) = self.board {
+                        let shape_type = self.selected_shape;
                         let id = board.add_item(
                             point(px(pos_x), px(pos_y)),
                             ItemContent::Shape {
-                                shape_type: ShapeType::Rectangle,
-                                fill_color: None,
+                                shape_type,
+                                fill_color: shape_type.default_fill().map(str::to_string),
                                 border_color: "".to_string(),
                                 border_width: 2.0,
+                                label: None,
                             },
                         );
-                        if let Some(item) = board.get_item_mut(id) {
+                        if let (false, Some(item)) = (is_click, board.get_item_mut(id)) {
                             item.size = (width, height);
                         }
                         self.selected_items.clear();
                         self.selected_items.insert(id);
+
+                        // Sticky notes are for writing on, so start editing straight away
+                        if shape_type == ShapeType::StickyNote {
+                            self.start_textbox_editing(id, window, cx);
+                        }
                     }
                 }
-                ToolType::Text => {
+                (ToolType::Text, None) => {
                     if let Some(ref mut board) = self.board {
                         let id = board.add_item(
                             point(px(pos_x), px(pos_y)),
@@ -156,6 +194,10 @@ impl Humanboard {
                                 text: "".to_string(),
                                 font_size: DEFAULT_FONT_SIZE,
                                 color: "".to_string(),
+                                format: TextFormat::Markdown,
+                                align: TextAlignment::default(),
+                                font_family: None,
+                                fill_color: None,
                             },
                         );
                         if let Some(item) = board.get_item_mut(id) {
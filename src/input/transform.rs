@@ -17,6 +17,23 @@ impl Humanboard {
         }
     }
 
+    /// Pan so that canvas point `center` sits in the middle of a canvas area of `area_size`.
+    pub fn pan_to_canvas_point(
+        &mut self,
+        center: (f32, f32),
+        area_size: (f32, f32),
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ref mut board) = self.board else {
+            return;
+        };
+        let (x, y) = crate::minimap::centre_on(center, board.zoom, area_size);
+        board.canvas_offset = point(px(x), px(y));
+        board.mark_dirty();
+        self.pan_animation = None;
+        cx.notify();
+    }
+
     pub fn handle_scroll(
         &mut self,
         event: &ScrollWheelEvent,
@@ -0,0 +1,551 @@
+//! JSON Canvas - import and export of Obsidian's open `.canvas` format.
+//!
+//! Text nodes become markdown text boxes (sticky notes when coloured), file
+//! nodes become the matching media item (a placed page for a PDF with a
+//! `#page=` subpath), link nodes become links or YouTube embeds and groups
+//! become frames. Edges become arrows between node sides.
+//! Exporting maps items back; arrows become edges when both of their ends
+//! touch an exported node. See <https://jsoncanvas.org/spec/1.0/>.
+
+use crate::board::BoardState;
+use crate::error::ImportExportError;
+use crate::pdf_pages;
+use crate::types::{
+    arrow_bounds, extract_youtube_id, ArrowHead, CanvasItem, ItemContent, ShapeType,
+    TextAlignment, TextFormat,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File extension of JSON Canvas files
+pub const EXTENSION: &str = "canvas";
+
+/// Hex values of the preset colours "1" to "6", as Obsidian draws them
+const PRESET_COLORS: [&str; 6] = [
+    "#fb464c", "#e9973f", "#e0de71", "#44cf6e", "#53dfdd", "#a882ff",
+];
+
+/// How far outside a node an arrow end may be and still attach to it
+const ATTACH_MARGIN: f32 = 12.0;
+
+/// A whole `.canvas` document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JsonCanvas {
+    #[serde(default)]
+    pub nodes: Vec<Node>,
+    #[serde(default)]
+    pub edges: Vec<Edge>,
+}
+
+/// A node; later nodes are drawn above earlier ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub id: String,
+    #[serde(flatten)]
+    pub kind: NodeKind,
+    #[serde(deserialize_with = "integer")]
+    pub x: i64,
+    #[serde(deserialize_with = "integer")]
+    pub y: i64,
+    #[serde(deserialize_with = "integer")]
+    pub width: i64,
+    #[serde(deserialize_with = "integer")]
+    pub height: i64,
+    /// Hex colour or preset "1" to "6"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// The type-specific part of a node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum NodeKind {
+    Text {
+        #[serde(default)]
+        text: String,
+    },
+    File {
+        /// Path relative to the vault root
+        file: String,
+        /// Heading or block within the file, starting with `#`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subpath: Option<String>,
+    },
+    Link {
+        url: String,
+    },
+    Group {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        background: Option<String>,
+        #[serde(
+            rename = "backgroundStyle",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        background_style: Option<String>,
+    },
+    /// A node type this version doesn't know
+    #[serde(other)]
+    Unknown,
+}
+
+/// A line between two nodes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Edge {
+    pub id: String,
+    pub from_node: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_side: Option<Side>,
+    /// Defaults to no arrow head
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_end: Option<End>,
+    pub to_node: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_side: Option<Side>,
+    /// Defaults to an arrow head
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_end: Option<End>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Side of a node an edge attaches to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+/// Shape of an edge end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum End {
+    None,
+    Arrow,
+}
+
+/// Coordinates are integers in the spec, but some tools write floats
+fn integer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    f64::deserialize(deserializer).map(|value| value.round() as i64)
+}
+
+/// Resolve a canvas colour (hex or preset "1" to "6") to hex
+pub fn resolve_color(color: &str) -> String {
+    match color.trim().parse::<usize>() {
+        Ok(n @ 1..=6) => PRESET_COLORS[n - 1].to_string(),
+        _ => color.trim().to_string(),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Rect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+impl Rect {
+    fn center(self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+
+    fn side_midpoint(self, side: Side) -> (f32, f32) {
+        let (cx, cy) = self.center();
+        match side {
+            Side::Top => (cx, self.y),
+            Side::Right => (self.x + self.w, cy),
+            Side::Bottom => (cx, self.y + self.h),
+            Side::Left => (self.x, cy),
+        }
+    }
+
+    /// The side that faces `other`, for edges that don't name one
+    fn facing_side(self, other: Rect) -> Side {
+        let (cx, cy) = self.center();
+        let (ox, oy) = other.center();
+        let dx = (ox - cx) / self.w.max(1.0);
+        let dy = (oy - cy) / self.h.max(1.0);
+        if dx.abs() >= dy.abs() {
+            if dx >= 0.0 { Side::Right } else { Side::Left }
+        } else if dy >= 0.0 {
+            Side::Bottom
+        } else {
+            Side::Top
+        }
+    }
+
+    /// The side closest to `point`
+    fn nearest_side(self, point: (f32, f32)) -> Side {
+        [
+            (Side::Top, (point.1 - self.y).abs()),
+            (Side::Right, (point.0 - self.x - self.w).abs()),
+            (Side::Bottom, (point.1 - self.y - self.h).abs()),
+            (Side::Left, (point.0 - self.x).abs()),
+        ]
+        .into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(side, _)| side)
+        .unwrap_or(Side::Right)
+    }
+
+    fn contains(self, point: (f32, f32), margin: f32) -> bool {
+        point.0 >= self.x - margin
+            && point.0 <= self.x + self.w + margin
+            && point.1 >= self.y - margin
+            && point.1 <= self.y + self.h + margin
+    }
+}
+
+/// Position, size and content of an item before it gets an id
+type Part = ((f32, f32), (f32, f32), ItemContent);
+
+/// The directory file nodes are relative to: the enclosing Obsidian vault
+/// (a folder with `.obsidian` in it), or the canvas file's own folder
+pub fn vault_root(canvas_path: &Path) -> PathBuf {
+    let dir = canvas_path.parent().unwrap_or(Path::new("."));
+    dir.ancestors()
+        .find(|ancestor| ancestor.join(".obsidian").is_dir())
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Read and parse a `.canvas` file
+pub fn read(path: &Path) -> Result<JsonCanvas, ImportExportError> {
+    let json = fs::read_to_string(path).map_err(|source| ImportExportError::ReadFailed {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_json::from_str(&json).map_err(ImportExportError::Parse)
+}
+
+/// Write a canvas to `path`
+pub fn write(canvas: &JsonCanvas, path: &Path) -> Result<(), ImportExportError> {
+    let json = serde_json::to_string_pretty(canvas).map_err(ImportExportError::Serialize)?;
+    fs::write(path, json).map_err(|source| ImportExportError::WriteFailed {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Read a `.canvas` file as board contents, with the number of nodes and
+/// edges that couldn't be imported
+pub fn import_file(path: &Path) -> Result<(BoardState, usize), ImportExportError> {
+    let canvas = read(path)?;
+    Ok(to_board_state(&canvas, &vault_root(path)))
+}
+
+/// Export items to a `.canvas` file, returning how many were left out
+pub fn export_file(items: &[CanvasItem], path: &Path) -> Result<usize, ImportExportError> {
+    let (canvas, skipped) = from_items(items, &vault_root(path));
+    write(&canvas, path)?;
+    Ok(skipped)
+}
+
+/// Convert a canvas to board contents, resolving file nodes against `base_dir`.
+///
+/// Groups go to the back, then arrows, then the other nodes in canvas order.
+/// Also returns the number of unknown nodes and dangling edges skipped.
+pub fn to_board_state(canvas: &JsonCanvas, base_dir: &Path) -> (BoardState, usize) {
+    let mut skipped = 0;
+    let mut groups: Vec<Part> = Vec::new();
+    let mut nodes: Vec<Part> = Vec::new();
+    let mut rects: HashMap<&str, Rect> = HashMap::new();
+
+    for node in &canvas.nodes {
+        let rect = Rect {
+            x: node.x as f32,
+            y: node.y as f32,
+            w: node.width as f32,
+            h: node.height as f32,
+        };
+        let color = node.color.as_deref().map(resolve_color);
+        let content = match &node.kind {
+            NodeKind::Text { text } => match color {
+                Some(color) => shape(ShapeType::StickyNote, Some(color), String::new(), text),
+                None => ItemContent::TextBox {
+                    text: text.clone(),
+                    font_size: crate::constants::DEFAULT_FONT_SIZE,
+                    color: String::new(),
+                    format: TextFormat::Markdown,
+                    align: TextAlignment::default(),
+                    font_family: None,
+                    fill_color: None,
+                },
+            },
+            NodeKind::File { file, subpath } => {
+                let path = base_dir.join(file);
+                match subpath.as_deref().and_then(pdf_pages::page_from_subpath) {
+                    Some(page)
+                        if path
+                            .extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf")) =>
+                    {
+                        pdf_pages::page_content(&path, page)
+                    }
+                    _ => ItemContent::from_path(&path),
+                }
+            }
+            NodeKind::Link { url } => match extract_youtube_id(url) {
+                Some(id) => ItemContent::YouTube(id),
+                None => ItemContent::Link(url.clone()),
+            },
+            NodeKind::Group { label, .. } => shape(
+                ShapeType::Frame,
+                None,
+                color.unwrap_or_default(),
+                label.as_deref().unwrap_or_default(),
+            ),
+            NodeKind::Unknown => {
+                skipped += 1;
+                continue;
+            }
+        };
+
+        rects.insert(&node.id, rect);
+        let part = ((rect.x, rect.y), (rect.w, rect.h), content);
+        if matches!(node.kind, NodeKind::Group { .. }) {
+            groups.push(part);
+        } else {
+            nodes.push(part);
+        }
+    }
+
+    // Larger groups first, so nested groups stay visible
+    groups.sort_by(|a, b| (b.1.0 * b.1.1).total_cmp(&(a.1.0 * a.1.1)));
+
+    let mut arrows: Vec<Part> = Vec::new();
+    for edge in &canvas.edges {
+        let (Some(&from), Some(&to)) = (
+            rects.get(edge.from_node.as_str()),
+            rects.get(edge.to_node.as_str()),
+        ) else {
+            skipped += 1;
+            continue;
+        };
+        let start = from.side_midpoint(edge.from_side.unwrap_or_else(|| from.facing_side(to)));
+        let end = to.side_midpoint(edge.to_side.unwrap_or_else(|| to.facing_side(from)));
+        let head_style = match edge.to_end {
+            Some(End::None) => ArrowHead::None,
+            _ => ArrowHead::Arrow,
+        };
+        let color = edge.color.as_deref().map(resolve_color).unwrap_or_default();
+        arrows.push(arrow(start, end, head_style, color, edge.label.clone()));
+    }
+
+    let items: Vec<CanvasItem> = groups
+        .into_iter()
+        .chain(arrows)
+        .chain(nodes)
+        .enumerate()
+        .map(|(id, (position, size, content))| CanvasItem {
+            id: id as u64,
+            position,
+            size,
+            content,
+        })
+        .collect();
+
+    (BoardState::from_items(items), skipped)
+}
+
+/// Convert board items to a canvas, writing file paths under `base_dir`
+/// relative to it. Also returns the number of items left out: strokes and
+/// arrows that don't join two exported nodes.
+pub fn from_items(items: &[CanvasItem], base_dir: &Path) -> (JsonCanvas, usize) {
+    let mut skipped = 0;
+    let mut canvas = JsonCanvas::default();
+    let mut rects: Vec<(String, Rect)> = Vec::new();
+
+    for item in items {
+        if matches!(item.content, ItemContent::Arrow { .. }) {
+            continue;
+        }
+        let Some((kind, color)) = node_kind(&item.content, base_dir) else {
+            skipped += 1;
+            continue;
+        };
+        let id = format!("{:016x}", item.id);
+        rects.push((
+            id.clone(),
+            Rect {
+                x: item.position.0,
+                y: item.position.1,
+                w: item.size.0,
+                h: item.size.1,
+            },
+        ));
+        canvas.nodes.push(Node {
+            id,
+            kind,
+            x: item.position.0.round() as i64,
+            y: item.position.1.round() as i64,
+            width: item.size.0.round() as i64,
+            height: item.size.1.round() as i64,
+            color,
+        });
+    }
+
+    // Groups contain other nodes, so an end touching both attaches to the smaller
+    let attached = |point: (f32, f32)| {
+        rects
+            .iter()
+            .filter(|(_, rect)| rect.contains(point, ATTACH_MARGIN))
+            .min_by(|a, b| (a.1.w * a.1.h).total_cmp(&(b.1.w * b.1.h)))
+            .map(|(id, rect)| (id.clone(), rect.nearest_side(point)))
+    };
+
+    for item in items {
+        let (
+            ItemContent::Arrow {
+                color,
+                head_style,
+                label,
+                ..
+            },
+            Some((start, end)),
+        ) = (&item.content, item.arrow_endpoints())
+        else {
+            continue;
+        };
+        let (Some((from_node, from_side)), Some((to_node, to_side))) =
+            (attached(start), attached(end))
+        else {
+            skipped += 1;
+            continue;
+        };
+        canvas.edges.push(Edge {
+            id: format!("{:016x}", item.id),
+            from_node,
+            from_side: Some(from_side),
+            from_end: None,
+            to_node,
+            to_side: Some(to_side),
+            to_end: (*head_style == ArrowHead::None).then_some(End::None),
+            color: non_empty(color),
+            label: label.clone(),
+        });
+    }
+
+    (canvas, skipped)
+}
+
+/// The node an item is exported as, with its colour; `None` for strokes
+fn node_kind(content: &ItemContent, base_dir: &Path) -> Option<(NodeKind, Option<String>)> {
+    let text = |text: &str| NodeKind::Text {
+        text: text.to_string(),
+    };
+    let file = |path: &Path| NodeKind::File {
+        file: relative_path(path, base_dir),
+        subpath: None,
+    };
+
+    Some(match content {
+        ItemContent::TextBox { text: body, .. } => (text(body), None),
+        ItemContent::Text(body) => (text(body), None),
+        ItemContent::Image(path, _) | ItemContent::Video(path) | ItemContent::Audio(path, _) => {
+            (file(path), None)
+        }
+        ItemContent::Pdf { path, .. }
+        | ItemContent::Markdown { path, .. }
+        | ItemContent::Code { path, .. } => (file(path), None),
+        ItemContent::PdfPage { path, page, .. } => (
+            NodeKind::File {
+                file: relative_path(path, base_dir),
+                subpath: Some(pdf_pages::page_subpath(*page)),
+            },
+            None,
+        ),
+        ItemContent::Link(url) => (NodeKind::Link { url: url.clone() }, None),
+        ItemContent::YouTube(id) => (
+            NodeKind::Link {
+                url: format!("https://www.youtube.com/watch?v={}", id),
+            },
+            None,
+        ),
+        ItemContent::Shape {
+            shape_type: ShapeType::Frame,
+            border_color,
+            label,
+            ..
+        } => (
+            NodeKind::Group {
+                label: label.clone(),
+                background: None,
+                background_style: None,
+            },
+            non_empty(border_color),
+        ),
+        ItemContent::Shape {
+            fill_color,
+            border_color,
+            label,
+            ..
+        } => (
+            text(label.as_deref().unwrap_or_default()),
+            fill_color
+                .as_deref()
+                .and_then(non_empty)
+                .or_else(|| non_empty(border_color)),
+        ),
+        ItemContent::Arrow { .. } | ItemContent::Stroke { .. } => return None,
+    })
+}
+
+/// `path` relative to `base_dir` with forward slashes, or unchanged if outside it
+fn relative_path(path: &Path, base_dir: &Path) -> String {
+    match path.strip_prefix(base_dir) {
+        Ok(relative) => relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.to_string_lossy().into_owned(),
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn shape(shape_type: ShapeType, fill: Option<String>, border: String, label: &str) -> ItemContent {
+    ItemContent::Shape {
+        shape_type,
+        fill_color: fill,
+        border_color: border,
+        border_width: 2.0,
+        label: (!label.is_empty()).then(|| label.to_string()),
+    }
+}
+
+/// A plain arrow from `start` to `end`
+fn arrow(
+    start: (f32, f32),
+    end: (f32, f32),
+    head_style: ArrowHead,
+    color: String,
+    label: Option<String>,
+) -> Part {
+    let (position, size) = arrow_bounds(start, end);
+    (
+        position,
+        size,
+        ItemContent::Arrow {
+            end_offset: (end.0 - start.0, end.1 - start.1),
+            color,
+            thickness: 2.0,
+            head_style,
+            label,
+        },
+    )
+}
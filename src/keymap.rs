@@ -0,0 +1,332 @@
+//! User keymap - keybindings loaded from `keymap.json` in the config dir.
+//!
+//! The file is a list of sections, each binding keystrokes to action names
+//! within an optional key context (one of the `FocusContext::KEY_*` values):
+//!
+//! ```json
+//! [
+//!   { "bindings": { "ctrl-p": "ToggleCommandPalette" } },
+//!   { "context": "Canvas", "bindings": { "j": "NudgeDown", "k": "NudgeUp", "up": null } }
+//! ]
+//! ```
+//!
+//! User bindings are layered over the built-in defaults: GPUI prefers bindings
+//! added later, and a `null` action unbinds a default. Parsing and validation
+//! don't need an `App`, so they can be tested on their own.
+
+use crate::focus::FocusContext;
+use gpui::{
+    Action, ActionBuildError, App, Global, KeyBinding, KeyBindingContextPredicate, NoAction,
+};
+use serde::Deserialize;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Namespace of the app's actions, implied when an action name has none
+const ACTION_NAMESPACE: &str = "humanboard";
+
+/// One section of the keymap file
+#[derive(Debug, Deserialize)]
+struct KeymapSection {
+    #[serde(default)]
+    context: Option<String>,
+    #[serde(default)]
+    bindings: SectionBindings,
+}
+
+/// A section's bindings in file order. Unlike a map this keeps repeated
+/// keystrokes, so they can be reported as conflicts.
+#[derive(Debug, Default)]
+struct SectionBindings(Vec<(String, Option<String>)>);
+
+impl<'de> Deserialize<'de> for SectionBindings {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BindingsVisitor;
+
+        impl<'de> Visitor<'de> for BindingsVisitor {
+            type Value = SectionBindings;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map of keystrokes to action names")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut bindings = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(binding) = map.next_entry()? {
+                    bindings.push(binding);
+                }
+                Ok(SectionBindings(bindings))
+            }
+        }
+
+        deserializer.deserialize_map(BindingsVisitor)
+    }
+}
+
+/// A single binding from the keymap file
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeymapEntry {
+    /// Keystrokes, e.g. `"cmd-k"` or `"g g"`
+    pub keystrokes: String,
+    /// Fully qualified action name, or `None` to unbind the keystrokes
+    pub action: Option<String>,
+    /// Key context the binding is active in, or `None` for everywhere
+    pub context: Option<String>,
+}
+
+/// A problem found while loading the keymap
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeymapIssue {
+    /// The file couldn't be read or isn't valid keymap JSON
+    Parse(String),
+    /// A section uses a key context no view sets
+    UnknownContext(String),
+    /// A binding's context isn't a valid context expression
+    InvalidContext { keystrokes: String, context: String },
+    /// A binding names an action that doesn't exist
+    UnknownAction { keystrokes: String, action: String },
+    /// A binding names an action that needs arguments
+    UnsupportedAction { keystrokes: String, action: String },
+    /// A binding's keystrokes can't be parsed
+    InvalidKeystrokes(String),
+    /// The same keystrokes are bound to different actions in the same context
+    Conflict {
+        keystrokes: String,
+        context: Option<String>,
+        actions: (String, String),
+    },
+}
+
+impl fmt::Display for KeymapIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapIssue::Parse(e) => write!(f, "Couldn't load keymap.json: {}", e),
+            KeymapIssue::UnknownContext(context) => {
+                write!(f, "Unknown key context \"{}\" in keymap", context)
+            }
+            KeymapIssue::InvalidContext {
+                keystrokes,
+                context,
+            } => write!(
+                f,
+                "Invalid key context \"{}\" for {} in keymap",
+                context, keystrokes
+            ),
+            KeymapIssue::UnknownAction { keystrokes, action } => {
+                write!(f, "Unknown action \"{}\" bound to {}", action, keystrokes)
+            }
+            KeymapIssue::UnsupportedAction { keystrokes, action } => write!(
+                f,
+                "\"{}\" can't be bound to {} from the keymap",
+                action, keystrokes
+            ),
+            KeymapIssue::InvalidKeystrokes(keystrokes) => {
+                write!(f, "Invalid keystrokes \"{}\" in keymap", keystrokes)
+            }
+            KeymapIssue::Conflict {
+                keystrokes,
+                context,
+                actions,
+            } => write!(
+                f,
+                "{} is bound to both {} and {} in {}",
+                keystrokes,
+                display_action(&actions.0),
+                display_action(&actions.1),
+                context.as_deref().unwrap_or("all contexts")
+            ),
+        }
+    }
+}
+
+/// Path of the user keymap file
+pub fn keymap_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|p| p.join("humanboard").join("keymap.json"))
+}
+
+/// Add the app's namespace to an action name that doesn't have one
+pub fn qualify_action(name: &str) -> String {
+    if name.contains("::") {
+        name.to_string()
+    } else {
+        format!("{}::{}", ACTION_NAMESPACE, name)
+    }
+}
+
+/// Action name as shown to the user, without the app's namespace
+fn display_action(name: &str) -> &str {
+    name.strip_prefix(ACTION_NAMESPACE)
+        .and_then(|n| n.strip_prefix("::"))
+        .unwrap_or(name)
+}
+
+/// Parse keymap JSON into bindings, in file order. An empty file has no bindings.
+pub fn parse_keymap(json: &str) -> Result<Vec<KeymapEntry>, KeymapIssue> {
+    if json.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sections: Vec<KeymapSection> =
+        serde_json::from_str(json).map_err(|e| KeymapIssue::Parse(e.to_string()))?;
+
+    Ok(sections
+        .into_iter()
+        .flat_map(|section| {
+            let context = section.context.filter(|c| !c.trim().is_empty());
+            section
+                .bindings
+                .0
+                .into_iter()
+                .map(move |(keystrokes, action)| KeymapEntry {
+                    keystrokes,
+                    action: action.as_deref().map(qualify_action),
+                    context: context.clone(),
+                })
+        })
+        .collect())
+}
+
+/// Find unknown key contexts and conflicting bindings
+pub fn check_keymap(entries: &[KeymapEntry]) -> Vec<KeymapIssue> {
+    let mut issues = Vec::new();
+
+    for entry in entries {
+        if let Some(ref context) = entry.context {
+            let known = FocusContext::all()
+                .iter()
+                .any(|c| c.key_context() == context);
+            let issue = KeymapIssue::UnknownContext(context.clone());
+            if !known && !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+    }
+
+    let mut bound: BTreeMap<(Option<&str>, &str), &str> = BTreeMap::new();
+    for entry in entries {
+        let Some(ref action) = entry.action else {
+            continue;
+        };
+        let key = (entry.context.as_deref(), entry.keystrokes.as_str());
+        match bound.get(&key) {
+            Some(&first) if first != action => issues.push(KeymapIssue::Conflict {
+                keystrokes: entry.keystrokes.clone(),
+                context: entry.context.clone(),
+                actions: (first.to_string(), action.clone()),
+            }),
+            Some(_) => {}
+            None => {
+                bound.insert(key, action);
+            }
+        }
+    }
+
+    issues
+}
+
+/// The built-in bindings, captured at startup so the keymap can be rebuilt
+struct DefaultKeymap(Vec<KeyBinding>);
+
+impl Global for DefaultKeymap {}
+
+/// Remember the currently registered bindings as the defaults the user keymap
+/// is layered over. Call once, after all built-in bindings are registered.
+pub fn init(cx: &mut App) {
+    let defaults = cx.key_bindings().borrow().bindings().cloned().collect();
+    cx.set_global(DefaultKeymap(defaults));
+}
+
+/// Rebuild the keymap from the defaults plus the user's keymap file.
+///
+/// Returns the problems found. If the file can't be parsed the current
+/// bindings are kept, so a half-edited file doesn't drop the user's keymap.
+pub fn reload(cx: &mut App) -> Vec<KeymapIssue> {
+    let Some(path) = keymap_path() else {
+        return Vec::new();
+    };
+
+    let (bindings, issues) = match load_bindings(&path, cx) {
+        Ok(loaded) => loaded,
+        Err(issue) => return vec![issue],
+    };
+
+    let defaults = cx
+        .try_global::<DefaultKeymap>()
+        .map(|d| d.0.clone())
+        .unwrap_or_default();
+    cx.clear_key_bindings();
+    cx.bind_keys(defaults);
+    cx.bind_keys(bindings);
+
+    issues
+}
+
+/// Read the keymap file and build its bindings. A missing file has no bindings.
+fn load_bindings(
+    path: &Path,
+    cx: &App,
+) -> Result<(Vec<KeyBinding>, Vec<KeymapIssue>), KeymapIssue> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), Vec::new())),
+        Err(e) => return Err(KeymapIssue::Parse(e.to_string())),
+    };
+
+    let entries = parse_keymap(&json)?;
+    let mut issues = check_keymap(&entries);
+    let mut bindings = Vec::new();
+
+    for entry in entries {
+        let action: Box<dyn Action> = match entry.action {
+            Some(ref name) => match cx.build_action(name, None) {
+                Ok(action) => action,
+                Err(e) => {
+                    let keystrokes = entry.keystrokes.clone();
+                    let action = display_action(name).to_string();
+                    issues.push(match e {
+                        ActionBuildError::NotFound { .. } => {
+                            KeymapIssue::UnknownAction { keystrokes, action }
+                        }
+                        ActionBuildError::BuildError { .. } => {
+                            KeymapIssue::UnsupportedAction { keystrokes, action }
+                        }
+                    });
+                    continue;
+                }
+            },
+            None => Box::new(NoAction),
+        };
+
+        // A context that doesn't parse would otherwise make the binding global
+        let predicate = match entry.context.as_deref() {
+            Some(context) => match KeyBindingContextPredicate::parse(context) {
+                Ok(predicate) => Some(Rc::new(predicate)),
+                Err(_) => {
+                    issues.push(KeymapIssue::InvalidContext {
+                        keystrokes: entry.keystrokes.clone(),
+                        context: context.to_string(),
+                    });
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        match KeyBinding::load(
+            &entry.keystrokes,
+            action,
+            predicate,
+            false,
+            None,
+            cx.keyboard_mapper().as_ref(),
+        ) {
+            Ok(binding) => bindings.push(binding),
+            Err(_) => issues.push(KeymapIssue::InvalidKeystrokes(entry.keystrokes)),
+        }
+    }
+
+    Ok((bindings, issues))
+}
@@ -5,7 +5,7 @@
 //!
 //! ## Components
 //!
-//! - **Header**: App title and "New Board" button
+//! - **Header**: App title, "Import" and "New Board" buttons
 //! - **Board Grid**: Cards for each board with edit/delete actions
 //! - **Trash Section**: Collapsible list of deleted boards (30-day retention)
 //! - **Empty State**: Shown when no boards exist
@@ -69,15 +69,30 @@ pub fn render_landing_header(cx: &mut Context<crate::app::Humanboard>) -> Div {
                 ),
         )
         .child(
-            Button::new("new-board")
-                .primary()
-                .small()
-                .icon(Icon::new(IconName::Plus))
-                .label("New Board")
-                .tooltip("Create a new board")
-                .on_click(cx.listener(|this, _, window, cx| {
-                    this.create_new_board(window, cx);
-                })),
+            h_flex()
+                .gap_2()
+                .child(
+                    Button::new("import-board")
+                        .ghost()
+                        .small()
+                        .icon(Icon::new(IconName::FolderOpen))
+                        .label("Import")
+                        .tooltip("Import a board from a JSON Canvas or Excalidraw file")
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.prompt_import_board(cx);
+                        })),
+                )
+                .child(
+                    Button::new("new-board")
+                        .primary()
+                        .small()
+                        .icon(Icon::new(IconName::Plus))
+                        .label("New Board")
+                        .tooltip("Create a new board")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.create_new_board(window, cx);
+                        })),
+                ),
         )
 }
 
@@ -23,30 +23,52 @@ pub mod app;
 pub mod background;
 pub mod constants;
 pub mod audio_webview;
+pub mod auto_layout;
 pub mod board;
 pub mod board_index;
 pub mod command_palette;
 pub mod command_registry;
+pub mod comments;
 pub mod error;
+pub mod excalidraw;
+pub mod exif;
+pub mod external_commands;
 pub mod focus;
+pub mod folder_import;
 pub mod focus_ring;
+pub mod fuzzy;
 pub mod hit_testing;
 pub mod home;
+pub mod image_edit;
 pub mod input;
+pub mod json_canvas;
+pub mod keymap;
 pub mod landing;
 pub mod loading;
 pub mod markdown_card;
+pub mod media;
+pub mod minimap;
 pub mod notifications;
 pub mod onboarding;
+pub mod palette;
+pub mod pdf_annotations;
+pub mod pdf_pages;
 pub mod pdf_thumbnail;
 pub mod pdf_webview;
 pub mod perf;
+pub mod presentation;
 pub mod preview;
 pub mod render;
 pub mod selection;
 pub mod settings;
 pub mod settings_watcher;
+pub mod shapes;
+pub mod strokes;
+pub mod templates;
+pub mod theme_editor;
+pub mod timestamps;
 pub mod types;
 pub mod validation;
 pub mod video_webview;
+pub mod waveform;
 pub mod youtube_webview;
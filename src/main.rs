@@ -6,12 +6,14 @@
 use anyhow::{Context, Result};
 use gpui::*;
 use humanboard::actions::{
-    CancelTextboxEdit, CloseCommandPalette, CloseTab, CmdPaletteDown, CmdPaletteUp, DeleteSelected,
-    DeselectAll, DuplicateSelected, GoBack, GoForward, GoHome, ModalFocusNext, ModalFocusPrev,
-    MoveTabToOtherPane, NewBoard, NextSearchMatch, NextTab, NudgeDown, NudgeLeft, NudgeRight,
-    NudgeUp, OpenFile, OpenSettings, Paste, PrevSearchMatch, PrevTab, Quit, Redo, ReopenClosedTab,
-    SaveCode, SelectAll, ShowShortcuts, ToggleCommandPalette, TogglePaneSplit, TogglePreviewSearch,
-    Undo, ZoomIn, ZoomOut, ZoomReset,
+    CancelTextboxEdit, CloseCommandPalette, CloseTab, CmdPaletteDown, CmdPaletteUp, CommitCrop,
+    DeleteSelected, DeselectAll, DuplicateSelected, ExitPresentation, GoBack, GoForward, GoHome,
+    GoToBookmark, ModalFocusNext, ModalFocusPrev, MoveTabToOtherPane, NewBoard, NextSearchMatch,
+    NextTab, NudgeDown, NudgeLeft, NudgeRight, NudgeUp, OpenFile, OpenSettings, Paste,
+    PresentationNext, PresentationPrev, PrevSearchMatch, PrevTab, Quit, Redo, ReopenClosedTab,
+    SaveCode, SelectAll, ShowImageInfo, ShowShortcuts, StartPresentation, TextboxBold, TextboxCode,
+    TextboxItalic, TextboxStrikethrough, ToggleCommandPalette, ToggleMinimap, TogglePaneSplit,
+    TogglePreviewSearch, Undo, ZoomIn, ZoomOut, ZoomReset, ZoomToFit, ZoomToSelection,
 };
 use humanboard::app::Humanboard;
 use humanboard::focus::FocusContext;
@@ -294,6 +296,44 @@ fn register_keybindings(cx: &mut App) {
         KeyBinding::new("down", NudgeDown, Some(FocusContext::KEY_CANVAS)),
         KeyBinding::new("left", NudgeLeft, Some(FocusContext::KEY_CANVAS)),
         KeyBinding::new("right", NudgeRight, Some(FocusContext::KEY_CANVAS)),
+        // Viewport
+        KeyBinding::new("shift-1", ZoomToFit, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("shift-2", ZoomToSelection, Some(FocusContext::KEY_CANVAS)),
+        // Minimap
+        KeyBinding::new("cmd-shift-m", ToggleMinimap, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new(
+            "ctrl-shift-m",
+            ToggleMinimap,
+            Some(FocusContext::KEY_CANVAS),
+        ),
+        // Presentation mode
+        KeyBinding::new("f5", StartPresentation, Some(FocusContext::KEY_CANVAS)),
+        // Image crop mode
+        KeyBinding::new("enter", CommitCrop, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("cmd-i", ShowImageInfo, Some(FocusContext::KEY_CANVAS)),
+        KeyBinding::new("ctrl-i", ShowImageInfo, Some(FocusContext::KEY_CANVAS)),
+    ]);
+
+    // Number keys jump to saved viewports
+    cx.bind_keys((1..=9).map(|n| {
+        KeyBinding::new(
+            &n.to_string(),
+            GoToBookmark { index: n - 1 },
+            Some(FocusContext::KEY_CANVAS),
+        )
+    }));
+
+    // Presentation mode - step through frames
+    let presenting = Some(FocusContext::KEY_PRESENTATION);
+    cx.bind_keys([
+        KeyBinding::new("right", PresentationNext, presenting),
+        KeyBinding::new("down", PresentationNext, presenting),
+        KeyBinding::new("space", PresentationNext, presenting),
+        KeyBinding::new("pagedown", PresentationNext, presenting),
+        KeyBinding::new("left", PresentationPrev, presenting),
+        KeyBinding::new("up", PresentationPrev, presenting),
+        KeyBinding::new("pageup", PresentationPrev, presenting),
+        KeyBinding::new("escape", ExitPresentation, presenting),
     ]);
 
     // Shortcuts that work even when input is active
@@ -301,6 +341,15 @@ fn register_keybindings(cx: &mut App) {
         KeyBinding::new("cmd-k", ToggleCommandPalette, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
         KeyBinding::new("ctrl-k", ToggleCommandPalette, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
         KeyBinding::new("escape", CancelTextboxEdit, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
+        // Text box formatting (wraps the selection in markdown markers)
+        KeyBinding::new("cmd-b", TextboxBold, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
+        KeyBinding::new("ctrl-b", TextboxBold, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
+        KeyBinding::new("cmd-i", TextboxItalic, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
+        KeyBinding::new("ctrl-i", TextboxItalic, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
+        KeyBinding::new("cmd-shift-x", TextboxStrikethrough, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
+        KeyBinding::new("ctrl-shift-x", TextboxStrikethrough, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
+        KeyBinding::new("cmd-e", TextboxCode, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
+        KeyBinding::new("ctrl-e", TextboxCode, Some(FocusContext::KEY_CANVAS_INPUT_ACTIVE)),
     ]);
 
     // Landing page shortcuts
@@ -342,6 +391,9 @@ fn initialize_subsystems(cx: &mut App) {
 
     // 3. Register keybindings
     register_keybindings(cx);
+
+    // 4. Remember the defaults the user keymap is layered over
+    humanboard::keymap::init(cx);
 }
 
 fn main() {
@@ -245,13 +245,15 @@ pub fn render_collapsed_markdown(
 }
 
 /// Text segment with styling info
-#[derive(Clone)]
-struct TextSegment {
-    text: String,
-    bold: bool,
-    italic: bool,
-    code: bool,
-    strikethrough: bool,
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSegment {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub code: bool,
+    pub strikethrough: bool,
+    /// Link target when this segment is the text of a link
+    pub link: Option<String>,
 }
 
 impl TextSegment {
@@ -262,26 +264,36 @@ impl TextSegment {
             italic,
             code,
             strikethrough,
+            link: None,
         }
     }
+
+    fn is_plain(&self) -> bool {
+        !self.code && !self.bold && !self.italic && !self.strikethrough && self.link.is_none()
+    }
 }
 
 /// Render a paragraph with mixed inline styles
 fn render_styled_paragraph(
     segments: Vec<TextSegment>,
     zoom: f32,
+    font_size: f32,
+    align: TextAlign,
     text_color: Hsla,
     text_bold: Hsla,
     text_italic: Hsla,
     text_muted: Hsla,
     code_bg: Hsla,
     code_text: Hsla,
+    link_color: Hsla,
 ) -> Div {
     // For simple paragraphs without mixed styles, just render as text
-    if segments.len() == 1 && !segments[0].code && !segments[0].bold && !segments[0].italic {
+    if segments.len() == 1 && segments[0].is_plain() {
         return div()
-            .text_size(px(13.0 * zoom))
+            .w_full()
+            .text_size(px(font_size * zoom))
             .text_color(text_color)
+            .text_align(align)
             .line_height(relative(1.6))
             .child(segments[0].text.clone());
     }
@@ -290,13 +302,12 @@ fn render_styled_paragraph(
     // Since GPUI doesn't support inline spans easily, we'll render segments inline
     let container = div()
         .w_full()
-        .text_size(px(13.0 * zoom))
+        .text_size(px(font_size * zoom))
+        .text_align(align)
         .line_height(relative(1.6));
 
     // Build combined text for simple cases
-    let all_plain = segments
-        .iter()
-        .all(|s| !s.code && !s.bold && !s.italic && !s.strikethrough);
+    let all_plain = segments.iter().all(TextSegment::is_plain);
 
     if all_plain {
         let combined: String = segments.iter().map(|s| s.text.as_str()).collect();
@@ -308,7 +319,9 @@ fn render_styled_paragraph(
         .flex()
         .flex_wrap()
         .items_baseline()
-        .gap_y(px(4.0 * zoom));
+        .gap_y(px(4.0 * zoom))
+        .when(align == TextAlign::Center, |d| d.justify_center())
+        .when(align == TextAlign::Right, |d| d.justify_end());
 
     for segment in segments {
         let text = segment.text;
@@ -324,10 +337,17 @@ fn render_styled_paragraph(
                 .py(px(1.0 * zoom))
                 .bg(code_bg)
                 .rounded(px(3.0 * zoom))
-                .text_size(px(12.0 * zoom))
+                .text_size(px((font_size - 1.0) * zoom))
                 .font_family("Iosevka Nerd Font")
                 .text_color(code_text)
                 .child(text)
+        } else if segment.link.is_some() {
+            div()
+                .text_color(link_color)
+                .underline()
+                .when(segment.bold, |d| d.font_weight(FontWeight::BOLD))
+                .when(segment.italic, |d| d.italic())
+                .child(text)
         } else {
             let mut span = div().text_color(text_color);
             if segment.bold {
@@ -337,7 +357,7 @@ fn render_styled_paragraph(
                 span = span.italic().text_color(text_italic);
             }
             if segment.strikethrough {
-                span = span.text_color(text_muted);
+                span = span.line_through().text_color(text_muted);
             }
             span.child(text)
         };
@@ -357,7 +377,7 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
     let bg = cx.theme().background;
     let muted_bg = cx.theme().muted;
     let border = cx.theme().border;
-    let _primary = cx.theme().primary;
+    let primary = cx.theme().primary;
     let success = cx.theme().success;
     let danger = cx.theme().danger;
 
@@ -386,6 +406,7 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
     let table_text = fg.opacity(0.85);
     let table_text_header = fg;
     let hr_color = border;
+    let link_color = primary;
     let mut options = Options::empty();
     options.insert(Options::ENABLE_STRIKETHROUGH);
     options.insert(Options::ENABLE_TABLES);
@@ -419,6 +440,9 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
     let mut current_cell = String::new();
     let mut is_table_header = false;
 
+    // Destination of the link currently being parsed
+    let mut link_url: Option<String> = None;
+
     // Current paragraph segments
     let mut paragraph_segments: Vec<TextSegment> = Vec::new();
     let mut current_text = String::new();
@@ -507,12 +531,15 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
                         container = container.child(render_styled_paragraph(
                             segments,
                             zoom,
+                            13.0,
+                            TextAlign::Left,
                             text_color,
                             text_bold,
                             text_italic,
                             text_muted,
                             code_bg,
                             code_text,
+                            link_color,
                         ));
                     }
                 }
@@ -646,12 +673,15 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
                             render_styled_paragraph(
                                 segments,
                                 zoom,
+                                13.0,
+                                TextAlign::Left,
                                 text_quote,
                                 text_bold,
                                 text_italic,
                                 text_muted,
                                 code_bg,
                                 code_text,
+                                link_color,
                             )
                             .italic(),
                         ),
@@ -711,12 +741,15 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
                         .child(div().flex_1().child(render_styled_paragraph(
                             segments,
                             zoom,
+                            13.0,
+                            TextAlign::Left,
                             text_color,
                             text_bold,
                             text_italic,
                             text_muted,
                             code_bg,
                             code_text,
+                            link_color,
                         ))),
                 );
             }
@@ -812,7 +845,7 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
             }
 
             // Links
-            Event::Start(Tag::Link { .. }) => {
+            Event::Start(Tag::Link { dest_url, .. }) => {
                 flush_text(
                     &mut current_text,
                     &mut paragraph_segments,
@@ -820,10 +853,12 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
                     in_italic,
                     in_strikethrough,
                 );
+                link_url = Some(dest_url.to_string());
             }
             Event::End(TagEnd::Link) => {
                 // Link text is in current_text, style it as a link
                 let text = std::mem::take(&mut current_text);
+                let url = link_url.take();
                 if !text.is_empty() {
                     paragraph_segments.push(TextSegment {
                         text,
@@ -831,6 +866,7 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
                         italic: false,
                         code: false,
                         strikethrough: false,
+                        link: url,
                     });
                 }
             }
@@ -884,14 +920,280 @@ pub fn render_markdown_content<V: 'static>(content: &str, zoom: f32, cx: &mut Co
         container = container.child(render_styled_paragraph(
             paragraph_segments,
             zoom,
+            13.0,
+            TextAlign::Left,
             text_color,
             text_bold,
             text_italic,
             text_muted,
             code_bg,
             code_text,
+            link_color,
         ));
     }
 
     container
 }
+
+// ============================================================================
+// Rich text boxes
+// ============================================================================
+
+/// Kind of a single line in a rich text box
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RichLineKind {
+    Paragraph,
+    Heading(u8),
+    Bullet,
+    Numbered(u64),
+    Checkbox(bool),
+}
+
+/// One rendered line of a rich text box
+#[derive(Clone, Debug, PartialEq)]
+pub struct RichLine {
+    pub kind: RichLineKind,
+    /// List nesting depth (0 = top level)
+    pub depth: usize,
+    pub segments: Vec<TextSegment>,
+}
+
+/// Accumulates pulldown-cmark events into `RichLine`s
+#[derive(Default)]
+struct RichTextBuilder {
+    lines: Vec<RichLine>,
+    segments: Vec<TextSegment>,
+    text: String,
+    bold: bool,
+    italic: bool,
+    strikethrough: bool,
+    link: Option<String>,
+    kind: Option<RichLineKind>,
+    depth: usize,
+}
+
+impl RichTextBuilder {
+    fn flush_text(&mut self) {
+        if !self.text.is_empty() {
+            let mut segment = TextSegment::new(
+                std::mem::take(&mut self.text),
+                self.bold,
+                self.italic,
+                false,
+                self.strikethrough,
+            );
+            segment.link = self.link.clone();
+            self.segments.push(segment);
+        }
+    }
+
+    /// Close the current line; continuation lines fall back to plain paragraphs
+    fn finish_line(&mut self) {
+        self.flush_text();
+        let kind = self.kind.take().unwrap_or(RichLineKind::Paragraph);
+        let is_list = !matches!(kind, RichLineKind::Paragraph | RichLineKind::Heading(_));
+        if !self.segments.is_empty() || is_list {
+            self.lines.push(RichLine {
+                kind,
+                depth: self.depth,
+                segments: std::mem::take(&mut self.segments),
+            });
+        }
+    }
+}
+
+/// Parse the markdown subset supported by text boxes into lines.
+///
+/// Unlike full markdown documents, every newline starts a new line so that
+/// text boxes keep the line structure the user typed.
+pub fn parse_rich_text(text: &str) -> Vec<RichLine> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut builder = RichTextBuilder::default();
+    // Ordered lists store their next number, unordered lists None
+    let mut list_stack: Vec<Option<u64>> = Vec::new();
+    let mut in_code_block = false;
+
+    for event in Parser::new_ext(text, options) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                builder.kind = Some(RichLineKind::Heading(level as u8));
+            }
+            Event::End(TagEnd::Heading(_)) | Event::End(TagEnd::Paragraph) => {
+                builder.finish_line();
+            }
+            Event::Start(Tag::List(first)) => {
+                // A nested list ends the parent item's text line
+                builder.finish_line();
+                list_stack.push(first);
+            }
+            Event::End(TagEnd::List(_)) => {
+                list_stack.pop();
+                builder.depth = list_stack.len();
+            }
+            Event::Start(Tag::Item) => {
+                builder.finish_line();
+                builder.depth = list_stack.len().saturating_sub(1);
+                builder.kind = Some(match list_stack.last_mut() {
+                    Some(Some(num)) => {
+                        let current = *num;
+                        *num += 1;
+                        RichLineKind::Numbered(current)
+                    }
+                    _ => RichLineKind::Bullet,
+                });
+            }
+            Event::End(TagEnd::Item) => {
+                builder.finish_line();
+                builder.depth = list_stack.len();
+            }
+            Event::TaskListMarker(checked) => {
+                builder.kind = Some(RichLineKind::Checkbox(checked));
+            }
+            Event::Start(Tag::Strong) => {
+                builder.flush_text();
+                builder.bold = true;
+            }
+            Event::End(TagEnd::Strong) => {
+                builder.flush_text();
+                builder.bold = false;
+            }
+            Event::Start(Tag::Emphasis) => {
+                builder.flush_text();
+                builder.italic = true;
+            }
+            Event::End(TagEnd::Emphasis) => {
+                builder.flush_text();
+                builder.italic = false;
+            }
+            Event::Start(Tag::Strikethrough) => {
+                builder.flush_text();
+                builder.strikethrough = true;
+            }
+            Event::End(TagEnd::Strikethrough) => {
+                builder.flush_text();
+                builder.strikethrough = false;
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                builder.flush_text();
+                builder.link = Some(dest_url.to_string());
+            }
+            Event::End(TagEnd::Link) => {
+                builder.flush_text();
+                builder.link = None;
+            }
+            Event::Code(code) => {
+                builder.flush_text();
+                builder.segments.push(TextSegment::new(
+                    code.to_string(),
+                    false,
+                    false,
+                    true,
+                    false,
+                ));
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                builder.finish_line();
+                in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+            }
+            Event::Text(text) => {
+                if in_code_block {
+                    for line in text.lines() {
+                        builder.segments.push(TextSegment::new(
+                            line.to_string(),
+                            false,
+                            false,
+                            true,
+                            false,
+                        ));
+                        builder.finish_line();
+                    }
+                } else {
+                    builder.text.push_str(&text);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                builder.finish_line();
+            }
+            _ => {}
+        }
+    }
+    builder.finish_line();
+
+    builder.lines
+}
+
+/// Render a text box's markdown subset using the styled paragraph renderer.
+///
+/// `font_size` is the text box's unscaled base size; headings scale from it.
+pub fn render_rich_text(
+    text: &str,
+    font_size: f32,
+    zoom: f32,
+    align: TextAlign,
+    text_color: Hsla,
+    link_color: Hsla,
+) -> Div {
+    let muted = text_color.opacity(0.6);
+    let code_bg = text_color.opacity(0.12);
+
+    let mut container = div().w_full().flex().flex_col().gap(px(2.0 * zoom));
+
+    for line in parse_rich_text(text) {
+        let (size, weight) = match line.kind {
+            RichLineKind::Heading(1) => (font_size * 1.6, FontWeight::BOLD),
+            RichLineKind::Heading(2) => (font_size * 1.35, FontWeight::BOLD),
+            RichLineKind::Heading(_) => (font_size * 1.15, FontWeight::SEMIBOLD),
+            _ => (font_size, FontWeight::NORMAL),
+        };
+        let checked = line.kind == RichLineKind::Checkbox(true);
+        let paragraph = render_styled_paragraph(
+            line.segments,
+            zoom,
+            size,
+            align,
+            if checked { muted } else { text_color },
+            text_color,
+            text_color,
+            muted,
+            code_bg,
+            text_color,
+            link_color,
+        )
+        .font_weight(weight);
+
+        let marker = match line.kind {
+            RichLineKind::Bullet => Some("•".to_string()),
+            RichLineKind::Numbered(num) => Some(format!("{}.", num)),
+            RichLineKind::Checkbox(true) => Some("☑".to_string()),
+            RichLineKind::Checkbox(false) => Some("☐".to_string()),
+            RichLineKind::Paragraph | RichLineKind::Heading(_) => None,
+        };
+
+        let indent = line.depth as f32 * font_size * 1.2;
+        container = container.child(match marker {
+            Some(marker) => div()
+                .flex()
+                .gap(px(font_size * 0.4 * zoom))
+                .pl(px(indent * zoom))
+                .child(
+                    div()
+                        .flex_shrink_0()
+                        .min_w(px(font_size * zoom))
+                        .text_size(px(size * zoom))
+                        .line_height(relative(1.6))
+                        .text_color(muted)
+                        .child(marker),
+                )
+                .child(div().flex_1().child(paragraph)),
+            None => div().pl(px(indent * zoom)).child(paragraph),
+        });
+    }
+
+    container
+}
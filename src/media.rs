@@ -0,0 +1,336 @@
+//! Media items - native posters and the budget of live players.
+//!
+//! Audio, video and YouTube items draw a lightweight poster (title, duration
+//! and cover art or a poster frame) until they are activated. Only activated
+//! items in view get a webview and its local HTTP server, and at most a
+//! configurable number of them stay alive; the rest fall back to posters.
+
+use crate::types::ItemContent;
+use lofty::{Accessor, AudioFile, MimeType, PictureType, Probe, TaggedFileExt};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::NamedTempFile;
+use tracing::{debug, warn};
+
+/// Default number of media players kept alive at once
+pub const DEFAULT_MAX_MEDIA_PLAYERS: usize = 4;
+
+/// Largest poster frame a video player may upload
+pub const MAX_POSTER_FRAME_BYTES: usize = 4 * 1024 * 1024;
+
+/// Timeout for fetching YouTube titles and thumbnails
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where a media item's content comes from
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MediaSource {
+    Audio(PathBuf),
+    Video(PathBuf),
+    YouTube(String),
+}
+
+impl MediaSource {
+    /// The source of an audio, video or YouTube item
+    pub fn of(content: &ItemContent) -> Option<Self> {
+        match content {
+            ItemContent::Audio(path, _) => Some(MediaSource::Audio(path.clone())),
+            ItemContent::Video(path) => Some(MediaSource::Video(path.clone())),
+            ItemContent::YouTube(video_id) => Some(MediaSource::YouTube(video_id.clone())),
+            _ => None,
+        }
+    }
+
+    /// Title to show when the poster has none
+    pub fn fallback_title(&self) -> String {
+        match self {
+            MediaSource::Audio(path) | MediaSource::Video(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "Untitled".to_string()),
+            MediaSource::YouTube(video_id) => format!("YouTube: {}", video_id),
+        }
+    }
+}
+
+/// What a media item shows while its player isn't loaded
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaPoster {
+    pub title: Option<String>,
+    /// Artist for audio, channel for YouTube
+    pub artist: Option<String>,
+    pub duration: Option<Duration>,
+    /// Cover art or a poster frame, cached on disk
+    pub image: Option<PathBuf>,
+}
+
+/// Posters loaded so far
+static POSTERS: Lazy<Mutex<HashMap<MediaSource, MediaPoster>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A poster that has already been loaded. Never touches the disk, so it's
+/// safe to call while rendering.
+pub fn cached_poster(source: &MediaSource) -> Option<MediaPoster> {
+    POSTERS.lock().get(source).cloned()
+}
+
+/// Read a source's poster and cache it. This reads tags or the network, so
+/// call it from a background task.
+pub fn load_poster(source: &MediaSource) -> MediaPoster {
+    let poster = match source {
+        MediaSource::Audio(path) => read_file_poster(path),
+        MediaSource::Video(path) => {
+            let frame = poster_frame_path(path);
+            MediaPoster {
+                image: frame.exists().then_some(frame),
+                ..read_file_poster(path)
+            }
+        }
+        MediaSource::YouTube(video_id) => read_youtube_poster(video_id),
+    };
+    POSTERS.lock().insert(source.clone(), poster.clone());
+    poster
+}
+
+/// Forget a cached poster so it's loaded again
+pub fn forget_poster(source: &MediaSource) {
+    POSTERS.lock().remove(source);
+}
+
+/// Title, artist, duration and cover art from a file's tags
+fn read_file_poster(path: &Path) -> MediaPoster {
+    let tagged_file = match Probe::open(path)
+        .and_then(|p| p.guess_file_type().map_err(Into::into))
+        .and_then(|p| p.read())
+    {
+        Ok(f) => f,
+        Err(e) => {
+            debug!("No tags for {:?}: {}", path, e);
+            return MediaPoster::default();
+        }
+    };
+
+    let duration = Some(tagged_file.properties().duration()).filter(|d| !d.is_zero());
+    let Some(tag) = tagged_file
+        .primary_tag()
+        .or_else(|| tagged_file.first_tag())
+    else {
+        return MediaPoster {
+            duration,
+            ..Default::default()
+        };
+    };
+
+    // Prefer the front cover, fall back to any picture
+    let cover = tag
+        .pictures()
+        .iter()
+        .find(|p| p.pic_type() == PictureType::CoverFront)
+        .or_else(|| tag.pictures().first())
+        .and_then(|picture| {
+            let extension = match picture.mime_type() {
+                Some(MimeType::Png) => "png",
+                Some(MimeType::Gif) => "gif",
+                Some(MimeType::Bmp) => "bmp",
+                _ => "jpg",
+            };
+            let name = format!("{}_cover.{}", path_hash(path), extension);
+            write_cached(&name, picture.data())
+        });
+
+    MediaPoster {
+        title: tag.title().map(|s| s.to_string()),
+        artist: tag.artist().map(|s| s.to_string()),
+        duration,
+        image: cover,
+    }
+}
+
+/// The parts of YouTube's oEmbed response that posters use
+#[derive(Deserialize)]
+struct OEmbed {
+    title: Option<String>,
+    author_name: Option<String>,
+}
+
+/// Title, channel and thumbnail of a YouTube video
+fn read_youtube_poster(video_id: &str) -> MediaPoster {
+    // IDs go into URLs and file names
+    if video_id.is_empty()
+        || !video_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return MediaPoster::default();
+    }
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("Failed to create HTTP client: {}", e);
+            return MediaPoster::default();
+        }
+    };
+
+    let oembed = client
+        .get("https://www.youtube.com/oembed")
+        .query(&[
+            (
+                "url",
+                format!("https://www.youtube.com/watch?v={}", video_id),
+            ),
+            ("format", "json".to_string()),
+        ])
+        .send()
+        .and_then(|r| r.error_for_status())
+        .and_then(|r| r.json::<OEmbed>())
+        .ok();
+
+    // Thumbnails don't change, so one download is kept for good
+    let name = format!("youtube_{}.jpg", video_id);
+    let cached = cache_dir().join(&name);
+    let image = if cached.exists() {
+        Some(cached)
+    } else {
+        client
+            .get(format!("https://i.ytimg.com/vi/{}/hqdefault.jpg", video_id))
+            .send()
+            .and_then(|r| r.error_for_status())
+            .and_then(|r| r.bytes())
+            .ok()
+            .and_then(|bytes| write_cached(&name, &bytes))
+    };
+
+    MediaPoster {
+        title: oembed.as_ref().and_then(|o| o.title.clone()),
+        artist: oembed.and_then(|o| o.author_name),
+        duration: None,
+        image,
+    }
+}
+
+/// Where a video's captured poster frame is kept
+pub fn poster_frame_path(video: &Path) -> PathBuf {
+    cache_dir().join(format!("{}_frame.jpg", path_hash(video)))
+}
+
+/// Store a JPEG frame captured by a video's player as its poster
+pub fn save_poster_frame(video: &Path, jpeg: &[u8]) -> bool {
+    if jpeg.len() > MAX_POSTER_FRAME_BYTES || !jpeg.starts_with(&[0xff, 0xd8, 0xff]) {
+        return false;
+    }
+    let name = format!("{}_frame.jpg", path_hash(video));
+    if write_cached(&name, jpeg).is_none() {
+        return false;
+    }
+    forget_poster(&MediaSource::Video(video.to_path_buf()));
+    true
+}
+
+fn cache_dir() -> PathBuf {
+    std::env::temp_dir()
+        .join("humanboard")
+        .join("media_posters")
+}
+
+fn path_hash(path: &Path) -> String {
+    let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_path.to_string_lossy().as_bytes());
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// Atomically write a file into the poster cache
+fn write_cached(name: &str, data: &[u8]) -> Option<PathBuf> {
+    let cache_dir = cache_dir();
+    std::fs::create_dir_all(&cache_dir).ok()?;
+    let path = cache_dir.join(name);
+    let mut temp_file = NamedTempFile::new_in(&cache_dir).ok()?;
+    if let Err(e) = temp_file.write_all(data) {
+        warn!("Failed to write media poster: {}", e);
+        return None;
+    }
+    if let Err(e) = temp_file.persist(&path) {
+        warn!("Failed to persist media poster to {:?}: {}", path, e);
+        return None;
+    }
+    Some(path)
+}
+
+/// `3:07`, or `1:02:03` for an hour or more
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Media items the user has activated, least recently activated first
+#[derive(Clone, Debug, Default)]
+pub struct LiveMedia {
+    activated: Vec<u64>,
+}
+
+impl LiveMedia {
+    /// Activate an item, or mark it as the most recently used
+    pub fn activate(&mut self, id: u64) {
+        self.activated.retain(|&active| active != id);
+        self.activated.push(id);
+    }
+
+    /// Returns whether the item was active
+    pub fn deactivate(&mut self, id: u64) -> bool {
+        let before = self.activated.len();
+        self.activated.retain(|&active| active != id);
+        self.activated.len() != before
+    }
+
+    pub fn clear(&mut self) {
+        self.activated.clear();
+    }
+
+    pub fn is_active(&self, id: u64) -> bool {
+        self.activated.contains(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.activated.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.activated.is_empty()
+    }
+
+    /// Forget deleted items, then deactivate items until at most `budget`
+    /// (at least one) remain: off-screen ones first, then the least recently
+    /// activated. Returns the items that were deactivated.
+    pub fn enforce(
+        &mut self,
+        existing: &HashSet<u64>,
+        visible: &HashSet<u64>,
+        budget: usize,
+    ) -> Vec<u64> {
+        self.activated.retain(|id| existing.contains(id));
+
+        let mut evicted = Vec::new();
+        while self.activated.len() > budget.max(1) {
+            let index = self
+                .activated
+                .iter()
+                .position(|id| !visible.contains(id))
+                .unwrap_or(0);
+            evicted.push(self.activated.remove(index));
+        }
+        evicted
+    }
+}
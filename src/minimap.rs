@@ -0,0 +1,89 @@
+//! Minimap - a scaled-down overview of the board
+//!
+//! The minimap shows every item as a small rectangle plus the region of the
+//! canvas currently on screen. The mapping between minimap and canvas space is
+//! kept here, free of GPUI, so rendering and click handling agree and can be
+//! tested without a window.
+
+use crate::presentation::items_bounds;
+use crate::types::CanvasItem;
+
+/// Minimap width in pixels
+pub const MINIMAP_WIDTH: f32 = 200.0;
+
+/// Minimap height in pixels
+pub const MINIMAP_HEIGHT: f32 = 140.0;
+
+/// Gap between the minimap and the edges of the canvas area
+pub const MINIMAP_MARGIN: f32 = 12.0;
+
+/// Inner padding between the minimap border and the board overview
+pub const MINIMAP_PADDING: f32 = 8.0;
+
+/// Mapping between canvas coordinates and a minimap of a given size
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MinimapLayout {
+    /// Canvas point drawn at the minimap's top-left corner
+    pub origin: (f32, f32),
+    /// Minimap pixels per canvas unit
+    pub scale: f32,
+}
+
+impl MinimapLayout {
+    /// Fit the board's items into a minimap of `size`, centred with `padding` on each side.
+    ///
+    /// Returns `None` for an empty board. The layout depends only on the items,
+    /// not the viewport, so it stays still while the minimap is dragged.
+    pub fn fit(items: &[CanvasItem], size: (f32, f32), padding: f32) -> Option<Self> {
+        let (x, y, w, h) = items_bounds(items)?;
+        let (w, h) = (w.max(1.0), h.max(1.0));
+        let usable_w = (size.0 - padding * 2.0).max(1.0);
+        let usable_h = (size.1 - padding * 2.0).max(1.0);
+        let scale = (usable_w / w).min(usable_h / h);
+
+        // Centre the board along the axis with spare room
+        let origin = (
+            x + w / 2.0 - size.0 / 2.0 / scale,
+            y + h / 2.0 - size.1 / 2.0 / scale,
+        );
+        Some(Self { origin, scale })
+    }
+
+    /// Convert a canvas point to minimap coordinates
+    pub fn to_minimap(&self, p: (f32, f32)) -> (f32, f32) {
+        (
+            (p.0 - self.origin.0) * self.scale,
+            (p.1 - self.origin.1) * self.scale,
+        )
+    }
+
+    /// Convert a minimap point to canvas coordinates
+    pub fn to_canvas(&self, p: (f32, f32)) -> (f32, f32) {
+        (
+            p.0 / self.scale + self.origin.0,
+            p.1 / self.scale + self.origin.1,
+        )
+    }
+}
+
+/// The canvas region visible in a canvas area of `area_size`, as (x, y, width, height)
+pub fn visible_region(
+    offset: (f32, f32),
+    zoom: f32,
+    area_size: (f32, f32),
+) -> (f32, f32, f32, f32) {
+    (
+        -offset.0 / zoom,
+        -offset.1 / zoom,
+        area_size.0 / zoom,
+        area_size.1 / zoom,
+    )
+}
+
+/// Canvas offset that puts canvas point `center` in the middle of a canvas area of `area_size`
+pub fn centre_on(center: (f32, f32), zoom: f32, area_size: (f32, f32)) -> (f32, f32) {
+    (
+        area_size.0 / 2.0 - center.0 * zoom,
+        area_size.1 / 2.0 - center.1 * zoom,
+    )
+}
@@ -0,0 +1,158 @@
+//! Dominant colour palettes for image items.
+//!
+//! Colours are found by median cut over a downsampled copy of the image as it
+//! is displayed (so crops are respected), and placed under the image as a row
+//! of swatch shapes labelled with their hex codes.
+
+use crate::error::MediaError;
+use crate::image_edit::ImageEdit;
+use crate::types::{CanvasItem, ItemContent, ShapeType};
+use image::DynamicImage;
+use std::path::Path;
+
+/// Number of colours extracted from an image
+pub const PALETTE_SIZE: usize = 6;
+
+/// Swatches are squares between these sizes, shrinking to fit under the image
+pub const MIN_SWATCH_SIZE: f32 = 72.0;
+pub const MAX_SWATCH_SIZE: f32 = 120.0;
+
+/// Space between swatches, and between the image and the row
+pub const SWATCH_GAP: f32 = 8.0;
+
+/// Longest edge of the copy that colours are sampled from
+const SAMPLE_EDGE: u32 = 256;
+
+/// Colours closer than this (squared RGB distance) count as the same
+const MIN_COLOR_DISTANCE: i32 = 24 * 24;
+
+/// An RGB colour
+pub type Rgb = [u8; 3];
+
+/// A swatch shape's content, position and size, for `Board::apply_item_changes`
+pub type Swatch = (ItemContent, (f32, f32), Option<(f32, f32)>);
+
+/// Read an image and find its dominant colours, most common first
+pub fn extract(path: &Path, edit: &ImageEdit, count: usize) -> Result<Vec<Rgb>, MediaError> {
+    let image = edit.apply(image::open(path)?);
+    Ok(dominant_colors(
+        &image.thumbnail(SAMPLE_EDGE, SAMPLE_EDGE),
+        count,
+    ))
+}
+
+/// Up to `count` distinct dominant colours by median cut, most common first.
+/// Mostly transparent pixels are ignored.
+pub fn dominant_colors(image: &DynamicImage, count: usize) -> Vec<Rgb> {
+    let pixels: Vec<Rgb> = image
+        .to_rgba8()
+        .pixels()
+        .filter(|p| p[3] >= 128)
+        .map(|p| [p[0], p[1], p[2]])
+        .collect();
+    if pixels.is_empty() || count == 0 {
+        return Vec::new();
+    }
+
+    // Cut more boxes than needed so near-duplicates can be dropped
+    let mut boxes = vec![pixels];
+    while boxes.len() < count * 2 {
+        let Some((index, channel)) = next_split(&boxes) else {
+            break;
+        };
+        let mut lower = boxes.swap_remove(index);
+        lower.sort_by_key(|p| p[channel]);
+        // Split where the value changes nearest the median, so a colour isn't cut in two
+        let median = lower.len() / 2;
+        let at = (1..lower.len())
+            .filter(|&i| lower[i][channel] != lower[i - 1][channel])
+            .min_by_key(|&i| i.abs_diff(median))
+            .unwrap_or(median);
+        let upper = lower.split_off(at);
+        boxes.push(lower);
+        boxes.push(upper);
+    }
+
+    let mut averages: Vec<(usize, Rgb)> = boxes.iter().map(|b| (b.len(), average(b))).collect();
+    averages.sort_by_key(|&(population, _)| std::cmp::Reverse(population));
+
+    let mut palette: Vec<Rgb> = Vec::with_capacity(count);
+    for (_, color) in averages {
+        if palette.len() == count {
+            break;
+        }
+        if palette
+            .iter()
+            .all(|&kept| distance(kept, color) >= MIN_COLOR_DISTANCE)
+        {
+            palette.push(color);
+        }
+    }
+    palette
+}
+
+/// The box to split next and the channel to split it on: the one with the
+/// most pixels spread over the widest range
+fn next_split(boxes: &[Vec<Rgb>]) -> Option<(usize, usize)> {
+    boxes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, pixels)| {
+            let (channel, range) = (0..3)
+                .map(|c| {
+                    let (min, max) = pixels
+                        .iter()
+                        .fold((u8::MAX, 0), |(min, max), p| (min.min(p[c]), max.max(p[c])));
+                    (c, max.saturating_sub(min))
+                })
+                .max_by_key(|&(_, range)| range)?;
+            (range > 0).then_some((index, channel, range as usize * pixels.len()))
+        })
+        .max_by_key(|&(_, _, weight)| weight)
+        .map(|(index, channel, _)| (index, channel))
+}
+
+fn average(pixels: &[Rgb]) -> Rgb {
+    let mut sums = [0u64; 3];
+    for p in pixels {
+        for (sum, &value) in sums.iter_mut().zip(p) {
+            *sum += value as u64;
+        }
+    }
+    let n = pixels.len().max(1) as u64;
+    sums.map(|sum| ((sum + n / 2) / n) as u8)
+}
+
+fn distance(a: Rgb, b: Rgb) -> i32 {
+    (0..3).map(|c| (a[c] as i32 - b[c] as i32).pow(2)).sum()
+}
+
+/// Format a colour as `#rrggbb`
+pub fn to_hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+/// Swatch shapes for `colors` in a row under `image`
+pub fn swatches(image: &CanvasItem, colors: &[Rgb]) -> Vec<Swatch> {
+    let n = colors.len() as f32;
+    let size =
+        ((image.size.0 - SWATCH_GAP * (n - 1.0)) / n).clamp(MIN_SWATCH_SIZE, MAX_SWATCH_SIZE);
+    let top = image.position.1 + image.size.1 + SWATCH_GAP;
+
+    colors
+        .iter()
+        .enumerate()
+        .map(|(i, &color)| {
+            let hex = to_hex(color);
+            let content = ItemContent::Shape {
+                shape_type: ShapeType::Rectangle,
+                fill_color: Some(hex.clone()),
+                border_color: hex.clone(),
+                border_width: 1.0,
+                label: Some(hex),
+            };
+            let x = image.position.0 + i as f32 * (size + SWATCH_GAP);
+            (content, (x, top), Some((size, size)))
+        })
+        .collect()
+}
@@ -0,0 +1,291 @@
+//! PDF annotations - highlights, notes and boxes on the pages of a PDF.
+//!
+//! Annotations are kept in the board rather than written into the PDF, and
+//! positioned in fractions of the page so they line up at any render size.
+//! A highlight can be sent to the canvas as a quote that links to its page.
+
+use crate::constants::DEFAULT_FONT_SIZE;
+use crate::pdf_pages::page_subpath;
+use crate::types::{CanvasItem, ItemContent, TextAlignment, TextFormat};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Space between a quote and the page or PDF item it's placed beside
+pub const QUOTE_GAP: f32 = 20.0;
+
+/// Width of a quote text box
+pub const QUOTE_WIDTH: f32 = 320.0;
+
+/// An area of a page, in fractions of its width and height from the top left
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PageRect {
+    /// The area between two dragged corners, kept on the page
+    pub fn from_corners(a: (f32, f32), b: (f32, f32)) -> Self {
+        let (left, right) = (a.0.min(b.0).max(0.0), a.0.max(b.0).min(1.0));
+        let (top, bottom) = (a.1.min(b.1).max(0.0), a.1.max(b.1).min(1.0));
+        Self {
+            x: left,
+            y: top,
+            width: (right - left).max(0.0),
+            height: (bottom - top).max(0.0),
+        }
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    pub fn contains(&self, (x, y): (f32, f32)) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+
+    /// The smallest area covering both
+    pub fn union(&self, other: &PageRect) -> PageRect {
+        let left = self.x.min(other.x);
+        let top = self.y.min(other.y);
+        PageRect {
+            x: left,
+            y: top,
+            width: (self.x + self.width).max(other.x + other.width) - left,
+            height: (self.y + self.height).max(other.y + other.height) - top,
+        }
+    }
+
+    /// Whether the two overlap by at least half the shorter one's height, as
+    /// characters on the same line do
+    fn shares_line(&self, other: &PageRect) -> bool {
+        let overlap = (self.y + self.height).min(other.y + other.height) - self.y.max(other.y);
+        overlap >= self.height.min(other.height) / 2.0
+    }
+}
+
+/// What an annotation marks on its page
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AnnotationKind {
+    /// Highlighted text, with one area per line
+    Highlight { rects: Vec<PageRect>, text: String },
+    /// Sticky note pinned to a point
+    Note { point: (f32, f32), text: String },
+    /// Box drawn around an area
+    Box { rect: PageRect },
+}
+
+impl AnnotationKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnnotationKind::Highlight { .. } => "Highlight",
+            AnnotationKind::Note { .. } => "Note",
+            AnnotationKind::Box { .. } => "Box",
+        }
+    }
+}
+
+/// An annotation on a page of a PDF, saved with the board
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PdfAnnotation {
+    pub id: u64,
+    pub path: PathBuf,
+    /// Page number, from 1
+    pub page: usize,
+    pub kind: AnnotationKind,
+}
+
+impl PdfAnnotation {
+    /// How far down its page the annotation starts
+    pub fn top(&self) -> f32 {
+        match &self.kind {
+            AnnotationKind::Highlight { rects, .. } => {
+                rects.iter().map(|r| r.y).fold(f32::MAX, f32::min)
+            }
+            AnnotationKind::Note { point, .. } => point.1,
+            AnnotationKind::Box { rect } => rect.y,
+        }
+    }
+}
+
+/// Tool used to annotate PDF pages
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnnotationTool {
+    #[default]
+    Highlight,
+    Note,
+    Box,
+}
+
+impl AnnotationTool {
+    pub const ALL: [AnnotationTool; 3] = [
+        AnnotationTool::Highlight,
+        AnnotationTool::Note,
+        AnnotationTool::Box,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnnotationTool::Highlight => "Highlight",
+            AnnotationTool::Note => "Note",
+            AnnotationTool::Box => "Box",
+        }
+    }
+}
+
+/// ID for a new annotation, after every existing one
+pub fn next_annotation_id(annotations: &[PdfAnnotation]) -> u64 {
+    annotations.iter().map(|a| a.id + 1).max().unwrap_or(0)
+}
+
+/// Annotations on the PDF at `path`, by page and then down each page
+pub fn annotations_for<'a>(
+    annotations: &'a [PdfAnnotation],
+    path: &Path,
+) -> Vec<&'a PdfAnnotation> {
+    let mut found: Vec<&PdfAnnotation> = annotations.iter().filter(|a| a.path == path).collect();
+    found.sort_by(|a, b| {
+        a.page
+            .cmp(&b.page)
+            .then(a.top().total_cmp(&b.top()))
+            .then(a.id.cmp(&b.id))
+    });
+    found
+}
+
+/// What's annotated in the PDF at `path`, such as `3 annotations on p.1, 4`
+pub fn summary(annotations: &[PdfAnnotation], path: &Path) -> String {
+    let found = annotations_for(annotations, path);
+    if found.is_empty() {
+        return "No annotations".to_string();
+    }
+    let mut pages: Vec<String> = Vec::new();
+    for annotation in &found {
+        let page = annotation.page.to_string();
+        if pages.last() != Some(&page) {
+            pages.push(page);
+        }
+    }
+    format!(
+        "{} annotation{} on p.{}",
+        found.len(),
+        if found.len() == 1 { "" } else { "s" },
+        pages.join(", ")
+    )
+}
+
+/// The text of characters whose centres lie in `area`, and the area it
+/// covers on each line. None if there's no text there.
+pub fn select_text(chars: &[(char, PageRect)], area: PageRect) -> Option<(String, Vec<PageRect>)> {
+    let mut lines: Vec<(String, PageRect)> = Vec::new();
+    for (ch, rect) in chars {
+        if !area.contains(rect.center()) {
+            continue;
+        }
+        if ch.is_whitespace() {
+            if let Some((text, _)) = lines.last_mut() {
+                text.push(' ');
+            }
+            continue;
+        }
+        match lines.last_mut() {
+            Some((text, line)) if line.shares_line(rect) => {
+                text.push(*ch);
+                *line = line.union(rect);
+            }
+            _ => lines.push((ch.to_string(), *rect)),
+        }
+    }
+
+    let text = lines
+        .iter()
+        .flat_map(|(text, _)| text.split_whitespace())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    Some((text, lines.into_iter().map(|(_, rect)| rect).collect()))
+}
+
+/// Where a page of `size` sits when fitted and centred in `area`, as
+/// (x, y, width, height)
+pub fn fit_rect(area: (f32, f32, f32, f32), size: (f32, f32)) -> (f32, f32, f32, f32) {
+    let (x, y, width, height) = area;
+    if size.0 <= 0.0 || size.1 <= 0.0 {
+        return area;
+    }
+    let scale = (width / size.0).min(height / size.1).max(0.0);
+    let (fit_width, fit_height) = (size.0 * scale, size.1 * scale);
+    (
+        x + (width - fit_width) / 2.0,
+        y + (height - fit_height) / 2.0,
+        fit_width,
+        fit_height,
+    )
+}
+
+/// Short name for a page of a PDF, such as `talk.pdf p.4`
+pub fn page_label(path: &Path, page: usize) -> String {
+    format!(
+        "{} p.{}",
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "PDF".to_string()),
+        page
+    )
+}
+
+/// Markdown for a quote of highlighted text, ending in a link to its page
+pub fn quote_text(path: &Path, page: usize, text: &str) -> String {
+    let escaped: String = text
+        .chars()
+        .flat_map(|c| {
+            let escape = matches!(c, '\\' | '*' | '_' | '[' | ']' | '`' | '#');
+            escape.then_some('\\').into_iter().chain(Some(c))
+        })
+        .collect();
+    format!(
+        "*“{}”*\n\n[{}](<{}{}>)",
+        escaped,
+        page_label(path, page),
+        path.display(),
+        page_subpath(page)
+    )
+}
+
+/// A markdown text box quoting highlighted text
+pub fn quote_content(path: &Path, page: usize, text: &str) -> ItemContent {
+    ItemContent::TextBox {
+        text: quote_text(path, page, text),
+        font_size: DEFAULT_FONT_SIZE,
+        color: String::new(),
+        format: TextFormat::Markdown,
+        align: TextAlignment::default(),
+        font_family: None,
+        fill_color: None,
+    }
+}
+
+/// Size of a quote text box, taller for longer quotes
+pub fn quote_size(text: &str) -> (f32, f32) {
+    let lines = 3 + text.chars().count() / 36;
+    (QUOTE_WIDTH, (lines as f32 * 24.0).clamp(120.0, 480.0))
+}
+
+/// Where to put a quote from a page: beside that page's item if it's on the
+/// canvas, otherwise under the PDF
+pub fn quote_anchor(items: &[CanvasItem], path: &Path, page: usize) -> Option<(f32, f32)> {
+    let page_item = items.iter().find(|item| {
+        matches!(&item.content, ItemContent::PdfPage { path: p, page: n, .. } if p == path && *n == page)
+    });
+    if let Some(item) = page_item {
+        return Some((item.position.0 + item.size.0 + QUOTE_GAP, item.position.1));
+    }
+    items
+        .iter()
+        .find(|item| matches!(&item.content, ItemContent::Pdf { path: p, .. } if p == path))
+        .map(|item| (item.position.0, item.position.1 + item.size.1 + QUOTE_GAP))
+}
@@ -0,0 +1,158 @@
+//! PDF pages placed on the canvas.
+//!
+//! A page picker (or the `pages 1-5, 8` command) places chosen pages of a PDF
+//! item beside it as image-like page items. Each one remembers its source PDF
+//! and page number, so double-clicking it opens the preview at that page.
+
+use crate::pdf_thumbnail::{render_pdf_pages, RenderedPage, PLACED_PAGE_WIDTH};
+use crate::types::{CanvasItem, ItemContent};
+use std::path::Path;
+
+/// Longest edge of a placed page item
+pub const PAGE_ITEM_SIZE: f32 = 320.0;
+
+/// Space between placed pages, and between them and the PDF
+pub const PAGE_GAP: f32 = 20.0;
+
+/// Placed pages wrap into rows of this many
+pub const PAGE_COLUMNS: usize = 4;
+
+/// Content, position and size of a page item to add
+pub type PlacedPage = (ItemContent, (f32, f32), Option<(f32, f32)>);
+
+/// Page numbers from a list of pages and ranges such as `1-5, 8` or `10-`,
+/// in the order given and without repeats. `all` selects every page.
+pub fn parse_page_ranges(spec: &str, page_count: usize) -> Result<Vec<usize>, String> {
+    let spec = spec.trim();
+    if spec.eq_ignore_ascii_case("all") {
+        return Ok((1..=page_count).collect());
+    }
+    if spec.is_empty() {
+        return Err("No pages given".to_string());
+    }
+
+    let parse = |number: &str| -> Result<usize, String> {
+        let page = number
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("'{}' isn't a page number", number.trim()))?;
+        if page == 0 || page > page_count {
+            return Err(format!("There's no page {} (1-{})", page, page_count));
+        }
+        Ok(page)
+    };
+
+    let mut pages = Vec::new();
+    for part in spec
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let (first, last) = match part.split_once('-') {
+            Some((first, "")) => (parse(first)?, page_count),
+            Some((first, last)) => (parse(first)?, parse(last)?),
+            None => {
+                let page = parse(part)?;
+                (page, page)
+            }
+        };
+        if first > last {
+            return Err(format!("'{}' runs backwards", part));
+        }
+        for page in first..=last {
+            if !pages.contains(&page) {
+                pages.push(page);
+            }
+        }
+    }
+    Ok(pages)
+}
+
+/// Page numbers as compact ranges, e.g. `1-3, 5`
+pub fn format_page_ranges(pages: &[usize]) -> String {
+    let mut sorted = pages.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for page in sorted {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == page => *last = page,
+            _ => ranges.push((page, page)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(first, last)| {
+            if first == last {
+                first.to_string()
+            } else {
+                format!("{}-{}", first, last)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Canvas size of a page item, keeping the page's shape
+pub fn page_item_size(page_size: (f32, f32)) -> (f32, f32) {
+    let (width, height) = page_size;
+    if width <= 0.0 || height <= 0.0 {
+        return (PAGE_ITEM_SIZE * 0.75, PAGE_ITEM_SIZE);
+    }
+    let scale = PAGE_ITEM_SIZE / width.max(height);
+    (width * scale, height * scale)
+}
+
+/// Page items for `pages` of `pdf`, in rows to the right of the PDF item
+pub fn page_items(pdf: &CanvasItem, path: &Path, pages: &[RenderedPage]) -> Vec<PlacedPage> {
+    let left = pdf.position.0 + pdf.size.0 + PAGE_GAP;
+    let mut top = pdf.position.1;
+
+    let mut placed = Vec::with_capacity(pages.len());
+    for row in pages.chunks(PAGE_COLUMNS) {
+        let mut x = left;
+        let mut row_height: f32 = 0.0;
+        for page in row {
+            let size = page_item_size(page.size);
+            let content = ItemContent::PdfPage {
+                path: path.to_path_buf(),
+                page: page.page,
+                image: Some(page.image.clone()),
+            };
+            placed.push((content, (x, top), Some(size)));
+            x += size.0 + PAGE_GAP;
+            row_height = row_height.max(size.1);
+        }
+        top += row_height + PAGE_GAP;
+    }
+    placed
+}
+
+/// A page item for `page` of the PDF at `path`, rendering the page now
+pub fn page_content(path: &Path, page: usize) -> ItemContent {
+    let image = render_pdf_pages(path, &[page], PLACED_PAGE_WIDTH)
+        .ok()
+        .and_then(|pages| pages.into_iter().next())
+        .map(|rendered| rendered.image);
+    ItemContent::PdfPage {
+        path: path.to_path_buf(),
+        page,
+        image,
+    }
+}
+
+/// The `#page=3` subpath that links to a page, as PDF viewers and Obsidian
+/// understand it
+pub fn page_subpath(page: usize) -> String {
+    format!("#page={}", page)
+}
+
+/// The page a `#page=3` subpath links to
+pub fn page_from_subpath(subpath: &str) -> Option<usize> {
+    subpath
+        .strip_prefix("#page=")?
+        .parse()
+        .ok()
+        .filter(|&page| page > 0)
+}
@@ -1,8 +1,10 @@
 //! PDF thumbnail generation using pdfium.
 //!
 //! This module renders the first page of a PDF document as a PNG thumbnail
-//! for display on the canvas. Thumbnails are cached in a temp directory
-//! with content-based naming to avoid regeneration.
+//! for display on the canvas, and renders individual pages for the page
+//! picker, pages placed on the board and annotating, where it also reads
+//! where each character sits. Images are cached in a temp directory with
+//! content-based naming to avoid regeneration.
 //!
 //! ## Security
 //!
@@ -17,6 +19,8 @@
 //! 3. `Resources/lib/libpdfium.dylib` in macOS bundle
 //! 4. System library fallback
 
+use crate::error::MediaError;
+use crate::pdf_annotations::PageRect;
 use pdfium_render::prelude::*;
 use sha2::{Digest, Sha256};
 use std::io::Write;
@@ -24,6 +28,22 @@ use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 use tracing::{debug, info_span, warn};
 
+/// Width of the page thumbnails in the page picker
+pub const PAGE_PICKER_WIDTH: i32 = 200;
+
+/// Width pages placed on the canvas are rendered at, sharp enough to zoom into
+pub const PLACED_PAGE_WIDTH: i32 = 1600;
+
+/// A PDF page rendered to a cached PNG
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedPage {
+    /// Page number, starting at 1
+    pub page: usize,
+    pub image: PathBuf,
+    /// Page size in points
+    pub size: (f32, f32),
+}
+
 /// Generate a thumbnail image for a PDF's first page
 pub fn generate_pdf_thumbnail<P: AsRef<Path>>(pdf_path: P) -> Option<PathBuf> {
     let pdf_path = pdf_path.as_ref();
@@ -51,9 +71,99 @@ pub fn generate_pdf_thumbnail<P: AsRef<Path>>(pdf_path: P) -> Option<PathBuf> {
     let page = document.pages().get(0).ok()?;
 
     // Render at a reasonable thumbnail size (400px width)
+    let thumbnail_filename = format!("{}_thumb.png", path_hash(pdf_path));
+    let thumbnail_path = render_to_cache(&page, 400, 600, &thumbnail_filename)?;
+
+    debug!("Generated PDF thumbnail: {:?}", thumbnail_path);
+    Some(thumbnail_path)
+}
+
+/// Number of pages in a PDF
+pub fn pdf_page_count(pdf_path: &Path) -> Result<usize, MediaError> {
+    let pdfium = load_pdfium().map_err(MediaError::PdfError)?;
+    let document = pdfium
+        .load_pdf_from_file(pdf_path, None)
+        .map_err(|e| MediaError::PdfError(format!("{:?}", e)))?;
+    Ok(document.pages().len() as usize)
+}
+
+/// Render pages of a PDF, numbered from 1, at `width` pixels. The document is
+/// loaded once and its pages rendered one after another.
+pub fn render_pdf_pages(
+    pdf_path: &Path,
+    pages: &[usize],
+    width: i32,
+) -> Result<Vec<RenderedPage>, MediaError> {
+    let _span = info_span!("render_pdf_pages", path = ?pdf_path, count = pages.len()).entered();
+    let pdfium = load_pdfium().map_err(MediaError::PdfError)?;
+    let document = pdfium
+        .load_pdf_from_file(pdf_path, None)
+        .map_err(|e| MediaError::PdfError(format!("{:?}", e)))?;
+    let hash = path_hash(pdf_path);
+
+    pages
+        .iter()
+        .map(|&number| {
+            let page = get_page(&document, number)?;
+            let name = format!("{}_page{}_w{}.png", hash, number, width);
+            let image = render_to_cache(&page, width, width * 2, &name)
+                .ok_or_else(|| MediaError::PdfError(format!("Failed to render page {}", number)))?;
+            Ok(RenderedPage {
+                page: number,
+                image,
+                size: (page.width().value, page.height().value),
+            })
+        })
+        .collect()
+}
+
+/// Characters on a page of a PDF, numbered from 1, with where each one sits
+/// on the page
+pub fn page_chars(pdf_path: &Path, number: usize) -> Result<Vec<(char, PageRect)>, MediaError> {
+    let pdfium = load_pdfium().map_err(MediaError::PdfError)?;
+    let document = pdfium
+        .load_pdf_from_file(pdf_path, None)
+        .map_err(|e| MediaError::PdfError(format!("{:?}", e)))?;
+    let page = get_page(&document, number)?;
+    let (width, height) = (page.width().value, page.height().value);
+    let text = page
+        .text()
+        .map_err(|e| MediaError::PdfError(format!("{:?}", e)))?;
+
+    // PDF coordinates run up from the bottom left, in points
+    Ok(text
+        .chars()
+        .iter()
+        .filter_map(|c| {
+            let ch = c.unicode_char()?;
+            let bounds = c.loose_bounds().ok()?;
+            Some((
+                ch,
+                PageRect {
+                    x: bounds.left().value / width,
+                    y: (height - bounds.top().value) / height,
+                    width: bounds.width().value / width,
+                    height: bounds.height().value / height,
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Page `number` of a document, counting from 1
+fn get_page<'a>(document: &'a PdfDocument, number: usize) -> Result<PdfPage<'a>, MediaError> {
+    number
+        .checked_sub(1)
+        .and_then(|index| PdfPageIndex::try_from(index).ok())
+        .and_then(|index| document.pages().get(index).ok())
+        .ok_or_else(|| MediaError::PdfError(format!("There's no page {}", number)))
+}
+
+/// Render a page as a PNG in the thumbnail cache
+fn render_to_cache(page: &PdfPage, width: i32, max_height: i32, name: &str) -> Option<PathBuf> {
     let render_config = PdfRenderConfig::new()
-        .set_target_width(400)
-        .set_maximum_height(600);
+        .set_target_width(width)
+        .set_maximum_height(max_height);
 
     let bitmap = page.render_with_config(&render_config).ok()?;
     let image = bitmap.as_image();
@@ -64,7 +174,7 @@ pub fn generate_pdf_thumbnail<P: AsRef<Path>>(pdf_path: P) -> Option<PathBuf> {
         &mut std::io::Cursor::new(&mut png_data),
         image::ImageFormat::Png,
     ) {
-        warn!("Failed to encode PDF thumbnail as PNG: {:?}", e);
+        warn!("Failed to encode PDF page as PNG: {:?}", e);
         return None;
     }
 
@@ -73,38 +183,39 @@ pub fn generate_pdf_thumbnail<P: AsRef<Path>>(pdf_path: P) -> Option<PathBuf> {
         .join("humanboard")
         .join("pdf_thumbnails");
     std::fs::create_dir_all(&temp_dir).ok()?;
-
-    // Generate cryptographic hash of the full canonical path for unique filename
-    let canonical_path = pdf_path.canonicalize().unwrap_or_else(|_| pdf_path.to_path_buf());
-    let mut hasher = Sha256::new();
-    hasher.update(canonical_path.to_string_lossy().as_bytes());
-    let path_hash = format!("{:x}", hasher.finalize());
-    let thumbnail_filename = format!("{}_thumb.png", &path_hash[..16]);
-    let thumbnail_path = temp_dir.join(&thumbnail_filename);
+    let image_path = temp_dir.join(name);
 
     // Use atomic write: create temp file, write, then persist
     // This prevents TOCTOU race conditions and symlink attacks
     let mut temp_file = match NamedTempFile::new_in(&temp_dir) {
         Ok(f) => f,
         Err(e) => {
-            warn!("Failed to create temp file for thumbnail: {:?}", e);
+            warn!("Failed to create temp file for PDF page: {:?}", e);
             return None;
         }
     };
 
     if let Err(e) = temp_file.write_all(&png_data) {
-        warn!("Failed to write thumbnail data: {:?}", e);
+        warn!("Failed to write PDF page data: {:?}", e);
         return None;
     }
 
     // Atomically persist to final location
-    if let Err(e) = temp_file.persist(&thumbnail_path) {
-        warn!("Failed to persist PDF thumbnail to {:?}: {:?}", thumbnail_path, e);
+    if let Err(e) = temp_file.persist(&image_path) {
+        warn!("Failed to persist PDF page to {:?}: {:?}", image_path, e);
         return None;
     }
+    Some(image_path)
+}
 
-    debug!("Generated PDF thumbnail: {:?}", thumbnail_path);
-    Some(thumbnail_path)
+/// Cryptographic hash of the full canonical path, for unique filenames
+fn path_hash(pdf_path: &Path) -> String {
+    let canonical_path = pdf_path
+        .canonicalize()
+        .unwrap_or_else(|_| pdf_path.to_path_buf());
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_path.to_string_lossy().as_bytes());
+    format!("{:x}", hasher.finalize())[..16].to_string()
 }
 
 fn load_pdfium() -> Result<Pdfium, String> {
@@ -13,6 +13,7 @@
 //! - Native PDF rendering with zoom and scroll
 //! - Bounds control for positioning within the canvas
 //! - Show/hide for visibility management
+//! - Opening at a page via a `#page=` fragment
 
 use gpui::*;
 use gpui_component::webview::WebView;
@@ -52,6 +53,15 @@ fn path_to_file_url(path: &Path) -> String {
     }
 }
 
+/// The file URL of a PDF, with a `#page=` fragment to open it at a page
+fn page_url(path: &Path, page: Option<usize>) -> String {
+    let file_url = path_to_file_url(path);
+    match page {
+        Some(page) => format!("{}{}", file_url, crate::pdf_pages::page_subpath(page)),
+        None => file_url,
+    }
+}
+
 /// WebView-based PDF viewer using native WKWebView (which uses PDFKit on macOS)
 pub struct PdfWebView {
     webview_entity: Entity<WebView>,
@@ -59,9 +69,14 @@ pub struct PdfWebView {
 }
 
 impl PdfWebView {
-    /// Create a new PDF WebView
-    pub fn new(path: PathBuf, window: &mut Window, cx: &mut App) -> Result<Self, String> {
-        let file_url = path_to_file_url(&path);
+    /// Create a new PDF WebView, opened at `page` (starting at 1) if given
+    pub fn new(
+        path: PathBuf,
+        page: Option<usize>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Result<Self, String> {
+        let file_url = page_url(&path, page);
 
         // Create WebView entity
         let builder = WebViewBuilder::new();
@@ -115,6 +130,14 @@ impl PdfWebView {
         });
     }
 
+    /// Show a page, starting at 1
+    pub fn go_to_page(&self, page: usize, cx: &mut App) {
+        let file_url = page_url(&self.path, Some(page));
+        self.webview_entity.update(cx, |view, _| {
+            view.load_url(&file_url);
+        });
+    }
+
     /// Set the bounds of the webview explicitly (x, y, width, height in logical pixels)
     pub fn set_bounds(&self, x: f32, y: f32, width: f32, height: f32, cx: &mut App) {
         self.webview_entity.update(cx, |view, _| {
@@ -0,0 +1,127 @@
+//! Presentation mode - the ordered stops a board is presented through
+//!
+//! A presentation steps through the board's frames in the order they were
+//! created, then its bookmarked viewports in the order they were saved.
+//! Boards with neither are presented as a single overview of all items.
+//! The viewport maths here is pure so it can be tested without a window.
+
+use crate::constants::{MAX_ZOOM, MIN_ZOOM};
+use crate::types::{CanvasItem, ItemContent, ShapeType, ViewportBookmark};
+
+/// Fraction of the viewport left empty around a presented region
+pub const PRESENTATION_MARGIN: f32 = 0.05;
+
+/// One step of a presentation: a titled region of the canvas
+#[derive(Clone, Debug, PartialEq)]
+pub struct PresentationStop {
+    /// Title shown in the progress indicator
+    pub title: String,
+    /// Region to show, in canvas coordinates (x, y, width, height)
+    pub bounds: (f32, f32, f32, f32),
+}
+
+/// Build the presentation stops for a board.
+///
+/// `viewport` is the size of the canvas area bookmarks are shown in, which
+/// decides how much of the canvas each bookmark covers.
+pub fn presentation_stops(
+    items: &[CanvasItem],
+    bookmarks: &[ViewportBookmark],
+    viewport: (f32, f32),
+) -> Vec<PresentationStop> {
+    let mut frames: Vec<&CanvasItem> = items
+        .iter()
+        .filter(|item| {
+            matches!(
+                item.content,
+                ItemContent::Shape {
+                    shape_type: ShapeType::Frame,
+                    ..
+                }
+            )
+        })
+        .collect();
+    frames.sort_by_key(|item| item.id);
+
+    if frames.is_empty() && bookmarks.is_empty() {
+        return items_bounds(items)
+            .map(|bounds| PresentationStop {
+                title: "Overview".to_string(),
+                bounds,
+            })
+            .into_iter()
+            .collect();
+    }
+
+    frames
+        .into_iter()
+        .enumerate()
+        .map(|(i, frame)| PresentationStop {
+            title: match &frame.content {
+                ItemContent::Shape {
+                    label: Some(label), ..
+                } => label.clone(),
+                _ => format!("Frame {}", i + 1),
+            },
+            bounds: (
+                frame.position.0,
+                frame.position.1,
+                frame.size.0,
+                frame.size.1,
+            ),
+        })
+        .chain(bookmarks.iter().map(|bookmark| PresentationStop {
+            title: bookmark.name.clone(),
+            bounds: bookmark_bounds(bookmark, viewport),
+        }))
+        .collect()
+}
+
+/// Region of the canvas a bookmark shows in a viewport of `viewport` size
+pub fn bookmark_bounds(bookmark: &ViewportBookmark, viewport: (f32, f32)) -> (f32, f32, f32, f32) {
+    let zoom = bookmark.zoom.max(MIN_ZOOM);
+    (
+        -bookmark.canvas_offset.0 / zoom,
+        -bookmark.canvas_offset.1 / zoom,
+        viewport.0 / zoom,
+        viewport.1 / zoom,
+    )
+}
+
+/// Bounding box of all items, or `None` for an empty board
+pub fn items_bounds(items: &[CanvasItem]) -> Option<(f32, f32, f32, f32)> {
+    let first = items.first()?;
+    let (mut min_x, mut min_y) = first.position;
+    let (mut max_x, mut max_y) = (min_x + first.size.0, min_y + first.size.1);
+    for item in &items[1..] {
+        min_x = min_x.min(item.position.0);
+        min_y = min_y.min(item.position.1);
+        max_x = max_x.max(item.position.0 + item.size.0);
+        max_y = max_y.max(item.position.1 + item.size.1);
+    }
+    Some((min_x, min_y, max_x - min_x, max_y - min_y))
+}
+
+/// Canvas offset and zoom that centre `bounds` in a viewport of `viewport` size.
+///
+/// `margin` is the fraction of the viewport kept free on every side.
+pub fn fit_viewport(
+    bounds: (f32, f32, f32, f32),
+    viewport: (f32, f32),
+    margin: f32,
+) -> ((f32, f32), f32) {
+    let (x, y, w, h) = bounds;
+    let usable_w = viewport.0 * (1.0 - margin * 2.0);
+    let usable_h = viewport.1 * (1.0 - margin * 2.0);
+    let zoom = (usable_w / w.max(1.0))
+        .min(usable_h / h.max(1.0))
+        .clamp(MIN_ZOOM, MAX_ZOOM);
+
+    let center_x = x + w / 2.0;
+    let center_y = y + h / 2.0;
+    let offset = (
+        viewport.0 / 2.0 - center_x * zoom,
+        viewport.1 / 2.0 - center_y * zoom,
+    );
+    (offset, zoom)
+}
@@ -173,6 +173,7 @@ pub fn tab_from_path(path: PathBuf) -> Option<PreviewTab> {
         "pdf" => Some(PreviewTab::Pdf {
             path,
             webview: None,
+            page: None,
             meta: crate::app::TabMeta::default(),
         }),
         _ => {
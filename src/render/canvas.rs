@@ -8,15 +8,21 @@
 
 use crate::app::Humanboard;
 use crate::audio_webview::AudioWebView;
-use crate::markdown_card::{render_collapsed_code, render_collapsed_markdown};
-use crate::types::{CanvasItem, ItemContent};
+use crate::comments::CommentThread;
+use crate::image_edit::{self, CropRect};
+use crate::markdown_card::{render_collapsed_code, render_collapsed_markdown, render_rich_text};
+use crate::media::{self, MediaSource};
+use crate::shapes::{self, ShapeOutline};
+use crate::types::{CanvasItem, ItemContent, ShapeType, StrokeKind, TextAlignment, TextFormat};
 use crate::video_webview::VideoWebView;
+use crate::waveform::{self, AudioMarker};
 use crate::youtube_webview::YouTubeWebView;
 use gpui::prelude::FluentBuilder;
 use gpui::{PathBuilder, *};
 use gpui_component::input::{Input, InputState};
 use gpui_component::{ActiveTheme as _, h_flex, v_flex};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Theme-aware colors for different content types
 #[derive(Clone, Copy)]
@@ -42,7 +48,7 @@ impl ContentTypeColors {
         let base_lightness = if is_dark { 0.4 } else { 0.55 };
         let alpha = 0.9;
 
-        Self {
+        let mut colors = Self {
             video: hsla(280.0 / 360.0, base_saturation, base_lightness, alpha),    // Purple for video
             audio: hsla(320.0 / 360.0, base_saturation, base_lightness, alpha),    // Pink for audio
             text: hsla(210.0 / 360.0, base_saturation, base_lightness, alpha),     // Blue for text
@@ -51,16 +57,33 @@ impl ContentTypeColors {
             youtube: hsla(0.0, 0.7, if is_dark { 0.45 } else { 0.5 }, alpha),      // Red for YouTube
             unknown: theme.muted,
             border: theme.border.opacity(0.5),
+        };
+
+        // Colours the theme sets itself win over the derived ones
+        let item_colors = crate::theme_editor::item_colors(theme.theme_name());
+        let overrides = [
+            (&mut colors.video, &item_colors.video),
+            (&mut colors.audio, &item_colors.audio),
+            (&mut colors.text, &item_colors.text),
+            (&mut colors.pdf, &item_colors.pdf),
+            (&mut colors.link, &item_colors.link),
+            (&mut colors.youtube, &item_colors.youtube),
+        ];
+        for (color, hex) in overrides {
+            if let Some(parsed) = hex.as_deref().and_then(parse_hex_color) {
+                *color = parsed.opacity(alpha);
+            }
         }
+        colors
     }
 
     /// Get color for a specific content type
     pub fn for_content(&self, content: &ItemContent) -> Hsla {
         match content {
             ItemContent::Video(_) => self.video,
-            ItemContent::Audio(_) => self.audio,
+            ItemContent::Audio(..) => self.audio,
             ItemContent::Text(_) => self.text,
-            ItemContent::Pdf { .. } => self.pdf,
+            ItemContent::Pdf { .. } | ItemContent::PdfPage { .. } => self.pdf,
             ItemContent::Link(_) => self.link,
             ItemContent::YouTube(_) => self.youtube,
             _ => self.unknown,
@@ -85,6 +108,55 @@ pub fn render_canvas(
     .size_full()
 }
 
+/// Smallest on-screen gap between grid lines; sparser lines are drawn when zoomed out
+const MIN_GRID_SPACING: f32 = 8.0;
+
+/// Render grid lines every `grid_size` canvas units
+pub fn render_grid(
+    canvas_offset: Point<Pixels>,
+    zoom: f32,
+    grid_size: f32,
+    color: Hsla,
+) -> impl IntoElement {
+    canvas(
+        move |_bounds, _window, _cx| (),
+        move |bounds, _data, window, _cx| {
+            let mut spacing = grid_size * zoom;
+            if spacing <= 0.0 {
+                return;
+            }
+            while spacing < MIN_GRID_SPACING {
+                spacing *= 2.0;
+            }
+
+            let width = f32::from(bounds.size.width);
+            let height = f32::from(bounds.size.height);
+
+            let mut x = f32::from(canvas_offset.x).rem_euclid(spacing);
+            while x < width {
+                let line = Bounds {
+                    origin: bounds.origin + point(px(x), px(0.0)),
+                    size: size(px(1.0), bounds.size.height),
+                };
+                window.paint_quad(fill(line, color));
+                x += spacing;
+            }
+
+            let mut y = f32::from(canvas_offset.y).rem_euclid(spacing);
+            while y < height {
+                let line = Bounds {
+                    origin: bounds.origin + point(px(0.0), px(y)),
+                    size: size(bounds.size.width, px(1.0)),
+                };
+                window.paint_quad(fill(line, color));
+                y += spacing;
+            }
+        },
+    )
+    .absolute()
+    .size_full()
+}
+
 /// Paint item background shapes directly to GPU
 fn render_item_backgrounds(
     bounds: Bounds<Pixels>,
@@ -95,15 +167,17 @@ fn render_item_backgrounds(
     colors: ContentTypeColors,
 ) {
     for item in items {
-        // Skip items that render themselves (images, markdown cards, code files, shapes, arrows, textboxes)
+        // Skip items that render themselves (images, PDF pages, markdown cards, code files, shapes, arrows, textboxes, strokes)
         if matches!(
             &item.content,
-            ItemContent::Image(_)
+            ItemContent::Image(..)
+                | ItemContent::PdfPage { .. }
                 | ItemContent::Markdown { .. }
                 | ItemContent::Code { .. }
                 | ItemContent::TextBox { .. }
                 | ItemContent::Arrow { .. }
                 | ItemContent::Shape { .. }
+                | ItemContent::Stroke { .. }
         ) {
             continue;
         }
@@ -130,6 +204,248 @@ fn render_item_backgrounds(
     }
 }
 
+/// Native stand-in for a media item whose player isn't loaded: cover art and
+/// tags for audio, a poster frame or thumbnail for video
+fn render_media_poster(item: &CanvasItem, zoom: f32) -> Div {
+    let Some(source) = MediaSource::of(&item.content) else {
+        return div();
+    };
+    let poster = media::cached_poster(&source).unwrap_or_default();
+    let title = poster
+        .title
+        .clone()
+        .unwrap_or_else(|| source.fallback_title());
+    let duration = poster.duration.map(media::format_duration);
+    let corner_radius = px(8.0 * zoom);
+    let text_color = hsla(0.0, 0.0, 0.95, 1.0);
+    let dim_text = hsla(0.0, 0.0, 0.6, 1.0);
+
+    if let MediaSource::Audio(_) = source {
+        let art_size = px((item.size.1 - 24.0).clamp(24.0, 96.0) * zoom);
+        let details = [
+            poster.artist.clone(),
+            duration,
+            Some("Double-click to play".into()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+        return h_flex()
+            .size_full()
+            .p(px(12.0 * zoom))
+            .gap(px(12.0 * zoom))
+            .items_center()
+            .bg(hsla(0.0, 0.0, 0.07, 1.0))
+            .rounded(corner_radius)
+            .overflow_hidden()
+            .child(match poster.image {
+                Some(cover) => img(cover)
+                    .flex_shrink_0()
+                    .size(art_size)
+                    .rounded(px(4.0 * zoom))
+                    .object_fit(ObjectFit::Cover)
+                    .into_any_element(),
+                None => div()
+                    .flex_shrink_0()
+                    .size(art_size)
+                    .rounded(px(4.0 * zoom))
+                    .bg(hsla(330.0 / 360.0, 0.6, 0.45, 1.0))
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .text_size(px(24.0 * zoom))
+                    .text_color(text_color)
+                    .child("♪")
+                    .into_any_element(),
+            })
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .gap(px(2.0 * zoom))
+                    .child(
+                        div()
+                            .text_size(px(14.0 * zoom))
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(text_color)
+                            .whitespace_nowrap()
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .child(title),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(11.0 * zoom))
+                            .text_color(dim_text)
+                            .whitespace_nowrap()
+                            .overflow_hidden()
+                            .text_ellipsis()
+                            .child(details),
+                    ),
+            );
+    }
+
+    div()
+        .size_full()
+        .relative()
+        .bg(hsla(0.0, 0.0, 0.1, 1.0))
+        .rounded(corner_radius)
+        .overflow_hidden()
+        .when_some(poster.image, |d, image| {
+            d.child(
+                img(image)
+                    .absolute()
+                    .size_full()
+                    .object_fit(ObjectFit::Cover),
+            )
+        })
+        // Play button
+        .child(
+            div()
+                .absolute()
+                .size_full()
+                .flex()
+                .items_center()
+                .justify_center()
+                .child(
+                    div()
+                        .size(px(56.0 * zoom))
+                        .rounded_full()
+                        .bg(hsla(0.0, 0.0, 0.0, 0.6))
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .text_size(px(22.0 * zoom))
+                        .text_color(text_color)
+                        .child("▶"),
+                ),
+        )
+        // Title and duration
+        .child(
+            h_flex()
+                .absolute()
+                .left_0()
+                .right_0()
+                .bottom_0()
+                .px(px(10.0 * zoom))
+                .py(px(6.0 * zoom))
+                .gap(px(8.0 * zoom))
+                .bg(hsla(0.0, 0.0, 0.0, 0.6))
+                .child(
+                    div()
+                        .flex_1()
+                        .min_w_0()
+                        .text_size(px(12.0 * zoom))
+                        .text_color(text_color)
+                        .whitespace_nowrap()
+                        .overflow_hidden()
+                        .text_ellipsis()
+                        .child(title),
+                )
+                .when_some(duration, |d, duration| {
+                    d.child(
+                        div()
+                            .flex_shrink_0()
+                            .text_size(px(11.0 * zoom))
+                            .text_color(dim_text)
+                            .child(duration),
+                    )
+                }),
+        )
+}
+
+/// Native waveform strip along the bottom of an audio item, with its markers
+fn render_waveform(item: &CanvasItem, path: &Path, markers: &[AudioMarker], zoom: f32) -> Div {
+    let inset = waveform::WAVEFORM_INSET * zoom;
+    let bar_color = hsla(320.0 / 360.0, 0.6, 0.65, 0.9);
+    let marker_color = hsla(45.0 / 360.0, 0.9, 0.6, 1.0);
+    let dim_text = hsla(0.0, 0.0, 0.6, 1.0);
+    let strip = div()
+        .flex_shrink_0()
+        .w_full()
+        .h(px(waveform::strip_height(item.size.1) * zoom))
+        .relative()
+        .bg(hsla(0.0, 0.0, 0.05, 1.0));
+
+    let wave = match waveform::cached_waveform(path) {
+        Some(wave) if !wave.is_empty() => wave,
+        loaded => {
+            let status = if loaded.is_some() {
+                "No waveform for this file"
+            } else {
+                "Reading waveform…"
+            };
+            return strip
+                .flex()
+                .items_center()
+                .justify_center()
+                .text_size(px(10.0 * zoom))
+                .text_color(dim_text)
+                .child(status);
+        }
+    };
+
+    // About one bar per three pixels on screen
+    let track_width = ((item.size.0 - 2.0 * waveform::WAVEFORM_INSET) * zoom).max(1.0);
+    let bars = wave.bars(((track_width / 3.0) as usize).clamp(1, waveform::WAVEFORM_PEAKS));
+    let marker_x: Vec<f32> = markers
+        .iter()
+        .map(|marker| inset + wave.fraction_at(marker.seconds) * track_width)
+        .collect();
+
+    let painted_markers = marker_x.clone();
+    strip
+        .child(
+            canvas(
+                move |_bounds, _window, _cx| (),
+                move |bounds, _data, window, _cx| {
+                    let middle = bounds.origin.y + bounds.size.height / 2.0;
+                    let max_half = (f32::from(bounds.size.height) / 2.0 - 4.0 * zoom).max(1.0);
+                    let step = track_width / bars.len() as f32;
+                    for (i, bar) in bars.iter().enumerate() {
+                        let half = (bar * max_half).max(0.5);
+                        let x = bounds.origin.x + px(inset + i as f32 * step);
+                        let bar = Bounds {
+                            origin: point(x, middle - px(half)),
+                            size: size(px((step * 0.6).max(1.0)), px(half * 2.0)),
+                        };
+                        window.paint_quad(fill(bar, bar_color));
+                    }
+                    for x in &painted_markers {
+                        let line = Bounds {
+                            origin: point(bounds.origin.x + px(x - 0.75), bounds.origin.y),
+                            size: size(px(1.5), bounds.size.height),
+                        };
+                        window.paint_quad(fill(line, marker_color));
+                    }
+                },
+            )
+            .absolute()
+            .size_full(),
+        )
+        // Marker notes, or their times when they have none
+        .children(markers.iter().zip(marker_x).map(|(marker, x)| {
+            let label = if marker.note.is_empty() {
+                waveform::format_timestamp(marker.seconds)
+            } else {
+                marker.note.clone()
+            };
+            div()
+                .absolute()
+                .top(px(2.0 * zoom))
+                .left(px(x + 3.0 * zoom))
+                .max_w(px(90.0 * zoom))
+                .text_size(px(9.0 * zoom))
+                .text_color(marker_color)
+                .whitespace_nowrap()
+                .overflow_hidden()
+                .text_ellipsis()
+                .child(label)
+        }))
+}
+
 /// Render a single canvas item based on its content type
 fn render_item_content(
     item: &CanvasItem,
@@ -143,15 +459,35 @@ fn render_item_content(
     muted_fg: Hsla,
     muted_bg: Hsla,
     _danger: Hsla,
+    link_color: Hsla,
 ) -> Div {
     let corner_radius = px(8.0 * zoom);
 
     match &item.content {
-        ItemContent::Image(path) => div()
-            .size_full()
-            .overflow_hidden()
-            .rounded(corner_radius)
-            .child(img(path.clone()).size_full().object_fit(ObjectFit::Contain)),
+        ItemContent::Image(path, edit) => {
+            let frame = div().size_full().overflow_hidden().rounded(corner_radius);
+            // Turned and flipped images wait for their oriented copy
+            let Some(source) = image_edit::oriented_path(path, edit) else {
+                return frame.bg(muted_bg);
+            };
+            match edit.crop {
+                // Cropped images fill their item, with the rest hanging outside it
+                Some(crop) => {
+                    let width = item.size.0 * zoom / crop.width;
+                    let height = item.size.1 * zoom / crop.height;
+                    frame.relative().child(
+                        img(source)
+                            .absolute()
+                            .left(px(-crop.x * width))
+                            .top(px(-crop.y * height))
+                            .w(px(width))
+                            .h(px(height))
+                            .object_fit(ObjectFit::Fill),
+                    )
+                }
+                None => frame.child(img(source).size_full().object_fit(ObjectFit::Contain)),
+            }
+        }
 
         ItemContent::Pdf {
             thumbnail: Some(thumb_path),
@@ -200,6 +536,29 @@ fn render_item_content(
                     ),
             ),
 
+        ItemContent::PdfPage {
+            image: Some(image_path),
+            ..
+        } => div().size_full().overflow_hidden().rounded(corner_radius).child(
+            img(image_path.clone())
+                .size_full()
+                .object_fit(ObjectFit::Contain),
+        ),
+
+        ItemContent::PdfPage { page, .. } => div()
+            .size_full()
+            .bg(muted_bg)
+            .rounded(corner_radius)
+            .flex()
+            .items_center()
+            .justify_center()
+            .child(
+                div()
+                    .text_size(px(14.0 * zoom))
+                    .text_color(muted_fg)
+                    .child(format!("Page {}", page)),
+            ),
+
         ItemContent::Video(_path) => {
             // Render Video WebView if available
             if let Some(webview) = video_webviews.get(&item.id) {
@@ -234,26 +593,14 @@ fn render_item_content(
                             .child(webview.webview_entity.clone()),
                     )
             } else {
-                // Placeholder while loading
-                div()
-                    .size_full()
-                    .bg(hsla(0.0, 0.0, 0.1, 1.0))
-                    .rounded(corner_radius)
-                    .flex()
-                    .items_center()
-                    .justify_center()
-                    .child(
-                        div()
-                            .text_size(px(14.0 * zoom))
-                            .text_color(muted_fg)
-                            .child("Loading video..."),
-                    )
+                // Poster until the player is activated
+                render_media_poster(item, zoom)
             }
         }
 
-        ItemContent::Audio(_path) => {
+        ItemContent::Audio(path, markers) => {
             // Render Audio WebView if available
-            if let Some(webview) = audio_webviews.get(&item.id) {
+            let player = if let Some(webview) = audio_webviews.get(&item.id) {
                 v_flex()
                     .size_full()
                     .rounded(corner_radius)
@@ -285,21 +632,15 @@ fn render_item_content(
                             .child(webview.webview_entity.clone()),
                     )
             } else {
-                // Placeholder while loading
-                div()
-                    .size_full()
-                    .bg(hsla(0.0, 0.0, 0.1, 1.0))
-                    .rounded(corner_radius)
-                    .flex()
-                    .items_center()
-                    .justify_center()
-                    .child(
-                        div()
-                            .text_size(px(14.0 * zoom))
-                            .text_color(muted_fg)
-                            .child("Loading audio..."),
-                    )
-            }
+                // Poster until the player is activated
+                render_media_poster(item, zoom)
+            };
+            v_flex()
+                .size_full()
+                .rounded(corner_radius)
+                .overflow_hidden()
+                .child(div().flex_1().min_h_0().w_full().child(player))
+                .child(render_waveform(item, path, markers, zoom))
         }
 
         ItemContent::Text(text) => div()
@@ -342,7 +683,7 @@ fn render_item_content(
                     .child(url.clone()),
             ),
 
-        ItemContent::YouTube(video_id) => {
+        ItemContent::YouTube(_) => {
             // Render YouTube WebView if available, otherwise placeholder
             if let Some(webview) = youtube_webviews.get(&item.id) {
                 // Use vertical flex with drag bar ABOVE the webview
@@ -378,25 +719,8 @@ fn render_item_content(
                             .child(webview.webview().clone()),
                     )
             } else {
-                div()
-                    .size_full()
-                    .flex()
-                    .items_center()
-                    .justify_center()
-                    .bg(muted_bg)
-                    .rounded(corner_radius)
-                    .child(
-                        v_flex()
-                            .items_center()
-                            .gap(px(8.0 * zoom))
-                            .child(div().text_size(px(48.0 * zoom)).child("▶️"))
-                            .child(
-                                div()
-                                    .text_size(px(12.0 * zoom))
-                                    .text_color(muted_fg)
-                                    .child(format!("YouTube: {}", video_id)),
-                            ),
-                    )
+                // Thumbnail until the player is activated
+                render_media_poster(item, zoom)
             }
         }
 
@@ -457,62 +781,65 @@ fn render_item_content(
             text,
             font_size,
             color,
+            format,
+            align,
+            font_family,
+            fill_color,
         } => {
             // Parse color from hex string, fallback to theme foreground
             let text_color = parse_hex_color(color).unwrap_or(fg);
+            let fill = fill_color.as_ref().and_then(|c| parse_hex_color(c));
             let scaled_font = font_size * zoom;
+            let text_align = match align {
+                TextAlignment::Left => TextAlign::Left,
+                TextAlignment::Center => TextAlign::Center,
+                TextAlignment::Right => TextAlign::Right,
+            };
 
             // Check if this textbox is being edited
             let is_editing = editing_textbox_id == Some(item.id);
 
-            if is_editing {
-                if let Some(input) = textbox_input {
-                    // Render the input field for inline editing (multiline)
-                    div()
-                        .size_full()
-                        .rounded(px(4.0 * zoom))
-                        .overflow_hidden()
-                        .child(Input::new(input).appearance(false).size_full())
-                } else {
-                    // Fallback to static text if input not available
-                    div()
+            let content = match (is_editing, textbox_input) {
+                // Render the input field for inline editing (multiline), showing raw markdown
+                (true, Some(input)) => {
+                    return div()
                         .size_full()
                         .rounded(px(4.0 * zoom))
-                        .p(px(8.0 * zoom))
                         .overflow_hidden()
-                        .flex()
-                        .flex_col()
-                        .children(text.lines().map(|line| {
-                            div()
-                                .text_size(px(scaled_font))
-                                .text_color(text_color)
-                                .child(if line.is_empty() {
-                                    " ".to_string() // Preserve empty lines
-                                } else {
-                                    line.to_string()
-                                })
-                        }))
+                        .when_some(font_family.clone(), |d, family| d.font_family(family))
+                        .child(Input::new(input).appearance(false).size_full());
                 }
-            } else {
-                // Normal display mode - just text, no background
-                div()
-                    .size_full()
-                    .rounded(px(4.0 * zoom))
-                    .p(px(8.0 * zoom))
-                    .overflow_hidden()
+                _ if *format == TextFormat::Markdown => {
+                    render_rich_text(text, *font_size, zoom, text_align, text_color, link_color)
+                }
+                // Plain text boxes render line by line, as they always have
+                _ => div()
+                    .w_full()
                     .flex()
                     .flex_col()
                     .children(text.lines().map(|line| {
                         div()
+                            .w_full()
                             .text_size(px(scaled_font))
                             .text_color(text_color)
+                            .text_align(text_align)
                             .child(if line.is_empty() {
                                 " ".to_string() // Preserve empty lines
                             } else {
                                 line.to_string()
                             })
-                    }))
-            }
+                    })),
+            };
+
+            // Normal display mode - just text, on the fill if there is one
+            div()
+                .size_full()
+                .rounded(px(4.0 * zoom))
+                .when_some(fill, |d, c| d.bg(c))
+                .p(px(8.0 * zoom))
+                .overflow_hidden()
+                .when_some(font_family.clone(), |d, family| d.font_family(family))
+                .child(content)
         }
 
         ItemContent::Arrow {
@@ -520,6 +847,7 @@ fn render_item_content(
             thickness,
             end_offset,
             head_style,
+            label,
         } => {
             // Parse color from hex string
             let arrow_color = parse_hex_color(color).unwrap_or(fg);
@@ -530,8 +858,24 @@ fn render_item_content(
             let item_w = item.size.0 * zoom;
             let item_h = item.size.1 * zoom;
 
+            // The label sits on the mid-point of the line, wherever the arrow starts
+            let mid_x = if dx >= 0.0 { 0.0 } else { item_w } + dx / 2.0;
+            let mid_y = if dy >= 0.0 { 0.0 } else { item_h } + dy / 2.0;
+            let editing_input = textbox_input.filter(|_| editing_textbox_id == Some(item.id));
+            let label_w = 200.0 * zoom;
+            let label_h = 60.0 * zoom;
+            let label_slot = div()
+                .absolute()
+                .left(px(mid_x - label_w / 2.0))
+                .top(px(mid_y - label_h / 2.0))
+                .w(px(label_w))
+                .h(px(label_h))
+                .flex()
+                .items_center()
+                .justify_center();
+
             // Use a canvas element to draw the arrow with PathBuilder
-            div().size_full().child(
+            let arrow = div().size_full().child(
                 canvas(
                     move |_, _, _| {},
                     move |bounds, _, window, _| {
@@ -591,7 +935,34 @@ fn render_item_content(
                     },
                 )
                 .size_full(),
-            )
+            );
+
+            match (editing_input, label) {
+                (Some(input), _) => arrow.child(
+                    label_slot.child(
+                        div()
+                            .size_full()
+                            .rounded(px(4.0 * zoom))
+                            .bg(muted_bg)
+                            .child(Input::new(input).appearance(false).size_full()),
+                    ),
+                ),
+                (None, Some(label)) => arrow.child(
+                    label_slot.child(
+                        div()
+                            .max_w_full()
+                            .px(px(6.0 * zoom))
+                            .py(px(2.0 * zoom))
+                            .rounded(px(4.0 * zoom))
+                            .bg(muted_bg)
+                            .text_size(px(13.0 * zoom))
+                            .text_color(fg)
+                            .text_center()
+                            .child(label.clone()),
+                    ),
+                ),
+                (None, None) => arrow,
+            }
         }
 
         ItemContent::Shape {
@@ -599,27 +970,211 @@ fn render_item_content(
             fill_color,
             border_color,
             border_width,
+            label,
         } => {
             let fill = fill_color.as_ref().and_then(|c| parse_hex_color(c));
             let stroke = parse_hex_color(border_color).unwrap_or(fg);
             let scaled_border = (border_width * zoom).max(1.0);
+            let is_sticky = *shape_type == ShapeType::StickyNote;
+            let is_frame = *shape_type == ShapeType::Frame;
+
+            let editing_input = textbox_input.filter(|_| editing_textbox_id == Some(item.id));
+
+            let body = if is_sticky {
+                // Paper-like note; text flows from the top-left like a real sticky
+                div()
+                    .size_full()
+                    .rounded(px(2.0 * zoom))
+                    .bg(fill.unwrap_or(hsla(45.0 / 360.0, 0.96, 0.77, 1.0)))
+                    .shadow_md()
+                    .overflow_hidden()
+                    .p(px(12.0 * zoom))
+            } else if let Some(outline) =
+                shapes::outline(*shape_type, item.size.0 * zoom, item.size.1 * zoom)
+            {
+                div()
+                    .size_full()
+                    .relative()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .child(
+                        canvas(
+                            move |_, _, _| {},
+                            move |bounds, _, window, _| {
+                                paint_shape_outline(
+                                    &outline,
+                                    bounds.origin,
+                                    fill,
+                                    stroke,
+                                    scaled_border,
+                                    window,
+                                );
+                            },
+                        )
+                        .absolute()
+                        .size_full(),
+                    )
+            } else {
+                let radius = match shape_type {
+                    ShapeType::RoundedRect => px(8.0 * zoom),
+                    ShapeType::Ellipse => px(9999.0),
+                    _ => px(0.0),
+                };
+
+                div()
+                    .size_full()
+                    .rounded(radius)
+                    .border(px(scaled_border))
+                    .border_color(stroke)
+                    .when_some(fill, |d, c| d.bg(c))
+                    // A frame's title hangs above its top edge
+                    .when(!is_frame, |d| d.overflow_hidden())
+                    .flex()
+                    .items_center()
+                    .justify_center()
+            };
 
-            let radius = match shape_type {
-                crate::types::ShapeType::Rectangle => px(0.0),
-                crate::types::ShapeType::RoundedRect => px(8.0 * zoom),
-                crate::types::ShapeType::Ellipse => px(9999.0),
+            let frame_title = || {
+                div()
+                    .absolute()
+                    .left_0()
+                    .top(px(-24.0 * zoom))
+                    .h(px(20.0 * zoom))
+                    .min_w(px(120.0 * zoom))
+                    .text_size(px(13.0 * zoom))
+                    .text_color(muted_fg)
             };
 
-            div()
-                .size_full()
-                .rounded(radius)
-                .border(px(scaled_border))
-                .border_color(stroke)
-                .when_some(fill, |d, c| d.bg(c))
+            match (editing_input, label) {
+                (Some(input), _) if is_frame => {
+                    body.child(frame_title().child(Input::new(input).appearance(false).size_full()))
+                }
+                (None, Some(label)) if is_frame => body.child(frame_title().child(label.clone())),
+                (Some(input), _) => body.child(
+                    div()
+                        .size_full()
+                        .when(!is_sticky, |d| d.p(px(8.0 * zoom)))
+                        .child(Input::new(input).appearance(false).size_full()),
+                ),
+                (None, Some(label)) if is_sticky => body.child(
+                    div()
+                        .w_full()
+                        .text_size(px(16.0 * zoom))
+                        .text_color(hsla(0.0, 0.0, 0.12, 1.0))
+                        .child(label.clone()),
+                ),
+                (None, Some(label)) => body.child(
+                    div()
+                        .max_w_full()
+                        .px(px(8.0 * zoom))
+                        .text_size(px(14.0 * zoom))
+                        .text_color(fill.map(label_color).unwrap_or(fg))
+                        .text_center()
+                        .child(label.clone()),
+                ),
+                (None, None) => body,
+            }
+        }
+
+        ItemContent::Stroke {
+            points,
+            color,
+            width,
+            kind,
+        } => {
+            let color = parse_hex_color(color).unwrap_or(fg).opacity(kind.opacity());
+            let (w, h) = (item.size.0 * zoom, item.size.1 * zoom);
+            let local: Vec<(f32, f32)> = points.iter().map(|&(x, y)| (x * w, y * h)).collect();
+            let scaled_width = (width * zoom).max(1.0);
+
+            div().size_full().child(
+                canvas(
+                    move |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        paint_stroke(&local, bounds.origin, scaled_width, color, window);
+                    },
+                )
+                .size_full(),
+            )
         }
     }
 }
 
+/// Paint a freehand stroke; a single point is drawn as a dot
+fn paint_stroke(
+    points: &[(f32, f32)],
+    origin: Point<Pixels>,
+    width: f32,
+    color: Hsla,
+    window: &mut Window,
+) {
+    let points: Vec<Point<Pixels>> = points
+        .iter()
+        .map(|&(x, y)| point(origin.x + px(x), origin.y + px(y)))
+        .collect();
+
+    match points.as_slice() {
+        [] => {}
+        [dot] => {
+            let radius = px(width / 2.0);
+            window.paint_quad(quad(
+                Bounds {
+                    origin: point(dot.x - radius, dot.y - radius),
+                    size: size(radius * 2.0, radius * 2.0),
+                },
+                radius,
+                color,
+                px(0.0),
+                transparent_black(),
+                Default::default(),
+            ));
+        }
+        _ => {
+            let mut path = PathBuilder::stroke(px(width));
+            path.add_polygon(&points, false);
+            if let Ok(built) = path.build() {
+                window.paint_path(built, color);
+            }
+        }
+    }
+}
+
+/// Paint a path-based shape: optional fill, then the stroke and any detail lines
+fn paint_shape_outline(
+    outline: &ShapeOutline,
+    origin: Point<Pixels>,
+    fill: Option<Hsla>,
+    stroke: Hsla,
+    stroke_width: f32,
+    window: &mut Window,
+) {
+    let to_screen = |points: &[(f32, f32)]| -> Vec<Point<Pixels>> {
+        points
+            .iter()
+            .map(|&(x, y)| point(origin.x + px(x), origin.y + px(y)))
+            .collect()
+    };
+    let points = to_screen(&outline.points);
+
+    if let (true, Some(fill)) = (outline.closed, fill) {
+        let mut path = PathBuilder::fill();
+        path.add_polygon(&points, true);
+        if let Ok(built) = path.build() {
+            window.paint_path(built, fill);
+        }
+    }
+
+    let mut path = PathBuilder::stroke(px(stroke_width));
+    path.add_polygon(&points, outline.closed);
+    for detail in &outline.details {
+        path.add_polygon(&to_screen(detail), false);
+    }
+    if let Ok(built) = path.build() {
+        window.paint_path(built, stroke);
+    }
+}
+
 /// Parse a hex color string like "#ffffff" into an Hsla color
 fn parse_hex_color(hex: &str) -> Option<Hsla> {
     let hex = hex.trim_start_matches('#');
@@ -658,6 +1213,15 @@ fn parse_hex_color(hex: &str) -> Option<Hsla> {
     Some(hsla(h, s, l, 1.0))
 }
 
+/// Dark or light text, whichever reads better on `fill`
+fn label_color(fill: Hsla) -> Hsla {
+    if fill.l > 0.6 {
+        hsla(0.0, 0.0, 0.12, 1.0)
+    } else {
+        hsla(0.0, 0.0, 0.96, 1.0)
+    }
+}
+
 /// Render all canvas items with positioning and selection
 pub fn render_items(
     items: &[CanvasItem],
@@ -712,6 +1276,7 @@ pub fn render_items(
                     muted_fg,
                     muted_bg,
                     danger,
+                    primary,
                 ))
                 .when(show_selection, |d| {
                     d
@@ -748,10 +1313,20 @@ pub fn render_canvas_area(
     editing_textbox_id: Option<u64>,
     textbox_input: Option<&Entity<InputState>>,
     marquee: Option<(Point<Pixels>, Point<Pixels>)>,
-    drawing_preview: Option<(Point<Pixels>, Point<Pixels>, crate::types::ToolType)>,
+    drawing_preview: Option<(Point<Pixels>, Point<Pixels>, crate::types::ToolType, bool)>,
+    stroke_preview: Option<(Vec<(f32, f32)>, StrokeKind)>,
+    crop_overlay: Option<((f32, f32), (f32, f32), Option<CropRect>)>,
+    comment_threads: &[CommentThread],
     cx: &Context<Humanboard>,
 ) -> Div {
-    let bg = cx.theme().background;
+    let canvas_settings = crate::settings::canvas_settings();
+    let bg = canvas_settings
+        .background
+        .or_else(|| crate::theme_editor::item_colors(cx.theme().theme_name()).canvas)
+        .as_deref()
+        .and_then(parse_hex_color)
+        .unwrap_or(cx.theme().background);
+    let grid_color = cx.theme().border.opacity(0.35);
     let primary = cx.theme().primary;
     let fg = cx.theme().foreground;
     let content_colors = ContentTypeColors::from_theme(cx.theme());
@@ -761,6 +1336,14 @@ pub fn render_canvas_area(
         .bg(bg)
         .overflow_hidden()
         .relative()
+        .when(canvas_settings.show_grid, |d| {
+            d.child(render_grid(
+                canvas_offset,
+                zoom,
+                canvas_settings.grid_size,
+                grid_color,
+            ))
+        })
         .child(render_canvas(canvas_offset, zoom, items.to_vec(), content_colors))
         .children(render_items(
             items,
@@ -774,6 +1357,13 @@ pub fn render_canvas_area(
             textbox_input,
             cx,
         ))
+        .children(crate::render::comments::render_comment_badges(
+            comment_threads,
+            items,
+            canvas_offset,
+            zoom,
+            cx,
+        ))
         // Render marquee selection rectangle
         .when_some(marquee, |d, (start, current)| {
             let min_x = f32::from(start.x).min(f32::from(current.x));
@@ -801,8 +1391,68 @@ pub fn render_canvas_area(
                 d
             }
         })
+        // Shade an image being cropped, leaving the area to keep clear
+        .when_some(crop_overlay, |d, (position, image_size, selection)| {
+            let x = position.0 * zoom + f32::from(canvas_offset.x);
+            let y = position.1 * zoom + f32::from(canvas_offset.y);
+            let (w, h) = (image_size.0 * zoom, image_size.1 * zoom);
+            let keep = selection.unwrap_or(CropRect {
+                x: 0.0,
+                y: 0.0,
+                width: 0.0,
+                height: 0.0,
+            });
+            let (kx, ky) = (x + keep.x * w, y + keep.y * h);
+            let (kw, kh) = (keep.width * w, keep.height * h);
+            let shade = black().opacity(0.5);
+
+            d.child(
+                canvas(
+                    move |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        let local = |x: f32, y: f32, w: f32, h: f32| Bounds {
+                            origin: bounds.origin + point(px(x), px(y)),
+                            size: size(px(w), px(h)),
+                        };
+                        // Above, below, left of and right of the kept area
+                        window.paint_quad(fill(local(x, y, w, ky - y), shade));
+                        window.paint_quad(fill(local(x, ky + kh, w, y + h - ky - kh), shade));
+                        window.paint_quad(fill(local(x, ky, kx - x, kh), shade));
+                        window.paint_quad(fill(local(kx + kw, ky, x + w - kx - kw, kh), shade));
+                        window.paint_quad(quad(
+                            local(kx, ky, kw, kh),
+                            px(0.0),
+                            transparent_black(),
+                            px(2.0),
+                            primary,
+                            Default::default(),
+                        ));
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+        })
+        // Render the pen/highlighter stroke being drawn
+        .when_some(stroke_preview, |d, (local, kind)| {
+            let color = parse_hex_color(kind.default_color())
+                .unwrap_or(fg)
+                .opacity(kind.opacity());
+            let width = (kind.default_width() * zoom).max(1.0);
+
+            d.child(
+                canvas(
+                    move |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        paint_stroke(&local, bounds.origin, width, color, window);
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+        })
         // Render drawing preview (for TextBox, Shape, Arrow while dragging)
-        .when_some(drawing_preview, |d, (start, current, tool)| {
+        .when_some(drawing_preview, |d, (start, current, tool, is_line)| {
             // Account for dock width (44px) since mouse coords are in window space
             // but we're rendering in canvas space (after dock)
             let dock_offset = crate::render::dock::DOCK_WIDTH;
@@ -821,7 +1471,7 @@ pub fn render_canvas_area(
             // Only show if has some size
             if width > 5.0 || height > 5.0 {
                 match tool {
-                    crate::types::ToolType::Text | crate::types::ToolType::Shape => {
+                    crate::types::ToolType::Text | crate::types::ToolType::Shape if !is_line => {
                         // Rectangle preview for TextBox and Shape
                         d.child(
                             div()
@@ -836,8 +1486,9 @@ pub fn render_canvas_area(
                                 .rounded(px(4.0)),
                         )
                     }
-                    crate::types::ToolType::Arrow => {
-                        // Arrow preview - line from start to current
+                    crate::types::ToolType::Arrow | crate::types::ToolType::Shape => {
+                        // Arrow preview - line from start to current, headless for the line shape
+                        let with_head = tool == crate::types::ToolType::Arrow;
                         let arrow_start_x = start_x;
                         let arrow_start_y = start_y - 40.0; // Account for header
                         let arrow_end_x = current_x;
@@ -874,7 +1525,7 @@ pub fn render_canvas_area(
                                             let dx = f32::from(end_pt.x - start_pt.x);
                                             let dy = f32::from(end_pt.y - start_pt.y);
                                             let len = (dx * dx + dy * dy).sqrt();
-                                            if len > 10.0 {
+                                            if with_head && len > 10.0 {
                                                 let nx = dx / len;
                                                 let ny = dy / len;
                                                 let head_size = 12.0;
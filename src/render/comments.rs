@@ -0,0 +1,303 @@
+//! Comments sidebar - the board's comment threads down the right of the
+//! canvas area, and the badges that mark what they're on.
+
+use crate::app::{CommentsPanel, Humanboard};
+use crate::comments::{self, CommentAnchor, CommentThread};
+use crate::constants::COMMENTS_SIDEBAR_WIDTH;
+use crate::timestamps::unix_now;
+use crate::types::CanvasItem;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{h_flex, v_flex, ActiveTheme as _, IconName, Selectable as _, Sizable};
+
+/// Height of a comment badge, which is also the width of a single-digit one
+const BADGE_SIZE: f32 = 20.0;
+
+/// Colour of comment badges and pins
+fn badge_color() -> Hsla {
+    hsla(35.0 / 360.0, 0.9, 0.55, 1.0)
+}
+
+/// Badges counting the open threads on items, and pins for open threads on
+/// canvas points, placed over the canvas area
+pub fn render_comment_badges(
+    threads: &[CommentThread],
+    items: &[CanvasItem],
+    canvas_offset: Point<Pixels>,
+    zoom: f32,
+    cx: &Context<Humanboard>,
+) -> Vec<Stateful<Div>> {
+    let offset = (f32::from(canvas_offset.x), f32::from(canvas_offset.y));
+    let counts = comments::open_counts(threads);
+
+    // One badge per item, opening its most recent open thread
+    let mut badged = std::collections::HashSet::new();
+    comments::sorted_threads(threads, false)
+        .into_iter()
+        .filter_map(|thread| {
+            let label = match thread.anchor {
+                CommentAnchor::Item(id) if badged.insert(id) => counts[&id].to_string(),
+                CommentAnchor::Item(_) => return None,
+                CommentAnchor::Point(..) => thread.comments.len().to_string(),
+            };
+            let (x, y) = thread.position(items)?;
+            let id = thread.id;
+            Some(
+                div()
+                    .id(("comment-badge", id as usize))
+                    .absolute()
+                    .left(px(x * zoom + offset.0 - BADGE_SIZE / 2.0))
+                    .top(px(y * zoom + offset.1 - BADGE_SIZE / 2.0))
+                    .h(px(BADGE_SIZE))
+                    .min_w(px(BADGE_SIZE))
+                    .px_1()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .rounded_full()
+                    .bg(badge_color())
+                    .shadow_md()
+                    .cursor_pointer()
+                    .text_xs()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(gpui::white())
+                    .child(label)
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _, _, cx| {
+                            this.open_comments(Some(id), cx);
+                            cx.stop_propagation();
+                        }),
+                    ),
+            )
+        })
+        .collect()
+}
+
+/// Render the comments sidebar over the right of the canvas area
+pub fn render_comments_sidebar(
+    panel: &CommentsPanel,
+    threads: &[CommentThread],
+    items: &[CanvasItem],
+    cx: &Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().popover;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+
+    let open = threads.iter().filter(|t| !t.resolved).count();
+    let listed = comments::sorted_threads(threads, panel.show_resolved);
+    let now = unix_now();
+
+    let header = h_flex()
+        .flex_shrink_0()
+        .px_4()
+        .py_3()
+        .gap_2()
+        .items_center()
+        .border_b_1()
+        .border_color(border)
+        .child(
+            v_flex()
+                .flex_1()
+                .child(
+                    div()
+                        .text_sm()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(fg)
+                        .child("Comments"),
+                )
+                .child(div().text_xs().text_color(muted_fg).child(match open {
+                    1 => "1 open thread".to_string(),
+                    n => format!("{} open threads", n),
+                })),
+        )
+        .child(
+            Button::new("comments-add")
+                .icon(IconName::Plus)
+                .small()
+                .ghost()
+                .tooltip("Add comment")
+                .on_click(cx.listener(|this, _, window, cx| this.add_comment(window, cx))),
+        )
+        .child(
+            Button::new("comments-show-resolved")
+                .icon(if panel.show_resolved {
+                    IconName::Eye
+                } else {
+                    IconName::EyeOff
+                })
+                .small()
+                .ghost()
+                .selected(panel.show_resolved)
+                .tooltip("Show resolved threads")
+                .on_click(cx.listener(|this, _, _, cx| this.toggle_resolved_comments(cx))),
+        )
+        .child(
+            Button::new("comments-close")
+                .icon(IconName::Close)
+                .small()
+                .ghost()
+                .tooltip("Close")
+                .on_click(cx.listener(|this, _, _, cx| this.close_comments(cx))),
+        );
+
+    let empty = listed.is_empty().then(|| {
+        div()
+            .px_4()
+            .py_6()
+            .text_sm()
+            .text_color(muted_fg)
+            .child("No open comments. Select an item and choose Add Comment to start a thread.")
+    });
+    let cards = listed
+        .into_iter()
+        .map(|thread| render_thread(thread, items, panel.focused == Some(thread.id), now, cx));
+
+    let hint = panel.pending.map(|_| {
+        div()
+            .flex_shrink_0()
+            .px_4()
+            .py_3()
+            .border_t_1()
+            .border_color(border)
+            .text_xs()
+            .text_color(muted_fg)
+            .child("Type the comment in the command palette and press Enter")
+    });
+
+    div()
+        .id("comments-sidebar")
+        .absolute()
+        .top_0()
+        .right_0()
+        .bottom_0()
+        .w(px(COMMENTS_SIDEBAR_WIDTH))
+        .flex()
+        .flex_col()
+        .bg(bg)
+        .border_l_1()
+        .border_color(border)
+        .shadow_lg()
+        // Keep clicks and scrolling off the canvas underneath
+        .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+        .on_scroll_wheel(|_, _, cx| cx.stop_propagation())
+        .child(header)
+        .child(
+            div()
+                .id("comments-list")
+                .flex_1()
+                .min_h_0()
+                .p_2()
+                .overflow_y_scroll()
+                .children(empty)
+                .child(v_flex().gap_2().children(cards)),
+        )
+        .children(hint)
+}
+
+/// A thread's card, which centres the canvas on what it's about when clicked
+fn render_thread(
+    thread: &CommentThread,
+    items: &[CanvasItem],
+    focused: bool,
+    now: u64,
+    cx: &Context<Humanboard>,
+) -> impl IntoElement {
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let primary = cx.theme().primary;
+    let list_hover = cx.theme().list_hover;
+
+    let id = thread.id;
+    let resolved = thread.resolved;
+    let messages = thread.comments.iter().map(|comment| {
+        v_flex()
+            .gap_0p5()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(fg)
+                            .child(comment.author.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(muted_fg)
+                            .child(comments::written_at(comment.created_at, now)),
+                    ),
+            )
+            .child(div().text_sm().text_color(fg).child(comment.text.clone()))
+    });
+
+    v_flex()
+        .id(("comment-thread", id as usize))
+        .gap_2()
+        .px_3()
+        .py_2()
+        .rounded(px(8.0))
+        .border_1()
+        .border_color(if focused { primary } else { border })
+        .when(resolved, |d| d.opacity(0.6))
+        .hover(|s| s.bg(list_hover))
+        .cursor_pointer()
+        .on_click(cx.listener(move |this, _, window, cx| {
+            this.jump_to_comment_thread(id, window, cx);
+        }))
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(
+                    div()
+                        .flex_1()
+                        .min_w_0()
+                        .overflow_hidden()
+                        .text_ellipsis()
+                        .text_xs()
+                        .text_color(muted_fg)
+                        .child(comments::anchor_label(thread.anchor, items)),
+                )
+                .when(resolved, |d| {
+                    d.child(div().text_xs().text_color(muted_fg).child("Resolved"))
+                }),
+        )
+        .children(messages)
+        .child(
+            h_flex()
+                .gap_1()
+                .child(
+                    Button::new(("comment-reply", id as usize))
+                        .label("Reply")
+                        .xsmall()
+                        .ghost()
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.reply_to_comment_thread(id, window, cx);
+                            cx.stop_propagation();
+                        })),
+                )
+                .child(
+                    Button::new(("comment-resolve", id as usize))
+                        .icon(if resolved {
+                            IconName::Undo
+                        } else {
+                            IconName::Check
+                        })
+                        .label(if resolved { "Reopen" } else { "Resolve" })
+                        .xsmall()
+                        .ghost()
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.set_comment_thread_resolved(id, !resolved, cx);
+                            cx.stop_propagation();
+                        })),
+                ),
+        )
+}
@@ -2,7 +2,8 @@
 
 use crate::app::Humanboard;
 use crate::focus_ring::focus_ring_shadow;
-use crate::types::ToolType;
+use crate::types::{ShapeType, ToolType};
+use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::ActiveTheme as _;
 
@@ -52,19 +53,51 @@ fn render_tool_button(
         )
 }
 
+/// Render a button in the shape picker shown under the Shape tool
+fn render_shape_button(shape: ShapeType, selected: bool, cx: &App) -> Stateful<Div> {
+    let fg = if selected {
+        cx.theme().primary
+    } else {
+        cx.theme().muted_foreground
+    };
+    let selected_bg = cx.theme().primary.opacity(0.15);
+    let hover_bg = cx.theme().muted;
+    let focus_color = cx.theme().primary;
+
+    div()
+        .id(ElementId::Name(format!("shape-{:?}", shape).into()))
+        .w(px(28.0))
+        .h(px(28.0))
+        .rounded(px(6.0))
+        .when(selected, |d| d.bg(selected_bg))
+        .hover(|s| s.bg(if selected { selected_bg } else { hover_bg }))
+        .focus(|s| s.shadow(focus_ring_shadow(focus_color)))
+        .cursor_pointer()
+        .flex()
+        .items_center()
+        .justify_center()
+        .child(div().text_size(px(14.0)).text_color(fg).child(shape.icon()))
+}
+
 /// Render the tool dock
-pub fn render_tool_dock<F>(
+pub fn render_tool_dock<F, G>(
     selected_tool: ToolType,
+    selected_shape: ShapeType,
     on_select: F,
+    on_select_shape: G,
     cx: &Context<Humanboard>,
 ) -> Stateful<Div>
 where
     F: Fn(&mut Humanboard, ToolType, &mut Window, &mut Context<Humanboard>) + 'static + Clone,
+    G: Fn(&mut Humanboard, ShapeType, &mut Window, &mut Context<Humanboard>) + 'static + Clone,
 {
     let on_select1 = on_select.clone();
     let on_select2 = on_select.clone();
     let on_select3 = on_select.clone();
     let on_select4 = on_select.clone();
+    let on_select5 = on_select.clone();
+    let on_select6 = on_select.clone();
+    let on_select7 = on_select.clone();
 
     let border_color = cx.theme().border;
 
@@ -99,9 +132,50 @@ where
                 })),
         )
         .child(
-            render_tool_button(ToolType::Shape, selected_tool == ToolType::Shape, "□", cx)
+            render_tool_button(
+                ToolType::Shape,
+                selected_tool == ToolType::Shape,
+                selected_shape.icon(),
+                cx,
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                on_select4(this, ToolType::Shape, window, cx);
+            })),
+        )
+        .child(
+            render_tool_button(ToolType::Pen, selected_tool == ToolType::Pen, "✎", cx).on_click(
+                cx.listener(move |this, _, window, cx| {
+                    on_select5(this, ToolType::Pen, window, cx);
+                }),
+            ),
+        )
+        .child(
+            render_tool_button(
+                ToolType::Highlighter,
+                selected_tool == ToolType::Highlighter,
+                "▍",
+                cx,
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                on_select6(this, ToolType::Highlighter, window, cx);
+            })),
+        )
+        .child(
+            render_tool_button(ToolType::Eraser, selected_tool == ToolType::Eraser, "⌫", cx)
                 .on_click(cx.listener(move |this, _, window, cx| {
-                    on_select4(this, ToolType::Shape, window, cx);
+                    on_select7(this, ToolType::Eraser, window, cx);
                 })),
         )
+        // Shape picker, expanded while the Shape tool is active
+        .when(selected_tool == ToolType::Shape, |d| {
+            d.child(div().w(px(24.0)).h(px(1.0)).my(px(4.0)).bg(border_color))
+                .children(ShapeType::ALL.into_iter().map(|shape| {
+                    let on_select_shape = on_select_shape.clone();
+                    render_shape_button(shape, shape == selected_shape, cx).on_click(cx.listener(
+                        move |this, _, window, cx| {
+                            on_select_shape(this, shape, window, cx);
+                        },
+                    ))
+                }))
+        })
 }
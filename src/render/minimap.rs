@@ -0,0 +1,140 @@
+//! Minimap overlay - board overview in the corner of the canvas area
+//!
+//! The minimap is a full-size canvas element layered over the canvas area, so
+//! it knows the area's on-screen bounds (and therefore the visible region)
+//! without duplicating the window layout maths. It only paints and handles
+//! mouse events inside its own corner rectangle.
+
+use crate::app::Humanboard;
+use crate::minimap::{
+    MINIMAP_HEIGHT, MINIMAP_MARGIN, MINIMAP_PADDING, MINIMAP_WIDTH, MinimapLayout, visible_region,
+};
+use crate::render::canvas::ContentTypeColors;
+use crate::types::CanvasItem;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+
+/// Render the minimap for the given board state
+pub fn render_minimap(
+    canvas_offset: Point<Pixels>,
+    zoom: f32,
+    items: &[CanvasItem],
+    cx: &Context<Humanboard>,
+) -> impl IntoElement {
+    let colors = ContentTypeColors::from_theme(cx.theme());
+    let bg = cx.theme().title_bar.opacity(0.92);
+    let border = cx.theme().border;
+    let primary = cx.theme().primary;
+
+    let layout = MinimapLayout::fit(items, (MINIMAP_WIDTH, MINIMAP_HEIGHT), MINIMAP_PADDING);
+    let rects: Vec<((f32, f32, f32, f32), Hsla)> = layout
+        .map(|layout| {
+            items
+                .iter()
+                .map(|item| {
+                    let (x, y) = layout.to_minimap(item.position);
+                    let (w, h) = (item.size.0 * layout.scale, item.size.1 * layout.scale);
+                    (
+                        (x, y, w.max(1.0), h.max(1.0)),
+                        colors.for_content(&item.content),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let offset = (f32::from(canvas_offset.x), f32::from(canvas_offset.y));
+    let entity = cx.entity();
+
+    canvas(
+        |_, _, _| (),
+        move |bounds, _, window, _| {
+            let area_size = (f32::from(bounds.size.width), f32::from(bounds.size.height));
+            let map_bounds = Bounds {
+                origin: point(
+                    bounds.right() - px(MINIMAP_MARGIN + MINIMAP_WIDTH),
+                    bounds.bottom() - px(MINIMAP_MARGIN + MINIMAP_HEIGHT),
+                ),
+                size: size(px(MINIMAP_WIDTH), px(MINIMAP_HEIGHT)),
+            };
+            let local = move |(x, y, w, h): (f32, f32, f32, f32)| Bounds {
+                origin: map_bounds.origin + point(px(x), px(y)),
+                size: size(px(w), px(h)),
+            };
+
+            window.paint_quad(quad(
+                map_bounds,
+                px(6.0),
+                bg,
+                px(1.0),
+                border,
+                Default::default(),
+            ));
+
+            let Some(layout) = layout else {
+                return;
+            };
+
+            window.with_content_mask(Some(ContentMask { bounds: map_bounds }), |window| {
+                for &(rect, color) in &rects {
+                    window.paint_quad(fill(local(rect), color));
+                }
+
+                let (vx, vy, vw, vh) = visible_region(offset, zoom, area_size);
+                let (x, y) = layout.to_minimap((vx, vy));
+                let viewport = local((x, y, vw * layout.scale, vh * layout.scale));
+                window.paint_quad(quad(
+                    viewport,
+                    px(2.0),
+                    primary.opacity(0.12),
+                    px(1.5),
+                    primary,
+                    Default::default(),
+                ));
+            });
+
+            // The clicked canvas point is panned to the centre of the canvas area
+            let target = move |position: Point<Pixels>| {
+                let p = position - map_bounds.origin;
+                layout.to_canvas((f32::from(p.x), f32::from(p.y)))
+            };
+
+            let down_entity = entity.clone();
+            window.on_mouse_event(move |event: &MouseDownEvent, phase, _, cx| {
+                if phase == DispatchPhase::Bubble
+                    && event.button == MouseButton::Left
+                    && map_bounds.contains(&event.position)
+                {
+                    down_entity.update(cx, |this, cx| {
+                        this.minimap_dragging = true;
+                        this.pan_to_canvas_point(target(event.position), area_size, cx);
+                    });
+                    cx.stop_propagation();
+                }
+            });
+
+            let move_entity = entity.clone();
+            window.on_mouse_event(move |event: &MouseMoveEvent, phase, _, cx| {
+                if phase == DispatchPhase::Bubble && move_entity.read(cx).minimap_dragging {
+                    move_entity.update(cx, |this, cx| {
+                        if event.pressed_button == Some(MouseButton::Left) {
+                            this.pan_to_canvas_point(target(event.position), area_size, cx);
+                        } else {
+                            this.minimap_dragging = false;
+                        }
+                    });
+                    cx.stop_propagation();
+                }
+            });
+
+            let up_entity = entity.clone();
+            window.on_mouse_event(move |_: &MouseUpEvent, phase, _, cx| {
+                if phase == DispatchPhase::Bubble && up_entity.read(cx).minimap_dragging {
+                    up_entity.update(cx, |this, _| this.minimap_dragging = false);
+                    cx.stop_propagation();
+                }
+            });
+        },
+    )
+    .absolute()
+    .size_full()
+}
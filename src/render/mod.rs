@@ -2,21 +2,28 @@
 //!
 //! This module is split into submodules for maintainability:
 //! - `canvas`: Canvas and item rendering
+//! - `comments`: Comments sidebar and the badges on commented items
 //! - `dock`: Tool dock (left sidebar)
+//! - `minimap`: Board overview in the corner of the canvas
 //! - `preview`: Preview panel, tabs, splitter
 //! - `overlays`: Header, footer, shortcuts, command palette
 
 pub mod canvas;
+pub mod comments;
 pub mod dock;
+pub mod minimap;
 pub mod overlays;
 pub mod preview;
 
 // Re-export commonly used items
 pub use canvas::{render_canvas, render_canvas_area, render_items};
+pub use comments::{render_comment_badges, render_comments_sidebar};
 pub use dock::render_tool_dock;
+pub use minimap::render_minimap;
 pub use overlays::{
     render_command_palette, render_create_board_modal, render_footer_bar, render_header_bar,
-    render_settings_modal, render_shortcuts_overlay,
+    render_image_info_overlay, render_pdf_annotator, render_pdf_page_picker,
+    render_presentation_progress, render_settings_modal, render_shortcuts_overlay,
 };
 pub use preview::{
     render_drag_ghost, render_preview_panel, render_search_bar, render_selected_item_label,
@@ -26,12 +33,15 @@ pub use preview::{
 
 use crate::actions::{
     CancelTextboxEdit, CloseCommandPalette, ClosePreview, CloseTab, CmdPaletteDown, CmdPaletteUp,
-    CommandPalette, DeleteSelected, DeselectAll, DuplicateSelected, GoBack, GoForward, GoHome,
-    MoveTabToOtherPane, NewBoard, NextPage, NextSearchMatch, NextTab, NudgeDown, NudgeLeft,
-    NudgeRight, NudgeUp, OpenFile, OpenSettings, Paste, PdfZoomIn, PdfZoomOut, PdfZoomReset,
-    PrevPage, PrevSearchMatch, PrevTab, Redo, ReopenClosedTab, SaveCode, SelectAll, ShowShortcuts,
-    ToggleCommandPalette, TogglePaneSplit, TogglePreviewSearch, ToggleSplit, ToolArrow, ToolSelect,
-    ToolShape, ToolText, Undo, ZoomIn, ZoomOut, ZoomReset,
+    CommandPalette, CommitCrop, DeleteSelected, DeselectAll, DuplicateSelected, ExitPresentation,
+    GoBack, GoForward, GoHome, GoToBookmark, MoveTabToOtherPane, NewBoard, NextPage,
+    NextSearchMatch, NextTab, NudgeDown, NudgeLeft, NudgeRight, NudgeUp, OpenFile, OpenSettings,
+    Paste, PdfZoomIn, PdfZoomOut, PdfZoomReset, PresentationNext, PresentationPrev, PrevPage,
+    PrevSearchMatch, PrevTab, Redo, ReopenClosedTab, SaveCode, SelectAll, ShowImageInfo, ShowShortcuts,
+    StartPresentation, TextboxBold, TextboxCode, TextboxItalic, TextboxStrikethrough,
+    ToggleCommandPalette, ToggleMinimap, TogglePaneSplit, TogglePreviewSearch, ToggleSplit,
+    ToolArrow, ToolEraser, ToolHighlighter, ToolPen, ToolSelect, ToolShape, ToolText, Undo, ZoomIn,
+    ZoomOut, ZoomReset, ZoomToFit, ZoomToSelection,
 };
 use crate::app::{AppView, Humanboard, SplitDirection};
 use crate::focus::FocusContext;
@@ -66,6 +76,11 @@ impl Render for Humanboard {
             window.request_animation_frame();
         }
 
+        // Move items towards an automatic layout
+        if self.update_layout_animation() {
+            window.request_animation_frame();
+        }
+
         // Update modal animations and request next frame if still animating
         if self.modal_animations.update() {
             window.request_animation_frame();
@@ -97,6 +112,9 @@ impl Render for Humanboard {
             AppView::Onboarding => self.render_onboarding_view(cx),
             AppView::Home => self.render_home_view(window, cx),
             AppView::Landing => self.render_landing_view(cx),
+            AppView::Board(_) if self.presentation.is_some() => {
+                self.render_presentation_view(window, cx)
+            }
             AppView::Board(_) => self.render_board_view(window, cx),
         };
 
@@ -105,9 +123,22 @@ impl Render for Humanboard {
 
         // Process completed background tasks
         self.background.process_results();
+        self.poll_external_commands(cx);
+        self.poll_saved_templates(cx);
+        self.poll_palettes(cx);
+        self.poll_dropped_files(cx);
+        self.poll_media_posters(cx);
+        self.poll_waveforms(cx);
+        self.poll_oriented_images(cx);
+        self.poll_baked_images(cx);
+        self.poll_capture_dates(cx);
+        self.poll_pdf_pages(cx);
+        self.poll_pdf_annotator(cx);
+        self.poll_file_prompts(cx);
 
-        // Check for settings file changes
+        // Check for settings and keymap file changes
         self.check_settings_reload(cx);
+        self.check_keymap_reload(cx);
 
         // Check for debounced save
         if let Some(ref mut board) = self.board {
@@ -140,6 +171,25 @@ impl Render for Humanboard {
             .when(self.show_shortcuts, |d| {
                 d.child(render_shortcuts_overlay(cx))
             })
+            .when_some(self.image_info.as_ref(), |d, info| {
+                d.child(render_image_info_overlay(info, cx))
+            })
+            .when_some(self.pdf_page_picker.as_ref(), |d, picker| {
+                d.child(render_pdf_page_picker(picker, cx))
+            })
+            .when_some(self.pdf_annotator.as_ref(), |d, annotator| {
+                let annotations = self
+                    .board
+                    .as_ref()
+                    .map(|board| board.pdf_annotations.as_slice())
+                    .unwrap_or_default();
+                d.child(render_pdf_annotator(
+                    annotator,
+                    annotations,
+                    window.bounds().size,
+                    cx,
+                ))
+            })
             .when(self.show_settings, |d| {
                 d.child(render_settings_modal(
                     &self.settings.theme,
@@ -147,6 +197,11 @@ impl Render for Humanboard {
                     self.settings_theme_index,
                     &self.settings_theme_scroll,
                     self.settings_tab,
+                    &crate::settings::canvas_settings(),
+                    self.board
+                        .as_ref()
+                        .map(|_| crate::settings::has_board_settings()),
+                    self.theme_editor.as_ref(),
                     &self.focus.modal,
                     self.modal_animations.settings_opacity(),
                     cx,
@@ -160,6 +215,8 @@ impl Render for Humanboard {
                     d.child(render_create_board_modal(
                         input,
                         &self.create_board_location,
+                        &self.create_board_template,
+                        &self.saved_templates,
                         &self.focus.modal,
                         self.modal_animations.create_board_opacity(),
                         cx,
@@ -234,6 +291,18 @@ impl Humanboard {
                 this.selected_tool = crate::types::ToolType::Shape;
                 cx.notify();
             }))
+            .on_action(cx.listener(|this, _: &ToolPen, _, cx| {
+                this.selected_tool = crate::types::ToolType::Pen;
+                cx.notify();
+            }))
+            .on_action(cx.listener(|this, _: &ToolHighlighter, _, cx| {
+                this.selected_tool = crate::types::ToolType::Highlighter;
+                cx.notify();
+            }))
+            .on_action(cx.listener(|this, _: &ToolEraser, _, cx| {
+                this.selected_tool = crate::types::ToolType::Eraser;
+                cx.notify();
+            }))
             .child(render_landing_page(
                 &self.board_index,
                 self.editing_board_id.as_deref(),
@@ -254,16 +323,8 @@ impl Humanboard {
         // Poll for file picker results (from Cmd+O)
         if let Some(rx) = &self.file_drop_rx {
             if let Ok((pos, paths)) = rx.try_recv() {
-                if let Some(ref mut board) = self.board {
-                    let errors = board.handle_file_drop(pos, paths);
-                    // Show toast notifications for any file copy errors
-                    for error in errors {
-                        self.toast_manager.push(crate::notifications::Toast::error(error)
-                            .with_action(crate::notifications::ToastAction::retry()));
-                    }
-                }
                 self.file_drop_rx = None;
-                cx.notify();
+                self.drop_files(pos, paths, cx);
             }
         }
 
@@ -277,22 +338,30 @@ impl Humanboard {
             self.ensure_code_editors(window, cx);
         }
 
-        // Ensure YouTube WebViews are created for any YouTube items
-        let youtube_errors = self.ensure_youtube_webviews(window, cx);
+        // Media items show posters until activated; only activated items in
+        // view get WebViews, within the player budget
+        self.ensure_media_posters();
+        self.ensure_waveforms();
+        self.ensure_oriented_images();
+        let visible = self.fully_visible_items(window);
+        self.enforce_media_budget(&visible);
+
+        // Ensure YouTube WebViews are created for activated YouTube items
+        let youtube_errors = self.ensure_youtube_webviews(&visible, window, cx);
         for error in youtube_errors {
             self.toast_manager.push(crate::notifications::Toast::error(error)
                 .with_action(crate::notifications::ToastAction::reload_webview()));
         }
 
-        // Ensure Audio WebViews are created for any Audio items
-        let audio_errors = self.ensure_audio_webviews(window, cx);
+        // Ensure Audio WebViews are created for activated Audio items
+        let audio_errors = self.ensure_audio_webviews(&visible, window, cx);
         for error in audio_errors {
             self.toast_manager.push(crate::notifications::Toast::error(error)
                 .with_action(crate::notifications::ToastAction::reload_webview()));
         }
 
-        // Ensure Video WebViews are created for any Video items
-        let video_errors = self.ensure_video_webviews(window, cx);
+        // Ensure Video WebViews are created for activated Video items
+        let video_errors = self.ensure_video_webviews(&visible, window, cx);
         for error in video_errors {
             self.toast_manager.push(crate::notifications::Toast::error(error)
                 .with_action(crate::notifications::ToastAction::reload_webview()));
@@ -337,11 +406,41 @@ impl Humanboard {
         };
 
         // Drawing preview state (for TextBox, Shape, Arrow while dragging)
+        let drawing_line = self.selected_tool == crate::types::ToolType::Shape
+            && self.selected_shape == crate::types::ShapeType::Line;
         let drawing_preview = match (self.drawing_start, self.drawing_current) {
-            (Some(start), Some(current)) => Some((start, current, self.selected_tool)),
+            (Some(start), Some(current)) => {
+                Some((start, current, self.selected_tool, drawing_line))
+            }
             _ => None,
         };
 
+        // In-progress pen/highlighter stroke, placed where the canvas is now
+        let stroke_preview = self
+            .selected_tool
+            .stroke_kind()
+            .zip(self.board.as_ref())
+            .filter(|_| !self.stroke_points.is_empty())
+            .map(|(kind, board)| {
+                let trail = self
+                    .stroke_points
+                    .iter()
+                    .map(|&(x, y)| {
+                        let screen = board.canvas_to_screen(point(px(x), px(y)));
+                        (f32::from(screen.x), f32::from(screen.y))
+                    })
+                    .collect::<Vec<_>>();
+                (trail, kind)
+            });
+
+        // Image being cropped: where its visible image sits and the area kept so far
+        let crop_overlay = self.crop_editing.as_ref().and_then(|crop| {
+            let item = self.board.as_ref()?.get_item(crop.item_id)?;
+            let (position, size) =
+                crate::image_edit::sub_bounds(item.position, item.size, crop.image_rect);
+            Some((position, size, crop.selection))
+        });
+
         // Get board name from index
         let board_name = if let AppView::Board(ref id) = self.view {
             self.board_index.get_board(id).map(|m| m.name.clone())
@@ -408,12 +507,21 @@ impl Humanboard {
             .on_action(cx.listener(|this, _: &ZoomIn, window, cx| this.zoom_in(window, cx)))
             .on_action(cx.listener(|this, _: &ZoomOut, window, cx| this.zoom_out(window, cx)))
             .on_action(cx.listener(|this, _: &ZoomReset, _, cx| this.zoom_reset(cx)))
+            .on_action(cx.listener(|this, _: &ZoomToFit, window, cx| this.zoom_to_fit(window, cx)))
+            .on_action(cx.listener(|this, _: &ZoomToSelection, window, cx| {
+                this.zoom_to_selection(window, cx)
+            }))
+            .on_action(cx.listener(|this, action: &GoToBookmark, _, cx| {
+                this.go_to_bookmark(action.index, cx)
+            }))
             .on_action(cx.listener(|this, _: &DeleteSelected, _, cx| this.delete_selected(cx)))
             .on_action(
                 cx.listener(|this, _: &DuplicateSelected, _, cx| this.duplicate_selected(cx)),
             )
             .on_action(cx.listener(|this, _: &SelectAll, _, cx| this.select_all(cx)))
             .on_action(cx.listener(|this, _: &DeselectAll, _, cx| this.deselect_all(cx)))
+            .on_action(cx.listener(|this, _: &CommitCrop, _, cx| this.commit_crop(cx)))
+            .on_action(cx.listener(|this, _: &ShowImageInfo, _, cx| this.toggle_image_info(cx)))
             .on_action(cx.listener(|this, _: &Paste, window, cx| this.paste(window, cx)))
             .on_action(cx.listener(|this, _: &NudgeUp, _, cx| this.nudge_up(cx)))
             .on_action(cx.listener(|this, _: &NudgeDown, _, cx| this.nudge_down(cx)))
@@ -445,6 +553,10 @@ impl Humanboard {
                 cx.listener(|this, _: &MoveTabToOtherPane, _, cx| this.move_tab_to_other_pane(cx)),
             )
             .on_action(cx.listener(|this, _: &ShowShortcuts, _, cx| this.toggle_shortcuts(cx)))
+            .on_action(cx.listener(|this, _: &ToggleMinimap, _, cx| this.toggle_minimap(cx)))
+            .on_action(cx.listener(|this, _: &StartPresentation, window, cx| {
+                this.start_presentation(window, cx)
+            }))
             .on_action(cx.listener(|this, _: &CommandPalette, window, cx| {
                 this.show_command_palette(window, cx)
             }))
@@ -489,11 +601,35 @@ impl Humanboard {
                 this.selected_tool = crate::types::ToolType::Shape;
                 cx.notify();
             }))
+            .on_action(cx.listener(|this, _: &ToolPen, _, cx| {
+                this.selected_tool = crate::types::ToolType::Pen;
+                cx.notify();
+            }))
+            .on_action(cx.listener(|this, _: &ToolHighlighter, _, cx| {
+                this.selected_tool = crate::types::ToolType::Highlighter;
+                cx.notify();
+            }))
+            .on_action(cx.listener(|this, _: &ToolEraser, _, cx| {
+                this.selected_tool = crate::types::ToolType::Eraser;
+                cx.notify();
+            }))
             .on_action(cx.listener(|this, _: &CancelTextboxEdit, window, cx| {
                 if this.editing_textbox_id.is_some() {
                     this.cancel_textbox_editing_with_window(window, cx);
                 }
             }))
+            .on_action(cx.listener(|this, _: &TextboxBold, window, cx| {
+                this.wrap_textbox_selection("**", window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &TextboxItalic, window, cx| {
+                this.wrap_textbox_selection("_", window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &TextboxStrikethrough, window, cx| {
+                this.wrap_textbox_selection("~~", window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &TextboxCode, window, cx| {
+                this.wrap_textbox_selection("`", window, cx);
+            }))
             .on_drop(cx.listener(|this, paths: &ExternalPaths, window, cx| {
                 let all_paths: Vec<_> = paths.paths().to_vec();
                 if all_paths.is_empty() {
@@ -530,21 +666,28 @@ impl Humanboard {
                     point(px(canvas_center_x), px(canvas_center_y))
                 };
 
-                let count = all_paths.len();
-                if let Some(ref mut board) = this.board {
-                    board.handle_file_drop(drop_pos, all_paths);
-                }
-                // Show toast notification
-                let msg = if count == 1 {
-                    "Added 1 item".to_string()
-                } else {
-                    format!("Added {} items", count)
-                };
-                this.show_toast(crate::notifications::Toast::success(msg));
-                cx.notify();
+                this.drop_files(drop_pos, all_paths, cx);
             }));
 
         let selected_tool = self.selected_tool;
+        let selected_shape = self.selected_shape;
+        let minimap = self
+            .show_minimap
+            .then(|| render_minimap(canvas_offset, zoom, &items, cx));
+        let comment_threads = self
+            .board
+            .as_ref()
+            .map(|b| b.comment_threads.clone())
+            .unwrap_or_default();
+        let comments_panel = self.comments_panel.clone();
+        let comments_sidebar = comments_panel
+            .as_ref()
+            .map(|panel| render_comments_sidebar(panel, &comment_threads, &items, cx));
+        let pdf_annotations = self
+            .board
+            .as_ref()
+            .map(|b| b.pdf_annotations.clone())
+            .unwrap_or_default();
         let content = match preview_info {
             Some((preview_ref, split, size, tabs, active_tab, is_pane_split)) => {
                 let canvas_size = 1.0 - size;
@@ -558,10 +701,15 @@ impl Humanboard {
                         .pb(px(28.0))
                         .child(render_tool_dock(
                             selected_tool,
+                            selected_shape,
                             |this, tool, _, cx| {
                                 this.selected_tool = tool;
                                 cx.notify();
                             },
+                            |this, shape, _, cx| {
+                                this.selected_shape = shape;
+                                cx.notify();
+                            },
                             cx,
                         ))
                         // Wrap canvas + splitter + preview in a flex_1 container
@@ -590,8 +738,13 @@ impl Humanboard {
                                             self.textbox_input.as_ref(),
                                             marquee,
                                             drawing_preview,
+                                            stroke_preview,
+                                            crop_overlay,
+                                            &comment_threads,
                                             cx,
-                                        )),
+                                        ))
+                                        .children(minimap)
+                                        .children(comments_sidebar),
                                 )
                                 .child(render_splitter(SplitDirection::Vertical, cx))
                                 .child({
@@ -617,6 +770,7 @@ impl Humanboard {
                                             self.preview_search_matches.len(),
                                             self.preview_search_current,
                                             self.tab_drag_split_zone,
+                                            &pdf_annotations,
                                             cx,
                                         ))
                                     } else {
@@ -665,7 +819,11 @@ impl Humanboard {
                                                             tabs.get(active_tab),
                                                             |d, tab| {
                                                                 d.child(render_tab_content(
-                                                                    tab, true, active_tab, cx,
+                                                                    tab,
+                                                                    true,
+                                                                    active_tab,
+                                                                    &pdf_annotations,
+                                                                    cx,
                                                                 ))
                                                             },
                                                         )
@@ -687,10 +845,15 @@ impl Humanboard {
                         .pb(px(28.0))
                         .child(render_tool_dock(
                             selected_tool,
+                            selected_shape,
                             |this, tool, _, cx| {
                                 this.selected_tool = tool;
                                 cx.notify();
                             },
+                            |this, shape, _, cx| {
+                                this.selected_shape = shape;
+                                cx.notify();
+                            },
                             cx,
                         ))
                         .child(
@@ -716,8 +879,13 @@ impl Humanboard {
                                             self.textbox_input.as_ref(),
                                             marquee,
                                             drawing_preview,
+                                            stroke_preview,
+                                            crop_overlay,
+                                            &comment_threads,
                                             cx,
-                                        )),
+                                        ))
+                                        .children(minimap)
+                                        .children(comments_sidebar),
                                 )
                                 .child(render_splitter(SplitDirection::Horizontal, cx))
                                 .child({
@@ -743,6 +911,7 @@ impl Humanboard {
                                             self.preview_search_matches.len(),
                                             self.preview_search_current,
                                             self.tab_drag_split_zone,
+                                            &pdf_annotations,
                                             cx,
                                         ))
                                     } else {
@@ -791,7 +960,11 @@ impl Humanboard {
                                                             tabs.get(active_tab),
                                                             |d, tab| {
                                                                 d.child(render_tab_content(
-                                                                    tab, true, active_tab, cx,
+                                                                    tab,
+                                                                    true,
+                                                                    active_tab,
+                                                                    &pdf_annotations,
+                                                                    cx,
                                                                 ))
                                                             },
                                                         )
@@ -815,26 +988,41 @@ impl Humanboard {
                 .pb(px(28.0))
                 .child(render_tool_dock(
                     selected_tool,
+                    selected_shape,
                     |this, tool, _, cx| {
                         this.selected_tool = tool;
                         cx.notify();
                     },
+                    |this, shape, _, cx| {
+                        this.selected_shape = shape;
+                        cx.notify();
+                    },
                     cx,
                 ))
-                .child(div().flex_1().h_full().child(render_canvas_area(
-                    canvas_offset,
-                    zoom,
-                    &items,
-                    &selected_items,
-                    &self.youtube_webviews,
-                    &self.audio_webviews,
-                    &self.video_webviews,
-                    self.editing_textbox_id,
-                    self.textbox_input.as_ref(),
-                    marquee,
-                    drawing_preview,
-                    cx,
-                ))),
+                .child(
+                    div()
+                        .flex_1()
+                        .h_full()
+                        .child(render_canvas_area(
+                            canvas_offset,
+                            zoom,
+                            &items,
+                            &selected_items,
+                            &self.youtube_webviews,
+                            &self.audio_webviews,
+                            &self.video_webviews,
+                            self.editing_textbox_id,
+                            self.textbox_input.as_ref(),
+                            marquee,
+                            drawing_preview,
+                            stroke_preview,
+                            crop_overlay,
+                            &comment_threads,
+                            cx,
+                        ))
+                        .children(minimap)
+                        .children(comments_sidebar),
+                ),
         }
         .child(render_footer_bar(
             fps,
@@ -876,4 +1064,66 @@ impl Humanboard {
 
         content
     }
+    /// Render the board full-window with no chrome while presenting
+    fn render_presentation_view(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Div {
+        self.update_webview_visibility(window, cx);
+
+        let (canvas_offset, zoom, items) = if let Some(ref board) = self.board {
+            (board.canvas_offset, board.zoom, board.items.clone())
+        } else {
+            (point(px(0.0), px(0.0)), 1.0, Vec::new())
+        };
+        let progress = self.presentation.as_ref().and_then(|p| {
+            p.stops
+                .get(p.current)
+                .map(|stop| (p.current, p.stops.len(), stop.title.clone()))
+        });
+
+        div()
+            .size_full()
+            .relative()
+            .track_focus(&self.focus.canvas)
+            .key_context(FocusContext::KEY_PRESENTATION)
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _: &MouseDownEvent, window, cx| {
+                    this.presentation_next(window, cx)
+                }),
+            )
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(|this, _: &MouseDownEvent, window, cx| {
+                    this.presentation_prev(window, cx)
+                }),
+            )
+            .on_action(cx.listener(|this, _: &PresentationNext, window, cx| {
+                this.presentation_next(window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &PresentationPrev, window, cx| {
+                this.presentation_prev(window, cx)
+            }))
+            .on_action(cx.listener(|this, _: &ExitPresentation, window, cx| {
+                this.exit_presentation(window, cx)
+            }))
+            .child(render_canvas_area(
+                canvas_offset,
+                zoom,
+                &items,
+                &std::collections::HashSet::new(),
+                &self.youtube_webviews,
+                &self.audio_webviews,
+                &self.video_webviews,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                &[],
+                cx,
+            ))
+            .when_some(progress, |d, (current, total, title)| {
+                d.child(render_presentation_progress(current, total, &title, cx))
+            })
+    }
 }
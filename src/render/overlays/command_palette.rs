@@ -2,7 +2,9 @@
 
 use crate::actions::{CloseCommandPalette, CmdPaletteDown, CmdPaletteUp};
 use crate::app::Humanboard;
+use crate::command_palette::SearchResult;
 use crate::focus::FocusContext;
+use crate::fuzzy::highlight_ranges;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::input::{Input, InputState, MoveDown, MoveUp};
@@ -10,10 +12,23 @@ use gpui_component::{h_flex, v_flex, ActiveTheme as _, Icon, IconName};
 
 use super::modal_base::render_kbd;
 
+/// Render a result name with the characters that matched the query highlighted
+pub fn render_highlighted_name(result: &SearchResult, highlight: Hsla) -> StyledText {
+    let style = HighlightStyle {
+        color: Some(highlight),
+        font_weight: Some(FontWeight::BOLD),
+        ..Default::default()
+    };
+    let highlights = highlight_ranges(&result.name, &result.positions)
+        .into_iter()
+        .map(|range| (range, style));
+    StyledText::new(result.name.clone()).with_highlights(highlights)
+}
+
 /// Render the command palette popup (legacy full-screen version)
 pub fn render_command_palette(
     input: &Entity<InputState>,
-    search_results: &[(u64, String)],
+    search_results: &[SearchResult],
     selected_result: usize,
     opacity: f32,
     cx: &mut Context<Humanboard>,
@@ -160,7 +175,7 @@ fn render_palette_search_input(
 
 #[allow(clippy::too_many_arguments)]
 fn render_palette_results(
-    search_results: &[(u64, String)],
+    search_results: &[SearchResult],
     selected_result: usize,
     has_results: bool,
     show_md_hint: bool,
@@ -180,9 +195,9 @@ fn render_palette_results(
         .on_scroll_wheel(|_, _, _| {})
         .when(has_results, |d| {
             d.child(v_flex().py_2().children(
-                search_results.iter().enumerate().map(|(idx, (item_id, name))| {
+                search_results.iter().enumerate().map(|(idx, result)| {
                     let is_selected = idx == selected_result;
-                    let item_id = *item_id;
+                    let item_id = result.id;
 
                     h_flex()
                         .id(ElementId::Name(format!("result-{}", item_id).into()))
@@ -213,7 +228,7 @@ fn render_palette_results(
                                 .text_color(if is_selected { fg } else { muted_fg })
                                 .overflow_hidden()
                                 .whitespace_nowrap()
-                                .child(name.clone()),
+                                .child(render_highlighted_name(result, primary)),
                         )
                         .when(is_selected, |d| {
                             d.child(div().text_xs().text_color(muted_fg).child("↵ jump"))
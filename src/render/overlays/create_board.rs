@@ -3,16 +3,19 @@
 use crate::actions::{ModalFocusNext, ModalFocusPrev};
 use crate::app::Humanboard;
 use crate::focus::FocusContext;
+use crate::templates::{BuiltInTemplate, SavedTemplate, TemplateChoice};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::input::{Escape, Input};
 use gpui_component::{h_flex, v_flex, ActiveTheme as _, Icon, IconName};
 
-/// Render the create board modal with name input and storage location picker
+/// Render the create board modal with name input, template picker and storage location picker
 pub fn render_create_board_modal(
     input: &Entity<gpui_component::input::InputState>,
     current_location: &crate::app::StorageLocation,
+    current_template: &TemplateChoice,
+    saved_templates: &[SavedTemplate],
     modal_focus: &FocusHandle,
     opacity: f32,
     cx: &mut Context<Humanboard>,
@@ -29,6 +32,60 @@ pub fn render_create_board_modal(
     let is_icloud_selected = *current_location == crate::app::StorageLocation::ICloud;
     let is_icloud_available = crate::app::StorageLocation::ICloud.is_available();
 
+    // Built-in templates first, then the user's saved ones (newest first)
+    let template_options: Vec<(TemplateChoice, &str)> = BuiltInTemplate::ALL
+        .iter()
+        .map(|&template| (TemplateChoice::BuiltIn(template), template.description()))
+        .chain(
+            saved_templates
+                .iter()
+                .map(|template| (TemplateChoice::Saved(template.clone()), "Saved template")),
+        )
+        .collect();
+    let template_cards: Vec<Stateful<Div>> = template_options
+        .into_iter()
+        .enumerate()
+        .map(|(ix, (template, description))| {
+            let is_selected = template == *current_template;
+            let name = template.name().to_string();
+            let (card_bg, weight) = if is_selected {
+                (list_active, FontWeight::MEDIUM)
+            } else {
+                (gpui::transparent_black(), FontWeight::NORMAL)
+            };
+            v_flex()
+                .id(("template", ix))
+                .w(px(187.0))
+                .px(px(10.0))
+                .py(px(8.0))
+                .gap(px(2.0))
+                .rounded(px(6.0))
+                .border_1()
+                .border_color(if is_selected { primary } else { border })
+                .bg(card_bg)
+                .cursor_pointer()
+                .hover(|s| s.bg(list_hover))
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.set_create_board_template(template.clone(), cx);
+                }))
+                .child(
+                    div()
+                        .text_size(px(13.0))
+                        .font_weight(weight)
+                        .text_color(fg)
+                        .truncate()
+                        .child(name),
+                )
+                .child(
+                    div()
+                        .text_size(px(11.0))
+                        .text_color(muted_fg)
+                        .truncate()
+                        .child(description.to_string()),
+                )
+        })
+        .collect();
+
     deferred(
         div()
             .id("create-board-backdrop")
@@ -141,6 +198,30 @@ pub fn render_create_board_modal(
                                     )
                                     .child(Input::new(input).w_full().cleanable(true)),
                             )
+                            // Template picker
+                            .child(
+                                v_flex()
+                                    .gap(px(8.0))
+                                    .child(
+                                        div()
+                                            .text_size(px(13.0))
+                                            .font_weight(FontWeight::MEDIUM)
+                                            .text_color(fg)
+                                            .child("Template"),
+                                    )
+                                    .child(
+                                        div()
+                                            .id("template-picker")
+                                            .max_h(px(190.0))
+                                            .overflow_y_scroll()
+                                            .child(
+                                                h_flex()
+                                                    .flex_wrap()
+                                                    .gap(px(6.0))
+                                                    .children(template_cards),
+                                            ),
+                                    ),
+                            )
                             // Storage location picker
                             .child(
                                 v_flex()
@@ -1,6 +1,7 @@
 //! Header bar and footer bar components.
 
 use crate::app::Humanboard;
+use crate::command_palette::SearchResult;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::input::InputState;
@@ -12,7 +13,7 @@ use super::header_palette::render_header_center;
 pub fn render_header_bar(
     board_name: Option<String>,
     command_palette: Option<&Entity<InputState>>,
-    search_results: &[(u64, String)],
+    search_results: &[SearchResult],
     selected_result: usize,
     scroll_handle: &ScrollHandle,
     palette_mode: crate::app::CmdPaletteMode,
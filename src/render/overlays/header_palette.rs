@@ -2,17 +2,20 @@
 
 use crate::actions::{CloseCommandPalette, CmdPaletteDown, CmdPaletteUp};
 use crate::app::Humanboard;
+use crate::command_palette::SearchResult;
 use crate::focus::FocusContext;
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::input::{Input, InputState, MoveDown, MoveUp};
 use gpui_component::{h_flex, v_flex, ActiveTheme as _, Icon, IconName};
 
+use super::command_palette::render_highlighted_name;
+
 /// Render the center command palette section of the header
 #[allow(clippy::too_many_arguments)]
 pub fn render_header_center(
     command_palette: Option<&Entity<InputState>>,
-    search_results: &[(u64, String)],
+    search_results: &[SearchResult],
     selected_result: usize,
     scroll_handle: &ScrollHandle,
     _palette_mode: crate::app::CmdPaletteMode,
@@ -126,7 +129,7 @@ fn render_palette_trigger(
 
 #[allow(clippy::too_many_arguments)]
 fn render_palette_dropdown(
-    search_results: &[(u64, String)],
+    search_results: &[SearchResult],
     selected_result: usize,
     scroll_handle: &ScrollHandle,
     has_results: bool,
@@ -177,7 +180,7 @@ fn render_palette_dropdown(
 
 #[allow(clippy::too_many_arguments)]
 fn render_dropdown_results(
-    search_results: &[(u64, String)],
+    search_results: &[SearchResult],
     selected_result: usize,
     scroll_handle: &ScrollHandle,
     is_theme_mode: bool,
@@ -199,10 +202,10 @@ fn render_dropdown_results(
         .flex()
         .flex_col()
         .gap(px(2.0))
-        .children(search_results.iter().enumerate().map(|(idx, (item_id, name))| {
+        .children(search_results.iter().enumerate().map(|(idx, result)| {
             let is_selected = idx == selected_result;
-            let item_id = *item_id;
-            let name_clone = name.clone();
+            let item_id = result.id;
+            let name_clone = result.name.clone();
 
             h_flex()
                 .id(ElementId::Integer(idx as u64))
@@ -240,7 +243,7 @@ fn render_dropdown_results(
                         .text_color(if is_selected { fg } else { muted_fg })
                         .overflow_hidden()
                         .whitespace_nowrap()
-                        .child(name.clone()),
+                        .child(render_highlighted_name(result, primary)),
                 )
                 .when(is_selected, |d| {
                     d.child(
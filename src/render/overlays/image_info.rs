@@ -0,0 +1,106 @@
+//! Image info overlay - an image's size and EXIF details.
+
+use crate::app::{Humanboard, ImageInfoView};
+use gpui::*;
+use gpui_component::{h_flex, v_flex, ActiveTheme as _};
+
+use super::modal_base::render_kbd;
+
+/// Render the info card for an image item
+pub fn render_image_info_overlay(
+    info: &ImageInfoView,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().popover;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+
+    let rows = info.fields.iter().map(|(label, value)| {
+        h_flex()
+            .min_h(px(28.0))
+            .items_center()
+            .justify_between()
+            .gap_4()
+            .child(
+                div()
+                    .flex_shrink_0()
+                    .text_sm()
+                    .text_color(muted_fg)
+                    .child(*label),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(fg)
+                    .text_right()
+                    .child(value.clone()),
+            )
+    });
+
+    deferred(
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .bg(hsla(0.0, 0.0, 0.0, 0.8))
+            .flex()
+            .items_center()
+            .justify_center()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, _, cx| {
+                    this.image_info = None;
+                    cx.notify();
+                }),
+            )
+            .child(
+                v_flex()
+                    .w(px(380.0))
+                    .bg(bg)
+                    .border_1()
+                    .border_color(border)
+                    .rounded(px(16.0))
+                    .overflow_hidden()
+                    .shadow_lg()
+                    .on_mouse_down(MouseButton::Left, |_, _, _| {})
+                    // Header
+                    .child(
+                        h_flex()
+                            .px_5()
+                            .py_4()
+                            .gap_4()
+                            .border_b_1()
+                            .border_color(border)
+                            .items_center()
+                            .justify_between()
+                            .child(
+                                div()
+                                    .min_w_0()
+                                    .overflow_hidden()
+                                    .text_ellipsis()
+                                    .text_base()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(fg)
+                                    .child(info.name.clone()),
+                            )
+                            .child(render_kbd("Cmd+I", cx)),
+                    )
+                    // Fields
+                    .child(v_flex().px_5().py_4().gap_1().children(rows).when(
+                        info.fields.len() <= 1,
+                        |d| {
+                            d.child(
+                                div()
+                                    .pt_2()
+                                    .text_xs()
+                                    .text_color(muted_fg)
+                                    .child("No EXIF data in this file"),
+                            )
+                        },
+                    )),
+            ),
+    )
+    .with_priority(1000)
+}
@@ -7,20 +7,34 @@
 //! - Command palette popup
 //! - Settings modal
 //! - Create board modal
+//! - Presentation progress indicator
+//! - Image info card
+//! - PDF page picker
+//! - PDF annotator
 
 mod command_palette;
 mod create_board;
 mod header;
 mod header_palette;
+mod image_info;
 mod modal_base;
+mod pdf_annotator;
+mod pdf_page_picker;
+mod presentation;
 mod settings;
+mod settings_canvas;
 mod settings_dropdowns;
 mod shortcuts;
+mod theme_editor;
 
 // Re-export all public items
 pub use command_palette::render_command_palette;
 pub use create_board::render_create_board_modal;
 pub use header::{render_footer_bar, render_header_bar};
+pub use image_info::render_image_info_overlay;
 pub use modal_base::{FontDropdownOpen, SettingsDropdown, ThemeDropdownOpen};
+pub use pdf_annotator::{annotator_page_frame, render_pdf_annotator};
+pub use pdf_page_picker::render_pdf_page_picker;
+pub use presentation::render_presentation_progress;
 pub use settings::render_settings_modal;
 pub use shortcuts::render_shortcuts_overlay;
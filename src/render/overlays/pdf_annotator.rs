@@ -0,0 +1,411 @@
+//! PDF annotator - highlighting, noting and boxing areas of PDF pages, with a
+//! sidebar listing the PDF's annotations.
+
+use crate::app::{Humanboard, PdfAnnotator};
+use crate::pdf_annotations::{self, AnnotationKind, AnnotationTool, PageRect, PdfAnnotation};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{
+    h_flex, v_flex, ActiveTheme as _, Disableable as _, IconName, Selectable as _, Sizable,
+};
+
+use super::modal_base::render_kbd;
+
+/// Space between the annotator card and the window edge
+const INSET: f32 = 24.0;
+
+/// Height of the annotator's header
+const HEADER_HEIGHT: f32 = 56.0;
+
+/// Width of the annotation list
+const SIDEBAR_WIDTH: f32 = 300.0;
+
+/// Space around the page
+const PAGE_PADDING: f32 = 20.0;
+
+/// Size of a note's marker on the page
+const NOTE_MARKER_SIZE: f32 = 22.0;
+
+/// Where the page shows in a window of `window_size`, as (x, y, width, height)
+pub fn annotator_page_frame(
+    window_size: Size<Pixels>,
+    page_size: (f32, f32),
+) -> (f32, f32, f32, f32) {
+    let width = f32::from(window_size.width) - 2.0 * (INSET + PAGE_PADDING) - SIDEBAR_WIDTH;
+    let height = f32::from(window_size.height) - 2.0 * (INSET + PAGE_PADDING) - HEADER_HEIGHT;
+    pdf_annotations::fit_rect(
+        (
+            INSET + PAGE_PADDING,
+            INSET + HEADER_HEIGHT + PAGE_PADDING,
+            width.max(0.0),
+            height.max(0.0),
+        ),
+        page_size,
+    )
+}
+
+/// Render the annotator for a PDF, over the whole window
+pub fn render_pdf_annotator(
+    annotator: &PdfAnnotator,
+    annotations: &[PdfAnnotation],
+    window_size: Size<Pixels>,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().popover;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let primary = cx.theme().primary;
+
+    let listed = pdf_annotations::annotations_for(annotations, &annotator.path);
+    let page = annotator.page;
+    let page_count = annotator.page_count.unwrap_or(page);
+
+    let page_status = match annotator.page_count {
+        Some(count) => format!("Page {} of {}", page, count),
+        None => format!("Page {}", page),
+    };
+
+    let tools = AnnotationTool::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, &tool)| {
+            Button::new(("pdf-annotation-tool", index))
+                .label(tool.label())
+                .small()
+                .ghost()
+                .selected(annotator.tool == tool)
+                .on_click(cx.listener(move |this, _, _, cx| this.set_annotation_tool(tool, cx)))
+        });
+
+    let header = h_flex()
+        .h(px(HEADER_HEIGHT))
+        .flex_shrink_0()
+        .px_5()
+        .gap_4()
+        .border_b_1()
+        .border_color(border)
+        .items_center()
+        .justify_between()
+        .child(
+            v_flex()
+                .min_w_0()
+                .child(
+                    div()
+                        .overflow_hidden()
+                        .text_ellipsis()
+                        .text_base()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(fg)
+                        .child(annotator.name.clone()),
+                )
+                .child(div().text_xs().text_color(muted_fg).child(page_status)),
+        )
+        .child(h_flex().gap_1().children(tools))
+        .child(
+            h_flex()
+                .gap_1()
+                .items_center()
+                .child(
+                    Button::new("pdf-annotation-prev")
+                        .icon(IconName::ChevronLeft)
+                        .small()
+                        .ghost()
+                        .tooltip("Previous page")
+                        .disabled(page <= 1)
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.go_to_annotator_page(page - 1, cx);
+                        })),
+                )
+                .child(
+                    Button::new("pdf-annotation-next")
+                        .icon(IconName::ChevronRight)
+                        .small()
+                        .ghost()
+                        .tooltip("Next page")
+                        .disabled(page >= page_count)
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.go_to_annotator_page(page + 1, cx);
+                        })),
+                )
+                .child(div().ml_2().child(render_kbd("Esc", cx))),
+        );
+
+    // The page area is left empty; the page is laid over it at a known spot
+    // so clicks can be placed on it
+    let status = match (&annotator.image, &annotator.error) {
+        (_, Some(error)) => Some(format!("Couldn't render the page: {}", error)),
+        (None, None) => Some("Rendering page…".to_string()),
+        (Some(_), None) => None,
+    };
+    let page_area = div()
+        .flex_1()
+        .flex()
+        .items_center()
+        .justify_center()
+        .text_sm()
+        .text_color(muted_fg)
+        .when_some(status, |d, status| d.child(status));
+
+    let hint = match annotator.tool {
+        AnnotationTool::Highlight => "Drag over text to highlight it",
+        AnnotationTool::Note => "Click the page to add a note, or a note to change it",
+        AnnotationTool::Box => "Drag to draw a box",
+    };
+    let rows = listed.iter().enumerate().map(|(index, annotation)| {
+        render_annotation_row(index + 1, annotation, annotation.page == page, cx)
+    });
+    let sidebar = v_flex()
+        .w(px(SIDEBAR_WIDTH))
+        .flex_shrink_0()
+        .border_l_1()
+        .border_color(border)
+        .child(
+            div()
+                .px_4()
+                .py_3()
+                .text_xs()
+                .font_weight(FontWeight::SEMIBOLD)
+                .text_color(muted_fg)
+                .child(match listed.len() {
+                    0 => "No annotations".to_string(),
+                    1 => "1 annotation".to_string(),
+                    n => format!("{} annotations", n),
+                }),
+        )
+        .child(
+            div()
+                .id("pdf-annotation-list")
+                .flex_1()
+                .min_h_0()
+                .px_2()
+                .overflow_y_scroll()
+                .child(v_flex().gap_1().children(rows)),
+        )
+        .child(
+            div()
+                .px_4()
+                .py_3()
+                .border_t_1()
+                .border_color(border)
+                .text_xs()
+                .text_color(muted_fg)
+                .child(hint),
+        );
+
+    let page_view = annotator.image.as_ref().map(|image| {
+        let (x, y, width, height) = annotator_page_frame(window_size, image.size);
+        let on_page = listed
+            .iter()
+            .enumerate()
+            .filter(|(_, annotation)| annotation.page == page)
+            .flat_map(|(index, annotation)| {
+                render_marks(index + 1, annotation, (width, height), primary)
+            });
+        let drag = annotator.drag.map(|(start, end)| {
+            let rect = PageRect::from_corners(start, end);
+            area(rect, (width, height))
+                .border_1()
+                .border_color(primary)
+                .bg(primary.opacity(0.12))
+        });
+
+        div()
+            .absolute()
+            .left(px(x))
+            .top(px(y))
+            .w(px(width))
+            .h(px(height))
+            .bg(gpui::white())
+            .shadow_lg()
+            .cursor(CursorStyle::Crosshair)
+            .child(
+                img(image.image.clone())
+                    .size_full()
+                    .object_fit(ObjectFit::Contain),
+            )
+            .children(on_page)
+            .children(drag)
+    });
+
+    deferred(
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .bg(hsla(0.0, 0.0, 0.0, 0.8))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, event: &MouseDownEvent, window, cx| {
+                    this.annotator_mouse_down(event.position, window, cx);
+                    cx.stop_propagation();
+                }),
+            )
+            .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, window, cx| {
+                this.annotator_mouse_move(event.position, window, cx);
+            }))
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _, _, cx| {
+                    this.annotator_mouse_up(cx);
+                    cx.stop_propagation();
+                }),
+            )
+            .child(
+                v_flex()
+                    .absolute()
+                    .top(px(INSET))
+                    .left(px(INSET))
+                    .right(px(INSET))
+                    .bottom(px(INSET))
+                    .bg(bg)
+                    .border_1()
+                    .border_color(border)
+                    .rounded(px(16.0))
+                    .overflow_hidden()
+                    .shadow_lg()
+                    .child(header)
+                    .child(h_flex().flex_1().min_h_0().child(page_area).child(sidebar)),
+            )
+            .children(page_view),
+    )
+    .with_priority(1000)
+}
+
+/// An absolutely placed area of a page shown at `size`
+fn area(rect: PageRect, size: (f32, f32)) -> Div {
+    div()
+        .absolute()
+        .left(px(rect.x * size.0))
+        .top(px(rect.y * size.1))
+        .w(px(rect.width * size.0))
+        .h(px(rect.height * size.1))
+}
+
+/// How an annotation shows on its page, numbered as in the sidebar
+fn render_marks(
+    number: usize,
+    annotation: &PdfAnnotation,
+    size: (f32, f32),
+    primary: Hsla,
+) -> Vec<Div> {
+    let highlight = hsla(50.0 / 360.0, 1.0, 0.5, 0.35);
+    let note = hsla(35.0 / 360.0, 0.9, 0.55, 1.0);
+    match &annotation.kind {
+        AnnotationKind::Highlight { rects, .. } => rects
+            .iter()
+            .map(|rect| area(*rect, size).bg(highlight))
+            .collect(),
+        AnnotationKind::Box { rect } => vec![
+            area(*rect, size)
+                .border_2()
+                .border_color(primary)
+                .rounded(px(2.0)),
+        ],
+        AnnotationKind::Note { point, .. } => vec![
+            div()
+                .absolute()
+                .left(px(point.0 * size.0 - NOTE_MARKER_SIZE / 2.0))
+                .top(px(point.1 * size.1 - NOTE_MARKER_SIZE / 2.0))
+                .size(px(NOTE_MARKER_SIZE))
+                .rounded_full()
+                .bg(note)
+                .shadow_md()
+                .flex()
+                .items_center()
+                .justify_center()
+                .text_xs()
+                .font_weight(FontWeight::BOLD)
+                .text_color(gpui::white())
+                .child(number.to_string()),
+        ],
+    }
+}
+
+/// A sidebar entry for an annotation, which shows its page when clicked
+fn render_annotation_row(
+    number: usize,
+    annotation: &PdfAnnotation,
+    on_current_page: bool,
+    cx: &Context<Humanboard>,
+) -> impl IntoElement {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let muted_bg = cx.theme().muted;
+    let list_hover = cx.theme().list_hover;
+
+    let id = annotation.id;
+    let is_highlight = matches!(annotation.kind, AnnotationKind::Highlight { .. });
+    let is_note = matches!(annotation.kind, AnnotationKind::Note { .. });
+    let text = match &annotation.kind {
+        AnnotationKind::Highlight { text, .. } | AnnotationKind::Note { text, .. } => text.clone(),
+        AnnotationKind::Box { .. } => String::new(),
+    };
+
+    v_flex()
+        .id(("pdf-annotation", id as usize))
+        .gap_1()
+        .px_2()
+        .py_2()
+        .rounded(px(6.0))
+        .when(on_current_page, |d| d.bg(muted_bg))
+        .hover(|s| s.bg(list_hover))
+        .cursor_pointer()
+        .on_click(cx.listener(move |this, _, _, cx| this.show_pdf_annotation(id, cx)))
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(div().flex_1().text_xs().text_color(muted_fg).child(format!(
+                    "{}. {} · p.{}",
+                    number,
+                    annotation.kind.label(),
+                    annotation.page
+                )))
+                .when(is_highlight, |d| {
+                    d.child(
+                        Button::new(("pdf-annotation-quote", id as usize))
+                            .label("To Canvas")
+                            .xsmall()
+                            .ghost()
+                            .tooltip("Add as a quote linked to this page")
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.send_highlight_to_canvas(id, window, cx);
+                            })),
+                    )
+                })
+                .when(is_note, |d| {
+                    d.child(
+                        Button::new(("pdf-annotation-edit", id as usize))
+                            .label("Edit")
+                            .xsmall()
+                            .ghost()
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.edit_pdf_note(id, window, cx);
+                            })),
+                    )
+                })
+                .child(
+                    Button::new(("pdf-annotation-delete", id as usize))
+                        .icon(IconName::Close)
+                        .xsmall()
+                        .ghost()
+                        .tooltip("Delete")
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.remove_pdf_annotation(id, cx);
+                        })),
+                ),
+        )
+        .when(!text.is_empty(), |d| {
+            d.child(
+                div()
+                    .text_sm()
+                    .text_color(fg)
+                    .max_h(px(80.0))
+                    .overflow_hidden()
+                    .child(text),
+            )
+        })
+}
@@ -0,0 +1,212 @@
+//! PDF page picker - choosing pages of a PDF to place on the canvas.
+
+use crate::app::{Humanboard, PdfPagePicker};
+use crate::pdf_pages;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::{h_flex, v_flex, ActiveTheme as _, Disableable as _};
+
+use super::modal_base::render_kbd;
+
+/// Width of a page card in the picker grid
+const CARD_WIDTH: f32 = 120.0;
+
+/// Render the page picker for a PDF item
+pub fn render_pdf_page_picker(
+    picker: &PdfPagePicker,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let bg = cx.theme().popover;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let muted_bg = cx.theme().muted;
+    let primary = cx.theme().primary;
+
+    let selected_count = picker.selected.len();
+    let page_count = picker.pages.as_ref().map_or(0, Vec::len);
+    let summary = if picker.pages.is_none() {
+        String::new()
+    } else if selected_count == 0 {
+        format!(
+            "{} page{}",
+            page_count,
+            if page_count == 1 { "" } else { "s" }
+        )
+    } else {
+        format!(
+            "Pages {} of {}",
+            pdf_pages::format_page_ranges(&picker.selected.iter().copied().collect::<Vec<_>>()),
+            page_count
+        )
+    };
+
+    let body = match (&picker.pages, &picker.error) {
+        (_, Some(error)) => message(format!("Couldn't read the pages: {}", error), muted_fg),
+        (None, None) => message("Rendering pages…".to_string(), muted_fg),
+        (Some(pages), None) if pages.is_empty() => {
+            message("This PDF has no pages".to_string(), muted_fg)
+        }
+        (Some(pages), None) => {
+            let cards = pages.iter().map(|page| {
+                let number = page.page;
+                let is_selected = picker.selected.contains(&number);
+                let (width, height) = page.size;
+                let aspect = if width > 0.0 { height / width } else { 1.3 };
+                v_flex()
+                    .id(("pdf-page", number))
+                    .w(px(CARD_WIDTH))
+                    .gap_1()
+                    .p_1()
+                    .rounded(px(8.0))
+                    .border_2()
+                    .border_color(if is_selected {
+                        primary
+                    } else {
+                        gpui::transparent_black()
+                    })
+                    .cursor_pointer()
+                    .hover(|s| s.bg(muted_bg))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, event: &MouseDownEvent, _, cx| {
+                            this.toggle_picker_page(number, event.modifiers.shift, cx);
+                        }),
+                    )
+                    .child(
+                        img(page.image.clone())
+                            .w_full()
+                            .h(px((CARD_WIDTH - 8.0) * aspect.min(1.5)))
+                            .object_fit(ObjectFit::Contain)
+                            .rounded(px(4.0)),
+                    )
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_center()
+                            .text_color(if is_selected { primary } else { muted_fg })
+                            .child(number.to_string()),
+                    )
+            });
+            div()
+                .id("pdf-page-grid")
+                .flex_1()
+                .min_h_0()
+                .px_5()
+                .py_4()
+                .overflow_y_scroll()
+                .child(h_flex().flex_wrap().gap_2().children(cards))
+        }
+    };
+
+    deferred(
+        div()
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .bg(hsla(0.0, 0.0, 0.0, 0.8))
+            .flex()
+            .items_center()
+            .justify_center()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, _, cx| this.close_pdf_page_picker(cx)),
+            )
+            .child(
+                v_flex()
+                    .w(px(680.0))
+                    .h(relative(0.8))
+                    .bg(bg)
+                    .border_1()
+                    .border_color(border)
+                    .rounded(px(16.0))
+                    .overflow_hidden()
+                    .shadow_lg()
+                    .on_mouse_down(MouseButton::Left, |_, _, _| {})
+                    // Header
+                    .child(
+                        h_flex()
+                            .px_5()
+                            .py_4()
+                            .gap_4()
+                            .border_b_1()
+                            .border_color(border)
+                            .items_center()
+                            .justify_between()
+                            .child(
+                                v_flex()
+                                    .min_w_0()
+                                    .child(
+                                        div()
+                                            .overflow_hidden()
+                                            .text_ellipsis()
+                                            .text_base()
+                                            .font_weight(FontWeight::SEMIBOLD)
+                                            .text_color(fg)
+                                            .child(picker.name.clone()),
+                                    )
+                                    .child(div().text_xs().text_color(muted_fg).child(summary)),
+                            )
+                            .child(render_kbd("Esc", cx)),
+                    )
+                    .child(body)
+                    // Footer
+                    .child(
+                        h_flex()
+                            .px_5()
+                            .py_4()
+                            .gap_3()
+                            .border_t_1()
+                            .border_color(border)
+                            .items_center()
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .text_xs()
+                                    .text_color(muted_fg)
+                                    .child("Click pages to choose them, shift-click for a range"),
+                            )
+                            .child(
+                                Button::new("pdf-pages-all")
+                                    .label(if page_count > 0 && selected_count == page_count {
+                                        "Select None"
+                                    } else {
+                                        "Select All"
+                                    })
+                                    .ghost()
+                                    .disabled(page_count == 0)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.toggle_all_picker_pages(cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("pdf-pages-place")
+                                    .label(match selected_count {
+                                        1 => "Place 1 Page".to_string(),
+                                        n => format!("Place {} Pages", n),
+                                    })
+                                    .primary()
+                                    .disabled(selected_count == 0)
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.place_picked_pages(cx);
+                                    })),
+                            ),
+                    ),
+            ),
+    )
+    .with_priority(1000)
+}
+
+/// A line of text in place of the page grid
+fn message(text: String, color: Hsla) -> Stateful<Div> {
+    div()
+        .id("pdf-page-grid")
+        .flex_1()
+        .flex()
+        .items_center()
+        .justify_center()
+        .text_sm()
+        .text_color(color)
+        .child(text)
+}
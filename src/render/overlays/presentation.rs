@@ -0,0 +1,50 @@
+//! Presentation progress indicator.
+
+use crate::app::Humanboard;
+use gpui::*;
+use gpui_component::{ActiveTheme as _, h_flex};
+
+/// Render the progress indicator shown along the bottom edge while presenting
+pub fn render_presentation_progress(
+    current: usize,
+    total: usize,
+    title: &str,
+    cx: &mut Context<Humanboard>,
+) -> Div {
+    let bg = cx.theme().title_bar;
+    let border = cx.theme().border;
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let primary = cx.theme().primary;
+    let progress = (current + 1) as f32 / total.max(1) as f32;
+
+    div()
+        .absolute()
+        .bottom_0()
+        .left_0()
+        .right_0()
+        .child(
+            h_flex().justify_center().pb_4().child(
+                h_flex()
+                    .gap_3()
+                    .px_3()
+                    .py_1()
+                    .rounded(px(6.0))
+                    .bg(bg.opacity(0.9))
+                    .border_1()
+                    .border_color(border)
+                    .text_xs()
+                    .text_color(muted_fg)
+                    .child(format!("{} / {}", current + 1, total))
+                    .child(div().text_color(fg).child(title.to_string()))
+                    .child("Esc to exit"),
+            ),
+        )
+        .child(
+            div()
+                .h(px(3.0))
+                .w_full()
+                .bg(border)
+                .child(div().h_full().w(relative(progress)).bg(primary)),
+        )
+}
@@ -1,28 +1,35 @@
 //! Settings modal component.
 
 use crate::actions::{ModalFocusNext, ModalFocusPrev, OpenSettings};
-use crate::app::{Humanboard, SettingsTab};
+use crate::app::{Humanboard, SettingsTab, ThemeEditorState};
 use crate::focus::FocusContext;
-use crate::settings::Settings;
+use crate::settings::{CanvasSettings, Settings};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::{h_flex, v_flex, ActiveTheme as _, Icon, IconName};
 
 use super::modal_base::{
     render_section_header, render_setting_row, FontDropdownOpen, ThemeDropdownOpen,
 };
+use super::settings_canvas::render_canvas_settings;
 use super::settings_dropdowns::{
     render_font_dropdown, render_font_dropdown_menu, render_theme_dropdown,
     render_theme_dropdown_menu,
 };
+use super::theme_editor::render_theme_editor;
 
 /// Render the settings modal
+#[allow(clippy::too_many_arguments)]
 pub fn render_settings_modal(
     current_theme: &str,
     current_font: &str,
     _theme_index: usize,
     _theme_scroll: &ScrollHandle,
     active_tab: SettingsTab,
+    canvas: &CanvasSettings,
+    board_only: Option<bool>,
+    theme_editor: Option<&ThemeEditorState>,
     modal_focus: &FocusHandle,
     opacity: f32,
     cx: &mut Context<Humanboard>,
@@ -63,6 +70,7 @@ pub fn render_settings_modal(
                 MouseButton::Left,
                 cx.listener(|this, _, window, cx| {
                     if this.settings_backdrop_clicked {
+                        this.cancel_theme_editor(cx);
                         this.show_settings = false;
                         this.settings_backdrop_clicked = false;
                         this.focus.force_canvas_focus(window);
@@ -115,6 +123,9 @@ pub fn render_settings_modal(
                         &current_font_display,
                         &themes,
                         &fonts,
+                        canvas,
+                        board_only,
+                        theme_editor,
                         bg,
                         border,
                         fg,
@@ -228,6 +239,9 @@ fn render_settings_content(
     current_font: &str,
     themes: &[String],
     fonts: &[&str],
+    canvas: &CanvasSettings,
+    board_only: Option<bool>,
+    theme_editor: Option<&ThemeEditorState>,
     bg: Hsla,
     border: Hsla,
     fg: Hsla,
@@ -246,11 +260,15 @@ fn render_settings_content(
         .id("settings-content")
         .flex_1()
         .h_full()
-        .overflow_hidden()
+        .overflow_y_scroll()
         .px_6()
         .py_6()
-        // Content - Appearance tab
-        .when(active_tab == SettingsTab::Appearance, |d| {
+        // Content - Appearance tab, or the theme editor while it's open
+        .when_some(
+            theme_editor.filter(|_| active_tab == SettingsTab::Appearance),
+            |d, editor| d.child(render_theme_editor(editor, cx)),
+        )
+        .when(active_tab == SettingsTab::Appearance && theme_editor.is_none(), |d| {
             d.child(
                 v_flex()
                     .gap_4()
@@ -261,13 +279,25 @@ fn render_settings_content(
                         render_theme_dropdown(&current_theme_clone, fg, muted_fg, input_bg, border, cx),
                         cx,
                     ))
+                    .child(render_setting_row(
+                        "Customize Theme",
+                        "Edit a copy of the current theme and save it as a new one",
+                        Button::new("customize-theme")
+                            .label("Customize")
+                            .ghost()
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.open_theme_editor(window, cx);
+                            })),
+                        cx,
+                    ))
                     .child(render_section_header("Font", cx))
                     .child(render_setting_row(
                         "Font Family",
                         "Choose a font for the interface",
                         render_font_dropdown(&current_font_clone, fg, muted_fg, input_bg, border, cx),
                         cx,
-                    )),
+                    ))
+                    .child(render_canvas_settings(canvas, board_only, cx)),
             )
         })
         // Content - Integrations tab
@@ -0,0 +1,138 @@
+//! Canvas settings section - background, grid and snapping, per board or for all boards.
+
+use crate::app::Humanboard;
+use crate::settings::CanvasSettings;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::switch::Switch;
+use gpui_component::{h_flex, v_flex, ActiveTheme as _};
+
+use super::modal_base::{render_section_header, render_setting_row};
+
+/// Background choices offered in the settings modal; `None` follows the theme
+const CANVAS_BACKGROUNDS: &[(&str, Option<&str>)] = &[
+    ("Theme", None),
+    ("Charcoal", Some("#1a1a1a")),
+    ("Slate", Some("#1e293b")),
+    ("Paper", Some("#f5f0e6")),
+    ("White", Some("#ffffff")),
+];
+
+/// Render the canvas settings section.
+///
+/// `board_only` is `None` when no board is open, otherwise whether the open
+/// board has its own settings that the controls below edit.
+pub fn render_canvas_settings(
+    canvas: &CanvasSettings,
+    board_only: Option<bool>,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let border = cx.theme().border;
+    let primary = cx.theme().primary;
+    let fg = cx.theme().foreground;
+    let theme_bg = cx.theme().background;
+
+    let swatches = h_flex()
+        .gap_1()
+        .children(CANVAS_BACKGROUNDS.iter().map(|&(name, color)| {
+            let is_current = canvas.background.as_deref() == color;
+            let fill = color
+                .and_then(|hex| Rgba::try_from(hex).ok())
+                .map(Hsla::from)
+                .unwrap_or(theme_bg);
+
+            div()
+                .id(SharedString::from(format!("canvas-bg-{}", name)))
+                .size(px(22.0))
+                .rounded(px(4.0))
+                .bg(fill)
+                .border_2()
+                .border_color(if is_current { primary } else { border })
+                .cursor(CursorStyle::PointingHand)
+                .when(color.is_none(), |d| {
+                    d.flex()
+                        .items_center()
+                        .justify_center()
+                        .text_xs()
+                        .text_color(fg)
+                        .child("T")
+                })
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.set_canvas_background(color.map(str::to_string), cx);
+                }))
+        }));
+
+    let grid_size = canvas.grid_size;
+    let grid_stepper = h_flex()
+        .gap_2()
+        .items_center()
+        .child(
+            Button::new("grid-size-down")
+                .label("-")
+                .ghost()
+                .on_click(cx.listener(|this, _, _, cx| this.step_grid_size(-1.0, cx))),
+        )
+        .child(
+            div()
+                .w(px(32.0))
+                .text_sm()
+                .text_center()
+                .text_color(fg)
+                .child(format!("{}", grid_size)),
+        )
+        .child(
+            Button::new("grid-size-up")
+                .label("+")
+                .ghost()
+                .on_click(cx.listener(|this, _, _, cx| this.step_grid_size(1.0, cx))),
+        );
+
+    v_flex()
+        .gap_4()
+        .child(render_section_header("Canvas", cx))
+        .when_some(board_only, |d, board_only| {
+            d.child(render_setting_row(
+                "This board only",
+                "Save the canvas settings below with this board instead of for all boards",
+                Switch::new("board-only-settings")
+                    .checked(board_only)
+                    .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                        this.set_board_only_settings(*checked, cx);
+                    })),
+                cx,
+            ))
+        })
+        .child(render_setting_row(
+            "Background",
+            "Canvas background colour",
+            swatches,
+            cx,
+        ))
+        .child(render_setting_row(
+            "Show Grid",
+            "Draw grid lines on the canvas",
+            Switch::new("show-grid")
+                .checked(canvas.show_grid)
+                .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                    this.set_show_grid(*checked, cx);
+                })),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Snap to Grid",
+            "Snap items to the grid while dragging",
+            Switch::new("snap-to-grid")
+                .checked(canvas.snap_to_grid)
+                .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                    this.set_snap_to_grid(*checked, cx);
+                })),
+            cx,
+        ))
+        .child(render_setting_row(
+            "Grid Size",
+            "Spacing of grid lines and snapping, in canvas units",
+            grid_stepper,
+            cx,
+        ))
+}
@@ -80,6 +80,10 @@ pub fn render_shortcuts_overlay(cx: &mut Context<Humanboard>) -> impl IntoElemen
                                     ("Cmd+=", "Zoom in"),
                                     ("Cmd+-", "Zoom out"),
                                     ("Cmd+0", "Reset zoom"),
+                                    ("Shift+1", "Zoom to fit"),
+                                    ("Shift+2", "Zoom to selection"),
+                                    ("1-9", "Go to saved view"),
+                                    ("Cmd+Shift+M", "Toggle minimap"),
                                     ("Cmd+D", "Duplicate selected"),
                                     ("Del", "Delete selected"),
                                     ("Cmd+Z", "Undo"),
@@ -88,6 +92,38 @@ pub fn render_shortcuts_overlay(cx: &mut Context<Humanboard>) -> impl IntoElemen
                                 ],
                                 cx,
                             ))
+                            .child(render_shortcut_section(
+                                "Text Editing",
+                                vec![
+                                    ("Cmd+B", "Bold"),
+                                    ("Cmd+I", "Italic"),
+                                    ("Cmd+Shift+X", "Strikethrough"),
+                                    ("Cmd+E", "Inline code"),
+                                    ("Esc", "Finish editing"),
+                                ],
+                                cx,
+                            ))
+                            .child(render_shortcut_section(
+                                "Images",
+                                vec![
+                                    ("Double-click", "Crop image"),
+                                    ("Enter", "Apply crop"),
+                                    ("Esc", "Cancel crop"),
+                                    ("Cmd+I", "Image info"),
+                                ],
+                                cx,
+                            ))
+                            .child(render_shortcut_section(
+                                "Media",
+                                vec![
+                                    ("Double-click", "Load player"),
+                                    ("Double-click waveform", "Add marker"),
+                                    ("Click marker", "Play from marker"),
+                                    ("Double-click page", "Open PDF at page"),
+                                    ("Drag on page", "Highlight / box in annotator"),
+                                ],
+                                cx,
+                            ))
                             .child(render_shortcut_section(
                                 "PDF Preview",
                                 vec![
@@ -99,6 +135,16 @@ pub fn render_shortcuts_overlay(cx: &mut Context<Humanboard>) -> impl IntoElemen
                                     ("Esc", "Close preview"),
                                 ],
                                 cx,
+                            ))
+                            .child(render_shortcut_section(
+                                "Presentation",
+                                vec![
+                                    ("F5", "Present frames and saved views"),
+                                    ("←  →", "Prev / Next frame"),
+                                    ("Space", "Next frame"),
+                                    ("Esc", "Exit presentation"),
+                                ],
+                                cx,
                             )),
                     ),
             ),
@@ -0,0 +1,130 @@
+//! Theme editor section - tweak a copy of the current theme with live preview.
+
+use crate::app::{resolved_theme_color, Humanboard, ThemeEditorState};
+use crate::theme_editor::{hsla_to_hex, ThemeColorSlot};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::button::{Button, ButtonVariants};
+use gpui_component::input::Input;
+use gpui_component::{h_flex, v_flex, ActiveTheme as _};
+
+use super::modal_base::{render_section_header, render_setting_row};
+
+/// Render the theme editor in place of the appearance settings
+pub fn render_theme_editor(
+    editor: &ThemeEditorState,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let muted_fg = cx.theme().muted_foreground;
+    let danger = cx.theme().danger;
+    let selected_label = editor.selected.label();
+
+    let (interface, items): (Vec<_>, Vec<_>) = ThemeColorSlot::ALL
+        .into_iter()
+        .partition(|slot| !slot.is_item_color());
+
+    v_flex()
+        .gap_4()
+        .child(render_section_header("Theme Editor", cx))
+        .child(render_setting_row(
+            "Name",
+            "Saved as a new theme in your themes folder",
+            div()
+                .w(px(200.0))
+                .child(Input::new(&editor.name_input).w_full()),
+            cx,
+        ))
+        .child(render_section_header("Interface", cx))
+        .child(render_color_grid(&interface, editor.selected, cx))
+        .child(render_section_header("Canvas & Items", cx))
+        .child(render_color_grid(&items, editor.selected, cx))
+        .child(render_setting_row(
+            selected_label,
+            "Hex colour, applied as you type",
+            div()
+                .w(px(120.0))
+                .child(Input::new(&editor.hex_input).w_full()),
+            cx,
+        ))
+        .when_some(editor.error.clone(), |d, error| {
+            d.child(div().text_xs().text_color(danger).child(error))
+        })
+        .child(
+            h_flex()
+                .justify_end()
+                .gap_2()
+                .child(
+                    div()
+                        .flex_1()
+                        .text_xs()
+                        .text_color(muted_fg)
+                        .child("Changes preview live until you save or cancel"),
+                )
+                .child(
+                    Button::new("theme-editor-cancel")
+                        .label("Cancel")
+                        .ghost()
+                        .on_click(cx.listener(|this, _, _, cx| this.cancel_theme_editor(cx))),
+                )
+                .child(
+                    Button::new("theme-editor-save")
+                        .label("Save Theme")
+                        .primary()
+                        .on_click(cx.listener(|this, _, _, cx| this.save_theme_draft(cx))),
+                ),
+        )
+}
+
+/// Render a two-column grid of colour swatches; clicking one selects it for editing
+fn render_color_grid(
+    slots: &[ThemeColorSlot],
+    selected: ThemeColorSlot,
+    cx: &mut Context<Humanboard>,
+) -> impl IntoElement {
+    let fg = cx.theme().foreground;
+    let muted_fg = cx.theme().muted_foreground;
+    let border = cx.theme().border;
+    let list_active = cx.theme().list_active;
+    let list_hover = cx.theme().list_hover;
+
+    h_flex().flex_wrap().gap_1().children(slots.iter().map(|&slot| {
+        let color = resolved_theme_color(slot, cx.theme());
+        let is_selected = slot == selected;
+
+        h_flex()
+            .id(SharedString::from(format!("theme-color-{:?}", slot)))
+            .w(px(200.0))
+            .px_2()
+            .py_1()
+            .gap_2()
+            .items_center()
+            .rounded(px(4.0))
+            .cursor(CursorStyle::PointingHand)
+            .when(is_selected, |d| d.bg(list_active))
+            .when(!is_selected, |d| d.hover(|s| s.bg(list_hover)))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select_theme_color(slot, window, cx);
+            }))
+            .child(
+                div()
+                    .size(px(18.0))
+                    .rounded(px(4.0))
+                    .bg(color)
+                    .border_1()
+                    .border_color(border),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .text_sm()
+                    .text_color(fg)
+                    .child(slot.label()),
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(muted_fg)
+                    .child(hsla_to_hex(color)),
+            )
+    }))
+}
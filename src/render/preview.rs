@@ -7,9 +7,11 @@
 //! - Resizable splitter
 
 use crate::app::{Humanboard, PreviewTab, SplitDirection};
+use crate::constants::PDF_TAB_FOOTER_HEIGHT;
 use crate::focus::FocusContext;
 use crate::focus_ring::focus_ring_shadow;
 use crate::loading::render_loading_spinner;
+use crate::pdf_annotations::{self, PdfAnnotation};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
 use gpui_component::Disableable as _;
@@ -492,6 +494,7 @@ fn render_pane(
     search_match_count: usize,
     search_current: usize,
     is_left_pane: bool,
+    annotations: &[PdfAnnotation],
     cx: &mut Context<Humanboard>,
 ) -> Stateful<Div> {
     let bg = cx.theme().background;
@@ -548,7 +551,7 @@ fn render_pane(
                 .flex()
                 .flex_col()
                 .when_some(tabs.get(active_tab), |d, tab| {
-                    d.child(render_tab_content(tab, true, active_tab, cx))
+                    d.child(render_tab_content(tab, true, active_tab, annotations, cx))
                 }),
         )
 }
@@ -564,6 +567,7 @@ pub fn render_split_panes(
     search_match_count: usize,
     search_current: usize,
     split_zone: Option<crate::app::SplitDropZone>,
+    annotations: &[PdfAnnotation],
     cx: &mut Context<Humanboard>,
 ) -> Div {
     use crate::app::{FocusedPane, SplitDropZone};
@@ -588,6 +592,7 @@ pub fn render_split_panes(
         search_match_count,
         search_current,
         true,
+        annotations,
         cx,
     );
 
@@ -604,6 +609,7 @@ pub fn render_split_panes(
         search_match_count,
         search_current,
         false,
+        annotations,
         cx,
     );
 
@@ -810,6 +816,7 @@ pub fn render_tab_content(
     tab: &PreviewTab,
     _is_active: bool,
     _tab_index: usize,
+    annotations: &[PdfAnnotation],
     cx: &mut Context<Humanboard>,
 ) -> Div {
     let bg = cx.theme().background;
@@ -819,10 +826,42 @@ pub fn render_tab_content(
     let primary = cx.theme().primary;
 
     match tab {
-        PreviewTab::Pdf { .. } => {
+        PreviewTab::Pdf { path, .. } => {
             // PDF webviews are positioned explicitly via set_bounds in ensure_pdf_webview
-            // We just need an empty container - the webview renders as a native overlay
-            div().flex_1().w_full().min_h_0()
+            // We just need an empty container - the webview renders as a native overlay.
+            // Nothing can be drawn over it, so annotations are summed up underneath.
+            let annotate_path = path.clone();
+            v_flex()
+                .flex_1()
+                .w_full()
+                .min_h_0()
+                .child(div().flex_1().w_full().min_h_0())
+                .child(
+                    h_flex()
+                        .h(px(PDF_TAB_FOOTER_HEIGHT))
+                        .bg(title_bar)
+                        .border_t_1()
+                        .border_color(border)
+                        .items_center()
+                        .justify_between()
+                        .px_3()
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(muted_fg)
+                                .child(pdf_annotations::summary(annotations, path)),
+                        )
+                        .child(
+                            Button::new("annotate-pdf")
+                                .ghost()
+                                .small()
+                                .label("Annotate")
+                                .tooltip("Highlight, note and box pages of this PDF")
+                                .on_click(cx.listener(move |this, _, _window, cx| {
+                                    this.open_pdf_annotator(annotate_path.clone(), 1, cx);
+                                })),
+                        ),
+                )
         }
         PreviewTab::Markdown {
             content,
@@ -5,9 +5,13 @@
 //!
 //! 1. Default settings (lowest priority)
 //! 2. User settings (~/.config/humanboard/settings.json)
-//! 3. Project settings (.humanboard/settings.json) (highest priority)
+//! 3. Project settings (.humanboard/settings.json)
+//! 4. Board settings (settings.json in the open board's directory) (highest priority)
 
 use crate::error::SettingsError;
+use crate::external_commands::ExternalCommandConfig;
+use crate::folder_import::FolderImportOptions;
+use crate::media::DEFAULT_MAX_MEDIA_PLAYERS;
 use gpui::*;
 use gpui_component::theme::{Theme, ThemeMode, ThemeRegistry};
 use serde::{Deserialize, Serialize};
@@ -55,7 +59,7 @@ pub struct SettingsContent {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub font_size: Option<f32>,
 
-    /// Canvas background color (hex)
+    /// Canvas background color (hex), or `THEME_BACKGROUND` for the theme's
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub canvas_background: Option<String>,
 
@@ -99,6 +103,23 @@ pub struct SettingsContent {
     /// Whether high contrast mode is enabled (accessibility)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub high_contrast: Option<bool>,
+
+    /// User-defined commands that run a shell command on the selection.
+    /// Only read from the user's own settings file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_commands: Option<Vec<ExternalCommandConfig>>,
+
+    /// Filters, depth and ordering for folders dropped or opened on a board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder_import: Option<FolderImportOptions>,
+
+    /// Most audio, video and YouTube players kept loaded at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_media_players: Option<usize>,
+
+    /// Name comments are signed with (Anonymous until set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_name: Option<String>,
 }
 
 impl SettingsContent {
@@ -147,6 +168,29 @@ impl SettingsContent {
         if other.high_contrast.is_some() {
             self.high_contrast = other.high_contrast;
         }
+        if other.external_commands.is_some() {
+            self.external_commands = other.external_commands.clone();
+        }
+        if other.folder_import.is_some() {
+            self.folder_import = other.folder_import.clone();
+        }
+        if other.max_media_players.is_some() {
+            self.max_media_players = other.max_media_players;
+        }
+        if other.author_name.is_some() {
+            self.author_name = other.author_name.clone();
+        }
+    }
+
+    /// A copy without the settings only the user's own file may set.
+    ///
+    /// External commands run through the shell, so a shared project or board
+    /// folder mustn't be able to add them.
+    pub fn shared_layer(&self) -> SettingsContent {
+        SettingsContent {
+            external_commands: None,
+            ..self.clone()
+        }
     }
 }
 
@@ -171,6 +215,11 @@ pub struct AppSettings {
     /// Reduce motion preference: "system", "on", or "off"
     pub reduce_motion: String,
     pub high_contrast: bool,
+    pub external_commands: Vec<ExternalCommandConfig>,
+    pub folder_import: FolderImportOptions,
+    pub max_media_players: usize,
+    /// Name comments are signed with; empty signs them Anonymous
+    pub author_name: String,
 }
 
 impl Default for AppSettings {
@@ -189,6 +238,10 @@ impl Default for AppSettings {
             pan_sensitivity: 1.0,
             reduce_motion: "system".to_string(),
             high_contrast: false,
+            external_commands: Vec::new(),
+            folder_import: FolderImportOptions::default(),
+            max_media_players: DEFAULT_MAX_MEDIA_PLAYERS,
+            author_name: String::new(),
         }
     }
 }
@@ -228,6 +281,18 @@ impl Setting for AppSettings {
                 .clone()
                 .unwrap_or(defaults.reduce_motion),
             high_contrast: content.high_contrast.unwrap_or(defaults.high_contrast),
+            external_commands: content
+                .external_commands
+                .clone()
+                .unwrap_or(defaults.external_commands),
+            folder_import: content
+                .folder_import
+                .clone()
+                .unwrap_or(defaults.folder_import),
+            max_media_players: content
+                .max_media_players
+                .unwrap_or(defaults.max_media_players),
+            author_name: content.author_name.clone().unwrap_or(defaults.author_name),
         }
     }
 
@@ -339,12 +404,39 @@ pub enum SettingsSource {
     User,
     /// Project settings from .humanboard/settings.json
     Project,
+    /// Per-board settings from settings.json in the open board's directory
+    Board,
+}
+
+/// `canvas_background` value for the theme's background. Stored rather than
+/// left unset so a board can choose it over a colour in the user settings.
+pub const THEME_BACKGROUND: &str = "theme";
+
+/// Canvas display settings for the open board, with per-board overrides applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasSettings {
+    /// Background colour (hex), or `None` to use the theme background
+    pub background: Option<String>,
+    pub grid_size: f32,
+    pub show_grid: bool,
+    pub snap_to_grid: bool,
+}
+
+impl CanvasSettings {
+    /// Snap a canvas coordinate to the grid, if snapping is enabled
+    pub fn snap(&self, value: f32) -> f32 {
+        if self.snap_to_grid && self.grid_size > 0.0 {
+            (value / self.grid_size).round() * self.grid_size
+        } else {
+            value
+        }
+    }
 }
 
 /// Central store for managing layered settings.
 ///
 /// Settings are merged from multiple sources with clear precedence:
-/// Default < User < Project
+/// Default < User < Project < Board
 pub struct SettingsStore {
     /// Default settings (always present)
     default_content: SettingsContent,
@@ -358,6 +450,12 @@ pub struct SettingsStore {
     /// Current project directory (for locating project settings)
     project_dir: Option<PathBuf>,
 
+    /// Settings for the open board, from settings.json in its directory
+    board_content: Option<SettingsContent>,
+
+    /// Directory of the open board (for locating board settings)
+    board_dir: Option<PathBuf>,
+
     /// Merged settings content
     merged_content: SettingsContent,
 
@@ -379,6 +477,8 @@ impl SettingsStore {
             user_content: None,
             project_content: None,
             project_dir: None,
+            board_content: None,
+            board_dir: None,
             merged_content: default_content,
             app_settings,
             file_errors: Vec::new(),
@@ -403,6 +503,10 @@ impl SettingsStore {
             onboarding_completed: Some(false),
             reduce_motion: Some(defaults.reduce_motion),
             high_contrast: Some(defaults.high_contrast),
+            external_commands: Some(defaults.external_commands),
+            folder_import: Some(defaults.folder_import),
+            max_media_players: Some(defaults.max_media_players),
+            author_name: Some(defaults.author_name),
         }
     }
 
@@ -422,6 +526,11 @@ impl SettingsStore {
             .map(|dir| dir.join(".humanboard").join("settings.json"))
     }
 
+    /// Get the settings file path for the open board.
+    pub fn board_settings_path(&self) -> Option<PathBuf> {
+        self.board_dir.as_ref().map(|dir| dir.join("settings.json"))
+    }
+
     /// Load user settings from disk.
     pub fn load_user_settings(&mut self) -> Result<(), SettingsError> {
         let path = Self::user_settings_path();
@@ -435,6 +544,59 @@ impl SettingsStore {
         self.load_settings_file(&path, SettingsSource::Project)
     }
 
+    /// Load the settings of the board in `board_dir`, replacing any previous board's.
+    pub fn load_board_settings(&mut self, board_dir: PathBuf) -> Result<(), SettingsError> {
+        let path = board_dir.join("settings.json");
+        self.board_dir = Some(board_dir);
+        self.load_settings_file(&path, SettingsSource::Board)
+    }
+
+    /// Drop the board settings layer when the board is closed.
+    pub fn unload_board_settings(&mut self) {
+        self.board_dir = None;
+        self.board_content = None;
+        self.file_errors.retain(|(s, _)| *s != SettingsSource::Board);
+        self.recompute_merged();
+    }
+
+    /// Turn per-board overrides on or off for the open board.
+    ///
+    /// Enabling starts the board with the current canvas settings so nothing
+    /// changes visually; disabling deletes the board's settings file.
+    pub fn set_board_overrides(&mut self, enabled: bool) -> Result<(), SettingsError> {
+        let path = self
+            .board_settings_path()
+            .ok_or_else(|| SettingsError::InvalidSource("No board is open".to_string()))?;
+
+        if enabled {
+            if self.board_content.is_none() {
+                let canvas = self.canvas_settings();
+                self.board_content = Some(SettingsContent {
+                    canvas_background: Some(
+                        canvas
+                            .background
+                            .unwrap_or_else(|| THEME_BACKGROUND.to_string()),
+                    ),
+                    grid_size: Some(canvas.grid_size),
+                    show_grid: Some(canvas.show_grid),
+                    snap_to_grid: Some(canvas.snap_to_grid),
+                    ..Default::default()
+                });
+                self.recompute_merged();
+            }
+            return self.save(SettingsSource::Board);
+        }
+
+        self.board_content = None;
+        self.recompute_merged();
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(SettingsError::SaveFailed { path, source: e })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Load settings from a specific file.
     fn load_settings_file(
         &mut self,
@@ -449,6 +611,7 @@ impl SettingsStore {
             match source {
                 SettingsSource::User => self.user_content = None,
                 SettingsSource::Project => self.project_content = None,
+                SettingsSource::Board => self.board_content = None,
                 SettingsSource::Default => {}
             }
             self.recompute_merged();
@@ -462,6 +625,7 @@ impl SettingsStore {
                     match source {
                         SettingsSource::User => self.user_content = Some(parsed),
                         SettingsSource::Project => self.project_content = Some(parsed),
+                        SettingsSource::Board => self.board_content = Some(parsed),
                         SettingsSource::Default => {}
                     }
                     self.recompute_merged();
@@ -499,9 +663,14 @@ impl SettingsStore {
             merged.merge_from(user);
         }
 
-        // Layer project settings (highest priority)
+        // Layer project settings
         if let Some(ref project) = self.project_content {
-            merged.merge_from(project);
+            merged.merge_from(&project.shared_layer());
+        }
+
+        // Layer board settings (highest priority)
+        if let Some(ref board) = self.board_content {
+            merged.merge_from(&board.shared_layer());
         }
 
         self.merged_content = merged;
@@ -520,6 +689,34 @@ impl SettingsStore {
         &self.merged_content
     }
 
+    /// Get the open board's own settings, if it overrides any.
+    pub fn board_content(&self) -> Option<&SettingsContent> {
+        self.board_content.as_ref()
+    }
+
+    /// Get the canvas settings for the open board.
+    ///
+    /// The background is only set when the nearest settings file choosing one
+    /// picks a colour, so boards follow the theme by default.
+    pub fn canvas_settings(&self) -> CanvasSettings {
+        let background = [
+            &self.board_content,
+            &self.project_content,
+            &self.user_content,
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|content| content.canvas_background.clone())
+        .filter(|background| background != THEME_BACKGROUND);
+
+        CanvasSettings {
+            background,
+            grid_size: self.app_settings.grid_size,
+            show_grid: self.app_settings.show_grid,
+            snap_to_grid: self.app_settings.snap_to_grid,
+        }
+    }
+
     /// Check if there are any parse errors.
     pub fn has_errors(&self) -> bool {
         !self.file_errors.is_empty()
@@ -545,6 +742,13 @@ impl SettingsStore {
             SettingsSource::Project => self
                 .project_content
                 .get_or_insert_with(SettingsContent::default),
+            SettingsSource::Board => {
+                if self.board_dir.is_none() {
+                    return Err(SettingsError::InvalidSource("No board is open".to_string()));
+                }
+                self.board_content
+                    .get_or_insert_with(SettingsContent::default)
+            }
         };
 
         updater(content);
@@ -567,6 +771,12 @@ impl SettingsStore {
                 })?;
                 (path, &self.project_content)
             }
+            SettingsSource::Board => {
+                let path = self
+                    .board_settings_path()
+                    .ok_or_else(|| SettingsError::InvalidSource("No board is open".to_string()))?;
+                (path, &self.board_content)
+            }
         };
 
         let content = content.as_ref().ok_or_else(|| {
@@ -647,6 +857,51 @@ where
     guard.update(source, updater)
 }
 
+/// Load the settings of the board being opened.
+pub fn load_board_settings(board_dir: PathBuf) -> Result<(), SettingsError> {
+    let store = global_settings();
+    let mut guard = store
+        .write()
+        .map_err(|_| SettingsError::LockPoisoned("settings store write lock poisoned".into()))?;
+    guard.load_board_settings(board_dir)
+}
+
+/// Drop the settings of the board being closed.
+pub fn unload_board_settings() {
+    let store = global_settings();
+    let mut guard = store
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.unload_board_settings();
+}
+
+/// Check if the open board has its own settings.
+pub fn has_board_settings() -> bool {
+    let store = global_settings();
+    let guard = store
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.board_content().is_some()
+}
+
+/// Turn per-board overrides on or off for the open board.
+pub fn set_board_overrides(enabled: bool) -> Result<(), SettingsError> {
+    let store = global_settings();
+    let mut guard = store
+        .write()
+        .map_err(|_| SettingsError::LockPoisoned("settings store write lock poisoned".into()))?;
+    guard.set_board_overrides(enabled)
+}
+
+/// Get the canvas settings for the open board (convenience function).
+pub fn canvas_settings() -> CanvasSettings {
+    let store = global_settings();
+    let guard = store
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.canvas_settings()
+}
+
 /// Check if onboarding has been completed.
 pub fn is_onboarding_completed() -> bool {
     let store = global_settings();
@@ -819,16 +1074,34 @@ fn find_themes_dir() -> Option<PathBuf> {
 }
 
 /// Initialize themes by watching the themes directory
+///
+/// The bundled themes are installed into the user themes directory, which the
+/// registry then watches so user themes are picked up as they change.
 pub fn init_themes(cx: &mut App) {
     // Initialize settings first
     if let Err(e) = init_settings() {
         warn!("Failed to initialize settings: {}", e);
     }
 
-    // Try multiple locations for themes directory
-    let themes_dir = find_themes_dir();
+    // Try multiple locations for the bundled themes directory
+    let bundled_dir = find_themes_dir();
+    let user_dir = crate::settings_watcher::themes_dir_path();
+
+    if let (Some(bundled_dir), Some(user_dir)) = (&bundled_dir, &user_dir) {
+        if let Err(e) = crate::theme_editor::install_bundled_themes(bundled_dir, user_dir) {
+            warn!("Failed to install bundled themes: {}", e);
+        }
+    }
+
+    if let Some(themes_dir) = user_dir.or(bundled_dir) {
+        // Keep theme item colours in step with the registry's reloads
+        crate::theme_editor::reload_item_colors(&themes_dir);
+        let item_colors_dir = themes_dir.clone();
+        cx.observe_global::<ThemeRegistry>(move |_cx| {
+            crate::theme_editor::reload_item_colors(&item_colors_dir);
+        })
+        .detach();
 
-    if let Some(themes_dir) = themes_dir {
         let saved_theme = app_settings().theme;
         let saved_theme_clone = saved_theme.clone();
 
@@ -39,7 +39,8 @@ impl SettingsWatcher {
     /// Create a new settings watcher for the given path.
     ///
     /// The path can be a file or directory. If a directory, all files
-    /// within it will be watched.
+    /// within it will be watched. A file does not need to exist yet, but
+    /// its directory does.
     pub fn new(path: PathBuf) -> Result<Self, notify::Error> {
         let (tx, rx) = mpsc::channel();
         let (event_tx, event_rx) = mpsc::channel();
@@ -67,12 +68,27 @@ impl SettingsWatcher {
             }
         });
 
+        // Watch the containing directory of a file (so it can be created or
+        // replaced later), but only report events for that file
+        let (watch_path, file_name) = if path.is_dir() {
+            (path.clone(), None)
+        } else {
+            let parent = path.parent().unwrap_or(&path).to_path_buf();
+            (parent, path.file_name().map(|name| name.to_os_string()))
+        };
+
         // Create the file watcher
         let event_tx_clone = event_tx.clone();
         let mut watcher = RecommendedWatcher::new(
             move |res: Result<Event, notify::Error>| {
                 match res {
                     Ok(event) => {
+                        if let Some(ref name) = file_name
+                            && !event.paths.iter().any(|p| p.file_name() == Some(name))
+                        {
+                            return;
+                        }
+
                         let settings_event = match event.kind {
                             EventKind::Modify(_) => Some(SettingsEvent::Modified),
                             EventKind::Create(_) => Some(SettingsEvent::Created),
@@ -95,12 +111,6 @@ impl SettingsWatcher {
         )?;
 
         // Start watching
-        let watch_path = if path.is_file() {
-            path.parent().unwrap_or(&path).to_path_buf()
-        } else {
-            path.clone()
-        };
-
         watcher.watch(&watch_path, RecursiveMode::NonRecursive)?;
         info!("Watching settings at: {:?}", watch_path);
 
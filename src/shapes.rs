@@ -0,0 +1,141 @@
+//! Shape geometry - outlines and hit testing for the shape library
+//!
+//! Box-like shapes (rectangles, ellipses, sticky notes, frames) are rendered as styled
+//! divs and hit on their bounding border. Every other shape is described here
+//! as a polyline in item-local coordinates so rendering and hit testing agree
+//! on the same outline.
+
+use crate::strokes::distance_to_segment;
+use crate::types::ShapeType;
+
+/// Number of segments used to approximate curved edges
+const CURVE_SEGMENTS: usize = 24;
+
+/// A shape outline in item-local coordinates (origin at the item's top-left)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShapeOutline {
+    /// Points of the main outline
+    pub points: Vec<(f32, f32)>,
+    /// Whether the outline is closed (and therefore fillable)
+    pub closed: bool,
+    /// Extra open strokes drawn on top of the outline (e.g. a cylinder's front rim)
+    pub details: Vec<Vec<(f32, f32)>>,
+}
+
+impl ShapeOutline {
+    fn closed(points: Vec<(f32, f32)>) -> Self {
+        Self {
+            points,
+            closed: true,
+            details: Vec::new(),
+        }
+    }
+}
+
+/// Outline for shapes drawn as paths, or `None` for box-like shapes drawn as divs
+pub fn outline(shape: ShapeType, w: f32, h: f32) -> Option<ShapeOutline> {
+    match shape {
+        ShapeType::Rectangle
+        | ShapeType::RoundedRect
+        | ShapeType::Ellipse
+        | ShapeType::StickyNote
+        | ShapeType::Frame => None,
+        ShapeType::Diamond => Some(ShapeOutline::closed(vec![
+            (w / 2.0, 0.0),
+            (w, h / 2.0),
+            (w / 2.0, h),
+            (0.0, h / 2.0),
+        ])),
+        ShapeType::Triangle => Some(ShapeOutline::closed(vec![(w / 2.0, 0.0), (w, h), (0.0, h)])),
+        ShapeType::Parallelogram => {
+            let skew = w * 0.2;
+            Some(ShapeOutline::closed(vec![
+                (skew, 0.0),
+                (w, 0.0),
+                (w - skew, h),
+                (0.0, h),
+            ]))
+        }
+        ShapeType::Hexagon => Some(ShapeOutline::closed(vec![
+            (w * 0.25, 0.0),
+            (w * 0.75, 0.0),
+            (w, h / 2.0),
+            (w * 0.75, h),
+            (w * 0.25, h),
+            (0.0, h / 2.0),
+        ])),
+        ShapeType::Star => {
+            let (cx, cy) = (w / 2.0, h / 2.0);
+            let points = (0..10)
+                .map(|i| {
+                    let angle =
+                        -std::f32::consts::FRAC_PI_2 + i as f32 * std::f32::consts::PI / 5.0;
+                    let scale = if i % 2 == 0 { 1.0 } else { 0.4 };
+                    (cx + cx * scale * angle.cos(), cy + cy * scale * angle.sin())
+                })
+                .collect();
+            Some(ShapeOutline::closed(points))
+        }
+        ShapeType::Cylinder => {
+            // Elliptical caps, flattened so tall and wide cylinders both look right
+            let rx = w / 2.0;
+            let ry = (h * 0.12).min(w * 0.25);
+            let top = ry;
+            let bottom = h - ry;
+
+            // Back half of the top cap, right side, front half of the bottom cap, left side
+            let mut points = half_ellipse(rx, top, rx, ry, true);
+            points.extend(half_ellipse(rx, bottom, rx, ry, false));
+            Some(ShapeOutline {
+                points,
+                closed: true,
+                details: vec![half_ellipse(rx, top, rx, ry, false)],
+            })
+        }
+        // Lines are created as headless arrows and older line shapes are
+        // converted on load, so this only covers one that skipped validation
+        ShapeType::Line => Some(ShapeOutline {
+            points: vec![(0.0, h / 2.0), (w, h / 2.0)],
+            closed: false,
+            details: Vec::new(),
+        }),
+    }
+}
+
+/// Sample half an ellipse: the upper half left-to-right, or the lower half right-to-left
+fn half_ellipse(cx: f32, cy: f32, rx: f32, ry: f32, upper: bool) -> Vec<(f32, f32)> {
+    (0..=CURVE_SEGMENTS)
+        .map(|i| {
+            let t = i as f32 / CURVE_SEGMENTS as f32 * std::f32::consts::PI;
+            let angle = if upper { std::f32::consts::PI + t } else { t };
+            (cx + rx * angle.cos(), cy + ry * angle.sin())
+        })
+        .collect()
+}
+
+/// Check whether an item-local point lies on a shape's outline.
+///
+/// Shapes are only clickable on their stroke so that items inside them can be
+/// selected; `tolerance` is the half-width of the clickable band.
+pub fn hit_outline(shape: ShapeType, size: (f32, f32), local: (f32, f32), tolerance: f32) -> bool {
+    let (w, h) = size;
+    let (x, y) = local;
+
+    match outline(shape, w, h) {
+        Some(outline) => {
+            let mut segments: Vec<((f32, f32), (f32, f32))> =
+                outline.points.windows(2).map(|p| (p[0], p[1])).collect();
+            let ends = (outline.points.first(), outline.points.last());
+            if let (true, (Some(&first), Some(&last))) = (outline.closed, ends) {
+                segments.push((last, first));
+            }
+            for detail in &outline.details {
+                segments.extend(detail.windows(2).map(|p| (p[0], p[1])));
+            }
+            segments
+                .iter()
+                .any(|&(a, b)| distance_to_segment((x, y), a, b) <= tolerance)
+        }
+        None => x < tolerance || w - x < tolerance || y < tolerance || h - y < tolerance,
+    }
+}
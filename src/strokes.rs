@@ -0,0 +1,257 @@
+//! Freehand strokes - smoothing, fitting, hit testing and erasing
+//!
+//! A stroke item stores its points normalised to the item's bounds (0.0-1.0),
+//! so moving and resizing a stroke work exactly like any other item. Points
+//! are recorded in canvas coordinates while drawing and converted on commit.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A polyline as a list of `(x, y)` points
+pub type Polyline = Vec<(f32, f32)>;
+
+/// Minimum distance (in screen pixels) between two recorded pointer samples
+pub const MIN_POINT_SPACING: f32 = 1.0;
+
+/// Maximum deviation (in canvas units) allowed when simplifying a stroke
+pub const SIMPLIFY_TOLERANCE: f32 = 0.5;
+
+/// Clean up a raw pointer trail: round off the jitter, then drop redundant points
+pub fn finish(raw: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let smoothed = smooth(&smooth(raw));
+    simplify(&smoothed, SIMPLIFY_TOLERANCE)
+}
+
+/// One pass of Chaikin corner cutting. The end points are kept in place.
+pub fn smooth(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut result = Vec::with_capacity(points.len() * 2);
+    result.push(points[0]);
+    for pair in points.windows(2) {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        result.push((0.75 * x0 + 0.25 * x1, 0.75 * y0 + 0.25 * y1));
+        result.push((0.25 * x0 + 0.75 * x1, 0.25 * y0 + 0.75 * y1));
+    }
+    result.push(points[points.len() - 1]);
+    result
+}
+
+/// Ramer-Douglas-Peucker simplification
+pub fn simplify(points: &[(f32, f32)], tolerance: f32) -> Vec<(f32, f32)> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let mut max_dist = 0.0;
+        let mut max_idx = start;
+        for i in start + 1..end {
+            let dist = distance_to_segment(points[i], points[start], points[end]);
+            if dist > max_dist {
+                max_dist = dist;
+                max_idx = i;
+            }
+        }
+        if max_dist > tolerance {
+            keep[max_idx] = true;
+            stack.push((start, max_idx));
+            stack.push((max_idx, end));
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&p, k)| k.then_some(p))
+        .collect()
+}
+
+/// Fit canvas-space points into an item: returns `(position, size, normalised points)`.
+///
+/// The bounds are padded by the stroke width so thick strokes aren't clipped
+/// and perfectly straight strokes still get a non-zero size.
+pub fn fit(points: &[(f32, f32)], width: f32) -> ((f32, f32), (f32, f32), Polyline) {
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    for &(x, y) in points {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+    if points.is_empty() {
+        return ((0.0, 0.0), (width, width), Vec::new());
+    }
+
+    let pad = width.max(1.0);
+    let position = (min_x - pad, min_y - pad);
+    let size = (max_x - min_x + pad * 2.0, max_y - min_y + pad * 2.0);
+    let normalised = points
+        .iter()
+        .map(|&(x, y)| ((x - position.0) / size.0, (y - position.1) / size.1))
+        .collect();
+    (position, size, normalised)
+}
+
+/// Convert normalised points back to canvas space for an item at `position` with `size`
+pub fn to_canvas(points: &[(f32, f32)], position: (f32, f32), size: (f32, f32)) -> Vec<(f32, f32)> {
+    points
+        .iter()
+        .map(|&(x, y)| (position.0 + x * size.0, position.1 + y * size.1))
+        .collect()
+}
+
+/// Check whether a point lies within `tolerance` of the stroke's path
+pub fn hit_stroke(points: &[(f32, f32)], p: (f32, f32), tolerance: f32) -> bool {
+    match points {
+        [] => false,
+        [only] => distance(*only, p) <= tolerance,
+        _ => points
+            .windows(2)
+            .any(|seg| distance_to_segment(p, seg[0], seg[1]) <= tolerance),
+    }
+}
+
+/// Erase a circle from a stroke.
+///
+/// Returns `None` when the eraser doesn't touch the stroke, otherwise the
+/// pieces that remain (empty if the whole stroke was erased). Segments that
+/// cross the circle are cut where they enter and leave it, so splitting a
+/// stroke doesn't shorten the surviving pieces.
+pub fn erase(points: &[(f32, f32)], center: (f32, f32), radius: f32) -> Option<Vec<Polyline>> {
+    if !hit_stroke(points, center, radius) {
+        return None;
+    }
+
+    let inside = |p: (f32, f32)| distance(p, center) <= radius;
+    let mut pieces = Vec::new();
+    let mut current: Polyline = Vec::new();
+
+    if let Some(&first) = points.first().filter(|&&p| !inside(p)) {
+        current.push(first);
+    }
+
+    for seg in points.windows(2) {
+        let (a, b) = (seg[0], seg[1]);
+        let (t_in, t_out) = circle_crossings(a, b, center, radius);
+        let lerp = |t: f32| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+
+        match (t_in, t_out) {
+            // Segment misses the circle entirely
+            (None, None) if !inside(a) => current.push(b),
+            // Segment lies entirely inside the circle
+            (None, None) => {}
+            // Segment enters the circle
+            (Some(t), None) => {
+                current.push(lerp(t));
+                pieces.push(std::mem::take(&mut current));
+            }
+            // Segment leaves the circle
+            (None, Some(t)) => {
+                current.push(lerp(t));
+                current.push(b);
+            }
+            // Segment passes straight through the circle
+            (Some(t0), Some(t1)) => {
+                current.push(lerp(t0));
+                pieces.push(std::mem::take(&mut current));
+                current.push(lerp(t1));
+                current.push(b);
+            }
+        }
+    }
+    pieces.push(current);
+
+    pieces.retain(|piece| piece.len() >= 2 && path_length(piece) > radius * 0.5);
+    Some(pieces)
+}
+
+/// Parameters along `a`-`b` where the segment enters and leaves the circle
+fn circle_crossings(
+    a: (f32, f32),
+    b: (f32, f32),
+    center: (f32, f32),
+    radius: f32,
+) -> (Option<f32>, Option<f32>) {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let (fx, fy) = (a.0 - center.0, a.1 - center.1);
+    let qa = dx * dx + dy * dy;
+    if qa <= f32::EPSILON {
+        return (None, None);
+    }
+    let qb = 2.0 * (fx * dx + fy * dy);
+    let qc = fx * fx + fy * fy - radius * radius;
+    let disc = qb * qb - 4.0 * qa * qc;
+    if disc < 0.0 {
+        return (None, None);
+    }
+
+    let sqrt = disc.sqrt();
+    let t0 = (-qb - sqrt) / (2.0 * qa);
+    let t1 = (-qb + sqrt) / (2.0 * qa);
+    let within = |t: f32| (t > 0.0 && t < 1.0).then_some(t);
+    // Entering requires starting outside, leaving requires ending outside
+    (
+        within(t0).filter(|_| qc > 0.0),
+        within(t1).filter(|_| distance(b, center) > radius),
+    )
+}
+
+fn path_length(points: &[(f32, f32)]) -> f32 {
+    points.windows(2).map(|seg| distance(seg[0], seg[1])).sum()
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+/// Distance from point `p` to the segment `a`-`b`
+pub fn distance_to_segment(p: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq > 0.0 {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    distance(p, (a.0 + t * dx, a.1 + t * dy))
+}
+
+/// Serde adapter storing points as a flat `[x0, y0, x1, y1, ...]` array
+/// rounded to four decimals, which keeps long strokes small in board files.
+pub mod compact_points {
+    use super::*;
+
+    const PRECISION: f32 = 10_000.0;
+
+    pub fn serialize<S: Serializer>(
+        points: &[(f32, f32)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let round = |v: f32| (v * PRECISION).round() / PRECISION;
+        points
+            .iter()
+            .flat_map(|&(x, y)| [round(x), round(y)])
+            .collect::<Vec<f32>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(f32, f32)>, D::Error> {
+        let flat = Vec::<f32>::deserialize(deserializer)?;
+        if flat.len() % 2 != 0 {
+            return Err(serde::de::Error::custom(
+                "stroke points must have an even number of coordinates",
+            ));
+        }
+        Ok(flat.chunks_exact(2).map(|c| (c[0], c[1])).collect())
+    }
+}
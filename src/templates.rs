@@ -0,0 +1,435 @@
+//! Board templates - starting layouts for new boards.
+//!
+//! Built-in templates are generated in code. Saved templates live in the
+//! templates directory, one folder per template holding `template.json` (its
+//! name and date), `board.json` and a `files/` folder with copies of every file
+//! the board referenced, so a template keeps working after the original board
+//! or its files are gone. Comments and bookmarks belong to the board they were
+//! made on and aren't saved.
+
+use crate::board::BoardState;
+use crate::board_index::generate_uuid;
+use crate::error::TemplateError;
+use crate::types::{ArrowHead, CanvasItem, ItemContent, ShapeType, TextAlignment, TextFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Templates that ship with the app
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BuiltInTemplate {
+    #[default]
+    Blank,
+    Kanban,
+    Retrospective,
+    MoodboardGrid,
+    MindMap,
+}
+
+impl BuiltInTemplate {
+    /// Every built-in template, in the order shown by the template picker
+    pub const ALL: [BuiltInTemplate; 5] = [
+        BuiltInTemplate::Blank,
+        BuiltInTemplate::Kanban,
+        BuiltInTemplate::Retrospective,
+        BuiltInTemplate::MoodboardGrid,
+        BuiltInTemplate::MindMap,
+    ];
+
+    /// Human-readable name
+    pub fn name(self) -> &'static str {
+        match self {
+            BuiltInTemplate::Blank => "Blank",
+            BuiltInTemplate::Kanban => "Kanban",
+            BuiltInTemplate::Retrospective => "Retrospective",
+            BuiltInTemplate::MoodboardGrid => "Moodboard Grid",
+            BuiltInTemplate::MindMap => "Mind Map",
+        }
+    }
+
+    /// One-line summary shown under the name
+    pub fn description(self) -> &'static str {
+        match self {
+            BuiltInTemplate::Blank => "An empty canvas",
+            BuiltInTemplate::Kanban => "To do, in progress and done columns",
+            BuiltInTemplate::Retrospective => "Went well, to improve and action items",
+            BuiltInTemplate::MoodboardGrid => "A framed grid of image placeholders",
+            BuiltInTemplate::MindMap => "A central idea with four branches",
+        }
+    }
+
+    /// The template's items, with ids starting at 0
+    pub fn items(self) -> Vec<CanvasItem> {
+        let parts = match self {
+            BuiltInTemplate::Blank => Vec::new(),
+            BuiltInTemplate::Kanban => columns(&[
+                ("To Do", "#fde68a", "Drag cards between columns"),
+                (
+                    "In Progress",
+                    "#fde68a",
+                    "Add cards with the sticky note tool",
+                ),
+                ("Done", "#fde68a", "Move finished work here"),
+            ]),
+            BuiltInTemplate::Retrospective => {
+                let mut parts = vec![text_box((0.0, -140.0), (680.0, 60.0), "# Retrospective")];
+                parts.extend(columns(&[
+                    ("Went Well", "#bbf7d0", "What helped us?"),
+                    ("To Improve", "#fecaca", "What slowed us down?"),
+                    ("Action Items", "#bfdbfe", "Who does what, by when?"),
+                ]));
+                parts
+            }
+            BuiltInTemplate::MoodboardGrid => moodboard_grid(),
+            BuiltInTemplate::MindMap => mind_map(),
+        };
+
+        parts
+            .into_iter()
+            .enumerate()
+            .map(|(id, (position, size, content))| CanvasItem {
+                id: id as u64,
+                position,
+                size,
+                content,
+            })
+            .collect()
+    }
+
+    /// Board contents for a new board made from this template
+    pub fn board_state(self) -> BoardState {
+        let items = self.items();
+        BoardState {
+            canvas_offset: (120.0, 160.0),
+            zoom: 1.0,
+            next_item_id: items.len() as u64,
+            items,
+            bookmarks: Vec::new(),
+            pdf_annotations: Vec::new(),
+            comment_threads: Vec::new(),
+        }
+    }
+}
+
+/// Position, size and content of an item before it gets an id
+type Part = ((f32, f32), (f32, f32), ItemContent);
+
+const COLUMN_WIDTH: f32 = 320.0;
+const COLUMN_HEIGHT: f32 = 560.0;
+const COLUMN_GAP: f32 = 40.0;
+
+/// Titled frames side by side, each with a starter sticky note
+fn columns(columns: &[(&str, &str, &str)]) -> Vec<Part> {
+    let mut parts = Vec::new();
+    for (i, (title, note_color, note)) in columns.iter().enumerate() {
+        let x = i as f32 * (COLUMN_WIDTH + COLUMN_GAP);
+        parts.push((
+            (x, 0.0),
+            (COLUMN_WIDTH, COLUMN_HEIGHT),
+            shape(ShapeType::Frame, None, Some(title)),
+        ));
+        parts.push((
+            (x + 20.0, 20.0),
+            (COLUMN_WIDTH - 40.0, 140.0),
+            shape(ShapeType::StickyNote, Some(note_color), Some(note)),
+        ));
+    }
+    parts
+}
+
+fn moodboard_grid() -> Vec<Part> {
+    const CELL: (f32, f32) = (280.0, 200.0);
+    const GAP: f32 = 20.0;
+
+    let mut parts = vec![(
+        (0.0, 0.0),
+        (CELL.0 * 3.0 + GAP * 4.0, CELL.1 * 2.0 + GAP * 3.0),
+        shape(ShapeType::Frame, None, Some("Moodboard")),
+    )];
+    for row in 0..2 {
+        for col in 0..3 {
+            parts.push((
+                (
+                    GAP + col as f32 * (CELL.0 + GAP),
+                    GAP + row as f32 * (CELL.1 + GAP),
+                ),
+                CELL,
+                shape(
+                    ShapeType::RoundedRect,
+                    Some("#e5e7eb"),
+                    Some("Drop an image"),
+                ),
+            ));
+        }
+    }
+    parts
+}
+
+fn mind_map() -> Vec<Part> {
+    const CENTRE: (f32, f32) = (400.0, 260.0);
+    const CENTRE_SIZE: (f32, f32) = (220.0, 110.0);
+    const BRANCH_SIZE: (f32, f32) = (170.0, 70.0);
+
+    let centre = (
+        CENTRE.0 + CENTRE_SIZE.0 / 2.0,
+        CENTRE.1 + CENTRE_SIZE.1 / 2.0,
+    );
+    let mut parts = vec![(
+        CENTRE,
+        CENTRE_SIZE,
+        shape(ShapeType::Ellipse, Some("#c7d2fe"), Some("Central idea")),
+    )];
+
+    let branches = [(40.0, 40.0), (800.0, 40.0), (40.0, 520.0), (800.0, 520.0)];
+    for (i, &position) in branches.iter().enumerate() {
+        let label = format!("Idea {}", i + 1);
+        parts.push((
+            position,
+            BRANCH_SIZE,
+            shape(ShapeType::RoundedRect, None, Some(&label)),
+        ));
+
+        let branch = (
+            position.0 + BRANCH_SIZE.0 / 2.0,
+            position.1 + BRANCH_SIZE.1 / 2.0,
+        );
+        parts.push(connector(centre, branch));
+    }
+
+    // Connectors go first so they sit behind the shapes they join
+    parts.sort_by_key(|(_, _, content)| !matches!(content, ItemContent::Arrow { .. }));
+    parts
+}
+
+fn shape(shape_type: ShapeType, fill: Option<&str>, label: Option<&str>) -> ItemContent {
+    ItemContent::Shape {
+        shape_type,
+        fill_color: fill.map(str::to_string),
+        border_color: String::new(),
+        border_width: 2.0,
+        label: label.map(str::to_string),
+    }
+}
+
+fn text_box(position: (f32, f32), size: (f32, f32), text: &str) -> Part {
+    (
+        position,
+        size,
+        ItemContent::TextBox {
+            text: text.to_string(),
+            font_size: crate::constants::DEFAULT_FONT_SIZE,
+            color: String::new(),
+            format: TextFormat::Markdown,
+            align: TextAlignment::default(),
+            font_family: None,
+            fill_color: None,
+        },
+    )
+}
+
+/// Headless line between two canvas points, sized like one drawn with the arrow tool
+fn connector(start: (f32, f32), end: (f32, f32)) -> Part {
+    (
+        (start.0.min(end.0), start.1.min(end.1)),
+        (
+            (end.0 - start.0).abs().max(20.0),
+            (end.1 - start.1).abs().max(20.0),
+        ),
+        ItemContent::Arrow {
+            end_offset: (end.0 - start.0, end.1 - start.1),
+            color: String::new(),
+            thickness: 2.0,
+            head_style: ArrowHead::None,
+            label: None,
+        },
+    )
+}
+
+/// A board saved as a template, as listed in its `template.json`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedTemplate {
+    /// Folder name inside the templates directory
+    pub id: String,
+    /// User-visible template name
+    pub name: String,
+    /// Unix timestamp when the template was saved
+    pub created_at: u64,
+}
+
+/// The template a new board starts from
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemplateChoice {
+    BuiltIn(BuiltInTemplate),
+    Saved(SavedTemplate),
+}
+
+impl Default for TemplateChoice {
+    fn default() -> Self {
+        TemplateChoice::BuiltIn(BuiltInTemplate::Blank)
+    }
+}
+
+impl TemplateChoice {
+    /// Human-readable name
+    pub fn name(&self) -> &str {
+        match self {
+            TemplateChoice::BuiltIn(template) => template.name(),
+            TemplateChoice::Saved(template) => &template.name,
+        }
+    }
+}
+
+/// Directory holding saved templates
+pub fn templates_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("humanboard")
+        .join("templates")
+}
+
+/// Saved templates in `dir`, newest first. Unreadable folders are skipped.
+pub fn list_saved_templates(dir: &Path) -> Vec<SavedTemplate> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut templates: Vec<SavedTemplate> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| fs::read_to_string(e.path().join("template.json")).ok())
+        .filter_map(|json| serde_json::from_str(&json).ok())
+        .collect();
+    templates.sort_by_key(|t| std::cmp::Reverse(t.created_at));
+    templates
+}
+
+/// Save a board as a template in `dir`, copying every file it references.
+///
+/// Files that no longer exist are left pointing at their old location. Nothing
+/// is left behind in `dir` if saving fails.
+pub fn save_template(
+    dir: &Path,
+    name: &str,
+    state: &BoardState,
+) -> Result<SavedTemplate, TemplateError> {
+    let template = SavedTemplate {
+        id: generate_uuid(),
+        name: name.to_string(),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    };
+    let template_dir = dir.join(&template.id);
+    let result = write_template(&template_dir, &template, state);
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&template_dir);
+    }
+    result.map(|()| template)
+}
+
+/// Write a template's folder, copying the board's files into it
+fn write_template(
+    template_dir: &Path,
+    template: &SavedTemplate,
+    state: &BoardState,
+) -> Result<(), TemplateError> {
+    let files_dir = template_dir.join("files");
+    fs::create_dir_all(&files_dir)?;
+
+    let mut state = state.clone();
+    state.comment_threads.clear();
+    state.bookmarks.clear();
+
+    let mut copied: HashMap<PathBuf, PathBuf> = HashMap::new();
+    let paths = state
+        .items
+        .iter_mut()
+        .flat_map(|item| item.content.file_paths_mut())
+        .chain(state.pdf_annotations.iter_mut().map(|a| &mut a.path));
+    for path in paths {
+        if let Some(dest) = copied.get(path.as_path()) {
+            *path = dest.clone();
+        } else if path.is_file() {
+            let dest = unique_path(&files_dir, path);
+            fs::copy(&*path, &dest)?;
+            copied.insert(path.clone(), dest.clone());
+            *path = dest;
+        }
+    }
+
+    state.save_to_path(&template_dir.join("board.json"))?;
+    let json = serde_json::to_string_pretty(template).map_err(crate::error::BoardError::from)?;
+    fs::write(template_dir.join("template.json"), json)?;
+    Ok(())
+}
+
+/// Write the starting contents of a new board into `board_dir`.
+///
+/// Saved templates are looked up in `templates_dir`, and their files are copied
+/// into the board's own `files/` folder. A blank template writes nothing.
+pub fn apply_template(
+    choice: &TemplateChoice,
+    templates_dir: &Path,
+    board_dir: &Path,
+) -> Result<(), TemplateError> {
+    let state = match choice {
+        TemplateChoice::BuiltIn(BuiltInTemplate::Blank) => return Ok(()),
+        TemplateChoice::BuiltIn(template) => template.board_state(),
+        TemplateChoice::Saved(template) => {
+            let template_dir = templates_dir.join(&template.id);
+            let template_files = template_dir.join("files");
+            let mut state = BoardState::try_load(&template_dir.join("board.json"))
+                .ok_or_else(|| TemplateError::NotFound(template.name.clone()))?;
+
+            let board_files = board_dir.join("files");
+            fs::create_dir_all(&board_files)?;
+            if let Ok(entries) = fs::read_dir(&template_files) {
+                for entry in entries.filter_map(|e| e.ok()) {
+                    fs::copy(entry.path(), board_files.join(entry.file_name()))?;
+                }
+            }
+
+            let paths = state
+                .items
+                .iter_mut()
+                .flat_map(|item| item.content.file_paths_mut())
+                .chain(state.pdf_annotations.iter_mut().map(|a| &mut a.path));
+            for path in paths {
+                if let Ok(rest) = path.strip_prefix(&template_files) {
+                    *path = board_files.join(rest);
+                }
+            }
+            state
+        }
+    };
+
+    state.save_to_path(&board_dir.join("board.json"))?;
+    Ok(())
+}
+
+/// A path in `dir` for a copy of `source`, numbered if the name is taken
+fn unique_path(dir: &Path, source: &Path) -> PathBuf {
+    let name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "file".to_string());
+    let candidate = dir.join(&name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let stem = source
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "file".to_string());
+    let ext = source
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|n| dir.join(format!("{}-{}{}", stem, n, ext)))
+        .find(|p| !p.exists())
+        .unwrap_or(candidate)
+}
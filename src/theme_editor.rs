@@ -0,0 +1,413 @@
+//! Theme authoring - user themes, item colours and the theme editor draft.
+//!
+//! User themes are gpui-component theme files in the user themes directory,
+//! which the `ThemeRegistry` watches and reloads. Besides the interface colours,
+//! each theme in a file may carry an `item_colors` object with the canvas
+//! background and the colours of each content type on the canvas. The theme
+//! editor forks the active theme into a [`ThemeDraft`], previews edits live and
+//! saves the draft as a new theme file.
+
+use crate::error::ThemeError;
+use gpui::{Hsla, Rgba, SharedString};
+use gpui_component::theme::{ThemeConfig, ThemeSet};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+/// Canvas and item colour overrides stored alongside a theme (`#rrggbb` hex).
+/// Unset colours fall back to the ones derived from the interface colours.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ItemColors {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canvas: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdf: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub youtube: Option<String>,
+}
+
+impl ItemColors {
+    /// Check if no colour is overridden
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The parts of a theme file we read on top of what the `ThemeRegistry` loads
+#[derive(Deserialize)]
+struct ThemeFile {
+    #[serde(default)]
+    themes: Vec<ThemeFileEntry>,
+}
+
+#[derive(Deserialize)]
+struct ThemeFileEntry {
+    name: String,
+    #[serde(default)]
+    item_colors: Option<ItemColors>,
+}
+
+fn read_theme_file(path: &Path) -> Option<ThemeFile> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn is_theme_file(path: &Path) -> bool {
+    path.is_file() && path.extension().and_then(|e| e.to_str()) == Some("json")
+}
+
+/// Read the item colours of every theme in the theme files in `dir`, by theme name
+pub fn load_item_colors(dir: &Path) -> HashMap<String, ItemColors> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_theme_file(path))
+        .filter_map(|path| read_theme_file(&path))
+        .flat_map(|file| file.themes)
+        .filter_map(|theme| Some((theme.name, theme.item_colors?)))
+        .collect()
+}
+
+static ITEM_COLORS: OnceLock<RwLock<HashMap<String, ItemColors>>> = OnceLock::new();
+
+fn global_item_colors() -> &'static RwLock<HashMap<String, ItemColors>> {
+    ITEM_COLORS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Replace the known item colours with the ones in the theme files in `dir`
+pub fn reload_item_colors(dir: &Path) {
+    let colors = load_item_colors(dir);
+    let mut guard = global_item_colors()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *guard = colors;
+}
+
+/// Get the item colours of a theme (empty if it doesn't set any)
+pub fn item_colors(theme_name: &str) -> ItemColors {
+    let guard = global_item_colors()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.get(theme_name).cloned().unwrap_or_default()
+}
+
+/// Set the item colours of a theme in memory, e.g. to preview an unsaved theme
+pub fn set_item_colors(theme_name: &str, colors: ItemColors) {
+    let mut guard = global_item_colors()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.insert(theme_name.to_string(), colors);
+}
+
+/// File in the user themes directory recording the hash of each bundled theme
+/// as last installed. It has no `.json` extension so the registry skips it.
+pub const BUNDLED_MANIFEST: &str = ".bundled-themes";
+
+fn content_hash(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Copy the bundled theme files into the user themes directory so the registry
+/// can load both from one watched directory.
+///
+/// A theme is copied when it's missing, or when the installed file is still
+/// the bundled version last installed, so updated bundled themes reach
+/// existing users while a user's edits are kept. Files installed before
+/// versions were recorded are only replaced if they already match.
+pub fn install_bundled_themes(bundled_dir: &Path, user_dir: &Path) -> std::io::Result<()> {
+    if fs::canonicalize(bundled_dir).ok() == fs::canonicalize(user_dir).ok() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(user_dir)?;
+    let manifest_path = user_dir.join(BUNDLED_MANIFEST);
+    let recorded: BTreeMap<String, String> = fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let mut installed = recorded.clone();
+
+    for entry in fs::read_dir(bundled_dir)?.flatten() {
+        let path = entry.path();
+        if !is_theme_file(&path) {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        let bundled = fs::read(&path)?;
+        let bundled_hash = content_hash(&bundled);
+        let target = user_dir.join(&name);
+        let current_hash = fs::read(&target).ok().map(|current| content_hash(&current));
+        match current_hash {
+            Some(hash) if hash == bundled_hash => {}
+            // Still the version installed last time, so take the update
+            Some(hash) if recorded.get(&name) == Some(&hash) => fs::write(&target, &bundled)?,
+            // Edited by the user
+            Some(_) => continue,
+            None => fs::write(&target, &bundled)?,
+        }
+        installed.insert(name, bundled_hash);
+    }
+
+    if installed != recorded {
+        let json = serde_json::to_string_pretty(&installed).map_err(std::io::Error::other)?;
+        fs::write(&manifest_path, json)?;
+    }
+    Ok(())
+}
+
+/// Format a colour as `#rrggbb` hex, dropping alpha
+pub fn hsla_to_hex(color: Hsla) -> String {
+    let rgba = Rgba::from(color);
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(rgba.r),
+        channel(rgba.g),
+        channel(rgba.b)
+    )
+}
+
+/// Normalise a user-entered `#rrggbb` (or `rrggbb`) colour to lowercase with a `#`
+pub fn normalize_hex(input: &str) -> Option<String> {
+    let hex = input.trim().trim_start_matches('#');
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("#{}", hex.to_ascii_lowercase()))
+    } else {
+        None
+    }
+}
+
+/// Colours the theme editor lets you change
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ThemeColorSlot {
+    #[default]
+    Background,
+    Foreground,
+    Primary,
+    Accent,
+    Border,
+    Muted,
+    Canvas,
+    Video,
+    Audio,
+    Text,
+    Pdf,
+    Link,
+    YouTube,
+}
+
+impl ThemeColorSlot {
+    /// Every editable colour, in the order shown by the editor
+    pub const ALL: [ThemeColorSlot; 13] = [
+        ThemeColorSlot::Background,
+        ThemeColorSlot::Foreground,
+        ThemeColorSlot::Primary,
+        ThemeColorSlot::Accent,
+        ThemeColorSlot::Border,
+        ThemeColorSlot::Muted,
+        ThemeColorSlot::Canvas,
+        ThemeColorSlot::Video,
+        ThemeColorSlot::Audio,
+        ThemeColorSlot::Text,
+        ThemeColorSlot::Pdf,
+        ThemeColorSlot::Link,
+        ThemeColorSlot::YouTube,
+    ];
+
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeColorSlot::Background => "Background",
+            ThemeColorSlot::Foreground => "Text",
+            ThemeColorSlot::Primary => "Primary",
+            ThemeColorSlot::Accent => "Accent",
+            ThemeColorSlot::Border => "Border",
+            ThemeColorSlot::Muted => "Muted",
+            ThemeColorSlot::Canvas => "Canvas",
+            ThemeColorSlot::Video => "Video Items",
+            ThemeColorSlot::Audio => "Audio Items",
+            ThemeColorSlot::Text => "Text Items",
+            ThemeColorSlot::Pdf => "PDF Items",
+            ThemeColorSlot::Link => "Link Items",
+            ThemeColorSlot::YouTube => "YouTube Items",
+        }
+    }
+
+    /// Check if the colour is stored in `ItemColors` rather than the theme colours
+    pub fn is_item_color(self) -> bool {
+        !matches!(
+            self,
+            ThemeColorSlot::Background
+                | ThemeColorSlot::Foreground
+                | ThemeColorSlot::Primary
+                | ThemeColorSlot::Accent
+                | ThemeColorSlot::Border
+                | ThemeColorSlot::Muted
+        )
+    }
+}
+
+/// An unsaved theme being edited, forked from an existing theme
+#[derive(Debug, Clone)]
+pub struct ThemeDraft {
+    pub config: ThemeConfig,
+    pub item_colors: ItemColors,
+}
+
+impl ThemeDraft {
+    /// Fork `base` under a new name. The fork is never a default theme.
+    pub fn fork(base: &ThemeConfig, item_colors: ItemColors) -> Self {
+        let mut config = base.clone();
+        config.name = SharedString::from(format!("{} Custom", base.name));
+        config.is_default = false;
+        Self {
+            config,
+            item_colors,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.config.name = SharedString::from(name.trim().to_string());
+    }
+
+    /// Get a colour as set in the draft, if it is set
+    pub fn color(&self, slot: ThemeColorSlot) -> Option<&str> {
+        let colors = &self.config.colors;
+        let item_colors = &self.item_colors;
+        match slot {
+            ThemeColorSlot::Background => colors.background.as_deref(),
+            ThemeColorSlot::Foreground => colors.foreground.as_deref(),
+            ThemeColorSlot::Primary => colors.primary.as_deref(),
+            ThemeColorSlot::Accent => colors.accent.as_deref(),
+            ThemeColorSlot::Border => colors.border.as_deref(),
+            ThemeColorSlot::Muted => colors.muted.as_deref(),
+            ThemeColorSlot::Canvas => item_colors.canvas.as_deref(),
+            ThemeColorSlot::Video => item_colors.video.as_deref(),
+            ThemeColorSlot::Audio => item_colors.audio.as_deref(),
+            ThemeColorSlot::Text => item_colors.text.as_deref(),
+            ThemeColorSlot::Pdf => item_colors.pdf.as_deref(),
+            ThemeColorSlot::Link => item_colors.link.as_deref(),
+            ThemeColorSlot::YouTube => item_colors.youtube.as_deref(),
+        }
+    }
+
+    /// Set a colour from user input in `#rrggbb` form
+    pub fn set_color(&mut self, slot: ThemeColorSlot, input: &str) -> Result<(), ThemeError> {
+        let hex = normalize_hex(input).ok_or_else(|| ThemeError::InvalidColor(input.to_string()))?;
+        let colors = &mut self.config.colors;
+        let item_colors = &mut self.item_colors;
+        match slot {
+            ThemeColorSlot::Background => colors.background = Some(hex.into()),
+            ThemeColorSlot::Foreground => colors.foreground = Some(hex.into()),
+            ThemeColorSlot::Primary => colors.primary = Some(hex.into()),
+            ThemeColorSlot::Accent => colors.accent = Some(hex.into()),
+            ThemeColorSlot::Border => colors.border = Some(hex.into()),
+            ThemeColorSlot::Muted => colors.muted = Some(hex.into()),
+            ThemeColorSlot::Canvas => item_colors.canvas = Some(hex),
+            ThemeColorSlot::Video => item_colors.video = Some(hex),
+            ThemeColorSlot::Audio => item_colors.audio = Some(hex),
+            ThemeColorSlot::Text => item_colors.text = Some(hex),
+            ThemeColorSlot::Pdf => item_colors.pdf = Some(hex),
+            ThemeColorSlot::Link => item_colors.link = Some(hex),
+            ThemeColorSlot::YouTube => item_colors.youtube = Some(hex),
+        }
+        Ok(())
+    }
+
+    /// File name the draft is saved under, derived from its name
+    pub fn file_name(&self) -> String {
+        let slug: String = self
+            .name()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        let slug = slug
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        format!("{}.json", if slug.is_empty() { "theme" } else { &slug })
+    }
+
+    /// Serialize the draft as a theme file holding just this theme
+    pub fn to_json(&self) -> Result<String, ThemeError> {
+        let set = ThemeSet {
+            name: self.config.name.clone(),
+            themes: vec![self.config.clone()],
+            ..Default::default()
+        };
+
+        let mut value = serde_json::to_value(&set)?;
+        if !self.item_colors.is_empty()
+            && let Some(theme) = value
+                .get_mut("themes")
+                .and_then(|themes| themes.get_mut(0))
+                .and_then(|theme| theme.as_object_mut())
+        {
+            theme.insert(
+                "item_colors".to_string(),
+                serde_json::to_value(&self.item_colors)?,
+            );
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /// Save the draft into `dir`, returning the path written.
+    ///
+    /// `existing_names` are the themes already loaded. Saving may only replace
+    /// a file that holds nothing but an earlier save of this same theme.
+    pub fn save(&self, dir: &Path, existing_names: &[String]) -> Result<PathBuf, ThemeError> {
+        let name = self.name();
+        if name.is_empty() {
+            return Err(ThemeError::EmptyName);
+        }
+
+        let path = dir.join(self.file_name());
+        let replaces_own_save = read_theme_file(&path)
+            .is_some_and(|file| file.themes.len() == 1 && file.themes[0].name == name);
+        let name_taken = existing_names.iter().any(|existing| existing == name);
+        if (path.exists() || name_taken) && !replaces_own_save {
+            return Err(ThemeError::NameTaken(name.to_string()));
+        }
+
+        let json = self.to_json()?;
+        fs::create_dir_all(dir).map_err(|source| ThemeError::SaveFailed {
+            path: path.clone(),
+            source,
+        })?;
+        fs::write(&path, json).map_err(|source| ThemeError::SaveFailed {
+            path: path.clone(),
+            source,
+        })?;
+        Ok(path)
+    }
+}
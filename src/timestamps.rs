@@ -0,0 +1,34 @@
+//! Timestamps - the current Unix time and how long ago a moment was.
+//!
+//! Boards, comments and the palette history all keep Unix timestamps in
+//! seconds and show them relative to now.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds in a week, after which `time_ago` counts weeks
+pub const WEEK_SECS: u64 = 7 * 86400;
+
+/// Current Unix time in seconds
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// How long before `now` `timestamp` was, such as `5 min ago`. Times after
+/// `now`, from a clock that's behind, are `Just now`.
+pub fn time_ago(timestamp: u64, now: u64) -> String {
+    let diff = now.saturating_sub(timestamp);
+    if diff < 60 {
+        "Just now".to_string()
+    } else if diff < 3600 {
+        format!("{} min ago", diff / 60)
+    } else if diff < 86400 {
+        format!("{} hours ago", diff / 3600)
+    } else if diff < WEEK_SECS {
+        format!("{} days ago", diff / 86400)
+    } else {
+        format!("{} weeks ago", diff / WEEK_SECS)
+    }
+}
@@ -3,7 +3,9 @@
 //! This module defines the fundamental data structures used throughout the application,
 //! including canvas items, content types, and helper functions for content detection.
 
+use crate::image_edit::ImageEdit;
 use crate::pdf_thumbnail::generate_pdf_thumbnail;
+use crate::waveform::AudioMarker;
 use image::GenericImageView;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -24,6 +26,49 @@ pub struct CanvasItem {
     pub content: ItemContent,
 }
 
+impl CanvasItem {
+    /// Canvas points an arrow item starts and ends at
+    pub fn arrow_endpoints(&self) -> Option<((f32, f32), (f32, f32))> {
+        let ItemContent::Arrow { end_offset, .. } = self.content else {
+            return None;
+        };
+        // Arrows start at the corner their offset points away from
+        let start = (
+            if end_offset.0 < 0.0 { self.position.0 + self.size.0 } else { self.position.0 },
+            if end_offset.1 < 0.0 { self.position.1 + self.size.1 } else { self.position.1 },
+        );
+        Some((start, (start.0 + end_offset.0, start.1 + end_offset.1)))
+    }
+
+    /// Take `other`'s position, size and arrow endpoints, keeping the rest of
+    /// this item's content
+    pub fn copy_geometry_from(&mut self, other: &CanvasItem) {
+        self.position = other.position;
+        self.size = other.size;
+        if let (
+            ItemContent::Arrow { end_offset, .. },
+            ItemContent::Arrow {
+                end_offset: other_offset,
+                ..
+            },
+        ) = (&mut self.content, &other.content)
+        {
+            *end_offset = *other_offset;
+        }
+    }
+}
+
+/// A named, saved viewport that can be jumped back to.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ViewportBookmark {
+    /// Name shown in the command palette
+    pub name: String,
+    /// Canvas offset (x, y) when the bookmark was saved
+    pub canvas_offset: (f32, f32),
+    /// Zoom level when the bookmark was saved
+    pub zoom: f32,
+}
+
 /// Tool types for the Miro-style tool dock
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ToolType {
@@ -32,6 +77,64 @@ pub enum ToolType {
     Text,
     Arrow,
     Shape,
+    Pen,
+    Highlighter,
+    Eraser,
+}
+
+impl ToolType {
+    /// Tools that follow the pointer rather than drawing a box
+    pub fn is_freehand(self) -> bool {
+        matches!(
+            self,
+            ToolType::Pen | ToolType::Highlighter | ToolType::Eraser
+        )
+    }
+
+    /// The kind of stroke this tool draws, if any
+    pub fn stroke_kind(self) -> Option<StrokeKind> {
+        match self {
+            ToolType::Pen => Some(StrokeKind::Pen),
+            ToolType::Highlighter => Some(StrokeKind::Highlighter),
+            _ => None,
+        }
+    }
+}
+
+/// How a freehand stroke is painted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StrokeKind {
+    /// Opaque pen line
+    #[default]
+    Pen,
+    /// Wide translucent highlighter
+    Highlighter,
+}
+
+impl StrokeKind {
+    /// Default line width in canvas units
+    pub fn default_width(self) -> f32 {
+        match self {
+            StrokeKind::Pen => 3.0,
+            StrokeKind::Highlighter => 18.0,
+        }
+    }
+
+    /// Default color (empty means the theme foreground)
+    pub fn default_color(self) -> &'static str {
+        match self {
+            StrokeKind::Pen => "",
+            StrokeKind::Highlighter => "#facc15",
+        }
+    }
+
+    /// Opacity the stroke is painted with
+    pub fn opacity(self) -> f32 {
+        match self {
+            StrokeKind::Pen => 1.0,
+            StrokeKind::Highlighter => 0.35,
+        }
+    }
 }
 
 /// Shape types for the Shape tool
@@ -41,6 +144,135 @@ pub enum ShapeType {
     Rectangle,
     RoundedRect,
     Ellipse,
+    Diamond,
+    Triangle,
+    Parallelogram,
+    Cylinder,
+    Hexagon,
+    Star,
+    /// Straight horizontal line through the middle of the item bounds
+    Line,
+    /// Filled note with editable text, clickable across its whole body
+    StickyNote,
+    /// Titled slide region that presentation mode steps through
+    Frame,
+}
+
+impl ShapeType {
+    /// Every shape, in the order shown by the shape picker
+    pub const ALL: [ShapeType; 12] = [
+        ShapeType::Rectangle,
+        ShapeType::RoundedRect,
+        ShapeType::Ellipse,
+        ShapeType::Diamond,
+        ShapeType::Triangle,
+        ShapeType::Parallelogram,
+        ShapeType::Cylinder,
+        ShapeType::Hexagon,
+        ShapeType::Star,
+        ShapeType::Line,
+        ShapeType::StickyNote,
+        ShapeType::Frame,
+    ];
+
+    /// Human-readable name
+    pub fn name(self) -> &'static str {
+        match self {
+            ShapeType::Rectangle => "Rectangle",
+            ShapeType::RoundedRect => "Rounded Rect",
+            ShapeType::Ellipse => "Ellipse",
+            ShapeType::Diamond => "Diamond",
+            ShapeType::Triangle => "Triangle",
+            ShapeType::Parallelogram => "Parallelogram",
+            ShapeType::Cylinder => "Cylinder",
+            ShapeType::Hexagon => "Hexagon",
+            ShapeType::Star => "Star",
+            ShapeType::Line => "Line",
+            ShapeType::StickyNote => "Sticky Note",
+            ShapeType::Frame => "Frame",
+        }
+    }
+
+    /// Glyph used for the shape in the tool dock picker
+    pub fn icon(self) -> &'static str {
+        match self {
+            ShapeType::Rectangle => "□",
+            ShapeType::RoundedRect => "▢",
+            ShapeType::Ellipse => "○",
+            ShapeType::Diamond => "◇",
+            ShapeType::Triangle => "△",
+            ShapeType::Parallelogram => "▱",
+            ShapeType::Cylinder => "⌭",
+            ShapeType::Hexagon => "⬡",
+            ShapeType::Star => "☆",
+            ShapeType::Line => "─",
+            ShapeType::StickyNote => "▤",
+            ShapeType::Frame => "⬚",
+        }
+    }
+
+    /// Size used when the shape is placed with a click instead of a drag
+    pub fn default_size(self) -> (f32, f32) {
+        match self {
+            ShapeType::Rectangle | ShapeType::RoundedRect | ShapeType::Ellipse => (150.0, 100.0),
+            ShapeType::Diamond | ShapeType::Star => (140.0, 140.0),
+            ShapeType::Triangle => (140.0, 120.0),
+            ShapeType::Parallelogram => (170.0, 100.0),
+            ShapeType::Cylinder => (120.0, 150.0),
+            ShapeType::Hexagon => (160.0, 140.0),
+            ShapeType::Line => (200.0, 20.0),
+            ShapeType::StickyNote => (200.0, 200.0),
+            ShapeType::Frame => (960.0, 540.0),
+        }
+    }
+
+    /// Fill colour a newly created shape starts with
+    pub fn default_fill(self) -> Option<&'static str> {
+        match self {
+            ShapeType::StickyNote => Some("#fde68a"),
+            _ => None,
+        }
+    }
+}
+
+/// How the text of a text box is interpreted when rendered
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextFormat {
+    /// Literal text, rendered line by line (legacy text boxes)
+    #[default]
+    Plain,
+    /// Lightweight markdown subset: emphasis, headings, lists, checkboxes and links
+    Markdown,
+}
+
+/// Horizontal alignment of text inside a text box
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl TextAlignment {
+    /// Parse an alignment name as typed in the command palette
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "left" | "l" => Some(TextAlignment::Left),
+            "center" | "centre" | "c" => Some(TextAlignment::Center),
+            "right" | "r" => Some(TextAlignment::Right),
+            _ => None,
+        }
+    }
+
+    /// The next alignment in left → center → right order
+    pub fn next(self) -> Self {
+        match self {
+            TextAlignment::Left => TextAlignment::Center,
+            TextAlignment::Center => TextAlignment::Right,
+            TextAlignment::Right => TextAlignment::Left,
+        }
+    }
 }
 
 /// Arrow head styles
@@ -59,14 +291,22 @@ pub enum ArrowHead {
 /// Each variant represents a different type of media or element.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ItemContent {
-    /// An image file (PNG, JPEG, GIF, WebP, etc.)
-    Image(PathBuf),
+    /// An image file (PNG, JPEG, GIF, WebP, etc.) with its crop, rotation and flips
+    #[serde(
+        serialize_with = "crate::image_edit::serialize_image",
+        deserialize_with = "crate::image_edit::deserialize_image"
+    )]
+    Image(PathBuf, ImageEdit),
     /// Plain text content
     Text(String),
     /// A video file (MP4, MOV, WebM, etc.)
     Video(PathBuf),
-    /// An audio file (MP3, WAV, OGG, etc.)
-    Audio(PathBuf),
+    /// An audio file (MP3, WAV, OGG, etc.) with its timestamped markers
+    #[serde(
+        serialize_with = "crate::waveform::serialize_audio",
+        deserialize_with = "crate::waveform::deserialize_audio"
+    )]
+    Audio(PathBuf, Vec<AudioMarker>),
     /// A PDF document with optional thumbnail
     Pdf {
         /// Path to the PDF file
@@ -74,6 +314,15 @@ pub enum ItemContent {
         /// Path to generated thumbnail image
         thumbnail: Option<PathBuf>,
     },
+    /// One page of a PDF, placed on the canvas as an image
+    PdfPage {
+        /// Path to the source PDF
+        path: PathBuf,
+        /// Page number, starting at 1
+        page: usize,
+        /// Path to the rendered page image
+        image: Option<PathBuf>,
+    },
     /// A web link/URL
     Link(String),
     /// An embedded YouTube video (stores video ID)
@@ -102,6 +351,18 @@ pub enum ItemContent {
         font_size: f32,
         /// Text color as hex string (e.g., "#ffffff")
         color: String,
+        /// How `text` is interpreted (boxes saved before rich text load as plain)
+        #[serde(default)]
+        format: TextFormat,
+        /// Horizontal text alignment
+        #[serde(default)]
+        align: TextAlignment,
+        /// Font family override (None uses the app font)
+        #[serde(default)]
+        font_family: Option<String>,
+        /// Optional background fill as hex string
+        #[serde(default)]
+        fill_color: Option<String>,
     },
     /// Arrow/line connecting points
     Arrow {
@@ -113,6 +374,9 @@ pub enum ItemContent {
         thickness: f32,
         /// Style of the arrow head
         head_style: ArrowHead,
+        /// Optional label drawn at the arrow's mid-point
+        #[serde(default)]
+        label: Option<String>,
     },
     /// Shape with optional fill and border
     Shape {
@@ -124,6 +388,22 @@ pub enum ItemContent {
         border_color: String,
         /// Border width in pixels
         border_width: f32,
+        /// Optional label centred inside the shape
+        #[serde(default)]
+        label: Option<String>,
+    },
+    /// Freehand pen or highlighter stroke
+    Stroke {
+        /// Points normalised to the item bounds (0.0-1.0), stored as a flat array
+        #[serde(with = "crate::strokes::compact_points")]
+        points: Vec<(f32, f32)>,
+        /// Stroke color as hex string
+        color: String,
+        /// Line width in pixels, independent of pointer pressure
+        width: f32,
+        /// Pen or highlighter
+        #[serde(default)]
+        kind: StrokeKind,
     },
 }
 
@@ -182,6 +462,17 @@ pub fn language_from_extension(ext: &str) -> Option<&'static str> {
     }
 }
 
+/// Position and size of an arrow item running from `start` to `end`, sized
+/// like one drawn with the arrow tool
+pub fn arrow_bounds(start: (f32, f32), end: (f32, f32)) -> ((f32, f32), (f32, f32)) {
+    let size = ((end.0 - start.0).abs().max(20.0), (end.1 - start.1).abs().max(20.0));
+    let position = (
+        if end.0 < start.0 { start.0 - size.0 } else { start.0 },
+        if end.1 < start.1 { start.1 - size.1 } else { start.1 },
+    );
+    (position, size)
+}
+
 /// Extract YouTube video ID from various URL formats
 pub fn extract_youtube_id(url: &str) -> Option<String> {
     // Handle youtu.be/VIDEO_ID
@@ -217,10 +508,19 @@ pub fn extract_youtube_id(url: &str) -> Option<String> {
 impl ItemContent {
     pub fn default_size(&self) -> (f32, f32) {
         match self {
-            ItemContent::Image(path) => {
+            ItemContent::Image(path, edit) => {
                 // Try to load the image and get its actual dimensions, scaled to max 800px
                 if let Ok(img) = image::open(path) {
                     let (width, height) = img.dimensions();
+                    // Size the image as it's displayed
+                    let (width, height) = if edit.is_sideways() {
+                        (height, width)
+                    } else {
+                        (width, height)
+                    };
+                    let crop = edit.crop_rect();
+                    let width = ((width as f32 * crop.width).round() as u32).max(1);
+                    let height = ((height as f32 * crop.height).round() as u32).max(1);
                     let max_dimension = 800.0;
 
                     let aspect_ratio = width as f32 / height as f32;
@@ -244,8 +544,9 @@ impl ItemContent {
             }
             ItemContent::Text(_) => (300.0, 100.0),
             ItemContent::Video(_) => (400.0, 300.0),
-            ItemContent::Audio(_) => (320.0, 160.0), // Compact audio player
+            ItemContent::Audio(..) => (320.0, 220.0), // Player above its waveform
             ItemContent::Pdf { .. } => (180.0, 240.0),
+            ItemContent::PdfPage { .. } => (240.0, 320.0),
             ItemContent::Link(_) => (300.0, 150.0),
             ItemContent::YouTube(_) => (560.0, 315.0), // 16:9 aspect ratio
             ItemContent::Markdown { .. } => (200.0, 36.0), // Simple filename button
@@ -257,13 +558,16 @@ impl ItemContent {
                 let h = end_offset.1.abs().max(20.0);
                 (w, h)
             }
-            ItemContent::Shape { .. } => (150.0, 100.0), // Default shape size
+            ItemContent::Shape { shape_type, .. } => shape_type.default_size(),
+            ItemContent::Stroke { width, .. } => (*width * 2.0, *width * 2.0),
         }
     }
 
     pub fn display_name(&self) -> String {
         match self {
-            ItemContent::Image(path) | ItemContent::Video(path) | ItemContent::Audio(path) => path
+            ItemContent::Image(path, _)
+            | ItemContent::Video(path)
+            | ItemContent::Audio(path, _) => path
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("Unknown")
@@ -273,6 +577,13 @@ impl ItemContent {
                 .and_then(|n| n.to_str())
                 .unwrap_or("Unknown")
                 .to_string(),
+            ItemContent::PdfPage { path, page, .. } => format!(
+                "{} p.{}",
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("Unknown"),
+                page
+            ),
             ItemContent::Text(text) => text.clone(),
             ItemContent::Link(url) => url.clone(),
             ItemContent::YouTube(id) => format!("YouTube: {}", id),
@@ -283,29 +594,102 @@ impl ItemContent {
                 .unwrap_or("Unknown")
                 .to_string(),
             ItemContent::TextBox { .. } => "TextBox".to_string(),
+            ItemContent::Arrow {
+                label: Some(label), ..
+            }
+            | ItemContent::Shape {
+                label: Some(label), ..
+            } => label.clone(),
             ItemContent::Arrow { .. } => "Arrow".to_string(),
-            ItemContent::Shape { shape_type, .. } => match shape_type {
-                ShapeType::Rectangle => "Rectangle".to_string(),
-                ShapeType::RoundedRect => "Rounded Rect".to_string(),
-                ShapeType::Ellipse => "Ellipse".to_string(),
-            },
+            ItemContent::Shape { shape_type, .. } => shape_type.name().to_string(),
+            ItemContent::Stroke {
+                kind: StrokeKind::Pen,
+                ..
+            } => "Pen Stroke".to_string(),
+            ItemContent::Stroke {
+                kind: StrokeKind::Highlighter,
+                ..
+            } => "Highlight".to_string(),
         }
     }
 
     /// Returns true if this item should appear in search results
     pub fn is_searchable(&self) -> bool {
-        !matches!(
-            self,
-            ItemContent::TextBox { .. } | ItemContent::Arrow { .. } | ItemContent::Shape { .. }
-        )
+        match self {
+            // Shapes and arrows are only worth finding once they carry a label
+            ItemContent::Arrow { label, .. } | ItemContent::Shape { label, .. } => label.is_some(),
+            ItemContent::TextBox { .. } | ItemContent::Stroke { .. } => false,
+            _ => true,
+        }
+    }
+
+    /// Text that can be edited inline on the canvas (text box contents or a shape/arrow label)
+    pub fn editable_text(&self) -> Option<&str> {
+        match self {
+            ItemContent::TextBox { text, .. } => Some(text),
+            ItemContent::Arrow { label, .. } | ItemContent::Shape { label, .. } => {
+                Some(label.as_deref().unwrap_or(""))
+            }
+            _ => None,
+        }
+    }
+
+    /// Replace the inline-editable text. Clearing a label removes it entirely.
+    pub fn set_editable_text(&mut self, new_text: String) {
+        match self {
+            ItemContent::TextBox { text, .. } => *text = new_text,
+            ItemContent::Arrow { label, .. } | ItemContent::Shape { label, .. } => {
+                *label = (!new_text.trim().is_empty()).then_some(new_text);
+            }
+            _ => {}
+        }
+    }
+
+    /// Files on disk this item refers to, for copying a board's files elsewhere
+    pub fn file_paths_mut(&mut self) -> Vec<&mut PathBuf> {
+        match self {
+            ItemContent::Image(path, _)
+            | ItemContent::Video(path)
+            | ItemContent::Audio(path, _)
+            | ItemContent::Markdown { path, .. }
+            | ItemContent::Code { path, .. } => vec![path],
+            ItemContent::Pdf { path, thumbnail } => {
+                std::iter::once(path).chain(thumbnail.as_mut()).collect()
+            }
+            ItemContent::PdfPage { path, image, .. } => {
+                std::iter::once(path).chain(image.as_mut()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Stable lowercase name of the content type, as used in settings
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ItemContent::Image(..) => "image",
+            ItemContent::Text(_) => "text",
+            ItemContent::Video(_) => "video",
+            ItemContent::Audio(..) => "audio",
+            ItemContent::Pdf { .. } => "pdf",
+            ItemContent::PdfPage { .. } => "pdf_page",
+            ItemContent::Link(_) => "link",
+            ItemContent::YouTube(_) => "youtube",
+            ItemContent::Markdown { .. } => "markdown",
+            ItemContent::Code { .. } => "code",
+            ItemContent::TextBox { .. } => "textbox",
+            ItemContent::Arrow { .. } => "arrow",
+            ItemContent::Shape { .. } => "shape",
+            ItemContent::Stroke { .. } => "stroke",
+        }
     }
 
     pub fn type_label(&self) -> &str {
         match self {
-            ItemContent::Image(_) => "IMAGE",
+            ItemContent::Image(..) => "IMAGE",
             ItemContent::Video(_) => "VIDEO",
-            ItemContent::Audio(_) => "AUDIO",
+            ItemContent::Audio(..) => "AUDIO",
             ItemContent::Pdf { .. } => "PDF",
+            ItemContent::PdfPage { .. } => "PAGE",
             ItemContent::Text(_) => "TEXT",
             ItemContent::Link(_) => "LINK",
             ItemContent::YouTube(_) => "YOUTUBE",
@@ -334,6 +718,19 @@ impl ItemContent {
                 ShapeType::Rectangle => "RECT",
                 ShapeType::RoundedRect => "RRECT",
                 ShapeType::Ellipse => "ELLIPSE",
+                ShapeType::Diamond => "DIAMOND",
+                ShapeType::Triangle => "TRIANGLE",
+                ShapeType::Parallelogram => "PARALLELOGRAM",
+                ShapeType::Cylinder => "CYLINDER",
+                ShapeType::Hexagon => "HEXAGON",
+                ShapeType::Star => "STAR",
+                ShapeType::Line => "LINE",
+                ShapeType::StickyNote => "STICKY",
+                ShapeType::Frame => "FRAME",
+            },
+            ItemContent::Stroke { kind, .. } => match kind {
+                StrokeKind::Pen => "PEN",
+                StrokeKind::Highlighter => "HIGHLIGHT",
             },
         }
     }
@@ -342,10 +739,12 @@ impl ItemContent {
         if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
             match ext.to_lowercase().as_str() {
                 "jpg" | "jpeg" | "png" | "gif" | "webp" | "bmp" | "svg" => {
-                    ItemContent::Image(path.clone())
+                    ItemContent::Image(path.clone(), ImageEdit::from_exif(path))
                 }
                 "mp4" | "mov" | "avi" | "webm" | "mkv" => ItemContent::Video(path.clone()),
-                "mp3" | "wav" | "ogg" | "m4a" | "aac" | "flac" => ItemContent::Audio(path.clone()),
+                "mp3" | "wav" | "ogg" | "m4a" | "aac" | "flac" => {
+                    ItemContent::Audio(path.clone(), Vec::new())
+                }
                 "pdf" => {
                     let thumbnail = generate_pdf_thumbnail(path);
                     ItemContent::Pdf {
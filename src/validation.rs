@@ -4,10 +4,10 @@
 //! to ensure they stay within acceptable bounds.
 
 use crate::constants::{
-    MAX_ARROW_THICKNESS, MAX_BORDER_WIDTH, MAX_FONT_SIZE, MIN_ARROW_THICKNESS, MIN_BORDER_WIDTH,
-    MIN_FONT_SIZE,
+    MAX_ARROW_THICKNESS, MAX_BORDER_WIDTH, MAX_FONT_SIZE, MAX_STROKE_WIDTH, MIN_ARROW_THICKNESS,
+    MIN_BORDER_WIDTH, MIN_FONT_SIZE, MIN_STROKE_WIDTH,
 };
-use crate::types::{CanvasItem, ItemContent};
+use crate::types::{arrow_bounds, ArrowHead, CanvasItem, ItemContent, ShapeType};
 
 /// Validation constraints for item properties
 pub struct ValidationConstraints {
@@ -113,6 +113,31 @@ fn clamp(value: f32, min: f32, max: f32) -> f32 {
 pub fn validate_item(item: &mut CanvasItem, constraints: &ValidationConstraints) -> ValidationResult {
     let mut result = ValidationResult::valid();
 
+    // Lines used to be shapes drawn across the middle of their box; they're
+    // headless arrows now so they can run in any direction
+    if let ItemContent::Shape {
+        shape_type: ShapeType::Line,
+        border_color,
+        border_width,
+        label,
+        ..
+    } = &item.content
+    {
+        let y = item.position.1 + item.size.1 / 2.0;
+        let (position, size) =
+            arrow_bounds((item.position.0, y), (item.position.0 + item.size.0, y));
+        item.content = ItemContent::Arrow {
+            end_offset: (item.size.0, 0.0),
+            color: border_color.clone(),
+            thickness: *border_width,
+            head_style: ArrowHead::None,
+            label: label.clone(),
+        };
+        item.position = position;
+        item.size = size;
+        result = result.with_fix("Line shape converted to a headless arrow".to_string());
+    }
+
     // Validate size
     let (min_w, min_h) = get_min_size_for_content(&item.content, constraints);
 
@@ -151,7 +176,10 @@ pub fn validate_item(item: &mut CanvasItem, constraints: &ValidationConstraints)
     // Validate content-specific properties
     match &mut item.content {
         ItemContent::TextBox {
-            font_size, color, ..
+            font_size,
+            color,
+            fill_color,
+            ..
         } => {
             let clamped_size = clamp(
                 *font_size,
@@ -173,6 +201,16 @@ pub fn validate_item(item: &mut CanvasItem, constraints: &ValidationConstraints)
                 ));
                 *color = "#ffffff".to_string();
             }
+
+            if let Some(fill) = fill_color {
+                if !is_valid_hex_color(fill) {
+                    result = result.with_fix(format!(
+                        "Invalid fill color '{}', removed",
+                        fill
+                    ));
+                    *fill_color = None;
+                }
+            }
         }
 
         ItemContent::Arrow {
@@ -244,6 +282,26 @@ pub fn validate_item(item: &mut CanvasItem, constraints: &ValidationConstraints)
             }
         }
 
+        ItemContent::Stroke { color, width, .. } => {
+            let clamped_width = clamp(*width, MIN_STROKE_WIDTH, MAX_STROKE_WIDTH);
+            if (*width - clamped_width).abs() > 0.01 {
+                result = result.with_fix(format!(
+                    "Stroke width {} out of range [{}, {}], clamped to {}",
+                    width, MIN_STROKE_WIDTH, MAX_STROKE_WIDTH, clamped_width
+                ));
+                *width = clamped_width;
+            }
+
+            // An empty color means the theme foreground
+            if !color.is_empty() && !is_valid_hex_color(color) {
+                result = result.with_fix(format!(
+                    "Invalid stroke color '{}', reset to theme default",
+                    color
+                ));
+                color.clear();
+            }
+        }
+
         // Other content types don't have configurable properties to validate
         _ => {}
     }
@@ -257,6 +315,8 @@ fn get_min_size_for_content(content: &ItemContent, constraints: &ValidationConst
         ItemContent::TextBox { .. } => (50.0, 30.0),
         ItemContent::Arrow { .. } => (constraints.min_item_width, constraints.min_item_height),
         ItemContent::Shape { .. } => (30.0, 30.0),
+        // Strokes are sized to their path, so a straight line is legitimately thin
+        ItemContent::Stroke { .. } => (1.0, 1.0),
         ItemContent::Markdown { .. } => (100.0, 36.0),
         ItemContent::Code { .. } => (100.0, 36.0),
         _ => (constraints.min_item_width, constraints.min_item_height),
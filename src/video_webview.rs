@@ -8,11 +8,13 @@
 //! Each video player spawns a local HTTP server on a unique port that serves:
 //! - HTML page with native video element
 //! - Video data with HTTP range request support for seeking
+//! - An upload endpoint for the poster frame shown while the player is unloaded
 //!
 //! ## Supported Formats
 //!
 //! MP4, WebM, MOV, AVI, MKV
 
+use crate::media;
 use gpui::*;
 use gpui_component::webview::WebView;
 use std::io::{Read, Seek, SeekFrom};
@@ -20,7 +22,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
 use std::thread::{self, JoinHandle};
-use tiny_http::{Header, Response, Server, StatusCode};
+use tiny_http::{Header, Method, Response, Server, StatusCode};
 use tracing::error;
 use wry::WebViewBuilder;
 
@@ -57,23 +59,47 @@ impl VideoWebView {
                 }
             };
 
-            let html = r#"<!DOCTYPE html>
+            // Capture a frame for the item's poster the first time it plays
+            let needs_poster = !media::poster_frame_path(&video_path_clone).exists();
+            let html = format!(
+                r#"<!DOCTYPE html>
 <html>
 <head>
     <meta charset="UTF-8">
     <style>
-        * { margin: 0; padding: 0; box-sizing: border-box; }
-        html, body { width: 100%; height: 100%; background: #000; overflow: hidden; }
-        video { width: 100%; height: 100%; object-fit: contain; }
+        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
+        html, body {{ width: 100%; height: 100%; background: #000; overflow: hidden; }}
+        video {{ width: 100%; height: 100%; object-fit: contain; }}
     </style>
 </head>
 <body>
     <video controls>
         <source src="/video" type="video/mp4">
     </video>
+    <script>
+        const video = document.querySelector('video');
+        let needsPoster = {needs_poster};
+        function capturePoster() {{
+            if (!needsPoster || !video.videoWidth) return;
+            needsPoster = false;
+            const scale = Math.min(1, 640 / video.videoWidth);
+            const canvas = document.createElement('canvas');
+            canvas.width = Math.round(video.videoWidth * scale);
+            canvas.height = Math.round(video.videoHeight * scale);
+            canvas.getContext('2d').drawImage(video, 0, 0, canvas.width, canvas.height);
+            canvas.toBlob(blob => {{
+                if (blob) fetch('/poster', {{ method: 'POST', body: blob }});
+            }}, 'image/jpeg', 0.8);
+        }}
+        // A second in, past any fade from black
+        video.addEventListener('timeupdate', () => {{
+            if (video.currentTime >= Math.min(1, video.duration / 2)) capturePoster();
+        }});
+        video.addEventListener('pause', capturePoster);
+    </script>
 </body>
 </html>"#
-                .to_string();
+            );
 
             loop {
                 if shutdown_flag_clone.load(Ordering::Relaxed) {
@@ -86,6 +112,8 @@ impl VideoWebView {
                         if url.starts_with("/video") {
                             // Handle range requests for video streaming
                             Self::serve_video_file(&video_path_clone, request);
+                        } else if url.starts_with("/poster") && *request.method() == Method::Post {
+                            Self::save_poster(&video_path_clone, request);
                         } else {
                             let mut response = Response::from_string(&html);
                             if let Some(header) =
@@ -140,6 +168,21 @@ impl VideoWebView {
         self.webview_entity.update(cx, |wv, _| wv.hide());
     }
 
+    /// Store a frame uploaded by the player as the item's poster
+    fn save_poster(path: &PathBuf, mut request: tiny_http::Request) {
+        let mut frame = Vec::new();
+        let read = request
+            .as_reader()
+            .take(media::MAX_POSTER_FRAME_BYTES as u64 + 1)
+            .read_to_end(&mut frame);
+        let status = if read.is_ok() && media::save_poster_frame(path, &frame) {
+            204
+        } else {
+            400
+        };
+        let _ = request.respond(Response::empty(StatusCode(status)));
+    }
+
     fn serve_video_file(path: &PathBuf, request: tiny_http::Request) {
         let mut file = match std::fs::File::open(path) {
             Ok(f) => f,
@@ -0,0 +1,359 @@
+//! Audio waveforms - peaks decoded in the background, and timestamped markers.
+//!
+//! An audio file is decoded once into a fixed number of peak amplitudes,
+//! which the canvas draws as a native waveform along the bottom of the item.
+//! Markers with notes are stored on the audio item itself; clicking one seeks
+//! the item's player to it.
+
+use crate::error::MediaError;
+use crate::media::format_duration;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as DecodeError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use tracing::{debug, warn};
+
+/// Number of peaks kept for a file
+pub const WAVEFORM_PEAKS: usize = 1024;
+
+/// Height of the waveform strip along the bottom of an audio item
+pub const WAVEFORM_HEIGHT: f32 = 56.0;
+
+/// Space between the waveform and the sides of its item
+pub const WAVEFORM_INSET: f32 = 10.0;
+
+/// Markers closer together than this, in seconds, count as the same marker
+pub const MARKER_SNAP_SECONDS: f64 = 0.5;
+
+/// Frames folded into each peak while decoding
+const FRAMES_PER_BLOCK: usize = 256;
+
+/// A note at a point in an audio item
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AudioMarker {
+    /// Offset from the start of the audio
+    pub seconds: f64,
+    #[serde(default)]
+    pub note: String,
+}
+
+/// An audio file's loudness over time
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Waveform {
+    /// Loudest sample in each slice of the audio (0-1)
+    pub peaks: Vec<f32>,
+    pub duration: Duration,
+}
+
+impl Waveform {
+    /// The waveform of interleaved samples
+    pub fn from_samples(samples: &[f32], channels: usize, sample_rate: u32) -> Self {
+        let mut peaks = PeakBuilder::new(channels);
+        peaks.push(samples);
+        peaks.finish(sample_rate)
+    }
+
+    /// Whether there's nothing to draw, e.g. because the file couldn't be decoded
+    pub fn is_empty(&self) -> bool {
+        self.peaks.is_empty() || self.duration.is_zero()
+    }
+
+    /// `count` bar heights (0-1), scaled so the loudest reaches the top
+    pub fn bars(&self, count: usize) -> Vec<f32> {
+        if self.peaks.is_empty() {
+            return Vec::new();
+        }
+        let loudest = self.peaks.iter().copied().fold(0.0, f32::max);
+        let scale = if loudest > 0.0 { 1.0 / loudest } else { 0.0 };
+        let len = self.peaks.len();
+        (0..count)
+            .map(|i| {
+                let start = i * len / count;
+                let end = ((i + 1) * len / count).max(start + 1);
+                self.peaks[start..end].iter().copied().fold(0.0, f32::max) * scale
+            })
+            .collect()
+    }
+
+    /// How far through the audio `seconds` is (0-1)
+    pub fn fraction_at(&self, seconds: f64) -> f32 {
+        if self.duration.is_zero() {
+            return 0.0;
+        }
+        (seconds / self.duration.as_secs_f64()).clamp(0.0, 1.0) as f32
+    }
+
+    /// The time a fraction (0-1) of the way through the audio
+    pub fn seconds_at(&self, fraction: f32) -> f64 {
+        self.duration.as_secs_f64() * fraction.clamp(0.0, 1.0) as f64
+    }
+}
+
+/// Collects block peaks while samples are decoded
+struct PeakBuilder {
+    channels: usize,
+    blocks: Vec<f32>,
+    block_peak: f32,
+    block_frames: usize,
+    frames: u64,
+}
+
+impl PeakBuilder {
+    fn new(channels: usize) -> Self {
+        Self {
+            channels: channels.max(1),
+            blocks: Vec::new(),
+            block_peak: 0.0,
+            block_frames: 0,
+            frames: 0,
+        }
+    }
+
+    /// Add interleaved samples
+    fn push(&mut self, samples: &[f32]) {
+        for frame in samples.chunks(self.channels) {
+            let peak = frame.iter().fold(0.0, |peak: f32, s| peak.max(s.abs()));
+            self.block_peak = self.block_peak.max(peak.min(1.0));
+            self.block_frames += 1;
+            self.frames += 1;
+            if self.block_frames == FRAMES_PER_BLOCK {
+                self.blocks.push(self.block_peak);
+                self.block_peak = 0.0;
+                self.block_frames = 0;
+            }
+        }
+    }
+
+    fn finish(mut self, sample_rate: u32) -> Waveform {
+        if self.block_frames > 0 {
+            self.blocks.push(self.block_peak);
+        }
+        // Fold the blocks down to at most WAVEFORM_PEAKS
+        let len = self.blocks.len();
+        let count = len.min(WAVEFORM_PEAKS);
+        let peaks = (0..count)
+            .map(|i| {
+                let (start, end) = (i * len / count, (i + 1) * len / count);
+                self.blocks[start..end].iter().copied().fold(0.0, f32::max)
+            })
+            .collect();
+        let duration = if sample_rate == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(self.frames as f64 / sample_rate as f64)
+        };
+        Waveform { peaks, duration }
+    }
+}
+
+/// Decode an audio file into its waveform. This reads the whole file, so
+/// call it from a background task.
+pub fn decode(path: &Path) -> Result<Waveform, MediaError> {
+    let file = std::fs::File::open(path).map_err(|source| MediaError::ReadFailed {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(extension);
+    }
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        stream,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| MediaError::UnsupportedType("no audio track".to_string()))?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut peaks: Option<PeakBuilder> = None;
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(DecodeError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt packet only leaves a gap
+            Err(DecodeError::DecodeError(e)) => {
+                debug!("Skipping bad packet in {:?}: {}", path, e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let spec = *decoded.spec();
+        let channels = spec.channels.count();
+        sample_rate = spec.rate;
+        let needed = decoded.capacity() * channels;
+        let samples = match buffer.take() {
+            Some(samples) if samples.capacity() >= needed => buffer.insert(samples),
+            _ => buffer.insert(SampleBuffer::new(decoded.capacity() as u64, spec)),
+        };
+        samples.copy_interleaved_ref(decoded);
+        peaks
+            .get_or_insert_with(|| PeakBuilder::new(channels))
+            .push(samples.samples());
+    }
+
+    Ok(peaks
+        .unwrap_or_else(|| PeakBuilder::new(1))
+        .finish(sample_rate))
+}
+
+/// Waveforms loaded so far
+static WAVEFORMS: Lazy<Mutex<HashMap<PathBuf, Arc<Waveform>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A waveform that has already been decoded. Never touches the disk, so it's
+/// safe to call while rendering.
+pub fn cached_waveform(path: &Path) -> Option<Arc<Waveform>> {
+    WAVEFORMS.lock().get(path).cloned()
+}
+
+/// Decode a file's waveform and cache it. Files that can't be decoded get an
+/// empty waveform so they aren't tried again.
+pub fn load_waveform(path: &Path) -> Arc<Waveform> {
+    let waveform = Arc::new(decode(path).unwrap_or_else(|e| {
+        warn!("No waveform for {:?}: {}", path, e);
+        Waveform::default()
+    }));
+    WAVEFORMS
+        .lock()
+        .insert(path.to_path_buf(), waveform.clone());
+    waveform
+}
+
+/// Height of the waveform strip on an audio item `item_height` tall
+pub fn strip_height(item_height: f32) -> f32 {
+    WAVEFORM_HEIGHT.min(item_height * 0.4)
+}
+
+/// How far along the waveform (0-1) a point `x` from an item's left edge is
+pub fn strip_fraction(x: f32, item_width: f32) -> f32 {
+    let width = item_width - 2.0 * WAVEFORM_INSET;
+    if width <= 0.0 {
+        return 0.0;
+    }
+    ((x - WAVEFORM_INSET) / width).clamp(0.0, 1.0)
+}
+
+/// Add a marker in time order, or replace the note of one at about the same time
+pub fn set_marker(markers: &mut Vec<AudioMarker>, seconds: f64, note: String) {
+    let seconds = seconds.max(0.0);
+    if let Some(marker) = markers
+        .iter_mut()
+        .find(|m| (m.seconds - seconds).abs() < MARKER_SNAP_SECONDS)
+    {
+        marker.note = note;
+        return;
+    }
+    let index = markers.partition_point(|m| m.seconds < seconds);
+    markers.insert(index, AudioMarker { seconds, note });
+}
+
+/// Remove the marker at about `seconds`
+pub fn remove_marker(markers: &mut Vec<AudioMarker>, seconds: f64) -> Option<AudioMarker> {
+    let index = markers
+        .iter()
+        .position(|m| (m.seconds - seconds).abs() < MARKER_SNAP_SECONDS)?;
+    Some(markers.remove(index))
+}
+
+/// The marker closest to `seconds`, if one is within `tolerance` seconds
+pub fn marker_near(markers: &[AudioMarker], seconds: f64, tolerance: f64) -> Option<&AudioMarker> {
+    markers
+        .iter()
+        .filter(|m| (m.seconds - seconds).abs() <= tolerance)
+        .min_by(|a, b| {
+            (a.seconds - seconds)
+                .abs()
+                .total_cmp(&(b.seconds - seconds).abs())
+        })
+}
+
+/// Parse `83`, `1:23`, `1:23.5` or `1:02:03` into seconds
+pub fn parse_timestamp(text: &str) -> Option<f64> {
+    let parts: Vec<&str> = text.trim().split(':').collect();
+    if parts.len() > 3 {
+        return None;
+    }
+    let (seconds, whole) = parts.split_last()?;
+    let seconds: f64 = seconds.parse().ok()?;
+    if !seconds.is_finite() || seconds < 0.0 || (!whole.is_empty() && seconds >= 60.0) {
+        return None;
+    }
+    let mut total = 0.0;
+    for (i, part) in whole.iter().enumerate() {
+        let value: u32 = part.parse().ok()?;
+        // Minutes after hours stay under an hour
+        if i > 0 && value >= 60 {
+            return None;
+        }
+        total = total * 60.0 + value as f64;
+    }
+    Some(total * 60.0 + seconds)
+}
+
+/// `1:23`, `1:23.5`, or `1:02:03` for an hour or more, to a tenth of a second
+pub fn format_timestamp(seconds: f64) -> String {
+    let tenths = (seconds.max(0.0) * 10.0).round() as u64;
+    let whole = format_duration(Duration::from_secs(tenths / 10));
+    match tenths % 10 {
+        0 => whole,
+        tenth => format!("{}.{}", whole, tenth),
+    }
+}
+
+pub(crate) fn serialize_audio<S: Serializer>(
+    path: &PathBuf,
+    markers: &Vec<AudioMarker>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if markers.is_empty() {
+        path.serialize(serializer)
+    } else {
+        (path, markers).serialize(serializer)
+    }
+}
+
+pub(crate) fn deserialize_audio<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(PathBuf, Vec<AudioMarker>), D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Path(PathBuf),
+        Marked(PathBuf, Vec<AudioMarker>),
+    }
+
+    Ok(match Stored::deserialize(deserializer)? {
+        Stored::Path(path) => (path, Vec::new()),
+        Stored::Marked(path, markers) => (path, markers),
+    })
+}
@@ -191,6 +191,9 @@ fn test_board_state_serialization() {
             content: ItemContent::Text("Test".to_string()),
         }],
         next_item_id: 2,
+        bookmarks: Vec::new(),
+        pdf_annotations: Vec::new(),
+        comment_threads: Vec::new(),
     };
 
     let json = serde_json::to_string(&state).unwrap();
@@ -819,3 +822,193 @@ fn test_undo_redo_preserves_item_id() {
     board.redo();
     assert_eq!(board.items[0].id, original_id);
 }
+
+#[test]
+fn test_save_bookmark_replaces_same_name() {
+    let mut board = Board::new_for_test();
+    board.canvas_offset = point(px(10.0), px(20.0));
+    board.zoom = 2.0;
+    assert_eq!(board.save_bookmark("Overview"), 0);
+
+    board.canvas_offset = point(px(-50.0), px(0.0));
+    assert_eq!(board.save_bookmark("Detail"), 1);
+
+    board.zoom = 0.5;
+    assert_eq!(board.save_bookmark("Overview"), 0);
+    assert_eq!(board.bookmarks.len(), 2);
+    assert_eq!(board.bookmarks[0].canvas_offset, (-50.0, 0.0));
+    assert_eq!(board.bookmarks[0].zoom, 0.5);
+}
+
+#[test]
+fn test_remove_and_find_bookmarks() {
+    let mut board = Board::new_for_test();
+    board.save_bookmark("Moodboard");
+    board.save_bookmark("Mood lighting");
+    board.save_bookmark("Typography");
+
+    let found: Vec<usize> = board
+        .find_bookmarks("MOOD")
+        .into_iter()
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(found, [0, 1]);
+
+    assert!(board.remove_bookmark("Moodboard"));
+    assert!(!board.remove_bookmark("Moodboard"));
+    assert_eq!(board.find_bookmarks("").len(), 2);
+}
+
+#[test]
+fn test_board_state_without_bookmarks_still_loads() {
+    use humanboard::board::BoardState;
+
+    let json = r#"{"canvas_offset":[0.0,0.0],"zoom":1.0,"items":[],"next_item_id":0}"#;
+    let state: BoardState = serde_json::from_str(json).unwrap();
+    assert!(state.bookmarks.is_empty());
+    assert!(state.pdf_annotations.is_empty());
+    assert!(state.comment_threads.is_empty());
+}
+
+#[test]
+fn test_pdf_annotations_are_kept_with_the_board() {
+    use humanboard::board::BoardState;
+    use humanboard::pdf_annotations::AnnotationKind;
+
+    let mut board = Board::new_for_test();
+    let note = board.add_pdf_annotation(
+        "/decks/talk.pdf".into(),
+        2,
+        AnnotationKind::Note {
+            point: (0.5, 0.25),
+            text: "Check this".to_string(),
+        },
+    );
+    let highlight = board.add_pdf_annotation(
+        "/decks/talk.pdf".into(),
+        1,
+        AnnotationKind::Highlight {
+            rects: Vec::new(),
+            text: "Quarterly results".to_string(),
+        },
+    );
+    assert_ne!(note, highlight);
+
+    assert!(board.set_pdf_note_text(note, "Checked".to_string()));
+    assert!(!board.set_pdf_note_text(highlight, "Not a note".to_string()));
+
+    let json = serde_json::to_string(&board.to_state()).unwrap();
+    let state: BoardState = serde_json::from_str(&json).unwrap();
+    assert_eq!(state.pdf_annotations, board.pdf_annotations);
+    assert!(matches!(
+        &state.pdf_annotations[0].kind,
+        AnnotationKind::Note { text, .. } if text == "Checked"
+    ));
+
+    assert!(board.remove_pdf_annotation(note));
+    assert!(!board.remove_pdf_annotation(note));
+    assert_eq!(board.pdf_annotations.len(), 1);
+}
+
+#[test]
+fn test_comment_threads_are_kept_with_the_board() {
+    use humanboard::board::BoardState;
+    use humanboard::comments::{Comment, CommentAnchor};
+
+    let mut board = Board::new_for_test();
+    let item_id = board.add_item(
+        point(px(100.0), px(100.0)),
+        ItemContent::Text("Logo".to_string()),
+    );
+
+    let on_item = board.add_comment_thread(
+        CommentAnchor::Item(item_id),
+        Comment::new("Ana", "Can we try it in blue?"),
+    );
+    let on_point = board.add_comment_thread(
+        CommentAnchor::Point(40.0, 60.0),
+        Comment::new("Ben", "Gap here"),
+    );
+    assert_ne!(on_item, on_point);
+
+    assert!(board.reply_to_thread(on_item, Comment::new("Ben", "Sure")));
+    assert!(board.set_thread_resolved(on_item, true));
+    assert!(board.comment_threads[0].resolved);
+
+    // Replying reopens a resolved thread
+    assert!(board.reply_to_thread(on_item, Comment::new("Ana", "One more thing")));
+    assert!(!board.comment_threads[0].resolved);
+    assert_eq!(board.comment_threads[0].comments.len(), 3);
+
+    assert!(!board.reply_to_thread(99, Comment::new("Ana", "Lost")));
+    assert!(!board.set_thread_resolved(99, true));
+
+    let json = serde_json::to_string(&board.to_state()).unwrap();
+    let state: BoardState = serde_json::from_str(&json).unwrap();
+    assert_eq!(state.comment_threads, board.comment_threads);
+}
+
+#[test]
+fn test_center_on_item() {
+    use gpui::size;
+
+    let mut board = Board::new_for_test();
+    board.add_item(
+        point(px(100.0), px(100.0)),
+        ItemContent::Text("Logo".to_string()),
+    );
+    let item = board.items[0].clone();
+    board.zoom = 2.0;
+
+    board.center_on_item(item.id, size(px(800.0), px(600.0)));
+    let center = (
+        item.position.0 + item.size.0 / 2.0,
+        item.position.1 + item.size.1 / 2.0,
+    );
+    assert_eq!(f32::from(board.canvas_offset.x), 400.0 - center.0 * 2.0);
+    assert_eq!(f32::from(board.canvas_offset.y), 300.0 - center.1 * 2.0);
+
+    board.center_on_point((10.0, 20.0), size(px(800.0), px(600.0)));
+    assert_eq!(f32::from(board.canvas_offset.x), 380.0);
+    assert_eq!(f32::from(board.canvas_offset.y), 260.0);
+}
+
+#[test]
+fn test_pdf_annotation_changes_can_be_undone() {
+    use humanboard::pdf_annotations::{AnnotationKind, PageRect};
+
+    let mut board = Board::new_for_test();
+    let note = board.add_pdf_annotation(
+        "/decks/talk.pdf".into(),
+        1,
+        AnnotationKind::Note {
+            point: (0.5, 0.5),
+            text: "First".to_string(),
+        },
+    );
+    let rect = PageRect::from_corners((0.1, 0.1), (0.4, 0.3));
+    let boxed = board.add_pdf_annotation("/decks/talk.pdf".into(), 2, AnnotationKind::Box { rect });
+    board.set_pdf_note_text(note, "Second".to_string());
+    board.remove_pdf_annotation(boxed);
+    let note_text = |board: &Board| match &board.pdf_annotations[0].kind {
+        AnnotationKind::Note { text, .. } => text.clone(),
+        _ => String::new(),
+    };
+
+    // Removing the box comes back first, then the note's old text
+    assert!(board.undo());
+    assert_eq!(board.pdf_annotations.len(), 2);
+    assert!(board.undo());
+    assert_eq!(note_text(&board), "First");
+    assert!(board.undo());
+    assert!(board.undo());
+    assert!(board.pdf_annotations.is_empty());
+
+    assert!(board.redo());
+    assert!(board.redo());
+    assert!(board.redo());
+    assert_eq!(note_text(&board), "Second");
+    assert!(board.redo());
+    assert_eq!(board.pdf_annotations.len(), 1);
+    assert_eq!(board.pdf_annotations[0].id, note);
+}
@@ -2,6 +2,7 @@
 
 use humanboard::board::{Board, BoardState};
 use humanboard::board_index::{BoardIndex, BoardMetadata};
+use humanboard::image_edit::ImageEdit;
 use humanboard::types::{CanvasItem, ItemContent};
 use gpui::{point, px};
 
@@ -51,6 +52,9 @@ fn test_board_state_round_trip() {
             content: item.content.clone(),
         }).collect(),
         next_item_id: board.next_item_id,
+        bookmarks: Vec::new(),
+        pdf_annotations: Vec::new(),
+        comment_threads: Vec::new(),
     };
 
     let json = serde_json::to_string_pretty(&state).unwrap();
@@ -151,6 +155,9 @@ fn test_complete_board_lifecycle() {
             id: item.id, position: item.position, size: item.size, content: item.content.clone(),
         }).collect(),
         next_item_id: board.next_item_id,
+        bookmarks: Vec::new(),
+        pdf_annotations: Vec::new(),
+        comment_threads: Vec::new(),
     };
 
     let json = serde_json::to_string(&state).unwrap();
@@ -164,10 +171,10 @@ fn test_board_with_mixed_content_types() {
     let mut board = Board::new_for_test();
 
     board.add_item(point(px(0.0), px(0.0)), ItemContent::Text("Plain text".to_string()));
-    board.add_item(point(px(200.0), px(0.0)), ItemContent::Image("/path/to/image.png".into()));
+    board.add_item(point(px(200.0), px(0.0)), ItemContent::Image("/path/to/image.png".into(), ImageEdit::default()));
     board.add_item(point(px(400.0), px(0.0)), ItemContent::Pdf { path: "/path/to/doc.pdf".into(), thumbnail: None });
     board.add_item(point(px(0.0), px(200.0)), ItemContent::Video("/path/to/video.mp4".into()));
-    board.add_item(point(px(200.0), px(200.0)), ItemContent::Audio("/path/to/audio.mp3".into()));
+    board.add_item(point(px(200.0), px(200.0)), ItemContent::Audio("/path/to/audio.mp3".into(), Vec::new()));
     board.add_item(point(px(400.0), px(200.0)), ItemContent::YouTube("abc123".to_string()));
     board.add_item(point(px(0.0), px(400.0)), ItemContent::Markdown { path: "/notes.md".into(), title: "Notes".to_string(), content: "# Notes".to_string() });
     board.add_item(point(px(200.0), px(400.0)), ItemContent::Code { path: "/main.rs".into(), language: "rust".to_string() });
@@ -181,6 +188,9 @@ fn test_board_with_mixed_content_types() {
             id: item.id, position: item.position, size: item.size, content: item.content.clone(),
         }).collect(),
         next_item_id: board.next_item_id,
+        bookmarks: Vec::new(),
+        pdf_annotations: Vec::new(),
+        comment_threads: Vec::new(),
     };
 
     let json = serde_json::to_string(&state).unwrap();
@@ -2,6 +2,7 @@
 
 use humanboard::board::{Board, BoardState};
 use humanboard::board_index::{BoardIndex, BoardMetadata};
+use humanboard::image_edit::ImageEdit;
 use humanboard::notifications::ToastManager;
 use humanboard::types::{CanvasItem, ItemContent};
 use gpui::{point, px};
@@ -20,6 +21,9 @@ fn test_board_state_serialization() {
             },
         ],
         next_item_id: 1,
+        bookmarks: Vec::new(),
+        pdf_annotations: Vec::new(),
+        comment_threads: Vec::new(),
     };
 
     let json = serde_json::to_string(&state).unwrap();
@@ -105,6 +109,9 @@ fn test_canvas_state_persistence() {
             id: i.id, position: i.position, size: i.size, content: i.content.clone(),
         }).collect(),
         next_item_id: board.next_item_id,
+        bookmarks: Vec::new(),
+        pdf_annotations: Vec::new(),
+        comment_threads: Vec::new(),
     };
 
     assert_eq!(state.canvas_offset, (150.0, 250.0));
@@ -114,10 +121,10 @@ fn test_canvas_state_persistence() {
 #[test]
 fn test_item_content_variants() {
     let text = ItemContent::Text("Hello".to_string());
-    let image = ItemContent::Image("/path/image.png".into());
+    let image = ItemContent::Image("/path/image.png".into(), ImageEdit::default());
     let pdf = ItemContent::Pdf { path: "/doc.pdf".into(), thumbnail: None };
     let video = ItemContent::Video("/video.mp4".into());
-    let audio = ItemContent::Audio("/audio.mp3".into());
+    let audio = ItemContent::Audio("/audio.mp3".into(), Vec::new());
     let youtube = ItemContent::YouTube("123".to_string());
     let markdown = ItemContent::Markdown { path: "/notes.md".into(), title: "Notes".to_string(), content: "# Title".to_string() };
     let code = ItemContent::Code { path: "/main.rs".into(), language: "rust".to_string() };
@@ -147,12 +154,15 @@ fn test_board_state_with_all_content_types() {
         zoom: 1.0,
         items: vec![
             CanvasItem { id: 0, position: (0.0, 0.0), size: (200.0, 100.0), content: ItemContent::Text("Text".to_string()) },
-            CanvasItem { id: 1, position: (250.0, 0.0), size: (200.0, 200.0), content: ItemContent::Image("/img.png".into()) },
+            CanvasItem { id: 1, position: (250.0, 0.0), size: (200.0, 200.0), content: ItemContent::Image("/img.png".into(), ImageEdit::default()) },
             CanvasItem { id: 2, position: (500.0, 0.0), size: (200.0, 300.0), content: ItemContent::Pdf { path: "/doc.pdf".into(), thumbnail: None } },
             CanvasItem { id: 3, position: (0.0, 350.0), size: (320.0, 180.0), content: ItemContent::Video("/vid.mp4".into()) },
-            CanvasItem { id: 4, position: (350.0, 350.0), size: (200.0, 50.0), content: ItemContent::Audio("/audio.mp3".into()) },
+            CanvasItem { id: 4, position: (350.0, 350.0), size: (200.0, 50.0), content: ItemContent::Audio("/audio.mp3".into(), Vec::new()) },
         ],
         next_item_id: 5,
+        bookmarks: Vec::new(),
+        pdf_annotations: Vec::new(),
+        comment_threads: Vec::new(),
     };
 
     let json = serde_json::to_string_pretty(&state).unwrap();
@@ -169,3 +169,33 @@ fn test_history_respects_limit() {
 
     assert!(board.history_len() <= MAX_HISTORY + 1);
 }
+
+#[test]
+fn test_erasing_stroke_is_one_undo_step() {
+    use humanboard::board::UndoOperation;
+    use humanboard::types::StrokeKind;
+
+    let mut board = Board::new_for_test();
+    let id = board
+        .add_stroke(
+            &[(0.0, 0.0), (100.0, 0.0)],
+            StrokeKind::Pen,
+            String::new(),
+            3.0,
+        )
+        .unwrap();
+    assert_eq!(board.items.len(), 1);
+
+    // Erasing through the middle leaves two strokes
+    let ops = board.erase_strokes((50.0, 0.0), 10.0);
+    assert_eq!(board.items.len(), 2);
+    assert!(board.get_item(id).is_none());
+    board.push_operation(UndoOperation::Batch(ops));
+
+    board.undo();
+    assert_eq!(board.items.len(), 1);
+    assert!(board.get_item(id).is_some());
+
+    board.redo();
+    assert_eq!(board.items.len(), 2);
+}
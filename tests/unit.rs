@@ -1,37 +1,109 @@
 //! Unit tests for Humanboard.
 
+#[path = "unit/auto_layout_tests.rs"]
+mod auto_layout_tests;
+
 #[path = "unit/background_tests.rs"]
 mod background_tests;
 
 #[path = "unit/board_index_tests.rs"]
 mod board_index_tests;
 
+#[path = "unit/board_settings_tests.rs"]
+mod board_settings_tests;
+
 #[path = "unit/command_registry_tests.rs"]
 mod command_registry_tests;
 
+#[path = "unit/comments_tests.rs"]
+mod comments_tests;
+
+#[path = "unit/excalidraw_tests.rs"]
+mod excalidraw_tests;
+
+#[path = "unit/exif_tests.rs"]
+mod exif_tests;
+
+#[path = "unit/external_commands_tests.rs"]
+mod external_commands_tests;
+
 #[path = "unit/focus_tests.rs"]
 mod focus_tests;
 
+#[path = "unit/folder_import_tests.rs"]
+mod folder_import_tests;
+
+#[path = "unit/fuzzy_tests.rs"]
+mod fuzzy_tests;
+
 #[path = "unit/hit_testing_tests.rs"]
 mod hit_testing_tests;
 
+#[path = "unit/image_edit_tests.rs"]
+mod image_edit_tests;
+
+#[path = "unit/json_canvas_tests.rs"]
+mod json_canvas_tests;
+
+#[path = "unit/keymap_tests.rs"]
+mod keymap_tests;
+
 #[path = "unit/loading_tests.rs"]
 mod loading_tests;
 
+#[path = "unit/markdown_card_tests.rs"]
+mod markdown_card_tests;
+
+#[path = "unit/media_tests.rs"]
+mod media_tests;
+
+#[path = "unit/minimap_tests.rs"]
+mod minimap_tests;
+
 #[path = "unit/notifications_tests.rs"]
 mod notifications_tests;
 
+#[path = "unit/palette_tests.rs"]
+mod palette_tests;
+
+#[path = "unit/pdf_annotations_tests.rs"]
+mod pdf_annotations_tests;
+
+#[path = "unit/pdf_pages_tests.rs"]
+mod pdf_pages_tests;
+
 #[path = "unit/perf_tests.rs"]
 mod perf_tests;
 
+#[path = "unit/presentation_tests.rs"]
+mod presentation_tests;
+
 #[path = "unit/selection_tests.rs"]
 mod selection_tests;
 
 #[path = "unit/settings_watcher_tests.rs"]
 mod settings_watcher_tests;
 
+#[path = "unit/shapes_tests.rs"]
+mod shapes_tests;
+
+#[path = "unit/strokes_tests.rs"]
+mod strokes_tests;
+
+#[path = "unit/templates_tests.rs"]
+mod templates_tests;
+
+#[path = "unit/theme_editor_tests.rs"]
+mod theme_editor_tests;
+
+#[path = "unit/timestamps_tests.rs"]
+mod timestamps_tests;
+
 #[path = "unit/types_tests.rs"]
 mod types_tests;
 
 #[path = "unit/validation_tests.rs"]
 mod validation_tests;
+
+#[path = "unit/waveform_tests.rs"]
+mod waveform_tests;
@@ -0,0 +1,393 @@
+//! Unit tests for automatic layouts.
+
+use humanboard::auto_layout::{
+    COLUMN_WIDTH, GAP, LayoutKind, arrange, arrange_sorted, interpolate,
+};
+use humanboard::board::Board;
+use humanboard::image_edit::ImageEdit;
+use humanboard::types::{ArrowHead, CanvasItem, ItemContent, ShapeType};
+use std::collections::{HashMap, HashSet};
+
+fn image(id: u64, position: (f32, f32), size: (f32, f32)) -> CanvasItem {
+    CanvasItem {
+        id,
+        position,
+        size,
+        content: ItemContent::Image(format!("/photos/{}.jpg", id).into(), ImageEdit::default()),
+    }
+}
+
+fn arrow(id: u64, start: (f32, f32), end: (f32, f32)) -> CanvasItem {
+    CanvasItem {
+        id,
+        position: (start.0.min(end.0), start.1.min(end.1)),
+        size: ((end.0 - start.0).abs(), (end.1 - start.1).abs()),
+        content: ItemContent::Arrow {
+            end_offset: (end.0 - start.0, end.1 - start.1),
+            color: String::new(),
+            thickness: 2.0,
+            head_style: ArrowHead::Arrow,
+            label: None,
+        },
+    }
+}
+
+/// Every item's final state, unchanged items included
+fn apply(items: &[CanvasItem], changed: &[CanvasItem]) -> Vec<CanvasItem> {
+    items
+        .iter()
+        .map(|item| {
+            changed
+                .iter()
+                .find(|c| c.id == item.id)
+                .unwrap_or(item)
+                .clone()
+        })
+        .collect()
+}
+
+fn overlaps(a: &CanvasItem, b: &CanvasItem) -> bool {
+    a.position.0 < b.position.0 + b.size.0
+        && b.position.0 < a.position.0 + a.size.0
+        && a.position.1 < b.position.1 + b.size.1
+        && b.position.1 < a.position.1 + a.size.1
+}
+
+fn assert_no_overlaps(items: &[CanvasItem]) {
+    for (i, a) in items.iter().enumerate() {
+        for b in &items[i + 1..] {
+            assert!(!overlaps(a, b), "{} overlaps {}", a.id, b.id);
+        }
+    }
+}
+
+/// Images of assorted aspect ratios piled on top of each other
+fn pile() -> Vec<CanvasItem> {
+    let sizes = [
+        (800.0, 600.0),
+        (400.0, 600.0),
+        (1000.0, 500.0),
+        (600.0, 600.0),
+        (300.0, 500.0),
+        (900.0, 600.0),
+        (500.0, 400.0),
+    ];
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &size)| image(i as u64 + 1, (100.0 + i as f32 * 10.0, 50.0), size))
+        .collect()
+}
+
+fn aspect(item: &CanvasItem) -> f32 {
+    item.size.0 / item.size.1
+}
+
+#[test]
+fn test_masonry_keeps_aspect_ratios_in_fixed_columns() {
+    let items = pile();
+    let arranged = apply(
+        &items,
+        &arrange(LayoutKind::Masonry, &items, &HashSet::new()),
+    );
+
+    for (before, after) in items.iter().zip(&arranged) {
+        assert_eq!(after.size.0, COLUMN_WIDTH);
+        assert!((aspect(before) - aspect(after)).abs() < 0.001);
+    }
+    assert_no_overlaps(&arranged);
+    // The arranged items keep the original top-left corner
+    let min_x = arranged
+        .iter()
+        .map(|i| i.position.0)
+        .fold(f32::MAX, f32::min);
+    let min_y = arranged
+        .iter()
+        .map(|i| i.position.1)
+        .fold(f32::MAX, f32::min);
+    assert_eq!((min_x, min_y), (100.0, 50.0));
+}
+
+#[test]
+fn test_justified_rows_share_a_width() {
+    let items = pile();
+    let arranged = apply(
+        &items,
+        &arrange(LayoutKind::Justified, &items, &HashSet::new()),
+    );
+
+    for (before, after) in items.iter().zip(&arranged) {
+        assert!((aspect(before) - aspect(after)).abs() < 0.001);
+    }
+    assert_no_overlaps(&arranged);
+
+    // Items in a row share a height, and full rows end at the same x
+    let mut rows: Vec<Vec<&CanvasItem>> = Vec::new();
+    for item in &arranged {
+        match rows
+            .iter_mut()
+            .find(|row| row[0].position.1 == item.position.1)
+        {
+            Some(row) => row.push(item),
+            None => rows.push(vec![item]),
+        }
+    }
+    assert!(rows.len() > 1);
+    let right = |row: &Vec<&CanvasItem>| {
+        row.iter()
+            .map(|i| i.position.0 + i.size.0)
+            .fold(f32::MIN, f32::max)
+    };
+    for row in &rows {
+        assert!(row.iter().all(|i| (i.size.1 - row[0].size.1).abs() < 0.01));
+    }
+    for row in &rows[..rows.len() - 1] {
+        assert!((right(row) - right(&rows[0])).abs() < 0.5);
+    }
+}
+
+#[test]
+fn test_grid_uses_uniform_cells() {
+    let items: Vec<CanvasItem> = (1..=6)
+        .map(|id| image(id, (0.0, id as f32 * 5.0), (100.0 + id as f32 * 10.0, 80.0)))
+        .collect();
+    let arranged = apply(&items, &arrange(LayoutKind::Grid, &items, &HashSet::new()));
+
+    assert_no_overlaps(&arranged);
+    // Sizes are kept, and items are centred in cells as wide as the widest
+    let mut centers: Vec<f32> = arranged
+        .iter()
+        .map(|i| i.position.0 + i.size.0 / 2.0)
+        .collect();
+    centers.sort_by(f32::total_cmp);
+    centers.dedup();
+    assert!(centers.len() > 1);
+    assert!(
+        centers
+            .windows(2)
+            .all(|pair| (pair[1] - pair[0] - (160.0 + GAP)).abs() < 0.01)
+    );
+    for (before, after) in items.iter().zip(&arranged) {
+        assert_eq!(before.size, after.size);
+    }
+}
+
+#[test]
+fn test_only_selected_items_are_arranged() {
+    let mut items = pile();
+    items.push(CanvasItem {
+        id: 99,
+        position: (0.0, 0.0),
+        size: (960.0, 540.0),
+        content: ItemContent::Shape {
+            shape_type: ShapeType::Frame,
+            fill_color: None,
+            border_color: String::new(),
+            border_width: 2.0,
+            label: None,
+        },
+    });
+
+    let selection: HashSet<u64> = [1, 2, 3].into();
+    let changed = arrange(LayoutKind::Grid, &items, &selection);
+    assert!(changed.iter().all(|item| selection.contains(&item.id)));
+
+    // Frames never move, and one item is nothing to arrange
+    let changed = arrange(LayoutKind::Grid, &items, &HashSet::new());
+    assert!(changed.iter().all(|item| item.id != 99));
+    assert!(arrange(LayoutKind::Grid, &items, &[1].into()).is_empty());
+}
+
+#[test]
+fn test_sort_keys_come_first_in_key_order() {
+    let items: Vec<CanvasItem> = (1..=4)
+        .map(|id| image(id, (id as f32 * 150.0, 0.0), (100.0, 80.0)))
+        .collect();
+    let keys: HashMap<u64, String> = [
+        (4, "2019-03-01 10:00:00".to_string()),
+        (2, "2021-07-15 09:30:00".to_string()),
+    ]
+    .into();
+    let mut arranged = apply(
+        &items,
+        &arrange_sorted(LayoutKind::Grid, &items, &HashSet::new(), &keys),
+    );
+
+    // Reading order: dated oldest first, then the rest as they were
+    arranged.sort_by(|a, b| {
+        (a.position.1, a.position.0)
+            .partial_cmp(&(b.position.1, b.position.0))
+            .unwrap()
+    });
+    let order: Vec<u64> = arranged.iter().map(|i| i.id).collect();
+    assert_eq!(order, vec![4, 2, 1, 3]);
+}
+
+/// A root with two children, drawn left to right with arrows between them
+fn small_tree() -> Vec<CanvasItem> {
+    vec![
+        image(1, (0.0, 0.0), (100.0, 100.0)),
+        image(2, (300.0, 0.0), (100.0, 100.0)),
+        image(3, (600.0, 0.0), (100.0, 100.0)),
+        arrow(10, (100.0, 50.0), (300.0, 50.0)),
+        arrow(11, (100.0, 50.0), (600.0, 50.0)),
+    ]
+}
+
+fn find(items: &[CanvasItem], id: u64) -> &CanvasItem {
+    items.iter().find(|item| item.id == id).unwrap()
+}
+
+#[test]
+fn test_tree_places_children_below_their_parent() {
+    let items = small_tree();
+    let arranged = apply(&items, &arrange(LayoutKind::Tree, &items, &HashSet::new()));
+
+    let (root, left, right) = (find(&arranged, 1), find(&arranged, 2), find(&arranged, 3));
+    assert!(left.position.1 > root.position.1 + root.size.1);
+    assert_eq!(left.position.1, right.position.1);
+    assert!(left.position.0 < right.position.0);
+    // The parent is centred over its children
+    let children_center = (left.position.0 + right.position.0 + right.size.0) / 2.0;
+    assert!((root.position.0 + root.size.0 / 2.0 - children_center).abs() < 0.01);
+}
+
+#[test]
+fn test_arrows_follow_the_items_they_connect() {
+    let items = small_tree();
+    let arranged = apply(&items, &arrange(LayoutKind::Tree, &items, &HashSet::new()));
+
+    let root = find(&arranged, 1);
+    let child = find(&arranged, 2);
+    let (start, end) = find(&arranged, 10).arrow_endpoints().unwrap();
+    // From the root's bottom edge to the child's top edge
+    assert!((start.1 - (root.position.1 + root.size.1)).abs() < 0.01);
+    assert!((end.1 - child.position.1).abs() < 0.01);
+    assert!(matches!(
+        find(&arranged, 10).content,
+        ItemContent::Arrow {
+            head_style: ArrowHead::Arrow,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_force_layout_pulls_connected_items_together() {
+    let mut items = vec![
+        image(1, (0.0, 0.0), (100.0, 100.0)),
+        image(2, (2000.0, 0.0), (100.0, 100.0)),
+        image(3, (0.0, 2000.0), (100.0, 100.0)),
+        image(4, (2000.0, 2000.0), (100.0, 100.0)),
+    ];
+    items.push(arrow(10, (100.0, 50.0), (2000.0, 50.0)));
+    let arranged = apply(&items, &arrange(LayoutKind::Force, &items, &HashSet::new()));
+
+    let center = |id| {
+        let item = find(&arranged, id);
+        (
+            item.position.0 + item.size.0 / 2.0,
+            item.position.1 + item.size.1 / 2.0,
+        )
+    };
+    let distance = |a, b| {
+        let ((ax, ay), (bx, by)) = (center(a), center(b));
+        ((ax - bx).powi(2) + (ay - by).powi(2)).sqrt()
+    };
+    assert!(distance(1, 2) < distance(3, 4));
+    assert_no_overlaps(&arranged[..4]);
+
+    // The simulation is deterministic
+    assert_eq!(
+        format!("{:?}", arrange(LayoutKind::Force, &items, &HashSet::new())),
+        format!("{:?}", arrange(LayoutKind::Force, &items, &HashSet::new()))
+    );
+}
+
+#[test]
+fn test_interpolate_moves_arrow_endpoints() {
+    let from = arrow(1, (0.0, 0.0), (100.0, 0.0));
+    let to = arrow(1, (100.0, 0.0), (0.0, 0.0));
+    let halfway = interpolate(&from, &to, 0.5);
+    assert_eq!(halfway.arrow_endpoints(), Some(((50.0, 0.0), (50.0, 0.0))));
+
+    let from = image(2, (0.0, 0.0), (100.0, 100.0));
+    let to = image(2, (100.0, 200.0), (300.0, 100.0));
+    let quarter = interpolate(&from, &to, 0.25);
+    assert_eq!(quarter.position, (25.0, 50.0));
+    assert_eq!(quarter.size, (150.0, 100.0));
+}
+
+#[test]
+fn test_commit_layout_is_one_undo_step() {
+    let mut board = Board::new_for_test();
+    board.apply_item_changes(
+        small_tree()
+            .into_iter()
+            .map(|item| (item.content, item.position, Some(item.size)))
+            .collect(),
+        Vec::new(),
+    );
+
+    let before = board.items.clone();
+    let changed = arrange(LayoutKind::Tree, &board.items, &HashSet::new());
+    for item in &changed {
+        *board.get_item_mut(item.id).unwrap() = item.clone();
+    }
+    let history = board.history_len();
+    assert_eq!(board.commit_layout(&before), changed.len());
+    assert_eq!(board.history_len(), history + 1);
+
+    assert!(board.undo());
+    for item in &before {
+        let restored = board.get_item(item.id).unwrap();
+        assert_eq!(restored.position, item.position);
+        assert_eq!(restored.size, item.size);
+        assert_eq!(restored.arrow_endpoints(), item.arrow_endpoints());
+    }
+}
+
+#[test]
+fn test_layout_keeps_edits_made_while_animating() {
+    let mut board = Board::new_for_test();
+    let (added, _) = board.apply_item_changes(
+        vec![(
+            arrow(0, (0.0, 0.0), (100.0, 0.0)).content,
+            (0.0, 0.0),
+            Some((100.0, 0.0)),
+        )],
+        Vec::new(),
+    );
+    let id = added[0];
+    let before = board.items.clone();
+    let target = arrow(id, (0.0, 0.0), (0.0, 200.0));
+
+    // The arrow is labelled part way through, then the layout lands
+    let item = board.get_item_mut(id).unwrap();
+    item.copy_geometry_from(&interpolate(&before[0], &target, 0.5));
+    if let ItemContent::Arrow { label, .. } = &mut item.content {
+        *label = Some("Next".to_string());
+    }
+    item.copy_geometry_from(&target);
+    assert_eq!(board.commit_layout(&before), 1);
+
+    let labelled = |board: &Board| {
+        matches!(
+            &board.get_item(id).unwrap().content,
+            ItemContent::Arrow { label: Some(label), .. } if label == "Next"
+        )
+    };
+    assert!(labelled(&board));
+    assert_eq!(
+        board.get_item(id).unwrap().arrow_endpoints(),
+        target.arrow_endpoints()
+    );
+
+    // Undoing the layout restores the route but not the old label
+    assert!(board.undo());
+    assert!(labelled(&board));
+    assert_eq!(
+        board.get_item(id).unwrap().arrow_endpoints(),
+        before[0].arrow_endpoints()
+    );
+}
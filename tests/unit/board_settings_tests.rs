@@ -0,0 +1,128 @@
+//! Unit tests for per-board settings layered over the user's settings.
+
+use humanboard::settings::{CanvasSettings, SettingsSource, SettingsStore, THEME_BACKGROUND};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_board_settings_override_while_open() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("settings.json"),
+        r##"{ "show_grid": true, "grid_size": 40.0, "canvas_background": "#ffffff" }"##,
+    )
+    .unwrap();
+
+    let mut store = SettingsStore::new();
+    store.load_board_settings(dir.path().to_path_buf()).unwrap();
+    let canvas = store.canvas_settings();
+    assert!(canvas.show_grid);
+    assert_eq!(canvas.grid_size, 40.0);
+    assert_eq!(canvas.background.as_deref(), Some("#ffffff"));
+
+    store.unload_board_settings();
+    let canvas = store.canvas_settings();
+    assert!(!canvas.show_grid);
+    assert_eq!(canvas.grid_size, 20.0);
+    assert_eq!(canvas.background, None);
+}
+
+#[test]
+fn test_board_can_choose_the_theme_background() {
+    let project = tempdir().unwrap();
+    fs::create_dir(project.path().join(".humanboard")).unwrap();
+    fs::write(
+        project.path().join(".humanboard").join("settings.json"),
+        r##"{ "canvas_background": "#ffffff" }"##,
+    )
+    .unwrap();
+    let board = tempdir().unwrap();
+
+    let mut store = SettingsStore::new();
+    store
+        .load_project_settings(project.path().to_path_buf())
+        .unwrap();
+    store
+        .load_board_settings(board.path().to_path_buf())
+        .unwrap();
+    assert_eq!(
+        store.canvas_settings().background.as_deref(),
+        Some("#ffffff")
+    );
+
+    store
+        .update(SettingsSource::Board, |content| {
+            content.canvas_background = Some(THEME_BACKGROUND.to_string())
+        })
+        .unwrap();
+    assert_eq!(store.canvas_settings().background, None);
+}
+
+#[test]
+fn test_board_without_settings_file_has_no_overrides() {
+    let dir = tempdir().unwrap();
+    let mut store = SettingsStore::new();
+    store.load_board_settings(dir.path().to_path_buf()).unwrap();
+    assert!(store.board_content().is_none());
+}
+
+#[test]
+fn test_enabling_overrides_keeps_current_canvas_settings() {
+    let dir = tempdir().unwrap();
+    let mut store = SettingsStore::new();
+    store.load_board_settings(dir.path().to_path_buf()).unwrap();
+    let before = store.canvas_settings();
+
+    store.set_board_overrides(true).unwrap();
+    assert!(dir.path().join("settings.json").exists());
+    assert_eq!(store.canvas_settings(), before);
+
+    store
+        .update(SettingsSource::Board, |content| content.snap_to_grid = Some(true))
+        .unwrap();
+    let saved = fs::read_to_string(dir.path().join("settings.json")).unwrap();
+    assert!(saved.contains("\"snap_to_grid\": true"));
+
+    store.set_board_overrides(false).unwrap();
+    assert!(!dir.path().join("settings.json").exists());
+    assert!(store.board_content().is_none());
+    assert!(!store.canvas_settings().snap_to_grid);
+}
+
+#[test]
+fn test_board_source_requires_open_board() {
+    let mut store = SettingsStore::new();
+    let result = store.update(SettingsSource::Board, |content| content.show_grid = Some(true));
+    assert!(result.is_err());
+    assert!(store.set_board_overrides(true).is_err());
+}
+
+#[test]
+fn test_snap_rounds_to_grid_only_when_enabled() {
+    let mut canvas = CanvasSettings {
+        background: None,
+        grid_size: 20.0,
+        show_grid: false,
+        snap_to_grid: false,
+    };
+    assert_eq!(canvas.snap(33.0), 33.0);
+
+    canvas.snap_to_grid = true;
+    assert_eq!(canvas.snap(33.0), 40.0);
+    assert_eq!(canvas.snap(-29.0), -20.0);
+}
+
+#[test]
+fn test_board_settings_cannot_add_external_commands() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("settings.json"),
+        r#"{ "show_grid": true, "external_commands": [{ "name": "Wipe", "command": "rm -rf ~" }] }"#,
+    )
+    .unwrap();
+
+    let mut store = SettingsStore::new();
+    store.load_board_settings(dir.path().to_path_buf()).unwrap();
+    assert!(store.app_settings().show_grid);
+    assert!(store.app_settings().external_commands.is_empty());
+}
@@ -0,0 +1,201 @@
+//! Unit tests for comment threads and exporting them.
+
+use humanboard::comments::{
+    Comment, CommentAnchor, CommentThread, NOTE_GAP, NOTE_WIDTH, anchor_label, author_name,
+    export_items, format_date, next_thread_id, open_counts, sorted_threads, thread_text,
+    written_at,
+};
+use humanboard::json_canvas::{self, NodeKind};
+use humanboard::settings::{AppSettings, Setting, SettingsContent};
+use humanboard::types::{CanvasItem, ItemContent, ShapeType};
+use std::path::Path;
+
+fn comment(author: &str, text: &str, created_at: u64) -> Comment {
+    Comment {
+        author: author.to_string(),
+        text: text.to_string(),
+        created_at,
+    }
+}
+
+fn thread(id: u64, anchor: CommentAnchor, created_at: u64, resolved: bool) -> CommentThread {
+    CommentThread {
+        id,
+        anchor,
+        comments: vec![comment("Ana", "Looks good", created_at)],
+        resolved,
+    }
+}
+
+fn text_item(id: u64, position: (f32, f32)) -> CanvasItem {
+    CanvasItem {
+        id,
+        position,
+        size: (200.0, 100.0),
+        content: ItemContent::TextBox {
+            text: "Logo ideas".to_string(),
+            font_size: 16.0,
+            color: String::new(),
+            format: Default::default(),
+            align: Default::default(),
+            font_family: None,
+            fill_color: None,
+        },
+    }
+}
+
+#[test]
+fn test_open_counts_and_ordering() {
+    let threads = vec![
+        thread(0, CommentAnchor::Item(7), 100, false),
+        thread(1, CommentAnchor::Item(7), 300, false),
+        thread(2, CommentAnchor::Item(7), 500, true),
+        thread(3, CommentAnchor::Item(8), 200, false),
+        thread(4, CommentAnchor::Point(10.0, 20.0), 400, false),
+    ];
+
+    // Resolved threads and threads on points aren't counted on items
+    let counts = open_counts(&threads);
+    assert_eq!(counts.get(&7), Some(&2));
+    assert_eq!(counts.get(&8), Some(&1));
+    assert_eq!(counts.len(), 2);
+
+    // Open threads first, most recently active first
+    let ids = |listed: Vec<&CommentThread>| listed.iter().map(|t| t.id).collect::<Vec<_>>();
+    assert_eq!(ids(sorted_threads(&threads, false)), vec![4, 1, 3, 0]);
+    assert_eq!(ids(sorted_threads(&threads, true)), vec![4, 1, 3, 0, 2]);
+
+    assert_eq!(next_thread_id(&threads), 5);
+    assert_eq!(next_thread_id(&[]), 0);
+}
+
+#[test]
+fn test_thread_position_and_label() {
+    let items = vec![text_item(7, (100.0, 50.0))];
+
+    // Badges sit on an item's top right corner
+    let on_item = thread(0, CommentAnchor::Item(7), 0, false);
+    assert_eq!(on_item.position(&items), Some((300.0, 50.0)));
+    assert_eq!(anchor_label(on_item.anchor, &items), "TextBox");
+
+    let on_point = thread(1, CommentAnchor::Point(12.4, -30.0), 0, false);
+    assert_eq!(on_point.position(&items), Some((12.4, -30.0)));
+    assert_eq!(anchor_label(on_point.anchor, &items), "Canvas (12, -30)");
+
+    let orphan = thread(2, CommentAnchor::Item(99), 0, false);
+    assert_eq!(orphan.position(&items), None);
+    assert_eq!(anchor_label(orphan.anchor, &items), "Deleted item");
+}
+
+#[test]
+fn test_timestamps() {
+    assert_eq!(format_date(0), "1970-01-01 00:00 UTC");
+    assert_eq!(format_date(951_868_740), "2000-02-29 23:59 UTC");
+    assert_eq!(format_date(1_792_332_180), "2026-10-18 14:03 UTC");
+
+    let now = 1_792_332_180;
+    assert_eq!(written_at(now - 5 * 60, now), "5 min ago");
+    assert_eq!(written_at(now - 2 * 86400, now), "2 days ago");
+    // Older comments show their date
+    assert_eq!(written_at(951_868_740, now), "2000-02-29 23:59 UTC");
+    // A clock that's behind doesn't make comments from the future
+    assert_eq!(written_at(now + 100, now), "Just now");
+}
+
+#[test]
+fn test_author_name() {
+    assert_eq!(author_name("  Ana  "), "Ana");
+    // Without a setting comments are anonymous, not signed with the login name
+    assert_eq!(author_name(""), "Anonymous");
+    assert_eq!(author_name("   "), "Anonymous");
+
+    let content: SettingsContent = serde_json::from_str(r#"{ "author_name": "Ana" }"#).unwrap();
+    assert_eq!(AppSettings::from_content(&content).author_name, "Ana");
+    assert_eq!(
+        AppSettings::from_content(&SettingsContent::default()).author_name,
+        ""
+    );
+}
+
+#[test]
+fn test_thread_text() {
+    let mut discussion = thread(0, CommentAnchor::Item(7), 0, false);
+    discussion
+        .comments
+        .push(comment("Ben", "Agreed", 1_792_332_180));
+    assert_eq!(
+        thread_text(&discussion),
+        "Ana · 1970-01-01 00:00 UTC\nLooks good\n\nBen · 2026-10-18 14:03 UTC\nAgreed"
+    );
+
+    discussion.resolved = true;
+    assert!(thread_text(&discussion).starts_with("Resolved\n\nAna"));
+}
+
+#[test]
+fn test_export_items_adds_notes_and_arrows() {
+    let items = vec![text_item(7, (100.0, 50.0))];
+    let threads = vec![
+        thread(0, CommentAnchor::Item(7), 0, false),
+        thread(1, CommentAnchor::Item(7), 0, true),
+        thread(2, CommentAnchor::Point(-400.0, 10.0), 0, false),
+        thread(3, CommentAnchor::Item(99), 0, false),
+    ];
+
+    let exported = export_items(&items, &threads);
+    // The item, a note and an arrow for each thread on it, and a note for the
+    // point; the thread on a deleted item is left out
+    assert_eq!(exported.len(), 6);
+    assert_eq!(exported[0].id, 7);
+
+    let notes: Vec<&CanvasItem> = exported
+        .iter()
+        .filter(|item| {
+            matches!(
+                item.content,
+                ItemContent::Shape {
+                    shape_type: ShapeType::StickyNote,
+                    ..
+                }
+            )
+        })
+        .collect();
+    assert_eq!(notes.len(), 3);
+    assert_eq!(notes[0].position, (300.0 + NOTE_GAP, 50.0));
+    assert_eq!(notes[0].size.0, NOTE_WIDTH);
+    // Notes on the same item stack downwards
+    assert!(notes[1].position.1 > notes[0].position.1 + notes[0].size.1);
+    assert_eq!(notes[2].position, (-400.0, 10.0));
+
+    // Arrows run from each note back to the item
+    for (note, arrow) in [(notes[0], &exported[2]), (notes[1], &exported[4])] {
+        let (start, end) = arrow.arrow_endpoints().unwrap();
+        assert_eq!(start.0, note.position.0);
+        assert_eq!(end.0, 300.0);
+        assert!(end.1 >= 50.0 && end.1 <= 150.0);
+    }
+
+    // IDs don't clash with the board's
+    let mut ids: Vec<u64> = exported.iter().map(|item| item.id).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), exported.len());
+}
+
+#[test]
+fn test_exported_comments_become_json_canvas_notes_and_edges() {
+    let items = vec![text_item(7, (100.0, 50.0))];
+    let threads = vec![thread(0, CommentAnchor::Item(7), 0, false)];
+
+    let (canvas, skipped) =
+        json_canvas::from_items(&export_items(&items, &threads), Path::new("/boards"));
+    assert_eq!(skipped, 0);
+    assert_eq!(canvas.nodes.len(), 2);
+    assert!(matches!(
+        &canvas.nodes[1].kind,
+        NodeKind::Text { text } if text.ends_with("Looks good")
+    ));
+    assert_eq!(canvas.edges.len(), 1);
+    assert_eq!(canvas.edges[0].from_node, canvas.nodes[1].id);
+    assert_eq!(canvas.edges[0].to_node, canvas.nodes[0].id);
+}
@@ -0,0 +1,234 @@
+//! Unit tests for Excalidraw import and export.
+
+use humanboard::excalidraw::{self, ElementKind, Scene, SceneElement, from_items, to_board_state};
+use humanboard::image_edit::ImageEdit;
+use humanboard::types::{ArrowHead, CanvasItem, ItemContent, ShapeType, TextAlignment};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/excalidraw/architecture.excalidraw")
+}
+
+fn import_fixture(files_dir: &Path) -> (Vec<CanvasItem>, excalidraw::Unsupported) {
+    let scene = excalidraw::read(&fixture()).unwrap();
+    let (state, unsupported) = to_board_state(&scene, files_dir);
+    (state.items, unsupported)
+}
+
+fn known(scene: &Scene) -> Vec<&excalidraw::Element> {
+    scene
+        .elements
+        .iter()
+        .filter_map(|e| match e {
+            SceneElement::Known(element) => Some(element.as_ref()),
+            SceneElement::Other(_) => None,
+        })
+        .collect()
+}
+
+/// Items as JSON with numbers rounded, so float noise doesn't matter
+fn normalized(items: &[CanvasItem]) -> Value {
+    fn round(value: Value) -> Value {
+        match value {
+            Value::Number(n) => n
+                .as_f64()
+                .map(|f| serde_json::json!((f * 100.0).round() / 100.0))
+                .unwrap_or(Value::Number(n)),
+            Value::Array(values) => Value::Array(values.into_iter().map(round).collect()),
+            Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, round(v))).collect()),
+            other => other,
+        }
+    }
+    round(serde_json::to_value(items).unwrap())
+}
+
+#[test]
+fn test_import_maps_element_types_and_colors() {
+    let dir = tempdir().unwrap();
+    let (items, _) = import_fixture(dir.path());
+    assert_eq!(items.len(), 9);
+
+    // Frames go to the back
+    assert!(matches!(
+        &items[0].content,
+        ItemContent::Shape { shape_type: ShapeType::Frame, label: Some(label), .. } if label == "Architecture"
+    ));
+    assert!(matches!(
+        &items[1].content,
+        ItemContent::Shape {
+            shape_type: ShapeType::Rectangle,
+            fill_color: Some(fill),
+            border_color,
+            label: Some(label),
+            ..
+        } if fill == "#a5d8ff" && border_color == "#1971c2" && label == "Client"
+    ));
+    assert!(matches!(
+        &items[2].content,
+        ItemContent::Shape { shape_type: ShapeType::Ellipse, fill_color: None, border_width, .. }
+            if *border_width == 4.0
+    ));
+    assert!(matches!(
+        &items[3].content,
+        ItemContent::Shape { shape_type: ShapeType::Diamond, fill_color: Some(fill), .. } if fill == "#ffc9c9"
+    ));
+    assert!(matches!(
+        &items[5].content,
+        ItemContent::TextBox { text, font_size, color, align: TextAlignment::Left, .. }
+            if text == "Reads are cached\nfor 5 minutes" && *font_size == 16.0 && color == "#6741d9"
+    ));
+    assert!(matches!(items[8].content, ItemContent::Stroke { .. }));
+}
+
+#[test]
+fn test_import_arrows_and_lines() {
+    let dir = tempdir().unwrap();
+    let (items, _) = import_fixture(dir.path());
+
+    // Bent arrows are straightened between their first and last points
+    let call = &items[4];
+    assert_eq!(call.arrow_endpoints(), Some(((200.0, 50.0), (400.0, 50.0))));
+    assert!(matches!(
+        &call.content,
+        ItemContent::Arrow { head_style: ArrowHead::Arrow, color, label: Some(label), .. }
+            if color == "#f08c00" && label == "query"
+    ));
+
+    assert!(matches!(
+        items[6].content,
+        ItemContent::Arrow { head_style: ArrowHead::None, end_offset: (300.0, 0.0), .. }
+    ));
+}
+
+#[test]
+fn test_import_writes_embedded_images() {
+    let dir = tempdir().unwrap();
+    let (items, _) = import_fixture(dir.path());
+
+    let ItemContent::Image(path, _) = &items[7].content else {
+        panic!("expected an image, got {:?}", items[7].content);
+    };
+    assert!(path.starts_with(dir.path()));
+    assert_eq!(path.extension().unwrap(), "png");
+    let bytes = std::fs::read(path).unwrap();
+    assert!(bytes.starts_with(b"\x89PNG"));
+}
+
+#[test]
+fn test_import_reports_unsupported_elements() {
+    let dir = tempdir().unwrap();
+    let (_, unsupported) = import_fixture(dir.path());
+
+    // Deleted elements aren't reported
+    assert_eq!(unsupported.total(), 2);
+    assert_eq!(unsupported.count("embeddable"), 1);
+    assert_eq!(unsupported.count("iframe"), 1);
+    assert_eq!(unsupported.to_string(), "1 embeddable, 1 iframe");
+}
+
+#[test]
+fn test_fixture_round_trips() {
+    let dir = tempdir().unwrap();
+    let (items, _) = import_fixture(dir.path());
+
+    let (scene, unsupported) = from_items(&items);
+    assert!(unsupported.is_empty());
+    let json = serde_json::to_string(&scene).unwrap();
+    let reread: Scene = serde_json::from_str(&json).unwrap();
+    let (state, unsupported) = to_board_state(&reread, dir.path());
+
+    assert!(unsupported.is_empty());
+    assert_eq!(normalized(&state.items), normalized(&items));
+}
+
+#[test]
+fn test_export_binds_labels_to_containers() {
+    let dir = tempdir().unwrap();
+    let (items, _) = import_fixture(dir.path());
+    let (scene, _) = from_items(&items);
+    let elements = known(&scene);
+
+    let label = elements
+        .iter()
+        .find(|e| matches!(&e.kind, ElementKind::Text(text) if text.text == "Client"))
+        .unwrap();
+    let ElementKind::Text(text) = &label.kind else {
+        unreachable!();
+    };
+    let container_id = text.container_id.as_deref().unwrap();
+    let container = elements.iter().find(|e| e.id == container_id).unwrap();
+    assert_eq!(container.kind, ElementKind::Rectangle);
+    let bound = container.bound_elements.as_ref().unwrap();
+    assert_eq!(bound[0].id, label.id);
+    assert_eq!(container.background_color, "#a5d8ff");
+    assert_eq!(container.stroke_color, "#1971c2");
+}
+
+fn item(id: u64, content: ItemContent) -> CanvasItem {
+    CanvasItem {
+        id,
+        position: (id as f32 * 300.0, 0.0),
+        size: (200.0, 100.0),
+        content,
+    }
+}
+
+#[test]
+fn test_export_reports_items_without_equivalent() {
+    let items = vec![
+        item(1, ItemContent::Video("/clips/demo.mp4".into())),
+        item(
+            2,
+            ItemContent::Pdf {
+                path: "/docs/spec.pdf".into(),
+                thumbnail: None,
+            },
+        ),
+        item(
+            3,
+            ItemContent::Shape {
+                shape_type: ShapeType::Star,
+                fill_color: None,
+                border_color: String::new(),
+                border_width: 2.0,
+                label: None,
+            },
+        ),
+        item(4, ItemContent::Image("/missing/photo.png".into(), ImageEdit::default())),
+        item(5, ItemContent::YouTube("abc123".into())),
+    ];
+    let (scene, unsupported) = from_items(&items);
+
+    assert_eq!(unsupported.count("video"), 1);
+    assert_eq!(unsupported.count("pdf"), 1);
+    assert_eq!(unsupported.count("star"), 1);
+    assert_eq!(unsupported.count("image (missing file)"), 1);
+
+    // The YouTube video becomes a linked rectangle and its label
+    let elements = known(&scene);
+    assert_eq!(elements.len(), 2);
+    assert_eq!(
+        elements[0].link.as_deref(),
+        Some("https://www.youtube.com/watch?v=abc123")
+    );
+    assert!(scene.files.is_empty());
+}
+
+#[test]
+fn test_export_file_writes_scene() {
+    let dir = tempdir().unwrap();
+    let (items, _) = import_fixture(dir.path());
+    let path = dir.path().join("board.excalidraw");
+    excalidraw::export_file(&items, &path).unwrap();
+
+    let json: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(json["type"], "excalidraw");
+    assert_eq!(json["version"], 2);
+    // The embedded image keeps its content-hash file ID
+    assert!(json["files"]["6b7fa434f92a8b80aab02d9bf1a12e49ffcae424"]["dataURL"]
+        .as_str()
+        .unwrap()
+        .starts_with("data:image/png;base64,"));
+}
@@ -0,0 +1,242 @@
+//! Unit tests for EXIF metadata.
+
+use gpui::{point, px};
+use humanboard::board::Board;
+use humanboard::exif::{self, Exif};
+use humanboard::image_edit::ImageEdit;
+use humanboard::types::ItemContent;
+use image::codecs::jpeg::JpegEncoder;
+use image::metadata::Orientation;
+use image::{DynamicImage, ExtendedColorType, ImageEncoder, RgbImage};
+use std::path::Path;
+use tempfile::tempdir;
+
+/// A value in a test EXIF directory
+enum Value {
+    Ascii(&'static str),
+    Short(u16),
+    Rational(Vec<(u32, u32)>),
+    Ifd(Vec<(u16, Value)>),
+}
+
+/// Append a little-endian directory and its values, returning its offset
+fn write_ifd(buf: &mut Vec<u8>, entries: Vec<(u16, Value)>) -> u32 {
+    let offset = buf.len();
+    buf.extend((entries.len() as u16).to_le_bytes());
+    buf.resize(offset + 2 + entries.len() * 12 + 4, 0);
+
+    for (i, (tag, value)) in entries.into_iter().enumerate() {
+        let (kind, count, bytes) = match value {
+            Value::Ascii(text) => {
+                let mut bytes = text.as_bytes().to_vec();
+                bytes.push(0);
+                (2u16, bytes.len() as u32, bytes)
+            }
+            Value::Short(n) => (3, 1, n.to_le_bytes().to_vec()),
+            Value::Rational(parts) => {
+                let bytes = parts
+                    .iter()
+                    .flat_map(|&(n, d)| [n.to_le_bytes(), d.to_le_bytes()].concat())
+                    .collect();
+                (5, parts.len() as u32, bytes)
+            }
+            Value::Ifd(children) => {
+                let child = write_ifd(buf, children);
+                (4, 1, child.to_le_bytes().to_vec())
+            }
+        };
+        let field = if bytes.len() <= 4 {
+            let mut inline = bytes;
+            inline.resize(4, 0);
+            inline
+        } else {
+            let at = buf.len() as u32;
+            buf.extend(bytes);
+            at.to_le_bytes().to_vec()
+        };
+        let start = offset + 2 + i * 12;
+        buf[start..start + 2].copy_from_slice(&tag.to_le_bytes());
+        buf[start + 2..start + 4].copy_from_slice(&kind.to_le_bytes());
+        buf[start + 4..start + 8].copy_from_slice(&count.to_le_bytes());
+        buf[start + 8..start + 12].copy_from_slice(&field);
+    }
+    offset as u32
+}
+
+/// A raw EXIF block with `entries` in its first directory
+fn tiff(entries: Vec<(u16, Value)>) -> Vec<u8> {
+    let mut buf = b"II*\0".to_vec();
+    buf.extend(8u32.to_le_bytes());
+    write_ifd(&mut buf, entries);
+    buf
+}
+
+/// Save a JPEG, 40 by 20 pixels as stored, with an EXIF block
+fn write_jpeg(path: &Path, exif: Vec<u8>) {
+    let pixels = RgbImage::from_pixel(40, 20, image::Rgb([90, 120, 200]));
+    let file = std::fs::File::create(path).unwrap();
+    let mut encoder = JpegEncoder::new(file);
+    encoder.set_exif_metadata(exif).unwrap();
+    encoder
+        .write_image(pixels.as_raw(), 40, 20, ExtendedColorType::Rgb8)
+        .unwrap();
+}
+
+fn photo_exif(orientation: u16, date: &'static str) -> Vec<u8> {
+    tiff(vec![
+        (0x0112, Value::Short(orientation)),
+        (0x8769, Value::Ifd(vec![(0x9003, Value::Ascii(date))])),
+    ])
+}
+
+#[test]
+fn test_parse_reads_camera_exposure_and_location() {
+    let data = tiff(vec![
+        (0x010f, Value::Ascii("Canon")),
+        (0x0110, Value::Ascii("Canon EOS R6")),
+        (0x0112, Value::Short(6)),
+        (
+            0x8769,
+            Value::Ifd(vec![
+                (0x829a, Value::Rational(vec![(1, 250)])),
+                (0x829d, Value::Rational(vec![(28, 10)])),
+                (0x8827, Value::Short(400)),
+                (0x9003, Value::Ascii("2024:05:04 14:22:01")),
+                (0x920a, Value::Rational(vec![(50, 1)])),
+                (0xa434, Value::Ascii("RF50mm F1.8 STM")),
+            ]),
+        ),
+        (
+            0x8825,
+            Value::Ifd(vec![
+                (1, Value::Ascii("S")),
+                (2, Value::Rational(vec![(33, 1), (51, 1), (36, 1)])),
+                (3, Value::Ascii("E")),
+                (4, Value::Rational(vec![(151, 1), (12, 1), (0, 1)])),
+                (6, Value::Rational(vec![(58, 1)])),
+            ]),
+        ),
+    ]);
+    let exif = Exif::parse(&data).unwrap();
+
+    assert_eq!(exif.orientation(), Orientation::Rotate90);
+    assert_eq!(exif.captured.as_deref(), Some("2024-05-04 14:22:01"));
+    assert_eq!(exif.camera().as_deref(), Some("Canon EOS R6"));
+    let (lat, lon) = exif.gps.unwrap();
+    assert!((lat + 33.86).abs() < 1e-9);
+    assert!((lon - 151.2).abs() < 1e-9);
+
+    assert_eq!(
+        exif.fields(),
+        vec![
+            ("Taken", "2024-05-04 14:22:01".to_string()),
+            ("Camera", "Canon EOS R6".to_string()),
+            ("Lens", "RF50mm F1.8 STM".to_string()),
+            ("Exposure", "1/250 s".to_string()),
+            ("Aperture", "f/2.8".to_string()),
+            ("ISO", "400".to_string()),
+            ("Focal length", "50 mm".to_string()),
+            ("Location", "33.86000° S, 151.20000° E".to_string()),
+            ("Altitude", "58 m".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_rejects_garbage_and_blank_dates() {
+    assert!(Exif::parse(b"not exif").is_none());
+    assert!(Exif::parse(&[]).is_none());
+
+    // Cameras without a set clock write zeros; the make is kept when the model lacks it
+    let exif = Exif::parse(&tiff(vec![
+        (0x010f, Value::Ascii("FUJIFILM")),
+        (0x0110, Value::Ascii("X-T4")),
+        (0x0132, Value::Ascii("0000:00:00 00:00:00")),
+    ]))
+    .unwrap();
+    assert_eq!(exif.captured, None);
+    assert_eq!(exif.camera().as_deref(), Some("FUJIFILM X-T4"));
+    assert_eq!(exif.orientation(), Orientation::NoTransforms);
+}
+
+#[test]
+fn test_orientation_edits_match_the_image_crate() {
+    let stored = DynamicImage::ImageRgb8(RgbImage::from_fn(3, 2, |x, y| {
+        image::Rgb([x as u8 * 80, y as u8 * 120, 7])
+    }));
+    for value in 1..=8 {
+        let orientation = Orientation::from_exif(value).unwrap();
+        let mut expected = stored.clone();
+        expected.apply_orientation(orientation);
+        assert_eq!(
+            ImageEdit::from_orientation(orientation).apply(stored.clone()),
+            expected,
+            "orientation {}",
+            value
+        );
+    }
+}
+
+#[test]
+fn test_images_are_upright_on_import() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("portrait.jpg");
+    write_jpeg(&path, photo_exif(6, "2023:12:24 18:05:00"));
+
+    let info = exif::read(&path).unwrap();
+    assert_eq!((info.width, info.height), (40, 20));
+    assert_eq!(info.fields()[0], ("Dimensions", "20 × 40".to_string()));
+    assert_eq!(
+        exif::capture_date(&path).as_deref(),
+        Some("2023-12-24 18:05:00")
+    );
+
+    let content = ItemContent::from_path(&path);
+    let ItemContent::Image(_, ref edit) = content else {
+        panic!("Expected an image");
+    };
+    assert_eq!(edit.quarter_turns, 1);
+    let (width, height) = content.default_size();
+    assert!(height > width);
+
+    // Files without EXIF are left as they are
+    let plain = dir.path().join("plain.png");
+    DynamicImage::new_rgb8(4, 2).save(&plain).unwrap();
+    assert_eq!(exif::read(&plain).unwrap().exif, Exif::default());
+    assert_eq!(ImageEdit::from_exif(&plain), ImageEdit::default());
+    assert!(exif::read(&dir.path().join("missing.jpg")).is_none());
+}
+
+#[test]
+fn test_board_finds_photos_by_capture_date() {
+    let dir = tempdir().unwrap();
+    let mut board = Board::new_for_test();
+    for (name, date) in [
+        ("summer.jpg", "2024:07:02 09:00:00"),
+        ("winter.jpg", "2023:01:15 12:00:00"),
+        ("spring.jpg", "2024:04:20 16:30:00"),
+    ] {
+        let path = dir.path().join(name);
+        write_jpeg(&path, photo_exif(1, date));
+        board.add_item(point(px(0.0), px(0.0)), ItemContent::from_path(&path));
+    }
+    let undated = dir.path().join("scan.png");
+    DynamicImage::new_rgb8(4, 4).save(&undated).unwrap();
+    board.add_item(point(px(0.0), px(0.0)), ItemContent::from_path(&undated));
+
+    // Oldest first, and undated images are left out
+    let dates = exif::capture_dates(&board.image_paths());
+    assert_eq!(dates.len(), 3);
+    let labels: Vec<String> = board
+        .find_images_by_capture_date(&dates, "")
+        .into_iter()
+        .map(|(_, label, _)| label)
+        .collect();
+    assert_eq!(labels.len(), 3);
+    assert!(labels[0].starts_with("2023-01-15 12:00:00"));
+    assert!(labels[0].ends_with("winter.jpg"));
+    assert!(labels[2].starts_with("2024-07-02"));
+
+    let in_2024 = board.find_images_by_capture_date(&dates, "2024");
+    assert_eq!(in_2024.len(), 2);
+}
@@ -0,0 +1,242 @@
+//! Unit tests for user-defined external commands.
+
+use gpui::{point, px};
+use humanboard::board::Board;
+use humanboard::command_registry::{Command, CommandRegistry, GoHomeCommand};
+use humanboard::error::ExternalCommandError;
+use humanboard::external_commands::{
+    DEFAULT_TIMEOUT, ExternalCommandConfig, ExternalCommandOutput, register_all, run,
+};
+use humanboard::image_edit::ImageEdit;
+use humanboard::types::{CanvasItem, ItemContent};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+fn config(name: &str, command: &str, item_types: &[&str]) -> ExternalCommandConfig {
+    ExternalCommandConfig {
+        name: name.to_string(),
+        command: command.to_string(),
+        item_types: item_types.iter().map(|t| t.to_string()).collect(),
+        timeout_secs: None,
+    }
+}
+
+/// Run a command with the default timeout, never cancelled
+fn run_once(
+    command: &str,
+    items: &[CanvasItem],
+) -> Result<ExternalCommandOutput, ExternalCommandError> {
+    run(command, items, DEFAULT_TIMEOUT, &AtomicBool::new(false))
+}
+
+fn text_item(id: u64, text: &str) -> CanvasItem {
+    CanvasItem {
+        id,
+        position: (0.0, 0.0),
+        size: (100.0, 50.0),
+        content: ItemContent::Text(text.to_string()),
+    }
+}
+
+#[test]
+fn test_config_applies_to_item_types() {
+    let image = ItemContent::Image("a.png".into(), ImageEdit::default());
+    let text = ItemContent::Text("hi".to_string());
+
+    let images_only = config("Upscale", "upscale", &["Image"]);
+    assert!(images_only.applies_to(&image));
+    assert!(!images_only.applies_to(&text));
+
+    let any = config("Echo", "cat", &[]);
+    assert!(any.applies_to(&image));
+    assert!(any.applies_to(&text));
+}
+
+#[test]
+fn test_config_parses_from_settings_json() {
+    let json = r#"{ "name": "Open in IDE", "command": "code", "item_types": ["code"] }"#;
+    let parsed: ExternalCommandConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(parsed, config("Open in IDE", "code", &["code"]));
+
+    let json = r#"{ "name": "Echo", "command": "cat" }"#;
+    let parsed: ExternalCommandConfig = serde_json::from_str(json).unwrap();
+    assert!(parsed.item_types.is_empty());
+    assert_eq!(parsed.timeout(), DEFAULT_TIMEOUT);
+
+    let json = r#"{ "name": "Slow", "command": "sleep 5", "timeout_secs": 3 }"#;
+    let parsed: ExternalCommandConfig = serde_json::from_str(json).unwrap();
+    assert_eq!(parsed.timeout(), Duration::from_secs(3));
+}
+
+#[test]
+fn test_output_parse() {
+    assert!(ExternalCommandOutput::parse("  \n").unwrap().is_empty());
+
+    let output = ExternalCommandOutput::parse(
+        r#"{ "add": [{ "content": { "Text": "new" }, "position": [10, 20] }],
+             "update": [{ "id": 3, "position": [0, 0], "size": [1, 1], "content": { "Text": "changed" } }] }"#,
+    )
+    .unwrap();
+    assert_eq!(output.add.len(), 1);
+    assert_eq!(output.add[0].position, Some((10.0, 20.0)));
+    assert_eq!(output.add[0].size, None);
+    assert_eq!(output.update[0].id, 3);
+
+    assert!(matches!(
+        ExternalCommandOutput::parse("not json"),
+        Err(ExternalCommandError::InvalidOutput(_))
+    ));
+}
+
+#[test]
+fn test_output_parse_rejects_invalid_items() {
+    let duplicate = r#"{ "update": [
+        { "id": 3, "position": [0, 0], "size": [10, 10], "content": { "Text": "a" } },
+        { "id": 3, "position": [0, 0], "size": [10, 10], "content": { "Text": "b" } }
+    ] }"#;
+    let negative = r#"{ "add": [{ "content": { "Text": "new" }, "size": [-5, 10] }] }"#;
+    let infinite = r#"{ "add": [{ "content": { "Text": "new" }, "position": [1e50, 0] }] }"#;
+    for output in [duplicate, negative, infinite] {
+        assert!(matches!(
+            ExternalCommandOutput::parse(output),
+            Err(ExternalCommandError::InvalidItem(_))
+        ));
+    }
+
+    // Other bad properties are fixed rather than rejected
+    let output = ExternalCommandOutput::parse(
+        r##"{ "add": [{ "content": { "Stroke": {
+            "points": [0, 0, 1, 1], "color": "nope", "width": 500, "kind": "Pen"
+        } } }] }"##,
+    )
+    .unwrap();
+    assert!(matches!(
+        &output.add[0].content,
+        ItemContent::Stroke { color, width, .. } if color.is_empty() && *width < 500.0
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_passes_selection_on_stdin() {
+    // Echo the selection back as updates
+    let items = vec![text_item(1, "one"), text_item(2, "two")];
+    let output = run_once(r#"printf '{"update":'; cat; printf '}'"#, &items).unwrap();
+
+    let ids: Vec<u64> = output.update.iter().map(|item| item.id).collect();
+    assert_eq!(ids, vec![1, 2]);
+    assert!(output.add.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_reports_failure_with_stderr() {
+    let err = run_once("echo oops >&2; exit 3", &[text_item(1, "one")]).unwrap_err();
+    match err {
+        ExternalCommandError::Failed { stderr, .. } => assert_eq!(stderr, "oops"),
+        other => panic!("unexpected error: {}", other),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_without_reading_stdin_or_printing() {
+    let output = run_once("true", &[text_item(1, "one")]).unwrap();
+    assert!(output.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_kills_commands_that_time_out() {
+    let started = Instant::now();
+    let err = run(
+        "sleep 10",
+        &[],
+        Duration::from_millis(200),
+        &AtomicBool::new(false),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ExternalCommandError::TimedOut { .. }));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_stops_background_children_holding_output() {
+    let started = Instant::now();
+    let err = run(
+        "sleep 10 & echo started",
+        &[],
+        Duration::from_millis(300),
+        &AtomicBool::new(false),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ExternalCommandError::TimedOut { .. }));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_can_be_cancelled() {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let canceller = cancel.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        canceller.store(true, Ordering::Relaxed);
+    });
+
+    let started = Instant::now();
+    let err = run("sleep 10", &[], DEFAULT_TIMEOUT, &cancel).unwrap_err();
+    assert!(matches!(err, ExternalCommandError::Cancelled));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_register_all_replaces_external_commands() {
+    let mut registry = CommandRegistry::new();
+    registry.register(GoHomeCommand);
+
+    register_all(
+        &mut registry,
+        &[config("Upscale", "upscale", &["image"]), config("", "nameless", &[])],
+    );
+    assert_eq!(registry.len(), 2);
+    let upscale = registry.get("external:Upscale").unwrap();
+    assert_eq!(upscale.name(), "Upscale");
+    assert_eq!(upscale.category(), "External");
+
+    register_all(&mut registry, &[config("Open in IDE", "code", &["code"])]);
+    assert_eq!(registry.len(), 2);
+    assert!(registry.get("external:Upscale").is_none());
+    assert!(registry.get("external:Open in IDE").is_some());
+    assert!(registry.get("navigation:home").is_some());
+    assert_eq!(registry.by_category("External").len(), 1);
+}
+
+#[test]
+fn test_apply_item_changes_is_one_undo_step() {
+    let mut board = Board::new_for_test();
+    let id = board.add_item(point(px(0.0), px(0.0)), ItemContent::Text("old".to_string()));
+
+    let mut changed = board.get_item(id).unwrap().clone();
+    changed.content = ItemContent::Text("new".to_string());
+    let (added, updated) = board.apply_item_changes(
+        vec![(ItemContent::Text("added".to_string()), (300.0, 0.0), Some((80.0, 40.0)))],
+        vec![changed, text_item(999, "unknown")],
+    );
+
+    assert_eq!(added.len(), 1);
+    assert_eq!(updated, 1);
+    assert_eq!(board.items.len(), 2);
+    let new_item = board.get_item(added[0]).unwrap();
+    assert_eq!(new_item.position, (300.0, 0.0));
+    assert_eq!(new_item.size, (80.0, 40.0));
+
+    assert!(board.undo());
+    assert_eq!(board.items.len(), 1);
+    assert!(matches!(
+        &board.get_item(id).unwrap().content,
+        ItemContent::Text(text) if text == "old"
+    ));
+}
@@ -46,7 +46,7 @@ fn test_focus_change_event() {
 #[test]
 fn test_focus_context_all() {
     let all = FocusContext::all();
-    assert_eq!(all.len(), 8);
+    assert_eq!(all.len(), 9);
     // Should be in priority order (highest first)
     assert_eq!(all[0], FocusContext::Modal);
     assert_eq!(all[6], FocusContext::Canvas);
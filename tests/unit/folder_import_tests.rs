@@ -0,0 +1,291 @@
+//! Unit tests for importing folders as framed grids.
+
+use humanboard::board::{Board, prepare_file_drop};
+use humanboard::folder_import::{
+    CELL_SIZE, FolderImportOptions, FolderSort, FolderTree, GAP, PADDING, layout, scan,
+};
+use humanboard::types::{ItemContent, ShapeType};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tempfile::{TempDir, tempdir};
+
+/// A folder holding the given relative paths as small text files
+fn folder(files: &[&str]) -> TempDir {
+    let dir = tempdir().unwrap();
+    for file in files {
+        let path = dir.path().join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "hello").unwrap();
+    }
+    dir
+}
+
+fn names(files: &[PathBuf]) -> Vec<String> {
+    files
+        .iter()
+        .map(|f| f.file_name().unwrap().to_string_lossy().into_owned())
+        .collect()
+}
+
+fn frame_label(content: &ItemContent) -> Option<&str> {
+    match content {
+        ItemContent::Shape {
+            shape_type: ShapeType::Frame,
+            label,
+            ..
+        } => label.as_deref(),
+        _ => None,
+    }
+}
+
+fn text(path: &Path) -> ItemContent {
+    ItemContent::Text(path.file_name().unwrap().to_string_lossy().into_owned())
+}
+
+#[test]
+fn test_scan_walks_subfolders_and_skips_hidden_and_empty() {
+    let dir = folder(&[
+        "b.txt",
+        "A.txt",
+        ".DS_Store",
+        ".git/config",
+        "photos/cat.png",
+        "photos/2024/dog.png",
+    ]);
+    fs::create_dir(dir.path().join("empty")).unwrap();
+
+    let tree = scan(dir.path(), &FolderImportOptions::default()).unwrap();
+    assert_eq!(names(&tree.files), vec!["A.txt", "b.txt"]);
+    assert_eq!(tree.folders.len(), 1);
+    let photos = &tree.folders[0];
+    assert_eq!(photos.name, "photos");
+    assert_eq!(names(&photos.files), vec!["cat.png"]);
+    assert_eq!(photos.folders[0].name, "2024");
+    assert_eq!(tree.file_count(), 4);
+}
+
+#[test]
+fn test_scan_filters_by_extension() {
+    let dir = folder(&[
+        "notes.md",
+        "photo.PNG",
+        "data.csv",
+        "img/logo.png",
+        "docs/readme.txt",
+    ]);
+    let options = FolderImportOptions {
+        extensions: vec!["png".to_string(), ".md".to_string()],
+        ..Default::default()
+    };
+
+    let tree = scan(dir.path(), &options).unwrap();
+    assert_eq!(names(&tree.files), vec!["notes.md", "photo.PNG"]);
+    // Folders left empty by the filter are dropped
+    assert_eq!(tree.folders.len(), 1);
+    assert_eq!(tree.folders[0].name, "img");
+}
+
+#[test]
+fn test_scan_stops_at_max_depth() {
+    let dir = folder(&["top.txt", "one/a.txt", "one/two/b.txt"]);
+
+    let shallow = FolderImportOptions {
+        max_depth: 0,
+        ..Default::default()
+    };
+    let tree = scan(dir.path(), &shallow).unwrap();
+    assert_eq!(tree.file_count(), 1);
+    assert!(tree.folders.is_empty());
+
+    let one_level = FolderImportOptions {
+        max_depth: 1,
+        ..Default::default()
+    };
+    let tree = scan(dir.path(), &one_level).unwrap();
+    assert_eq!(tree.file_count(), 2);
+    assert!(tree.folders[0].folders.is_empty());
+}
+
+#[test]
+fn test_scan_sorts_by_type_and_date() {
+    let dir = folder(&["b.txt", "c.md", "a.png", "d.md"]);
+
+    let by_type = FolderImportOptions {
+        sort: FolderSort::Type,
+        ..Default::default()
+    };
+    let tree = scan(dir.path(), &by_type).unwrap();
+    assert_eq!(names(&tree.files), vec!["c.md", "d.md", "a.png", "b.txt"]);
+
+    let now = SystemTime::now();
+    for (i, name) in ["a.png", "b.txt", "c.md", "d.md"].iter().enumerate() {
+        let file = fs::File::options()
+            .write(true)
+            .open(dir.path().join(name))
+            .unwrap();
+        file.set_modified(now - Duration::from_secs(60 * (4 - i as u64)))
+            .unwrap();
+    }
+    let by_date = FolderImportOptions {
+        sort: FolderSort::Modified,
+        ..Default::default()
+    };
+    let tree = scan(dir.path(), &by_date).unwrap();
+    assert_eq!(names(&tree.files), vec!["d.md", "c.md", "b.txt", "a.png"]);
+}
+
+#[test]
+fn test_scan_missing_folder_is_an_error() {
+    let dir = tempdir().unwrap();
+    assert!(scan(&dir.path().join("gone"), &FolderImportOptions::default()).is_err());
+}
+
+#[test]
+fn test_layout_places_files_in_a_grid_inside_a_frame() {
+    let tree = FolderTree {
+        name: "Notes".to_string(),
+        files: (0..5)
+            .map(|i| PathBuf::from(format!("/notes/{}.txt", i)))
+            .collect(),
+        folders: Vec::new(),
+    };
+    let placed = layout(&tree, (100.0, 50.0), &mut text);
+
+    assert_eq!(placed.len(), 6);
+    let (frame, frame_pos, frame_size) = &placed[0];
+    assert_eq!(frame_label(frame), Some("Notes"));
+    assert_eq!(*frame_pos, (100.0, 50.0));
+    // Five files make a 3 x 2 grid
+    assert_eq!(
+        *frame_size,
+        (
+            3.0 * CELL_SIZE.0 + 2.0 * GAP + 2.0 * PADDING,
+            2.0 * CELL_SIZE.1 + GAP + 2.0 * PADDING
+        )
+    );
+
+    // Text items (300 x 100) are centred in their cells
+    let cell_x = 100.0 + PADDING;
+    let cell_y = 50.0 + PADDING;
+    assert_eq!(placed[1].1, (cell_x + 10.0, cell_y + 70.0));
+    assert_eq!(
+        placed[4].1,
+        (cell_x + 10.0, cell_y + CELL_SIZE.1 + GAP + 70.0)
+    );
+    assert_eq!(placed[1].2, (300.0, 100.0));
+}
+
+#[test]
+fn test_layout_nests_subfolders_as_frames_below_files() {
+    let tree = FolderTree {
+        name: "root".to_string(),
+        files: vec![PathBuf::from("/root/readme.txt")],
+        folders: vec![
+            FolderTree {
+                name: "a".to_string(),
+                files: vec![PathBuf::from("/root/a/1.txt")],
+                folders: Vec::new(),
+            },
+            FolderTree {
+                name: "b".to_string(),
+                files: vec![PathBuf::from("/root/b/2.txt")],
+                folders: Vec::new(),
+            },
+        ],
+    };
+    let placed = layout(&tree, (0.0, 0.0), &mut text);
+
+    let labels: Vec<Option<&str>> = placed.iter().map(|(c, _, _)| frame_label(c)).collect();
+    assert_eq!(
+        labels,
+        vec![Some("root"), None, Some("a"), None, Some("b"), None]
+    );
+
+    let (_, root_pos, root_size) = placed[0];
+    let (_, a_pos, a_size) = placed[2];
+    let (_, b_pos, b_size) = placed[4];
+    // Subfolders sit side by side below the files, inside the parent frame
+    assert!(a_pos.1 > PADDING + CELL_SIZE.1);
+    assert_eq!(a_pos.1, b_pos.1);
+    assert_eq!(b_pos.0, a_pos.0 + a_size.0 + GAP);
+    assert_eq!(root_pos.0 + root_size.0, b_pos.0 + b_size.0 + PADDING);
+    assert_eq!(root_pos.1 + root_size.1, b_pos.1 + b_size.1 + PADDING);
+}
+
+#[test]
+fn test_layout_scales_large_items_to_fit_cells() {
+    let tree = FolderTree {
+        name: "clips".to_string(),
+        files: vec![PathBuf::from("/clips/talk.mp4")],
+        folders: Vec::new(),
+    };
+    let placed = layout(&tree, (0.0, 0.0), &mut |path| {
+        ItemContent::YouTube(path.display().to_string())
+    });
+
+    // 560 x 315 scaled by 320 / 560
+    let (width, height) = placed[1].2;
+    assert_eq!(width, CELL_SIZE.0);
+    assert!((height - 180.0).abs() < 0.01);
+}
+
+#[test]
+fn test_limit_files_keeps_the_first_files_in_layout_order() {
+    let dir = folder(&["1.txt", "2.txt", "a/3.txt", "a/4.txt", "b/5.txt"]);
+    let mut tree = scan(dir.path(), &FolderImportOptions::default()).unwrap();
+
+    assert_eq!(tree.limit_files(3), 2);
+    assert_eq!(names(&tree.files), vec!["1.txt", "2.txt"]);
+    assert_eq!(names(&tree.folders[0].files), vec!["3.txt"]);
+    // Folders left empty are dropped
+    assert_eq!(tree.folders.len(), 1);
+    assert_eq!(tree.file_count(), 3);
+
+    assert_eq!(tree.limit_files(10), 0);
+    assert_eq!(tree.file_count(), 3);
+}
+
+#[test]
+fn test_file_drop_is_capped_and_added_as_one_undo_step() {
+    let dir = folder(&["loose.txt", "photos/a.txt", "photos/b.txt", "photos/c.txt"]);
+    let options = FolderImportOptions {
+        max_items: 3,
+        ..Default::default()
+    };
+    let paths = vec![dir.path().join("loose.txt"), dir.path().join("photos")];
+
+    let prepared = prepare_file_drop(paths, &options, None);
+    assert!(prepared.errors.is_empty());
+    assert_eq!(prepared.files.len(), 1);
+    assert_eq!(prepared.skipped, 1);
+    // The folder's frame and its first two files
+    assert_eq!(prepared.folders.len(), 1);
+    assert_eq!(prepared.folders[0].len(), 3);
+    assert_eq!(prepared.folders[0][0].1, (0.0, 0.0));
+
+    let mut board = Board::new_for_test();
+    let added = board.apply_file_drop((100.0, 50.0), prepared);
+    assert_eq!(added.len(), 4);
+    assert_eq!(board.get_item(added[0]).unwrap().position, (100.0, 50.0));
+    // The folder is laid out below the loose file
+    let frame = board.get_item(added[1]).unwrap();
+    assert_eq!(frame_label(&frame.content), Some("photos"));
+    assert_eq!(frame.position.0, 100.0);
+    assert!(frame.position.1 > 50.0);
+
+    assert!(board.undo());
+    assert!(board.items.is_empty());
+}
+
+#[test]
+fn test_folder_import_options_from_settings_json() {
+    let options: FolderImportOptions =
+        serde_json::from_str(r#"{ "extensions": ["jpg"], "sort": "modified" }"#).unwrap();
+    assert_eq!(options.extensions, vec!["jpg"]);
+    assert_eq!(options.sort, FolderSort::Modified);
+    assert_eq!(options.max_depth, FolderImportOptions::default().max_depth);
+    assert_eq!(options.max_items, 500);
+    assert!(options.accepts(Path::new("/a/B.JPG")));
+    assert!(!options.accepts(Path::new("/a/notes")));
+}
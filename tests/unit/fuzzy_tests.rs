@@ -0,0 +1,170 @@
+//! Unit tests for fuzzy matching and command palette ranking.
+
+use humanboard::command_palette::{PaletteHistory, PaletteMatch};
+use humanboard::fuzzy::{FuzzyMatch, fuzzy_match, highlight_ranges};
+use tempfile::tempdir;
+
+const DAY: u64 = 24 * 60 * 60;
+
+#[test]
+fn test_matches_subsequence_with_positions() {
+    let found = fuzzy_match("zmi", "Zoom In").unwrap();
+    assert_eq!(found.positions, vec![0, 3, 5]);
+}
+
+#[test]
+fn test_match_ignores_case_and_query_whitespace() {
+    assert!(fuzzy_match("ZOOM", "zoom in").is_some());
+    assert_eq!(
+        fuzzy_match("zoom in", "Zoom In").unwrap().positions,
+        fuzzy_match("zoomin", "Zoom In").unwrap().positions
+    );
+}
+
+#[test]
+fn test_rejects_out_of_order_characters() {
+    assert!(fuzzy_match("nz", "Zoom In").is_none());
+    assert!(fuzzy_match("zoom in out", "Zoom In").is_none());
+}
+
+#[test]
+fn test_empty_query_matches_everything() {
+    assert_eq!(fuzzy_match("", "anything"), Some(FuzzyMatch::default()));
+    assert_eq!(fuzzy_match("  ", "anything"), Some(FuzzyMatch::default()));
+}
+
+#[test]
+fn test_word_starts_score_higher() {
+    let word_start = fuzzy_match("fit", "Zoom to fit").unwrap();
+    let mid_word = fuzzy_match("fit", "outfitter").unwrap();
+    assert!(word_start.score > mid_word.score);
+
+    let initials = fuzzy_match("nt", "New Text").unwrap();
+    let scattered = fuzzy_match("mt", "format").unwrap();
+    assert!(initials.score > scattered.score);
+}
+
+#[test]
+fn test_prefers_best_alignment() {
+    // The "n" and "t" of "notes" beat the earlier "n" of "Meeting"
+    let found = fuzzy_match("nt", "Meeting notes").unwrap();
+    assert_eq!(found.positions, vec![8, 10]);
+}
+
+#[test]
+fn test_camel_case_bonus() {
+    let found = fuzzy_match("MT", "MarkdownText").unwrap();
+    assert_eq!(found.positions, vec![0, 8]);
+}
+
+#[test]
+fn test_offset_shifts_positions() {
+    let found = fuzzy_match("zi", "Zoom In").unwrap().offset(6);
+    assert_eq!(found.positions, vec![6, 11]);
+}
+
+#[test]
+fn test_highlight_ranges_merge_runs() {
+    assert_eq!(highlight_ranges("Zoom In", &[0, 1, 5]), vec![0..2, 5..6]);
+    assert!(highlight_ranges("Zoom In", &[]).is_empty());
+}
+
+#[test]
+fn test_highlight_ranges_cover_multibyte_chars() {
+    assert_eq!(highlight_ranges("café ok", &[2, 3]), vec![2..5]);
+    // Out of range and mid-character positions are skipped
+    assert_eq!(highlight_ranges("café", &[4, 9]), Vec::new());
+}
+
+#[test]
+fn test_palette_match_offsets_positions_past_prefix() {
+    let m = PaletteMatch::find("ho", 7, "View: ", "Home", "view:b:Home").unwrap();
+    assert_eq!(m.result.name, "View: Home");
+    assert_eq!(m.result.positions, vec![6, 7]);
+    assert!(PaletteMatch::find("view", 7, "View: ", "Home", "view:b:Home").is_none());
+}
+
+fn candidates(query: &str) -> Vec<PaletteMatch> {
+    ["Alpha notes", "Bravo notes", "Gamma notes"]
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| PaletteMatch::find(query, i as u64, "", name, format!("item:{}", i)))
+        .collect()
+}
+
+fn ranked_ids(history: &PaletteHistory, query: &str, now: u64) -> Vec<u64> {
+    history
+        .rank(candidates(query), now)
+        .iter()
+        .map(|r| r.id)
+        .collect()
+}
+
+#[test]
+fn test_rank_keeps_order_without_history() {
+    let history = PaletteHistory::default();
+    assert_eq!(ranked_ids(&history, "notes", 0), vec![0, 1, 2]);
+    assert_eq!(ranked_ids(&history, "", 0), vec![0, 1, 2]);
+}
+
+#[test]
+fn test_rank_boosts_frequently_used() {
+    let now = 100 * DAY;
+    let mut history = PaletteHistory::default();
+    for _ in 0..3 {
+        history.record("item:2", now);
+    }
+    history.record("item:1", now);
+
+    assert_eq!(ranked_ids(&history, "notes", now), vec![2, 1, 0]);
+}
+
+#[test]
+fn test_frecency_decays_with_age() {
+    let now = 365 * DAY;
+    let mut history = PaletteHistory::default();
+    history.record("recent", now - DAY);
+    history.record("old", now - 200 * DAY);
+
+    assert!(history.frecency("recent", now) > history.frecency("old", now));
+    assert!(history.frecency("old", now) > 0.0);
+    assert_eq!(history.frecency("unused", now), 0.0);
+}
+
+#[test]
+fn test_history_never_adds_non_matches() {
+    let now = 100 * DAY;
+    let mut history = PaletteHistory::default();
+    history.record("item:1", now);
+
+    // "alpha" only matches the first candidate
+    assert_eq!(ranked_ids(&history, "alpha", now), vec![0]);
+}
+
+#[test]
+fn test_history_round_trips_through_file() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("nested").join("palette_history.json");
+
+    let mut history = PaletteHistory::default();
+    history.record("cmd:fit", 42);
+    history.record("cmd:fit", 43);
+    history.save(&path).unwrap();
+
+    let loaded = PaletteHistory::load(&path);
+    let entry = loaded.get("cmd:fit").unwrap();
+    assert_eq!(entry.count, 2);
+    assert_eq!(entry.last_used, 43);
+}
+
+#[test]
+fn test_history_load_tolerates_missing_or_invalid_file() {
+    let dir = tempdir().unwrap();
+    assert!(PaletteHistory::load(&dir.path().join("missing.json"))
+        .get("cmd:fit")
+        .is_none());
+
+    let path = dir.path().join("broken.json");
+    std::fs::write(&path, "not json").unwrap();
+    assert!(PaletteHistory::load(&path).get("cmd:fit").is_none());
+}
@@ -1,7 +1,8 @@
 //! Unit tests for hit_testing module.
 
 use gpui::{point, px, size};
-use humanboard::hit_testing::{HitTestResult, HitTester};
+use humanboard::hit_testing::{HitTestContentType, HitTestResult, HitTester};
+use humanboard::types::{ItemContent, ShapeType};
 
 #[test]
 fn test_hit_test_header() {
@@ -53,3 +54,42 @@ fn test_screen_to_canvas() {
     assert_eq!(f32::from(canvas_pos.x), 100.0);
     assert_eq!(f32::from(canvas_pos.y), 100.0);
 }
+
+#[test]
+fn test_labelled_shape_hits_interior() {
+    let shape = |label: Option<&str>| ItemContent::Shape {
+        shape_type: ShapeType::Rectangle,
+        fill_color: None,
+        border_color: String::new(),
+        border_width: 2.0,
+        label: label.map(str::to_string),
+    };
+
+    assert!(matches!(
+        HitTestContentType::from_content(&shape(None)),
+        HitTestContentType::Shape { .. }
+    ));
+    assert!(matches!(
+        HitTestContentType::from_content(&shape(Some("Box"))),
+        HitTestContentType::Standard
+    ));
+}
+
+#[test]
+fn test_titled_frame_stays_click_through() {
+    let frame = ItemContent::Shape {
+        shape_type: ShapeType::Frame,
+        fill_color: None,
+        border_color: String::new(),
+        border_width: 2.0,
+        label: Some("Slide 1".to_string()),
+    };
+
+    assert!(matches!(
+        HitTestContentType::from_content(&frame),
+        HitTestContentType::Shape {
+            shape_type: ShapeType::Frame,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,197 @@
+//! Unit tests for non-destructive image edits.
+
+use humanboard::image_edit::{
+    CropRect, ImageEdit, bake, contain_rect, orient, orientation_key, oriented_path, turned_bounds,
+    uncropped_bounds,
+};
+use humanboard::types::ItemContent;
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+/// A 4 x 2 image where every pixel is different
+fn sample() -> DynamicImage {
+    DynamicImage::ImageRgba8(RgbaImage::from_fn(4, 2, |x, y| {
+        Rgba([x as u8 * 60, y as u8 * 120, 0, 255])
+    }))
+}
+
+fn pixels(image: &DynamicImage) -> ((u32, u32), Vec<u8>) {
+    (image.dimensions(), image.to_rgba8().into_raw())
+}
+
+fn cropped() -> ImageEdit {
+    ImageEdit {
+        crop: Some(CropRect {
+            x: 0.25,
+            y: 0.0,
+            width: 0.5,
+            height: 0.5,
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_unedited_images_keep_the_old_board_format() {
+    let old: ItemContent = serde_json::from_str(r#"{ "Image": "/photos/cat.png" }"#).unwrap();
+    let ItemContent::Image(path, edit) = &old else {
+        panic!("expected an image");
+    };
+    assert_eq!(*path, PathBuf::from("/photos/cat.png"));
+    assert!(edit.is_identity());
+    assert_eq!(
+        serde_json::to_string(&old).unwrap(),
+        r#"{"Image":"/photos/cat.png"}"#
+    );
+
+    let mut edit = cropped();
+    edit.rotate_clockwise();
+    let edited = ItemContent::Image("/photos/cat.png".into(), edit);
+    let json = serde_json::to_string(&edited).unwrap();
+    let ItemContent::Image(_, restored) = serde_json::from_str(&json).unwrap() else {
+        panic!("expected an image");
+    };
+    assert_eq!(restored, edit);
+}
+
+/// An edit step and what it does to the displayed pixels
+type Step = (fn(&mut ImageEdit), fn(&DynamicImage) -> DynamicImage);
+
+#[test]
+fn test_rotating_and_flipping_the_edit_matches_the_pixels() {
+    let steps: [Step; 4] = [
+        (ImageEdit::rotate_clockwise, DynamicImage::rotate90),
+        (ImageEdit::rotate_counterclockwise, DynamicImage::rotate270),
+        (ImageEdit::flip_horizontal, DynamicImage::fliph),
+        (ImageEdit::flip_vertical, DynamicImage::flipv),
+    ];
+
+    // Each step, applied on top of any other, turns the displayed crop with it
+    for (first, _) in steps {
+        for (step, expected) in steps {
+            let mut edit = cropped();
+            first(&mut edit);
+            let before = edit.apply(sample());
+            step(&mut edit);
+            assert_eq!(pixels(&edit.apply(sample())), pixels(&expected(&before)));
+        }
+    }
+}
+
+#[test]
+fn test_four_quarter_turns_restore_the_edit() {
+    let mut edit = cropped();
+    edit.flip_horizontal();
+    let original = edit;
+    for _ in 0..4 {
+        edit.rotate_clockwise();
+    }
+    assert_eq!(edit.quarter_turns, 0);
+    let crop = edit.crop.unwrap();
+    let expected = original.crop.unwrap();
+    assert!((crop.x - expected.x).abs() < 1e-6 && (crop.y - expected.y).abs() < 1e-6);
+    assert_eq!((crop.width, crop.height), (expected.width, expected.height));
+}
+
+#[test]
+fn test_crop_to_narrows_the_current_crop() {
+    let mut edit = cropped();
+    edit.crop_to(CropRect::from_corners((1.0, 1.0), (0.5, 0.0)));
+    assert_eq!(
+        edit.crop,
+        Some(CropRect {
+            x: 0.5,
+            y: 0.0,
+            width: 0.25,
+            height: 0.5,
+        })
+    );
+    assert_eq!(pixels(&edit.apply(sample())).0, (1, 1));
+
+    // Cropping to the whole image is no crop at all
+    let mut edit = ImageEdit::default();
+    edit.crop_to(CropRect::FULL);
+    assert!(edit.is_identity());
+    assert!(!CropRect::from_corners((0.5, 0.5), (0.51, 0.9)).is_usable());
+}
+
+#[test]
+fn test_item_geometry_helpers() {
+    // A wide image letterboxed in a square item
+    let rect = contain_rect((100.0, 100.0), (400.0, 200.0));
+    assert_eq!(
+        rect,
+        CropRect {
+            x: 0.0,
+            y: 0.25,
+            width: 1.0,
+            height: 0.5,
+        }
+    );
+
+    assert_eq!(
+        turned_bounds((0.0, 0.0), (200.0, 100.0)),
+        ((50.0, -50.0), (100.0, 200.0))
+    );
+    let crop = cropped().crop.unwrap();
+    assert_eq!(
+        uncropped_bounds((100.0, 100.0), (50.0, 40.0), crop),
+        ((75.0, 100.0), (100.0, 80.0))
+    );
+}
+
+#[test]
+fn test_oriented_path_caches_a_turned_copy() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("wide.png");
+    sample().save(&source).unwrap();
+
+    assert_eq!(oriented_path(&source, &cropped()), Some(source.clone()));
+    assert_eq!(orientation_key(&source, &cropped()), None);
+
+    let mut edit = ImageEdit::default();
+    edit.rotate_clockwise();
+    assert_eq!(oriented_path(&source, &edit), None);
+    orient(&orientation_key(&source, &edit).unwrap());
+    let oriented = oriented_path(&source, &edit).unwrap();
+    assert_ne!(oriented, source);
+    assert_eq!(image::image_dimensions(&oriented).unwrap(), (2, 4));
+
+    // Changing the source needs a new copy
+    let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+    std::fs::File::options()
+        .write(true)
+        .open(&source)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+    assert_eq!(oriented_path(&source, &edit), None);
+}
+
+#[test]
+fn test_bake_writes_a_new_file_next_to_the_board() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("photo.png");
+    sample().save(&source).unwrap();
+    let files_dir = dir.path().join("board").join("files");
+
+    let mut edit = cropped();
+    edit.rotate_counterclockwise();
+    let baked = bake(&source, &edit, &files_dir).unwrap();
+    assert_eq!(baked, files_dir.join("photo-edited.png"));
+    assert_eq!(
+        pixels(&image::open(&baked).unwrap()),
+        pixels(&edit.apply(sample()))
+    );
+    // The source is untouched and a second bake doesn't overwrite the first
+    assert_eq!(image::image_dimensions(&source).unwrap(), (4, 2));
+    assert_eq!(
+        bake(&source, &edit, &files_dir).unwrap(),
+        files_dir.join("photo-edited-2.png")
+    );
+
+    // Items are sized as they're displayed
+    let content = ItemContent::Image(source, edit);
+    assert_eq!(content.default_size(), (1.0, 2.0));
+}
@@ -0,0 +1,308 @@
+//! Unit tests for JSON Canvas import and export.
+
+use humanboard::image_edit::ImageEdit;
+use humanboard::json_canvas::{
+    self, End, JsonCanvas, NodeKind, Side, from_items, import_file, to_board_state, vault_root,
+};
+use humanboard::types::{ArrowHead, CanvasItem, ItemContent, ShapeType, StrokeKind};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/json_canvas")
+        .join(name)
+}
+
+type NodeKey = (i64, i64, i64, i64, NodeKind, Option<String>);
+type EdgeKey = (
+    (i64, i64),
+    Option<Side>,
+    End,
+    (i64, i64),
+    Option<Side>,
+    End,
+    Option<String>,
+    Option<String>,
+);
+
+/// Nodes without their ids, in position order, with colours as hex
+fn node_keys(canvas: &JsonCanvas) -> Vec<NodeKey> {
+    let mut keys: Vec<NodeKey> = canvas
+        .nodes
+        .iter()
+        .map(|n| {
+            let color = n.color.as_deref().map(json_canvas::resolve_color);
+            (n.x, n.y, n.width, n.height, n.kind.clone(), color)
+        })
+        .collect();
+    keys.sort_by_key(|k| (k.0, k.1, k.2, k.3));
+    keys
+}
+
+/// Edges with their nodes identified by position and default ends filled in
+fn edge_keys(canvas: &JsonCanvas) -> Vec<EdgeKey> {
+    let positions: HashMap<&str, (i64, i64)> = canvas
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), (n.x, n.y)))
+        .collect();
+    let mut keys: Vec<EdgeKey> = canvas
+        .edges
+        .iter()
+        .map(|e| {
+            (
+                positions[e.from_node.as_str()],
+                e.from_side,
+                e.from_end.unwrap_or(End::None),
+                positions[e.to_node.as_str()],
+                e.to_side,
+                e.to_end.unwrap_or(End::Arrow),
+                e.color.clone(),
+                e.label.clone(),
+            )
+        })
+        .collect();
+    keys.sort_by_key(|k| (k.0, k.3));
+    keys
+}
+
+fn assert_round_trips(path: &Path) {
+    let original = json_canvas::read(path).unwrap();
+    let base = vault_root(path);
+
+    let (state, skipped) = to_board_state(&original, &base);
+    assert_eq!(skipped, 0);
+    let (exported, skipped) = from_items(&state.items, &base);
+    assert_eq!(skipped, 0);
+
+    assert_eq!(node_keys(&exported), node_keys(&original));
+    assert_eq!(edge_keys(&exported), edge_keys(&original));
+}
+
+#[test]
+fn test_project_fixture_round_trips() {
+    assert_round_trips(&fixture("project.canvas"));
+}
+
+#[test]
+fn test_vault_fixture_round_trips() {
+    assert_round_trips(&fixture("vault/boards/map.canvas"));
+}
+
+#[test]
+fn test_import_maps_node_types() {
+    let (state, skipped) = import_file(&fixture("project.canvas")).unwrap();
+    assert_eq!(skipped, 0);
+    assert_eq!(state.items.len(), 11);
+    assert_eq!(state.next_item_id, 11);
+
+    // Groups first, then arrows, then the other nodes
+    assert!(matches!(
+        &state.items[0].content,
+        ItemContent::Shape { shape_type: ShapeType::Frame, label: Some(label), border_color, .. }
+            if label == "Sprint" && border_color == "#44cf6e"
+    ));
+    assert!(
+        state.items[1..5]
+            .iter()
+            .all(|item| matches!(item.content, ItemContent::Arrow { .. }))
+    );
+
+    let contents: Vec<&ItemContent> = state.items[5..].iter().map(|i| &i.content).collect();
+    assert!(matches!(contents[0], ItemContent::TextBox { text, .. } if text.starts_with("# Goals")));
+    assert!(matches!(
+        contents[1],
+        ItemContent::Shape { shape_type: ShapeType::StickyNote, fill_color: Some(fill), .. }
+            if fill == "#e0de71"
+    ));
+    assert!(matches!(
+        contents[2],
+        ItemContent::Image(path, _) if *path == fixture("images/mockup.png")
+    ));
+    assert!(matches!(
+        contents[3],
+        ItemContent::Markdown { content, .. } if content.starts_with("# Plan")
+    ));
+    assert!(matches!(contents[4], ItemContent::Link(url) if url == "https://jsoncanvas.org"));
+    assert!(matches!(contents[5], ItemContent::YouTube(id) if id == "dQw4w9WgXcQ"));
+}
+
+#[test]
+fn test_import_edges_become_arrows_between_sides() {
+    let (state, _) = import_file(&fixture("project.canvas")).unwrap();
+
+    // "then": goals (0,0 250x120) right side to reminder (400,0) left side
+    let then = state
+        .items
+        .iter()
+        .find(|item| matches!(&item.content, ItemContent::Arrow { label: Some(l), .. } if l == "then"))
+        .unwrap();
+    assert_eq!(then.position, (250.0, 60.0));
+    let ItemContent::Arrow { end_offset, head_style, .. } = &then.content else {
+        unreachable!();
+    };
+    assert_eq!(*end_offset, (150.0, 0.0));
+    assert_eq!(*head_style, ArrowHead::Arrow);
+
+    assert!(state.items.iter().any(|item| matches!(
+        item.content,
+        ItemContent::Arrow { head_style: ArrowHead::None, .. }
+    )));
+}
+
+#[test]
+fn test_file_nodes_resolve_against_vault_root() {
+    let path = fixture("vault/boards/map.canvas");
+    assert_eq!(vault_root(&path), fixture("vault"));
+    assert_eq!(
+        vault_root(&fixture("project.canvas")),
+        fixture("project.canvas").parent().unwrap()
+    );
+
+    let (state, _) = import_file(&path).unwrap();
+    assert!(state.items.iter().any(|item| matches!(
+        &item.content,
+        ItemContent::Image(p, _) if *p == fixture("vault/attachments/diagram.png")
+    )));
+}
+
+#[test]
+fn test_import_resolves_preset_colors_and_floats() {
+    let canvas: JsonCanvas = serde_json::from_str(
+        r#"{ "nodes": [
+            { "id": "a", "type": "text", "text": "Hot", "x": 10.4, "y": -5.6, "width": 100, "height": 50, "color": "1" }
+        ] }"#,
+    )
+    .unwrap();
+    assert_eq!(canvas.nodes[0].x, 10);
+    assert_eq!(canvas.nodes[0].y, -6);
+
+    let (state, _) = to_board_state(&canvas, Path::new("."));
+    assert!(matches!(
+        &state.items[0].content,
+        ItemContent::Shape { fill_color: Some(fill), label: Some(label), .. }
+            if fill == "#fb464c" && label == "Hot"
+    ));
+    assert_eq!(json_canvas::resolve_color("6"), "#a882ff");
+    assert_eq!(json_canvas::resolve_color("#123456"), "#123456");
+}
+
+#[test]
+fn test_import_skips_unknown_nodes_and_dangling_edges() {
+    let canvas: JsonCanvas = serde_json::from_str(
+        r#"{
+            "nodes": [
+                { "id": "a", "type": "text", "text": "A", "x": 0, "y": 0, "width": 100, "height": 50 },
+                { "id": "b", "type": "hologram", "x": 200, "y": 0, "width": 100, "height": 50 }
+            ],
+            "edges": [
+                { "id": "e", "fromNode": "a", "toNode": "b" }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let (state, skipped) = to_board_state(&canvas, Path::new("."));
+    assert_eq!(state.items.len(), 1);
+    assert_eq!(skipped, 2);
+}
+
+#[test]
+fn test_edges_without_sides_use_facing_sides() {
+    let canvas: JsonCanvas = serde_json::from_str(
+        r#"{
+            "nodes": [
+                { "id": "a", "type": "text", "text": "A", "x": 0, "y": 0, "width": 100, "height": 50 },
+                { "id": "b", "type": "text", "text": "B", "x": 0, "y": 300, "width": 100, "height": 50 }
+            ],
+            "edges": [
+                { "id": "e", "fromNode": "b", "toNode": "a" }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let (state, _) = to_board_state(&canvas, Path::new("."));
+    let arrow = &state.items[0];
+    // From b's top (50, 300) up to a's bottom (50, 50)
+    assert_eq!(arrow.position, (50.0, 50.0));
+    assert!(matches!(
+        arrow.content,
+        ItemContent::Arrow { end_offset: (0.0, -250.0), .. }
+    ));
+}
+
+fn item(id: u64, position: (f32, f32), size: (f32, f32), content: ItemContent) -> CanvasItem {
+    CanvasItem {
+        id,
+        position,
+        size,
+        content,
+    }
+}
+
+#[test]
+fn test_export_youtube_strokes_and_loose_arrows() {
+    let items = vec![
+        item(1, (0.0, 0.0), (560.0, 315.0), ItemContent::YouTube("abc123".into())),
+        item(
+            2,
+            (0.0, 400.0),
+            (50.0, 50.0),
+            ItemContent::Stroke {
+                points: vec![(0.0, 0.0), (1.0, 1.0)],
+                color: String::new(),
+                width: 3.0,
+                kind: StrokeKind::Pen,
+            },
+        ),
+        item(
+            3,
+            (1000.0, 1000.0),
+            (100.0, 20.0),
+            ItemContent::Arrow {
+                end_offset: (100.0, 0.0),
+                color: String::new(),
+                thickness: 2.0,
+                head_style: ArrowHead::Arrow,
+                label: None,
+            },
+        ),
+    ];
+    let (canvas, skipped) = from_items(&items, Path::new("/boards"));
+
+    assert_eq!(skipped, 2);
+    assert_eq!(canvas.nodes.len(), 1);
+    assert_eq!(canvas.nodes[0].id, "0000000000000001");
+    assert_eq!(
+        canvas.nodes[0].kind,
+        NodeKind::Link {
+            url: "https://www.youtube.com/watch?v=abc123".to_string()
+        }
+    );
+    assert!(canvas.edges.is_empty());
+}
+
+#[test]
+fn test_export_writes_relative_and_absolute_paths() {
+    let dir = tempdir().unwrap();
+    let inside = dir.path().join("media").join("cat.png");
+    let outside = PathBuf::from("/elsewhere/dog.png");
+    let items = vec![
+        item(1, (0.0, 0.0), (100.0, 100.0), ItemContent::Image(inside, ImageEdit::default())),
+        item(2, (200.0, 0.0), (100.0, 100.0), ItemContent::Image(outside, ImageEdit::default())),
+    ];
+
+    let path = dir.path().join("board.canvas");
+    assert_eq!(json_canvas::export_file(&items, &path).unwrap(), 0);
+    let canvas = json_canvas::read(&path).unwrap();
+    let files: Vec<&str> = canvas
+        .nodes
+        .iter()
+        .filter_map(|n| match &n.kind {
+            NodeKind::File { file, .. } => Some(file.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(files, vec!["media/cat.png", "/elsewhere/dog.png"]);
+}
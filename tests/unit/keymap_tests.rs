@@ -0,0 +1,101 @@
+//! Unit tests for keymap file parsing and validation.
+
+use humanboard::keymap::{KeymapEntry, KeymapIssue, check_keymap, parse_keymap, qualify_action};
+
+fn entry(keystrokes: &str, action: Option<&str>, context: Option<&str>) -> KeymapEntry {
+    KeymapEntry {
+        keystrokes: keystrokes.to_string(),
+        action: action.map(qualify_action),
+        context: context.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_parse_sections_in_order() {
+    let json = r#"[
+        { "bindings": { "ctrl-p": "ToggleCommandPalette" } },
+        { "context": "Canvas", "bindings": { "j": "NudgeDown", "up": null } }
+    ]"#;
+
+    let entries = parse_keymap(json).unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            entry("ctrl-p", Some("ToggleCommandPalette"), None),
+            entry("j", Some("NudgeDown"), Some("Canvas")),
+            entry("up", None, Some("Canvas")),
+        ]
+    );
+}
+
+#[test]
+fn test_empty_file_has_no_bindings() {
+    assert!(parse_keymap("").unwrap().is_empty());
+    assert!(parse_keymap("  \n").unwrap().is_empty());
+}
+
+#[test]
+fn test_invalid_json_is_a_parse_issue() {
+    assert!(matches!(
+        parse_keymap(r#"{ "bindings": "#),
+        Err(KeymapIssue::Parse(_))
+    ));
+}
+
+#[test]
+fn test_qualify_action() {
+    assert_eq!(qualify_action("ZoomIn"), "humanboard::ZoomIn");
+    assert_eq!(qualify_action("input::Copy"), "input::Copy");
+}
+
+#[test]
+fn test_unknown_context_reported_once() {
+    let entries = vec![
+        entry("j", Some("NudgeDown"), Some("Canvass")),
+        entry("k", Some("NudgeUp"), Some("Canvass")),
+        entry("f5", Some("StartPresentation"), Some("Canvas")),
+    ];
+    assert_eq!(
+        check_keymap(&entries),
+        vec![KeymapIssue::UnknownContext("Canvass".to_string())]
+    );
+}
+
+#[test]
+fn test_conflicts_only_within_same_context() {
+    let entries = vec![
+        entry("j", Some("NudgeDown"), Some("Canvas")),
+        entry("j", Some("PresentationNext"), Some("Presentation")),
+        entry("j", Some("NudgeDown"), Some("Canvas")),
+        entry("j", Some("NudgeUp"), Some("Canvas")),
+        entry("j", None, Some("Canvas")),
+    ];
+
+    let issues = check_keymap(&entries);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(
+        issues[0].to_string(),
+        "j is bound to both NudgeDown and NudgeUp in Canvas"
+    );
+}
+
+#[test]
+fn test_repeated_keystrokes_in_a_section_are_kept_in_order() {
+    let json = r#"[
+        { "context": "Canvas", "bindings": { "k": "NudgeUp", "j": "NudgeDown", "k": "ZoomIn" } }
+    ]"#;
+
+    let entries = parse_keymap(json).unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            entry("k", Some("NudgeUp"), Some("Canvas")),
+            entry("j", Some("NudgeDown"), Some("Canvas")),
+            entry("k", Some("ZoomIn"), Some("Canvas")),
+        ]
+    );
+    assert_eq!(
+        check_keymap(&entries)[0].to_string(),
+        "k is bound to both NudgeUp and ZoomIn in Canvas"
+    );
+}
@@ -0,0 +1,68 @@
+//! Unit tests for rich text box parsing.
+
+use humanboard::markdown_card::{RichLineKind, parse_rich_text};
+
+#[test]
+fn test_plain_lines_stay_separate() {
+    let lines = parse_rich_text("first\nsecond");
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].segments[0].text, "first");
+    assert_eq!(lines[1].segments[0].text, "second");
+    assert!(lines.iter().all(|l| l.kind == RichLineKind::Paragraph));
+}
+
+#[test]
+fn test_inline_emphasis() {
+    let lines = parse_rich_text("a **bold** _it_ ~~gone~~ `code`");
+    let segments = &lines[0].segments;
+
+    let bold = segments.iter().find(|s| s.text == "bold").unwrap();
+    assert!(bold.bold && !bold.italic);
+    let italic = segments.iter().find(|s| s.text == "it").unwrap();
+    assert!(italic.italic && !italic.bold);
+    let strike = segments.iter().find(|s| s.text == "gone").unwrap();
+    assert!(strike.strikethrough);
+    let code = segments.iter().find(|s| s.text == "code").unwrap();
+    assert!(code.code);
+}
+
+#[test]
+fn test_links_keep_destination() {
+    let lines = parse_rich_text("see [docs](https://example.com)");
+    let link = lines[0].segments.iter().find(|s| s.text == "docs").unwrap();
+    assert_eq!(link.link.as_deref(), Some("https://example.com"));
+    assert!(lines[0].segments[0].link.is_none());
+}
+
+#[test]
+fn test_headings() {
+    let lines = parse_rich_text("# Title\nbody");
+    assert_eq!(lines[0].kind, RichLineKind::Heading(1));
+    assert_eq!(lines[1].kind, RichLineKind::Paragraph);
+}
+
+#[test]
+fn test_lists_and_checkboxes() {
+    let lines =
+        parse_rich_text("- one\n  - nested\n\n1. first\n2. second\n\n- [x] done\n- [ ] todo");
+    let kinds: Vec<_> = lines.iter().map(|l| (l.kind, l.depth)).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (RichLineKind::Bullet, 0),
+            (RichLineKind::Bullet, 1),
+            (RichLineKind::Numbered(1), 0),
+            (RichLineKind::Numbered(2), 0),
+            (RichLineKind::Checkbox(true), 0),
+            (RichLineKind::Checkbox(false), 0),
+        ]
+    );
+}
+
+#[test]
+fn test_empty_list_item_is_kept() {
+    // A bullet the user has just started typing still shows its marker
+    let lines = parse_rich_text("-");
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].kind, RichLineKind::Bullet);
+}
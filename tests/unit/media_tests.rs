@@ -0,0 +1,144 @@
+//! Unit tests for media posters and the player budget.
+
+use humanboard::media::{
+    LiveMedia, MediaPoster, MediaSource, cached_poster, format_duration, load_poster,
+    poster_frame_path, save_poster_frame,
+};
+use humanboard::settings::{AppSettings, Setting, SettingsContent};
+use humanboard::types::ItemContent;
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+use tempfile::tempdir;
+
+/// Write a mono 16-bit WAV file of silence
+fn write_wav(path: &Path, sample_rate: u32, seconds: u32) {
+    let data_len = sample_rate * 2 * seconds;
+    let mut wav = Vec::new();
+    wav.extend(b"RIFF");
+    wav.extend((36 + data_len).to_le_bytes());
+    wav.extend(b"WAVEfmt ");
+    wav.extend(16u32.to_le_bytes());
+    wav.extend(1u16.to_le_bytes()); // PCM
+    wav.extend(1u16.to_le_bytes()); // mono
+    wav.extend(sample_rate.to_le_bytes());
+    wav.extend((sample_rate * 2).to_le_bytes());
+    wav.extend(2u16.to_le_bytes());
+    wav.extend(16u16.to_le_bytes());
+    wav.extend(b"data");
+    wav.extend(data_len.to_le_bytes());
+    wav.resize(wav.len() + data_len as usize, 0);
+    std::fs::write(path, wav).unwrap();
+}
+
+fn ids(items: &[u64]) -> HashSet<u64> {
+    items.iter().copied().collect()
+}
+
+#[test]
+fn test_media_sources() {
+    let audio = ItemContent::Audio("/music/track.mp3".into(), Vec::new());
+    let source = MediaSource::of(&audio).unwrap();
+    assert_eq!(source, MediaSource::Audio("/music/track.mp3".into()));
+    assert_eq!(source.fallback_title(), "track.mp3");
+
+    let youtube = MediaSource::of(&ItemContent::YouTube("abc123".to_string())).unwrap();
+    assert_eq!(youtube.fallback_title(), "YouTube: abc123");
+    assert!(MediaSource::of(&ItemContent::Text("hi".to_string())).is_none());
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_secs(0)), "0:00");
+    assert_eq!(format_duration(Duration::from_secs(187)), "3:07");
+    assert_eq!(format_duration(Duration::from_millis(59_900)), "0:59");
+    assert_eq!(format_duration(Duration::from_secs(3723)), "1:02:03");
+}
+
+#[test]
+fn test_activation_keeps_most_recent_last() {
+    let mut live = LiveMedia::default();
+    assert!(live.is_empty());
+    live.activate(1);
+    live.activate(2);
+    live.activate(1);
+    assert_eq!(live.len(), 2);
+    assert!(live.is_active(1) && live.is_active(2));
+
+    // 2 is now the least recently activated
+    assert_eq!(live.enforce(&ids(&[1, 2]), &ids(&[1, 2]), 1), vec![2]);
+    assert!(live.deactivate(1));
+    assert!(!live.deactivate(1));
+    assert!(live.is_empty());
+}
+
+#[test]
+fn test_budget_unloads_off_screen_players_first() {
+    let mut live = LiveMedia::default();
+    for id in 1..=5 {
+        live.activate(id);
+    }
+    let existing = ids(&[1, 2, 3, 4, 5]);
+
+    // 4 is off-screen, so it goes before the older visible ones
+    let evicted = live.enforce(&existing, &ids(&[1, 2, 3, 5]), 4);
+    assert_eq!(evicted, vec![4]);
+
+    // With nothing off-screen, the least recently activated go
+    let evicted = live.enforce(&existing, &ids(&[1, 2, 3, 5]), 2);
+    assert_eq!(evicted, vec![1, 2]);
+    assert!(live.is_active(3) && live.is_active(5));
+
+    // Deleted items are forgotten, and one player is always allowed
+    assert!(live.enforce(&ids(&[5]), &HashSet::new(), 0).is_empty());
+    assert_eq!(live.len(), 1);
+    assert!(live.is_active(5));
+}
+
+#[test]
+fn test_audio_poster_reads_duration() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("silence.wav");
+    write_wav(&path, 8000, 2);
+
+    let source = MediaSource::Audio(path.clone());
+    assert!(cached_poster(&source).is_none());
+    let poster = load_poster(&source);
+    assert_eq!(poster.duration, Some(Duration::from_secs(2)));
+    assert_eq!(poster.title, None);
+    assert_eq!(cached_poster(&source), Some(poster));
+
+    // Unreadable files still get a poster, so they aren't retried every frame
+    let missing = MediaSource::Audio(dir.path().join("missing.mp3"));
+    assert_eq!(load_poster(&missing), MediaPoster::default());
+    assert!(cached_poster(&missing).is_some());
+}
+
+#[test]
+fn test_poster_frames_replace_the_cached_video_poster() {
+    let dir = tempdir().unwrap();
+    let video = dir.path().join("clip.mp4");
+    std::fs::write(&video, b"not really a video").unwrap();
+    let source = MediaSource::Video(video.clone());
+    assert_eq!(load_poster(&source).image, None);
+
+    assert!(!save_poster_frame(&video, b"<script>"));
+    let jpeg = [0xff, 0xd8, 0xff, 0xe0, 0, 0x10];
+    assert!(save_poster_frame(&video, &jpeg));
+    assert!(cached_poster(&source).is_none());
+
+    let frame = poster_frame_path(&video);
+    assert_eq!(std::fs::read(&frame).unwrap(), jpeg);
+    assert_eq!(load_poster(&source).image, Some(frame.clone()));
+    std::fs::remove_file(frame).unwrap();
+}
+
+#[test]
+fn test_max_media_players_setting() {
+    let content: SettingsContent = serde_json::from_str(r#"{ "max_media_players": 2 }"#).unwrap();
+    assert_eq!(AppSettings::from_content(&content).max_media_players, 2);
+    assert_eq!(
+        AppSettings::from_content(&SettingsContent::default()).max_media_players,
+        AppSettings::default().max_media_players
+    );
+}
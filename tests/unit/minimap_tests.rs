@@ -0,0 +1,55 @@
+//! Unit tests for minimap layout and navigation maths.
+
+use humanboard::minimap::{MinimapLayout, centre_on, visible_region};
+use humanboard::types::{CanvasItem, ItemContent};
+
+fn text_item(id: u64, position: (f32, f32), size: (f32, f32)) -> CanvasItem {
+    CanvasItem {
+        id,
+        position,
+        size,
+        content: ItemContent::Text(String::new()),
+    }
+}
+
+fn approx(a: (f32, f32), b: (f32, f32)) -> bool {
+    (a.0 - b.0).abs() < 0.01 && (a.1 - b.1).abs() < 0.01
+}
+
+#[test]
+fn test_empty_board_has_no_layout() {
+    assert!(MinimapLayout::fit(&[], (200.0, 140.0), 8.0).is_none());
+}
+
+#[test]
+fn test_layout_fits_and_centres_board() {
+    // A 1000x200 board is limited by width: (200 - 2*10) / 1000 = 0.18
+    let items = vec![
+        text_item(1, (0.0, 0.0), (100.0, 100.0)),
+        text_item(2, (900.0, 100.0), (100.0, 100.0)),
+    ];
+    let layout = MinimapLayout::fit(&items, (200.0, 140.0), 10.0).unwrap();
+    assert!((layout.scale - 0.18).abs() < 0.0001);
+
+    assert!(approx(layout.to_minimap((0.0, 0.0)), (10.0, 52.0)));
+    assert!(approx(layout.to_minimap((1000.0, 200.0)), (190.0, 88.0)));
+}
+
+#[test]
+fn test_layout_round_trips_points() {
+    let items = vec![text_item(1, (-300.0, 50.0), (400.0, 600.0))];
+    let layout = MinimapLayout::fit(&items, (200.0, 140.0), 8.0).unwrap();
+    let p = (-120.0, 333.0);
+    assert!(approx(layout.to_canvas(layout.to_minimap(p)), p));
+}
+
+#[test]
+fn test_visible_region_and_centre_on_agree() {
+    let area = (800.0, 600.0);
+    let zoom = 2.0;
+    let offset = centre_on((500.0, 250.0), zoom, area);
+
+    let (x, y, w, h) = visible_region(offset, zoom, area);
+    assert!(approx((w, h), (400.0, 300.0)));
+    assert!(approx((x + w / 2.0, y + h / 2.0), (500.0, 250.0)));
+}
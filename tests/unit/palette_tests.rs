@@ -0,0 +1,119 @@
+//! Unit tests for colour palette extraction.
+
+use humanboard::image_edit::{CropRect, ImageEdit};
+use humanboard::palette::{
+    MAX_SWATCH_SIZE, MIN_SWATCH_SIZE, SWATCH_GAP, dominant_colors, extract, swatches, to_hex,
+};
+use humanboard::types::{CanvasItem, ItemContent, ShapeType};
+use image::{DynamicImage, Rgba, RgbaImage};
+use tempfile::tempdir;
+
+const RED: Rgba<u8> = Rgba([200, 30, 30, 255]);
+const TEAL: Rgba<u8> = Rgba([20, 160, 150, 255]);
+const CREAM: Rgba<u8> = Rgba([240, 230, 200, 255]);
+
+/// Vertical bands: half red, then 30% teal, then 20% cream
+fn bands() -> DynamicImage {
+    DynamicImage::ImageRgba8(RgbaImage::from_fn(100, 10, |x, _| match x {
+        0..50 => RED,
+        50..80 => TEAL,
+        _ => CREAM,
+    }))
+}
+
+fn rgb(color: Rgba<u8>) -> [u8; 3] {
+    [color[0], color[1], color[2]]
+}
+
+#[test]
+fn test_dominant_colors_are_ordered_by_coverage() {
+    assert_eq!(
+        dominant_colors(&bands(), 6),
+        vec![rgb(RED), rgb(TEAL), rgb(CREAM)]
+    );
+    assert_eq!(dominant_colors(&bands(), 2), vec![rgb(RED), rgb(TEAL)]);
+}
+
+#[test]
+fn test_near_duplicates_and_transparency_are_skipped() {
+    // Subtle noise on one colour still makes one swatch
+    let noisy = DynamicImage::ImageRgba8(RgbaImage::from_fn(20, 20, |x, y| {
+        let n = ((x + y) % 4) as u8;
+        if x < 10 {
+            Rgba([100 + n, 100, 100 - n, 255])
+        } else {
+            Rgba([255, 255, 255, 0])
+        }
+    }));
+    let colors = dominant_colors(&noisy, 6);
+    assert_eq!(colors.len(), 1);
+    assert!(colors[0].iter().all(|&c| c.abs_diff(100) <= 2));
+
+    let clear = DynamicImage::ImageRgba8(RgbaImage::new(4, 4));
+    assert!(dominant_colors(&clear, 6).is_empty());
+}
+
+#[test]
+fn test_extract_respects_the_crop() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("bands.png");
+    bands().save(&path).unwrap();
+
+    let edit = ImageEdit {
+        crop: Some(CropRect {
+            x: 0.8,
+            y: 0.0,
+            width: 0.2,
+            height: 1.0,
+        }),
+        ..Default::default()
+    };
+    assert_eq!(extract(&path, &edit, 6).unwrap(), vec![rgb(CREAM)]);
+    assert!(extract(&dir.path().join("missing.png"), &edit, 6).is_err());
+}
+
+#[test]
+fn test_swatches_sit_in_a_row_under_the_image() {
+    let image = CanvasItem {
+        id: 1,
+        position: (100.0, 50.0),
+        size: (400.0, 300.0),
+        content: ItemContent::Image("/photos/1.jpg".into(), ImageEdit::default()),
+    };
+    let colors = [rgb(RED), rgb(TEAL), rgb(CREAM)];
+    let row = swatches(&image, &colors);
+
+    assert_eq!(row.len(), 3);
+    for (i, (content, position, size)) in row.iter().enumerate() {
+        let size = size.unwrap();
+        assert_eq!(size, (MAX_SWATCH_SIZE, MAX_SWATCH_SIZE));
+        assert_eq!(
+            *position,
+            (100.0 + i as f32 * (size.0 + SWATCH_GAP), 350.0 + SWATCH_GAP)
+        );
+        let hex = to_hex(colors[i]);
+        match content {
+            ItemContent::Shape {
+                shape_type: ShapeType::Rectangle,
+                fill_color,
+                label,
+                ..
+            } => {
+                assert_eq!(fill_color.as_deref(), Some(hex.as_str()));
+                assert_eq!(label.as_deref(), Some(hex.as_str()));
+            }
+            _ => panic!("Expected a swatch shape"),
+        }
+    }
+    assert_eq!(to_hex(rgb(RED)), "#c81e1e");
+
+    // Narrow images get smaller swatches, down to a readable minimum
+    let narrow = CanvasItem {
+        size: (100.0, 100.0),
+        ..image
+    };
+    assert_eq!(
+        swatches(&narrow, &colors)[0].2,
+        Some((MIN_SWATCH_SIZE, MIN_SWATCH_SIZE))
+    );
+}
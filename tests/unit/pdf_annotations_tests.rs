@@ -0,0 +1,219 @@
+//! Unit tests for PDF annotations and quoting highlights.
+
+use humanboard::pdf_annotations::{
+    AnnotationKind, PageRect, PdfAnnotation, QUOTE_GAP, annotations_for, fit_rect,
+    next_annotation_id, page_label, quote_anchor, quote_content, quote_text, select_text, summary,
+};
+use humanboard::types::{CanvasItem, ItemContent, TextFormat};
+use std::path::{Path, PathBuf};
+
+fn rect(x: f32, y: f32, width: f32, height: f32) -> PageRect {
+    PageRect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+/// Characters laid out left to right from `x` on a line at `y`
+fn line(text: &str, x: f32, y: f32) -> Vec<(char, PageRect)> {
+    text.chars()
+        .enumerate()
+        .map(|(i, ch)| (ch, rect(x + i as f32 * 0.01, y, 0.01, 0.02)))
+        .collect()
+}
+
+fn annotation(id: u64, path: &str, page: usize, y: f32) -> PdfAnnotation {
+    PdfAnnotation {
+        id,
+        path: PathBuf::from(path),
+        page,
+        kind: AnnotationKind::Box {
+            rect: rect(0.1, y, 0.2, 0.1),
+        },
+    }
+}
+
+#[test]
+fn test_page_rect_from_corners() {
+    // Corners can be dragged in any direction
+    assert_eq!(
+        PageRect::from_corners((0.75, 0.5), (0.25, 0.125)),
+        rect(0.25, 0.125, 0.5, 0.375)
+    );
+
+    // Dragging off the page stops at its edges
+    let clamped = PageRect::from_corners((-0.5, 0.5), (0.5, 1.5));
+    assert_eq!(clamped, rect(0.0, 0.5, 0.5, 0.5));
+
+    assert!(clamped.contains((0.25, 0.75)));
+    assert!(!clamped.contains((0.75, 0.75)));
+    assert_eq!(clamped.center(), (0.25, 0.75));
+}
+
+#[test]
+fn test_select_text_groups_lines() {
+    let mut chars = line("Hello  world", 0.1, 0.1);
+    chars.extend(line("second line", 0.1, 0.15));
+    chars.extend(line("not selected", 0.1, 0.5));
+
+    let (text, rects) = select_text(&chars, rect(0.0, 0.0, 1.0, 0.3)).unwrap();
+    assert_eq!(text, "Hello world second line");
+    assert_eq!(rects.len(), 2);
+    assert!((rects[0].x - 0.1).abs() < 1e-5);
+    assert!((rects[0].width - 0.12).abs() < 1e-5);
+    assert!((rects[1].y - 0.15).abs() < 1e-5);
+
+    // Only characters with their centres in the area are taken
+    let (text, _) = select_text(&chars, rect(0.1, 0.1, 0.05, 0.03)).unwrap();
+    assert_eq!(text, "Hello");
+
+    assert_eq!(select_text(&chars, rect(0.5, 0.7, 0.2, 0.2)), None);
+    assert_eq!(
+        select_text(&line("   ", 0.1, 0.1), rect(0.0, 0.0, 1.0, 1.0)),
+        None
+    );
+}
+
+#[test]
+fn test_annotations_for_sorts_by_page_then_position() {
+    let annotations = vec![
+        annotation(0, "/decks/talk.pdf", 2, 0.1),
+        annotation(1, "/decks/other.pdf", 1, 0.1),
+        annotation(2, "/decks/talk.pdf", 1, 0.8),
+        annotation(3, "/decks/talk.pdf", 1, 0.2),
+    ];
+
+    let ids: Vec<u64> = annotations_for(&annotations, Path::new("/decks/talk.pdf"))
+        .iter()
+        .map(|a| a.id)
+        .collect();
+    assert_eq!(ids, vec![3, 2, 0]);
+
+    assert_eq!(next_annotation_id(&annotations), 4);
+    assert_eq!(next_annotation_id(&[]), 0);
+}
+
+#[test]
+fn test_summary_lists_annotated_pages() {
+    let annotations = vec![
+        annotation(0, "/decks/talk.pdf", 4, 0.1),
+        annotation(1, "/decks/other.pdf", 2, 0.1),
+        annotation(2, "/decks/talk.pdf", 1, 0.8),
+        annotation(3, "/decks/talk.pdf", 1, 0.2),
+    ];
+
+    assert_eq!(
+        summary(&annotations, Path::new("/decks/talk.pdf")),
+        "3 annotations on p.1, 4"
+    );
+    assert_eq!(
+        summary(&annotations, Path::new("/decks/other.pdf")),
+        "1 annotation on p.2"
+    );
+    assert_eq!(
+        summary(&annotations, Path::new("/decks/new.pdf")),
+        "No annotations"
+    );
+}
+
+#[test]
+fn test_fit_rect() {
+    // A portrait page in a square area is centred across
+    assert_eq!(
+        fit_rect((10.0, 20.0, 400.0, 400.0), (100.0, 200.0)),
+        (110.0, 20.0, 200.0, 400.0)
+    );
+    // A page with no size fills the area
+    assert_eq!(
+        fit_rect((0.0, 0.0, 300.0, 200.0), (0.0, 0.0)),
+        (0.0, 0.0, 300.0, 200.0)
+    );
+}
+
+#[test]
+fn test_quote_links_to_page() {
+    let path = Path::new("/decks/talk.pdf");
+    assert_eq!(page_label(path, 4), "talk.pdf p.4");
+
+    let text = quote_text(path, 4, "Growth was *flat*");
+    assert_eq!(
+        text,
+        "*“Growth was \\*flat\\*”*\n\n[talk.pdf p.4](</decks/talk.pdf#page=4>)"
+    );
+
+    match quote_content(path, 4, "Growth was flat") {
+        ItemContent::TextBox { text, format, .. } => {
+            assert_eq!(format, TextFormat::Markdown);
+            assert!(text.ends_with("(</decks/talk.pdf#page=4>)"));
+        }
+        other => panic!("expected a text box, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_quote_anchor() {
+    let path = PathBuf::from("/decks/talk.pdf");
+    let pdf = CanvasItem {
+        id: 1,
+        position: (100.0, 50.0),
+        size: (180.0, 240.0),
+        content: ItemContent::Pdf {
+            path: path.clone(),
+            thumbnail: None,
+        },
+    };
+    let page = CanvasItem {
+        id: 2,
+        position: (400.0, 50.0),
+        size: (240.0, 320.0),
+        content: ItemContent::PdfPage {
+            path: path.clone(),
+            page: 4,
+            image: None,
+        },
+    };
+
+    // Beside the page's item when it's on the canvas
+    let items = vec![pdf.clone(), page];
+    assert_eq!(
+        quote_anchor(&items, &path, 4),
+        Some((640.0 + QUOTE_GAP, 50.0))
+    );
+
+    // Otherwise under the PDF
+    assert_eq!(
+        quote_anchor(&items, &path, 2),
+        Some((100.0, 290.0 + QUOTE_GAP))
+    );
+    assert_eq!(quote_anchor(&[], &path, 4), None);
+}
+
+#[test]
+fn test_annotations_round_trip() {
+    let annotations = vec![
+        PdfAnnotation {
+            id: 0,
+            path: PathBuf::from("/decks/talk.pdf"),
+            page: 3,
+            kind: AnnotationKind::Highlight {
+                rects: vec![rect(0.1, 0.2, 0.5, 0.02)],
+                text: "Growth was flat".to_string(),
+            },
+        },
+        PdfAnnotation {
+            id: 1,
+            path: PathBuf::from("/decks/talk.pdf"),
+            page: 3,
+            kind: AnnotationKind::Note {
+                point: (0.4, 0.6),
+                text: "Check this".to_string(),
+            },
+        },
+    ];
+
+    let json = serde_json::to_string(&annotations).unwrap();
+    let loaded: Vec<PdfAnnotation> = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, annotations);
+}
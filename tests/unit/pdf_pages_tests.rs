@@ -0,0 +1,162 @@
+//! Unit tests for placing PDF pages on the canvas.
+
+use humanboard::json_canvas::{self, NodeKind, from_items};
+use humanboard::pdf_pages::{
+    PAGE_COLUMNS, PAGE_GAP, PAGE_ITEM_SIZE, format_page_ranges, page_from_subpath, page_item_size,
+    page_items, page_subpath, parse_page_ranges,
+};
+use humanboard::pdf_thumbnail::RenderedPage;
+use humanboard::types::{CanvasItem, ItemContent};
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn rendered(page: usize, size: (f32, f32)) -> RenderedPage {
+    RenderedPage {
+        page,
+        image: PathBuf::from(format!("/tmp/page{}.png", page)),
+        size,
+    }
+}
+
+fn pdf_item() -> CanvasItem {
+    CanvasItem {
+        id: 1,
+        position: (100.0, 50.0),
+        size: (180.0, 240.0),
+        content: ItemContent::Pdf {
+            path: "/decks/talk.pdf".into(),
+            thumbnail: None,
+        },
+    }
+}
+
+#[test]
+fn test_parse_page_ranges() {
+    assert_eq!(parse_page_ranges("1-3, 8", 10), Ok(vec![1, 2, 3, 8]));
+    assert_eq!(parse_page_ranges(" 5 ", 10), Ok(vec![5]));
+    assert_eq!(parse_page_ranges("8-", 10), Ok(vec![8, 9, 10]));
+    assert_eq!(parse_page_ranges("all", 3), Ok(vec![1, 2, 3]));
+    assert_eq!(parse_page_ranges("ALL", 0), Ok(vec![]));
+
+    // Pages keep the order given, without repeats
+    assert_eq!(parse_page_ranges("4, 1-2, 2, 4", 10), Ok(vec![4, 1, 2]));
+    assert_eq!(parse_page_ranges("3,,", 10), Ok(vec![3]));
+
+    assert!(parse_page_ranges("", 10).is_err());
+    assert!(parse_page_ranges("0", 10).is_err());
+    assert!(parse_page_ranges("11", 10).is_err());
+    assert!(parse_page_ranges("5-2", 10).is_err());
+    assert!(parse_page_ranges("2-x", 10).is_err());
+    assert!(parse_page_ranges("intro", 10).is_err());
+}
+
+#[test]
+fn test_format_page_ranges() {
+    assert_eq!(format_page_ranges(&[1, 2, 3, 5]), "1-3, 5");
+    assert_eq!(format_page_ranges(&[8, 2, 1, 2]), "1-2, 8");
+    assert_eq!(format_page_ranges(&[4]), "4");
+    assert_eq!(format_page_ranges(&[]), "");
+
+    // Formatted ranges parse back to the same pages
+    let pages = vec![1, 2, 3, 7, 9, 10];
+    assert_eq!(parse_page_ranges(&format_page_ranges(&pages), 10), Ok(pages));
+}
+
+#[test]
+fn test_page_items_keep_the_page_shape() {
+    // A4 portrait and 16:9 slides, in points
+    let (w, h) = page_item_size((595.0, 842.0));
+    assert_eq!(h, PAGE_ITEM_SIZE);
+    assert!((w / h - 595.0 / 842.0).abs() < 1e-4);
+
+    let (w, h) = page_item_size((960.0, 540.0));
+    assert_eq!(w, PAGE_ITEM_SIZE);
+    assert_eq!(h, 180.0);
+
+    assert_eq!(page_item_size((0.0, 0.0)).1, PAGE_ITEM_SIZE);
+}
+
+#[test]
+fn test_page_items_are_laid_out_beside_the_pdf() {
+    let pdf = pdf_item();
+    let pages: Vec<RenderedPage> = (1..=PAGE_COLUMNS + 1)
+        .map(|page| rendered(page, (960.0, 540.0)))
+        .collect();
+    let placed = page_items(&pdf, Path::new("/decks/talk.pdf"), &pages);
+    assert_eq!(placed.len(), pages.len());
+
+    let left = 100.0 + 180.0 + PAGE_GAP;
+    let (content, position, size) = &placed[0];
+    assert_eq!(*position, (left, 50.0));
+    assert_eq!(*size, Some((PAGE_ITEM_SIZE, 180.0)));
+    let ItemContent::PdfPage { path, page, image } = content else {
+        panic!("Expected a PDF page");
+    };
+    assert_eq!(path, Path::new("/decks/talk.pdf"));
+    assert_eq!(*page, 1);
+    assert_eq!(image.as_deref(), Some(Path::new("/tmp/page1.png")));
+
+    assert_eq!(placed[1].1, (left + PAGE_ITEM_SIZE + PAGE_GAP, 50.0));
+    // The row wraps under the tallest page of the one before
+    assert_eq!(placed[PAGE_COLUMNS].1, (left, 50.0 + 180.0 + PAGE_GAP));
+}
+
+#[test]
+fn test_page_subpaths() {
+    assert_eq!(page_subpath(3), "#page=3");
+    assert_eq!(page_from_subpath("#page=3"), Some(3));
+    assert_eq!(page_from_subpath("#page=0"), None);
+    assert_eq!(page_from_subpath("#Heading"), None);
+}
+
+#[test]
+fn test_pdf_pages_save_with_their_page() {
+    let content = ItemContent::PdfPage {
+        path: "/decks/talk.pdf".into(),
+        page: 4,
+        image: None,
+    };
+    assert_eq!(content.display_name(), "talk.pdf p.4");
+    assert_eq!(content.type_name(), "pdf_page");
+
+    let json = serde_json::to_string(&content).unwrap();
+    let ItemContent::PdfPage { path, page, .. } = serde_json::from_str(&json).unwrap() else {
+        panic!("Expected a PDF page");
+    };
+    assert_eq!(path, Path::new("/decks/talk.pdf"));
+    assert_eq!(page, 4);
+}
+
+#[test]
+fn test_json_canvas_links_pdf_pages() {
+    let dir = tempdir().unwrap();
+    let pdf = dir.path().join("talk.pdf");
+    let items = vec![CanvasItem {
+        id: 1,
+        position: (0.0, 0.0),
+        size: (320.0, 180.0),
+        content: ItemContent::PdfPage {
+            path: pdf.clone(),
+            page: 2,
+            image: None,
+        },
+    }];
+
+    let (canvas, skipped) = from_items(&items, dir.path());
+    assert_eq!(skipped, 0);
+    assert_eq!(
+        canvas.nodes[0].kind,
+        NodeKind::File {
+            file: "talk.pdf".to_string(),
+            subpath: Some("#page=2".to_string()),
+        }
+    );
+
+    let path = dir.path().join("board.canvas");
+    json_canvas::export_file(&items, &path).unwrap();
+    let (state, _) = json_canvas::import_file(&path).unwrap();
+    assert!(matches!(
+        &state.items[0].content,
+        ItemContent::PdfPage { path, page: 2, .. } if *path == pdf
+    ));
+}
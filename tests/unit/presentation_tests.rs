@@ -0,0 +1,114 @@
+//! Unit tests for presentation stops and viewport fitting.
+
+use humanboard::constants::MAX_ZOOM;
+use humanboard::presentation::{bookmark_bounds, fit_viewport, items_bounds, presentation_stops};
+use humanboard::types::{CanvasItem, ItemContent, ShapeType, ViewportBookmark};
+
+const VIEWPORT: (f32, f32) = (1000.0, 500.0);
+
+fn bookmark(name: &str, canvas_offset: (f32, f32), zoom: f32) -> ViewportBookmark {
+    ViewportBookmark {
+        name: name.to_string(),
+        canvas_offset,
+        zoom,
+    }
+}
+
+fn shape(id: u64, shape_type: ShapeType, position: (f32, f32), label: Option<&str>) -> CanvasItem {
+    CanvasItem {
+        id,
+        position,
+        size: (200.0, 100.0),
+        content: ItemContent::Shape {
+            shape_type,
+            fill_color: None,
+            border_color: String::new(),
+            border_width: 2.0,
+            label: label.map(str::to_string),
+        },
+    }
+}
+
+#[test]
+fn test_frames_are_presented_in_creation_order() {
+    let items = vec![
+        shape(7, ShapeType::Frame, (500.0, 0.0), Some("Outro")),
+        shape(2, ShapeType::Rectangle, (0.0, 0.0), None),
+        shape(3, ShapeType::Frame, (0.0, 0.0), None),
+        shape(5, ShapeType::Frame, (250.0, 0.0), Some("Middle")),
+    ];
+
+    let stops = presentation_stops(&items, &[], VIEWPORT);
+    let titles: Vec<&str> = stops.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(titles, ["Frame 1", "Middle", "Outro"]);
+    assert_eq!(stops[1].bounds, (250.0, 0.0, 200.0, 100.0));
+}
+
+#[test]
+fn test_board_without_frames_presents_overview() {
+    let items = vec![
+        shape(1, ShapeType::Rectangle, (-100.0, 50.0), None),
+        shape(2, ShapeType::Ellipse, (300.0, 400.0), None),
+    ];
+
+    let stops = presentation_stops(&items, &[], VIEWPORT);
+    assert_eq!(stops.len(), 1);
+    assert_eq!(stops[0].title, "Overview");
+    assert_eq!(stops[0].bounds, (-100.0, 50.0, 600.0, 450.0));
+}
+
+#[test]
+fn test_bookmarks_are_presented_after_frames() {
+    let items = vec![
+        shape(1, ShapeType::Rectangle, (0.0, 0.0), None),
+        shape(2, ShapeType::Frame, (0.0, 0.0), Some("Intro")),
+    ];
+    let bookmarks = vec![
+        bookmark("Detail", (-200.0, -100.0), 2.0),
+        bookmark("Wide", (0.0, 0.0), 0.5),
+    ];
+
+    let stops = presentation_stops(&items, &bookmarks, VIEWPORT);
+    let titles: Vec<&str> = stops.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(titles, ["Intro", "Detail", "Wide"]);
+    // A bookmark shows the region that was in view when it was saved
+    assert_eq!(stops[1].bounds, (100.0, 50.0, 500.0, 250.0));
+    assert_eq!(stops[2].bounds, (0.0, 0.0, 2000.0, 1000.0));
+
+    // Bookmarks alone replace the overview
+    let stops = presentation_stops(&items[..1], &bookmarks, VIEWPORT);
+    assert_eq!(stops.len(), 2);
+    assert_eq!(stops[0].title, "Detail");
+}
+
+#[test]
+fn test_bookmark_fits_back_to_its_viewport() {
+    let saved = bookmark("Detail", (-200.0, -100.0), 2.0);
+    let (offset, zoom) = fit_viewport(bookmark_bounds(&saved, VIEWPORT), VIEWPORT, 0.0);
+    assert!((zoom - 2.0).abs() < 0.001);
+    assert!((offset.0 + 200.0).abs() < 0.01 && (offset.1 + 100.0).abs() < 0.01);
+}
+
+#[test]
+fn test_empty_board_has_no_stops() {
+    assert!(presentation_stops(&[], &[], VIEWPORT).is_empty());
+    assert!(items_bounds(&[]).is_none());
+}
+
+#[test]
+fn test_fit_viewport_centres_region() {
+    let ((x, y), zoom) = fit_viewport((100.0, 100.0, 400.0, 200.0), (1000.0, 1000.0), 0.0);
+    assert!((zoom - 2.5).abs() < 0.001);
+    // The region's centre (300, 200) lands on the viewport centre
+    assert!((x + 300.0 * zoom - 500.0).abs() < 0.01);
+    assert!((y + 200.0 * zoom - 500.0).abs() < 0.01);
+}
+
+#[test]
+fn test_fit_viewport_respects_margin_and_zoom_limits() {
+    let (_, zoom) = fit_viewport((0.0, 0.0, 1000.0, 100.0), (1000.0, 1000.0), 0.1);
+    assert!((zoom - 0.8).abs() < 0.001);
+
+    let (_, zoom) = fit_viewport((0.0, 0.0, 1.0, 1.0), (1000.0, 1000.0), 0.0);
+    assert_eq!(zoom, MAX_ZOOM);
+}
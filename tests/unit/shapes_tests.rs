@@ -0,0 +1,80 @@
+//! Unit tests for shape geometry.
+
+use humanboard::shapes::{hit_outline, outline};
+use humanboard::types::{ItemContent, ShapeType};
+
+#[test]
+fn test_box_shapes_have_no_path_outline() {
+    for shape in [
+        ShapeType::Rectangle,
+        ShapeType::RoundedRect,
+        ShapeType::Ellipse,
+        ShapeType::StickyNote,
+        ShapeType::Frame,
+    ] {
+        assert!(outline(shape, 100.0, 100.0).is_none(), "{:?}", shape);
+    }
+}
+
+#[test]
+fn test_path_outlines_stay_in_bounds() {
+    for shape in ShapeType::ALL {
+        if let Some(outline) = outline(shape, 120.0, 80.0) {
+            let all_points = outline
+                .points
+                .iter()
+                .chain(outline.details.iter().flatten());
+            for &(x, y) in all_points {
+                assert!((-0.01..=120.01).contains(&x), "{:?} x={}", shape, x);
+                assert!((-0.01..=80.01).contains(&y), "{:?} y={}", shape, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_star_has_ten_points() {
+    let star = outline(ShapeType::Star, 100.0, 100.0).unwrap();
+    assert_eq!(star.points.len(), 10);
+    assert!(star.closed);
+}
+
+#[test]
+fn test_diamond_hit_only_on_outline() {
+    let size = (100.0, 100.0);
+    // Top vertex and the middle of an edge
+    assert!(hit_outline(ShapeType::Diamond, size, (50.0, 1.0), 4.0));
+    assert!(hit_outline(ShapeType::Diamond, size, (75.0, 25.0), 4.0));
+    // Centre and bounding-box corner are empty
+    assert!(!hit_outline(ShapeType::Diamond, size, (50.0, 50.0), 4.0));
+    assert!(!hit_outline(ShapeType::Diamond, size, (2.0, 2.0), 4.0));
+}
+
+#[test]
+fn test_line_hit_near_centre_line() {
+    let size = (200.0, 20.0);
+    assert!(hit_outline(ShapeType::Line, size, (100.0, 12.0), 4.0));
+    assert!(!hit_outline(ShapeType::Line, size, (100.0, 1.0), 4.0));
+}
+
+#[test]
+fn test_rectangle_hit_on_border() {
+    let size = (100.0, 60.0);
+    assert!(hit_outline(ShapeType::Rectangle, size, (2.0, 30.0), 8.0));
+    assert!(!hit_outline(ShapeType::Rectangle, size, (50.0, 30.0), 8.0));
+}
+
+#[test]
+fn test_shape_default_sizes() {
+    let content = ItemContent::Shape {
+        shape_type: ShapeType::StickyNote,
+        fill_color: None,
+        border_color: String::new(),
+        border_width: 2.0,
+        label: None,
+    };
+    assert_eq!(content.default_size(), (200.0, 200.0));
+    assert_eq!(content.display_name(), "Sticky Note");
+    assert_eq!(ShapeType::StickyNote.default_fill(), Some("#fde68a"));
+    assert_eq!(ShapeType::Rectangle.default_size(), (150.0, 100.0));
+}
@@ -0,0 +1,110 @@
+//! Unit tests for freehand stroke geometry.
+
+use humanboard::strokes::{erase, finish, fit, hit_stroke, simplify, smooth, to_canvas};
+use humanboard::types::{ItemContent, StrokeKind};
+
+#[test]
+fn test_smooth_keeps_end_points() {
+    let points = vec![(0.0, 0.0), (10.0, 10.0), (20.0, 0.0)];
+    let smoothed = smooth(&points);
+    assert_eq!(smoothed.first(), Some(&(0.0, 0.0)));
+    assert_eq!(smoothed.last(), Some(&(20.0, 0.0)));
+    assert!(smoothed.len() > points.len());
+}
+
+#[test]
+fn test_simplify_drops_collinear_points() {
+    let points: Vec<(f32, f32)> = (0..=10).map(|i| (i as f32 * 10.0, 0.0)).collect();
+    assert_eq!(simplify(&points, 0.5), vec![(0.0, 0.0), (100.0, 0.0)]);
+}
+
+#[test]
+fn test_simplify_keeps_corners() {
+    let points = vec![(0.0, 0.0), (50.0, 0.0), (50.0, 50.0)];
+    assert_eq!(simplify(&points, 0.5), points);
+}
+
+#[test]
+fn test_finish_handles_single_point() {
+    assert_eq!(finish(&[(5.0, 5.0)]), vec![(5.0, 5.0)]);
+}
+
+#[test]
+fn test_fit_pads_straight_lines() {
+    let (position, size, points) = fit(&[(10.0, 20.0), (110.0, 20.0)], 3.0);
+    assert_eq!(position, (7.0, 17.0));
+    assert_eq!(size, (106.0, 6.0));
+    assert_eq!(points[0].1, 0.5);
+
+    let back = to_canvas(&points, position, size);
+    assert!((back[1].0 - 110.0).abs() < 0.001);
+    assert!((back[1].1 - 20.0).abs() < 0.001);
+}
+
+#[test]
+fn test_hit_stroke() {
+    let points = vec![(0.0, 0.0), (100.0, 0.0)];
+    assert!(hit_stroke(&points, (50.0, 3.0), 4.0));
+    assert!(!hit_stroke(&points, (50.0, 10.0), 4.0));
+    assert!(hit_stroke(&[(5.0, 5.0)], (6.0, 6.0), 2.0));
+}
+
+#[test]
+fn test_erase_misses() {
+    let points = vec![(0.0, 0.0), (100.0, 0.0)];
+    assert!(erase(&points, (50.0, 50.0), 10.0).is_none());
+}
+
+#[test]
+fn test_erase_splits_stroke_in_two() {
+    let points = vec![(0.0, 0.0), (100.0, 0.0)];
+    let pieces = erase(&points, (50.0, 0.0), 10.0).unwrap();
+    assert_eq!(pieces.len(), 2);
+    assert_eq!(pieces[0].first(), Some(&(0.0, 0.0)));
+    assert!((pieces[0].last().unwrap().0 - 40.0).abs() < 0.01);
+    assert!((pieces[1].first().unwrap().0 - 60.0).abs() < 0.01);
+    assert_eq!(pieces[1].last(), Some(&(100.0, 0.0)));
+}
+
+#[test]
+fn test_erase_trims_end() {
+    let points = vec![(0.0, 0.0), (50.0, 0.0), (100.0, 0.0)];
+    let pieces = erase(&points, (100.0, 0.0), 10.0).unwrap();
+    assert_eq!(pieces.len(), 1);
+    assert!((pieces[0].last().unwrap().0 - 90.0).abs() < 0.01);
+}
+
+#[test]
+fn test_erase_removes_small_stroke_entirely() {
+    let points = vec![(0.0, 0.0), (4.0, 0.0)];
+    assert_eq!(erase(&points, (2.0, 0.0), 10.0), Some(Vec::new()));
+}
+
+#[test]
+fn test_stroke_points_serialize_flat() {
+    let content = ItemContent::Stroke {
+        points: vec![(0.123456, 0.5), (1.0, 0.25)],
+        color: "#ff0000".to_string(),
+        width: 3.0,
+        kind: StrokeKind::Highlighter,
+    };
+
+    let json = serde_json::to_string(&content).unwrap();
+    assert!(json.contains("[0.1235,0.5,1.0,0.25]"), "{}", json);
+
+    let parsed: ItemContent = serde_json::from_str(&json).unwrap();
+    match parsed {
+        ItemContent::Stroke { points, kind, .. } => {
+            assert_eq!(points.len(), 2);
+            assert_eq!(points[1], (1.0, 0.25));
+            assert_eq!(kind, StrokeKind::Highlighter);
+        }
+        other => panic!("unexpected content {:?}", other),
+    }
+}
+
+#[test]
+fn test_stroke_rejects_odd_coordinates() {
+    let json = r##"{"Stroke":{"points":[0.1,0.2,0.3],"color":"","width":3.0}}"##;
+    assert!(serde_json::from_str::<ItemContent>(json).is_err());
+}
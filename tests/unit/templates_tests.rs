@@ -0,0 +1,200 @@
+//! Unit tests for built-in and saved board templates.
+
+use humanboard::board::BoardState;
+use humanboard::comments::{CommentAnchor, CommentThread};
+use humanboard::image_edit::ImageEdit;
+use humanboard::pdf_annotations::{AnnotationKind, PdfAnnotation};
+use humanboard::templates::{
+    BuiltInTemplate, TemplateChoice, apply_template, list_saved_templates, save_template,
+};
+use humanboard::types::{CanvasItem, ItemContent, ShapeType, ViewportBookmark};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+fn image_board(paths: &[PathBuf]) -> BoardState {
+    BoardState {
+        canvas_offset: (10.0, 20.0),
+        zoom: 1.5,
+        items: paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| CanvasItem {
+                id: i as u64,
+                position: (i as f32 * 100.0, 0.0),
+                size: (80.0, 60.0),
+                content: ItemContent::Image(path.clone(), ImageEdit::default()),
+            })
+            .collect(),
+        next_item_id: paths.len() as u64,
+        bookmarks: Vec::new(),
+        pdf_annotations: Vec::new(),
+        comment_threads: Vec::new(),
+    }
+}
+
+fn image_paths(state: &BoardState) -> Vec<PathBuf> {
+    state
+        .items
+        .iter()
+        .filter_map(|item| match &item.content {
+            ItemContent::Image(path, _) => Some(path.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_built_in_templates_have_unique_ids() {
+    assert!(BuiltInTemplate::Blank.items().is_empty());
+
+    for template in BuiltInTemplate::ALL {
+        let state = template.board_state();
+        let ids: HashSet<u64> = state.items.iter().map(|item| item.id).collect();
+        assert_eq!(ids.len(), state.items.len(), "{}", template.name());
+        assert_eq!(state.next_item_id, state.items.len() as u64);
+    }
+}
+
+#[test]
+fn test_kanban_has_three_titled_columns() {
+    let titles: Vec<String> = BuiltInTemplate::Kanban
+        .items()
+        .into_iter()
+        .filter_map(|item| match item.content {
+            ItemContent::Shape {
+                shape_type: ShapeType::Frame,
+                label,
+                ..
+            } => label,
+            _ => None,
+        })
+        .collect();
+    assert_eq!(titles, ["To Do", "In Progress", "Done"]);
+}
+
+#[test]
+fn test_blank_template_writes_nothing() {
+    let dir = tempdir().unwrap();
+    let board_dir = dir.path().join("board");
+    fs::create_dir_all(&board_dir).unwrap();
+
+    apply_template(&TemplateChoice::default(), dir.path(), &board_dir).unwrap();
+    assert!(!board_dir.join("board.json").exists());
+}
+
+#[test]
+fn test_built_in_template_writes_board() {
+    let dir = tempdir().unwrap();
+    let board_dir = dir.path().join("board");
+    let choice = TemplateChoice::BuiltIn(BuiltInTemplate::MindMap);
+
+    apply_template(&choice, dir.path(), &board_dir).unwrap();
+    let state = BoardState::load_from_path(&board_dir.join("board.json")).unwrap();
+    assert_eq!(state.items.len(), BuiltInTemplate::MindMap.items().len());
+}
+
+#[test]
+fn test_saved_template_round_trips_files() {
+    let dir = tempdir().unwrap();
+    let source_a = dir.path().join("a");
+    let source_b = dir.path().join("b");
+    fs::create_dir_all(&source_a).unwrap();
+    fs::create_dir_all(&source_b).unwrap();
+    fs::write(source_a.join("photo.png"), b"first").unwrap();
+    fs::write(source_b.join("photo.png"), b"second").unwrap();
+
+    let missing = dir.path().join("gone.png");
+    let board = image_board(&[
+        source_a.join("photo.png"),
+        source_b.join("photo.png"),
+        missing.clone(),
+    ]);
+
+    let templates_dir = dir.path().join("templates");
+    let saved = save_template(&templates_dir, "Sprint", &board).unwrap();
+    assert_eq!(list_saved_templates(&templates_dir), vec![saved.clone()]);
+
+    // Deleting the originals doesn't break the template
+    fs::remove_dir_all(&source_a).unwrap();
+    fs::remove_dir_all(&source_b).unwrap();
+
+    let board_dir = dir.path().join("new-board");
+    apply_template(&TemplateChoice::Saved(saved), &templates_dir, &board_dir).unwrap();
+    let state = BoardState::load_from_path(&board_dir.join("board.json")).unwrap();
+    assert_eq!(state.zoom, 1.5);
+
+    let paths = image_paths(&state);
+    assert!(paths[0].starts_with(board_dir.join("files")));
+    assert!(paths[1].starts_with(board_dir.join("files")));
+    assert_ne!(paths[0], paths[1]);
+    assert_eq!(fs::read(&paths[0]).unwrap(), b"first");
+    assert_eq!(fs::read(&paths[1]).unwrap(), b"second");
+    assert_eq!(paths[2], missing);
+}
+
+#[test]
+fn test_saved_template_keeps_annotations_but_not_comments() {
+    let dir = tempdir().unwrap();
+    let pdf = dir.path().join("paper.pdf");
+    fs::write(&pdf, b"%PDF").unwrap();
+
+    let mut board = image_board(&[]);
+    board.items.push(CanvasItem {
+        id: 0,
+        position: (0.0, 0.0),
+        size: (200.0, 260.0),
+        content: ItemContent::Pdf {
+            path: pdf.clone(),
+            thumbnail: None,
+        },
+    });
+    board.pdf_annotations.push(PdfAnnotation {
+        id: 1,
+        path: pdf.clone(),
+        page: 1,
+        kind: AnnotationKind::Note {
+            point: (10.0, 10.0),
+            text: "Read this".to_string(),
+        },
+    });
+    board.bookmarks.push(ViewportBookmark {
+        name: "Start".to_string(),
+        canvas_offset: (0.0, 0.0),
+        zoom: 1.0,
+    });
+    board.comment_threads.push(CommentThread {
+        id: 1,
+        anchor: CommentAnchor::Item(0),
+        comments: Vec::new(),
+        resolved: false,
+    });
+
+    let templates_dir = dir.path().join("templates");
+    let saved = save_template(&templates_dir, "Reading", &board).unwrap();
+    let board_dir = dir.path().join("new-board");
+    apply_template(&TemplateChoice::Saved(saved), &templates_dir, &board_dir).unwrap();
+    let state = BoardState::load_from_path(&board_dir.join("board.json")).unwrap();
+
+    assert!(state.bookmarks.is_empty());
+    assert!(state.comment_threads.is_empty());
+    let annotated = &state.pdf_annotations[0].path;
+    assert!(annotated.starts_with(board_dir.join("files")));
+    assert!(matches!(
+        &state.items[0].content,
+        ItemContent::Pdf { path, .. } if path == annotated
+    ));
+}
+
+#[test]
+fn test_missing_saved_template_is_an_error() {
+    let dir = tempdir().unwrap();
+    let templates_dir = dir.path().join("templates");
+    let saved = save_template(&templates_dir, "Gone", &image_board(&[])).unwrap();
+    fs::remove_dir_all(templates_dir.join(&saved.id)).unwrap();
+
+    let board_dir = dir.path().join("board");
+    assert!(apply_template(&TemplateChoice::Saved(saved), &templates_dir, &board_dir).is_err());
+    assert!(list_saved_templates(&templates_dir).is_empty());
+}
@@ -0,0 +1,168 @@
+//! Unit tests for theme drafts, theme files and item colours.
+
+use gpui::hsla;
+use gpui_component::theme::ThemeConfig;
+use humanboard::theme_editor::{
+    BUNDLED_MANIFEST, ThemeColorSlot, ThemeDraft, hsla_to_hex, install_bundled_themes,
+    load_item_colors, normalize_hex,
+};
+use std::fs;
+use tempfile::tempdir;
+
+fn base_theme() -> ThemeConfig {
+    ThemeConfig {
+        name: "Midnight".into(),
+        is_default: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_fork_renames_and_is_not_default() {
+    let draft = ThemeDraft::fork(&base_theme(), Default::default());
+    assert_eq!(draft.name(), "Midnight Custom");
+    assert!(!draft.config.is_default);
+}
+
+#[test]
+fn test_set_color_normalizes_and_rejects_invalid_hex() {
+    let mut draft = ThemeDraft::fork(&base_theme(), Default::default());
+
+    draft.set_color(ThemeColorSlot::Primary, "FF8800").unwrap();
+    assert_eq!(draft.color(ThemeColorSlot::Primary), Some("#ff8800"));
+
+    draft.set_color(ThemeColorSlot::Video, " #123abc ").unwrap();
+    assert_eq!(draft.item_colors.video.as_deref(), Some("#123abc"));
+
+    assert!(draft.set_color(ThemeColorSlot::Canvas, "#12345").is_err());
+    assert!(draft.set_color(ThemeColorSlot::Canvas, "#gggggg").is_err());
+    assert_eq!(draft.color(ThemeColorSlot::Canvas), None);
+}
+
+#[test]
+fn test_normalize_hex_and_hsla_to_hex() {
+    assert_eq!(normalize_hex("#ABCDEF").as_deref(), Some("#abcdef"));
+    assert_eq!(normalize_hex("abc"), None);
+    assert_eq!(hsla_to_hex(hsla(0.0, 0.0, 1.0, 1.0)), "#ffffff");
+    assert_eq!(hsla_to_hex(hsla(0.0, 1.0, 0.5, 0.5)), "#ff0000");
+}
+
+#[test]
+fn test_file_name_is_slug_of_name() {
+    let mut draft = ThemeDraft::fork(&base_theme(), Default::default());
+    draft.set_name("  My Warm  Theme! ");
+    assert_eq!(draft.name(), "My Warm  Theme!");
+    assert_eq!(draft.file_name(), "my-warm-theme.json");
+
+    draft.set_name("???");
+    assert_eq!(draft.file_name(), "theme.json");
+}
+
+#[test]
+fn test_saved_theme_round_trips_item_colors() {
+    let dir = tempdir().unwrap();
+    let mut draft = ThemeDraft::fork(&base_theme(), Default::default());
+    draft.set_color(ThemeColorSlot::Background, "#101010").unwrap();
+    draft.set_color(ThemeColorSlot::Canvas, "#202020").unwrap();
+    draft.set_color(ThemeColorSlot::Pdf, "#ff6600").unwrap();
+
+    let path = draft.save(dir.path(), &["Midnight".to_string()]).unwrap();
+    assert_eq!(path, dir.path().join("midnight-custom.json"));
+
+    let saved: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(saved["themes"][0]["name"], "Midnight Custom");
+    assert_eq!(saved["themes"][0]["colors"]["background"], "#101010");
+
+    let item_colors = load_item_colors(dir.path());
+    let colors = &item_colors["Midnight Custom"];
+    assert_eq!(colors.canvas.as_deref(), Some("#202020"));
+    assert_eq!(colors.pdf.as_deref(), Some("#ff6600"));
+    assert_eq!(colors.video, None);
+}
+
+#[test]
+fn test_save_refuses_taken_names_but_replaces_own_file() {
+    let dir = tempdir().unwrap();
+    let mut draft = ThemeDraft::fork(&base_theme(), Default::default());
+
+    draft.set_name("Midnight");
+    assert!(draft.save(dir.path(), &["Midnight".to_string()]).is_err());
+
+    draft.set_name("");
+    assert!(draft.save(dir.path(), &[]).is_err());
+
+    draft.set_name("Dusk");
+    draft.save(dir.path(), &[]).unwrap();
+    draft.set_color(ThemeColorSlot::Border, "#333333").unwrap();
+    draft.save(dir.path(), &["Dusk".to_string()]).unwrap();
+
+    // A different file under the same name (e.g. a bundled theme set) is never replaced
+    fs::write(
+        dir.path().join("dawn.json"),
+        r#"{ "name": "Dawn", "themes": [{ "name": "Dawn Light" }, { "name": "Dawn Dark" }] }"#,
+    )
+    .unwrap();
+    draft.set_name("Dawn");
+    assert!(draft.save(dir.path(), &[]).is_err());
+}
+
+#[test]
+fn test_install_bundled_themes_copies_theme_files() {
+    let bundled = tempdir().unwrap();
+    let user = tempdir().unwrap();
+    fs::write(bundled.path().join("ayu.json"), r#"{ "themes": [] }"#).unwrap();
+    fs::write(bundled.path().join("README.md"), "not a theme").unwrap();
+
+    let user_dir = user.path().join("themes");
+    install_bundled_themes(bundled.path(), &user_dir).unwrap();
+    assert!(user_dir.join("ayu.json").exists());
+    assert!(!user_dir.join("README.md").exists());
+
+    // A user's edits to an installed theme survive the next launch
+    let edited = r#"{ "themes": [], "name": "Mine" }"#;
+    fs::write(user_dir.join("ayu.json"), edited).unwrap();
+    install_bundled_themes(bundled.path(), &user_dir).unwrap();
+    assert_eq!(fs::read_to_string(user_dir.join("ayu.json")).unwrap(), edited);
+
+    // Installing into the bundled directory itself is a no-op
+    install_bundled_themes(bundled.path(), bundled.path()).unwrap();
+    assert_eq!(fs::read_dir(bundled.path()).unwrap().count(), 2);
+}
+
+#[test]
+fn test_install_bundled_themes_updates_unedited_themes() {
+    let bundled = tempdir().unwrap();
+    let user = tempdir().unwrap();
+    let user_dir = user.path().join("themes");
+    fs::write(bundled.path().join("ayu.json"), r#"{ "themes": [] }"#).unwrap();
+    fs::write(bundled.path().join("dawn.json"), r#"{ "themes": [] }"#).unwrap();
+    install_bundled_themes(bundled.path(), &user_dir).unwrap();
+    assert!(user_dir.join(BUNDLED_MANIFEST).exists());
+
+    let edited = r#"{ "themes": [], "name": "Mine" }"#;
+    fs::write(user_dir.join("dawn.json"), edited).unwrap();
+    let updated = r#"{ "themes": [], "name": "Updated" }"#;
+    fs::write(bundled.path().join("ayu.json"), updated).unwrap();
+    fs::write(bundled.path().join("dawn.json"), updated).unwrap();
+    install_bundled_themes(bundled.path(), &user_dir).unwrap();
+
+    // The untouched theme takes the update, the edited one is kept
+    assert_eq!(
+        fs::read_to_string(user_dir.join("ayu.json")).unwrap(),
+        updated
+    );
+    assert_eq!(
+        fs::read_to_string(user_dir.join("dawn.json")).unwrap(),
+        edited
+    );
+
+    // And keeps being kept through later updates
+    let newer = r#"{ "themes": [], "name": "Newer" }"#;
+    fs::write(bundled.path().join("dawn.json"), newer).unwrap();
+    install_bundled_themes(bundled.path(), &user_dir).unwrap();
+    assert_eq!(
+        fs::read_to_string(user_dir.join("dawn.json")).unwrap(),
+        edited
+    );
+}
@@ -0,0 +1,21 @@
+//! Unit tests for timestamps and relative times.
+
+use humanboard::timestamps::{WEEK_SECS, time_ago, unix_now};
+
+#[test]
+fn test_time_ago() {
+    let now = 1_792_332_180;
+    assert_eq!(time_ago(now, now), "Just now");
+    assert_eq!(time_ago(now - 30, now), "Just now");
+    assert_eq!(time_ago(now - 5 * 60, now), "5 min ago");
+    assert_eq!(time_ago(now - 3 * 3600, now), "3 hours ago");
+    assert_eq!(time_ago(now - 2 * 86400, now), "2 days ago");
+    assert_eq!(time_ago(now - 3 * WEEK_SECS, now), "3 weeks ago");
+    // A clock that's behind doesn't put times in the future
+    assert_eq!(time_ago(now + 100, now), "Just now");
+}
+
+#[test]
+fn test_unix_now_is_after_2020() {
+    assert!(unix_now() > 1_577_836_800);
+}
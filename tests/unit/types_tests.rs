@@ -1,13 +1,14 @@
 //! Unit tests for types module.
 
-use humanboard::types::{CanvasItem, ItemContent};
+use humanboard::image_edit::ImageEdit;
+use humanboard::types::{CanvasItem, ItemContent, ShapeType, TextAlignment, TextFormat};
 use std::path::PathBuf;
 
 #[test]
 fn test_item_content_from_path_image() {
     let path = PathBuf::from("/test/image.png");
     let content = ItemContent::from_path(&path);
-    assert!(matches!(content, ItemContent::Image(_)));
+    assert!(matches!(content, ItemContent::Image(..)));
 }
 
 #[test]
@@ -41,7 +42,10 @@ fn test_item_content_from_path_unknown() {
 
 #[test]
 fn test_type_labels() {
-    assert_eq!(ItemContent::Image(PathBuf::new()).type_label(), "IMAGE");
+    assert_eq!(
+        ItemContent::Image(PathBuf::new(), ImageEdit::default()).type_label(),
+        "IMAGE"
+    );
     assert_eq!(ItemContent::Video(PathBuf::new()).type_label(), "VIDEO");
     assert_eq!(
         ItemContent::Pdf {
@@ -57,7 +61,7 @@ fn test_type_labels() {
 
 #[test]
 fn test_display_name_file() {
-    let content = ItemContent::Image(PathBuf::from("/path/to/photo.jpg"));
+    let content = ItemContent::Image(PathBuf::from("/path/to/photo.jpg"), ImageEdit::default());
     assert_eq!(content.display_name(), "photo.jpg");
 }
 
@@ -120,7 +124,7 @@ fn test_image_extensions() {
         let path = PathBuf::from(format!("/test/file.{}", ext));
         let content = ItemContent::from_path(&path);
         assert!(
-            matches!(content, ItemContent::Image(_)),
+            matches!(content, ItemContent::Image(..)),
             "Failed for {}",
             ext
         );
@@ -148,7 +152,7 @@ fn test_audio_extensions() {
         let path = PathBuf::from(format!("/test/file.{}", ext));
         let content = ItemContent::from_path(&path);
         assert!(
-            matches!(content, ItemContent::Audio(_)),
+            matches!(content, ItemContent::Audio(..)),
             "Failed for {}",
             ext
         );
@@ -159,16 +163,132 @@ fn test_audio_extensions() {
 fn test_item_content_from_path_audio() {
     let path = PathBuf::from("/test/audio.mp3");
     let content = ItemContent::from_path(&path);
-    assert!(matches!(content, ItemContent::Audio(_)));
+    assert!(matches!(content, ItemContent::Audio(..)));
 }
 
 #[test]
 fn test_default_size_audio() {
-    let content = ItemContent::Audio(PathBuf::new());
-    assert_eq!(content.default_size(), (320.0, 160.0));
+    let content = ItemContent::Audio(PathBuf::new(), Vec::new());
+    assert_eq!(content.default_size(), (320.0, 220.0));
 }
 
 #[test]
 fn test_type_label_audio() {
-    assert_eq!(ItemContent::Audio(PathBuf::new()).type_label(), "AUDIO");
+    assert_eq!(ItemContent::Audio(PathBuf::new(), Vec::new()).type_label(), "AUDIO");
+}
+
+#[test]
+fn test_legacy_textbox_loads_as_plain() {
+    // Text boxes saved before rich text support have no format/align/font fields
+    let json = r##"{"TextBox":{"text":"**not bold**","font_size":16.0,"color":"#ffffff"}}"##;
+    let content: ItemContent = serde_json::from_str(json).unwrap();
+
+    match content {
+        ItemContent::TextBox {
+            text,
+            format,
+            align,
+            font_family,
+            ..
+        } => {
+            assert_eq!(text, "**not bold**");
+            assert_eq!(format, TextFormat::Plain);
+            assert_eq!(align, TextAlignment::Left);
+            assert!(font_family.is_none());
+        }
+        _ => panic!("Expected TextBox"),
+    }
+}
+
+#[test]
+fn test_textbox_style_roundtrip() {
+    let content = ItemContent::TextBox {
+        text: "Title".to_string(),
+        font_size: 20.0,
+        color: String::new(),
+        format: TextFormat::Markdown,
+        align: TextAlignment::Center,
+        font_family: Some("Inter".to_string()),
+        fill_color: Some("#336699".to_string()),
+    };
+    let json = serde_json::to_string(&content).unwrap();
+    let restored: ItemContent = serde_json::from_str(&json).unwrap();
+
+    match restored {
+        ItemContent::TextBox {
+            format,
+            align,
+            font_family,
+            fill_color,
+            ..
+        } => {
+            assert_eq!(format, TextFormat::Markdown);
+            assert_eq!(align, TextAlignment::Center);
+            assert_eq!(font_family.as_deref(), Some("Inter"));
+            assert_eq!(fill_color.as_deref(), Some("#336699"));
+        }
+        _ => panic!("Expected TextBox"),
+    }
+}
+
+#[test]
+fn test_text_alignment_from_name() {
+    assert_eq!(
+        TextAlignment::from_name("Center"),
+        Some(TextAlignment::Center)
+    );
+    assert_eq!(TextAlignment::from_name("r"), Some(TextAlignment::Right));
+    assert_eq!(TextAlignment::from_name("justify"), None);
+    assert_eq!(TextAlignment::Right.next(), TextAlignment::Left);
+}
+
+#[test]
+fn test_shape_label_is_searchable() {
+    let mut content = ItemContent::Shape {
+        shape_type: ShapeType::Rectangle,
+        fill_color: None,
+        border_color: String::new(),
+        border_width: 2.0,
+        label: None,
+    };
+    assert!(!content.is_searchable());
+    assert_eq!(content.editable_text(), Some(""));
+
+    content.set_editable_text("Checkout flow".to_string());
+    assert!(content.is_searchable());
+    assert_eq!(content.display_name(), "Checkout flow");
+
+    // Clearing the label removes it
+    content.set_editable_text("   ".to_string());
+    assert!(matches!(content, ItemContent::Shape { label: None, .. }));
+}
+
+#[test]
+fn test_legacy_arrow_loads_without_label() {
+    let json =
+        r##"{"Arrow":{"end_offset":[100.0,0.0],"color":"","thickness":2.0,"head_style":"Arrow"}}"##;
+    let content: ItemContent = serde_json::from_str(json).unwrap();
+    assert!(matches!(content, ItemContent::Arrow { label: None, .. }));
+    assert_eq!(content.display_name(), "Arrow");
+}
+
+#[test]
+fn test_copy_geometry_from_keeps_content() {
+    let mut item = CanvasItem {
+        id: 1,
+        position: (0.0, 0.0),
+        size: (100.0, 50.0),
+        content: ItemContent::Text("edited".to_string()),
+    };
+    let target = CanvasItem {
+        id: 1,
+        position: (300.0, 200.0),
+        size: (50.0, 25.0),
+        content: ItemContent::Text("stale".to_string()),
+    };
+
+    item.copy_geometry_from(&target);
+    assert_eq!(item.position, (300.0, 200.0));
+    assert_eq!(item.size, (50.0, 25.0));
+    assert!(matches!(&item.content, ItemContent::Text(text) if text == "edited"));
 }
@@ -1,6 +1,8 @@
 //! Unit tests for validation module.
 
-use humanboard::types::{ArrowHead, CanvasItem, ItemContent, ShapeType};
+use humanboard::types::{
+    ArrowHead, CanvasItem, ItemContent, ShapeType, TextAlignment, TextFormat,
+};
 use humanboard::validation::{
     is_valid_hex_color, normalize_hex_color, validate_item, ValidationConstraints,
 };
@@ -36,6 +38,10 @@ fn test_validate_textbox_font_size() {
             text: "Test".to_string(),
             font_size: 5.0, // Too small
             color: "#ffffff".to_string(),
+            format: TextFormat::Plain,
+            align: TextAlignment::Left,
+            font_family: None,
+            fill_color: None,
         },
     };
 
@@ -58,6 +64,10 @@ fn test_validate_textbox_color() {
             text: "Test".to_string(),
             font_size: 16.0,
             color: "invalid".to_string(),
+            format: TextFormat::Plain,
+            align: TextAlignment::Left,
+            font_family: None,
+            fill_color: None,
         },
     };
 
@@ -81,6 +91,7 @@ fn test_validate_arrow_thickness() {
             color: "#ffffff".to_string(),
             thickness: 100.0, // Too thick
             head_style: ArrowHead::Arrow,
+            label: None,
         },
     };
 
@@ -104,6 +115,7 @@ fn test_validate_shape_border() {
             fill_color: Some("notacolor".to_string()),
             border_color: "#ffffff".to_string(),
             border_width: 100.0, // Too wide
+            label: None,
         },
     };
 
@@ -121,6 +133,33 @@ fn test_validate_shape_border() {
     }
 }
 
+#[test]
+fn test_line_shape_becomes_headless_arrow() {
+    let constraints = ValidationConstraints::default();
+    let mut item = CanvasItem {
+        id: 1,
+        position: (10.0, 20.0),
+        size: (200.0, 40.0),
+        content: ItemContent::Shape {
+            shape_type: ShapeType::Line,
+            fill_color: None,
+            border_color: "#ff0000".to_string(),
+            border_width: 3.0,
+            label: None,
+        },
+    };
+
+    validate_item(&mut item, &constraints);
+
+    assert_eq!(item.arrow_endpoints(), Some(((10.0, 40.0), (210.0, 40.0))));
+    let ItemContent::Arrow { head_style, thickness, color, .. } = &item.content else {
+        panic!("expected an arrow");
+    };
+    assert_eq!(*head_style, ArrowHead::None);
+    assert_eq!(*thickness, 3.0);
+    assert_eq!(color, "#ff0000");
+}
+
 #[test]
 fn test_validate_item_size() {
     let constraints = ValidationConstraints::default();
@@ -132,6 +171,10 @@ fn test_validate_item_size() {
             text: "Test".to_string(),
             font_size: 16.0,
             color: "#ffffff".to_string(),
+            format: TextFormat::Plain,
+            align: TextAlignment::Left,
+            font_family: None,
+            fill_color: None,
         },
     };
 
@@ -152,6 +195,10 @@ fn test_valid_item_passes() {
             text: "Test".to_string(),
             font_size: 16.0,
             color: "#ffffff".to_string(),
+            format: TextFormat::Plain,
+            align: TextAlignment::Left,
+            font_family: None,
+            fill_color: None,
         },
     };
 
@@ -0,0 +1,191 @@
+//! Unit tests for audio waveforms and markers.
+
+use humanboard::types::ItemContent;
+use humanboard::waveform::{
+    self, AudioMarker, Waveform, cached_waveform, format_timestamp, load_waveform, marker_near,
+    parse_timestamp, remove_marker, set_marker, strip_fraction, strip_height,
+};
+use std::path::Path;
+use std::time::Duration;
+use tempfile::tempdir;
+
+/// Write a stereo 16-bit WAV file: one second of silence, then one second of
+/// a 440 Hz tone at half volume
+fn write_wav(path: &Path, sample_rate: u32) {
+    let mut samples = Vec::new();
+    for i in 0..sample_rate * 2 {
+        let value = if i < sample_rate {
+            0
+        } else {
+            let t = i as f32 / sample_rate as f32;
+            ((t * 440.0 * std::f32::consts::TAU).sin() * 16384.0) as i16
+        };
+        samples.extend(value.to_le_bytes());
+        samples.extend(value.to_le_bytes());
+    }
+
+    let mut wav = Vec::new();
+    wav.extend(b"RIFF");
+    wav.extend((36 + samples.len() as u32).to_le_bytes());
+    wav.extend(b"WAVEfmt ");
+    wav.extend(16u32.to_le_bytes());
+    wav.extend(1u16.to_le_bytes()); // PCM
+    wav.extend(2u16.to_le_bytes()); // stereo
+    wav.extend(sample_rate.to_le_bytes());
+    wav.extend((sample_rate * 4).to_le_bytes());
+    wav.extend(4u16.to_le_bytes());
+    wav.extend(16u16.to_le_bytes());
+    wav.extend(b"data");
+    wav.extend((samples.len() as u32).to_le_bytes());
+    wav.extend(samples);
+    std::fs::write(path, wav).unwrap();
+}
+
+fn marker(seconds: f64, note: &str) -> AudioMarker {
+    AudioMarker {
+        seconds,
+        note: note.to_string(),
+    }
+}
+
+#[test]
+fn test_waveform_from_samples() {
+    // 1000 quiet frames then 1000 loud ones, in stereo
+    let mut samples = vec![0.1; 2000];
+    samples.extend([0.8, -0.9].repeat(1000));
+    let waveform = Waveform::from_samples(&samples, 2, 1000);
+
+    assert_eq!(waveform.duration, Duration::from_secs(2));
+    assert!(waveform.peaks.len() < 2000);
+    assert!(!waveform.is_empty());
+
+    let bars = waveform.bars(4);
+    assert_eq!(bars.len(), 4);
+    assert!((bars[0] - 0.1 / 0.9).abs() < 1e-6);
+    assert_eq!(bars[3], 1.0);
+
+    // More bars than peaks repeat them rather than running out
+    assert_eq!(waveform.bars(10_000).len(), 10_000);
+    assert!(Waveform::default().bars(8).is_empty());
+    assert!(Waveform::default().is_empty());
+}
+
+#[test]
+fn test_waveform_times() {
+    let waveform = Waveform::from_samples(&[0.5; 400], 1, 100);
+    assert_eq!(waveform.duration, Duration::from_secs(4));
+    assert_eq!(waveform.fraction_at(1.0), 0.25);
+    assert_eq!(waveform.fraction_at(10.0), 1.0);
+    assert_eq!(waveform.seconds_at(0.5), 2.0);
+    assert_eq!(waveform.seconds_at(-1.0), 0.0);
+}
+
+#[test]
+fn test_decode_wav() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("tone.wav");
+    write_wav(&path, 8000);
+
+    let waveform = waveform::decode(&path).unwrap();
+    assert_eq!(waveform.duration, Duration::from_secs(2));
+    let (silence, tone) = waveform.peaks.split_at(waveform.peaks.len() / 2);
+    assert!(silence.iter().all(|&peak| peak == 0.0));
+    assert!(tone.iter().all(|&peak| (peak - 0.5).abs() < 0.02));
+
+    assert!(cached_waveform(&path).is_none());
+    assert_eq!(*load_waveform(&path), waveform);
+    assert_eq!(*cached_waveform(&path).unwrap(), waveform);
+}
+
+#[test]
+fn test_undecodable_files_get_an_empty_waveform() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("broken.mp3");
+    std::fs::write(&path, b"not audio at all").unwrap();
+
+    assert!(waveform::decode(&path).is_err());
+    assert!(waveform::decode(&dir.path().join("missing.wav")).is_err());
+    assert!(load_waveform(&path).is_empty());
+    assert!(cached_waveform(&path).is_some());
+}
+
+#[test]
+fn test_markers_stay_in_time_order() {
+    let mut markers = Vec::new();
+    set_marker(&mut markers, 30.0, "Chorus".to_string());
+    set_marker(&mut markers, 5.0, "Intro".to_string());
+    set_marker(&mut markers, 12.5, String::new());
+    assert_eq!(
+        markers,
+        vec![
+            marker(5.0, "Intro"),
+            marker(12.5, ""),
+            marker(30.0, "Chorus")
+        ]
+    );
+
+    // A marker at about the same time gets the new note
+    set_marker(&mut markers, 12.3, "Verse".to_string());
+    assert_eq!(markers.len(), 3);
+    assert_eq!(markers[1], marker(12.5, "Verse"));
+
+    assert_eq!(marker_near(&markers, 29.0, 1.5), Some(&markers[2]));
+    assert_eq!(marker_near(&markers, 20.0, 1.5), None);
+
+    assert_eq!(remove_marker(&mut markers, 5.2), Some(marker(5.0, "Intro")));
+    assert_eq!(remove_marker(&mut markers, 5.2), None);
+    assert_eq!(markers.len(), 2);
+}
+
+#[test]
+fn test_timestamps() {
+    assert_eq!(parse_timestamp("83"), Some(83.0));
+    assert_eq!(parse_timestamp("1:23"), Some(83.0));
+    assert_eq!(parse_timestamp(" 1:23.5 "), Some(83.5));
+    assert_eq!(parse_timestamp("1:02:03"), Some(3723.0));
+    assert_eq!(parse_timestamp("1:75"), None);
+    assert_eq!(parse_timestamp("1:60:00"), None);
+    assert_eq!(parse_timestamp("-4"), None);
+    assert_eq!(parse_timestamp("chorus"), None);
+    assert_eq!(parse_timestamp("1:2:3:4"), None);
+
+    assert_eq!(format_timestamp(83.0), "1:23");
+    assert_eq!(format_timestamp(83.46), "1:23.5");
+    assert_eq!(format_timestamp(59.97), "1:00");
+    assert_eq!(format_timestamp(3723.0), "1:02:03");
+}
+
+#[test]
+fn test_waveform_strip_geometry() {
+    assert_eq!(strip_height(220.0), waveform::WAVEFORM_HEIGHT);
+    assert_eq!(strip_height(100.0), 40.0);
+
+    let inset = waveform::WAVEFORM_INSET;
+    assert_eq!(strip_fraction(inset, 320.0), 0.0);
+    assert_eq!(strip_fraction(160.0, 320.0), 0.5);
+    assert_eq!(strip_fraction(400.0, 320.0), 1.0);
+    assert_eq!(strip_fraction(5.0, 10.0), 0.0);
+}
+
+#[test]
+fn test_audio_markers_are_saved_with_the_item() {
+    // Items without markers save as before, and old boards load
+    let plain = ItemContent::Audio("/music/demo.mp3".into(), Vec::new());
+    let json = serde_json::to_string(&plain).unwrap();
+    assert_eq!(json, r#"{"Audio":"/music/demo.mp3"}"#);
+    let ItemContent::Audio(path, markers) = serde_json::from_str(&json).unwrap() else {
+        panic!("Expected audio");
+    };
+    assert_eq!(path, Path::new("/music/demo.mp3"));
+    assert!(markers.is_empty());
+
+    let marked = ItemContent::Audio(
+        "/music/demo.mp3".into(),
+        vec![marker(12.5, "Drop"), marker(40.0, "")],
+    );
+    let json = serde_json::to_string(&marked).unwrap();
+    let ItemContent::Audio(_, markers) = serde_json::from_str(&json).unwrap() else {
+        panic!("Expected audio");
+    };
+    assert_eq!(markers, vec![marker(12.5, "Drop"), marker(40.0, "")]);
+}